@@ -17,19 +17,30 @@ import type { Manifest, ResourceRef } from "@contentauth/c2pa-types";
 import * as fs from "fs-extra";
 import path from "path";
 import * as crypto from "crypto";
+import http from "http";
+import type { AddressInfo } from "net";
+import os from "os";
+import { Writable } from "stream";
 
 import type {
+  AssetIo,
   BuilderInterface,
   C2paSettings,
   JsCallbackSignerConfig,
+  DestinationAsset,
   DestinationBufferAsset,
+  SourceAsset,
   SourceBufferAsset,
   FileAsset,
+  HashAlgorithm,
+  ManifestPlacement,
+  SidecarDestination,
 } from "./types.d.ts";
 import { isActionsAssertion } from "./assertions.js";
 import { CallbackSigner, LocalSigner } from "./Signer.js";
 import { Reader } from "./Reader.js";
 import { Builder } from "./Builder.js";
+import { readDataHash } from "./Hashing.js";
 
 const tempDir = path.join(__dirname, "tmp");
 
@@ -134,6 +145,21 @@ describe("Builder", () => {
     await fs.remove(tempDir);
   });
 
+  it("should fail after the builder is disposed", async () => {
+    const builder = Builder.withJson(manifestDefinition);
+    builder.dispose();
+
+    expect(() => builder.getManifestDefinition()).toThrow(
+      "The Builder has been disposed",
+    );
+    await expect(
+      builder.addResource("thumbnail.jpg", {
+        buffer: testThumbnail,
+        mimeType: "jpeg",
+      }),
+    ).rejects.toThrow("disposed");
+  });
+
   it("should build a manifest store", async () => {
     const test_definition: Manifest = {
       claim_generator: "test-generator",
@@ -174,6 +200,162 @@ describe("Builder", () => {
     expect(definition.label).toBe("ABCDE");
   });
 
+  it("should add many ingredients in order, reading them in parallel", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    });
+    const files = ["CA.jpg", "A.jpg", "cloud.jpg", "C_with_CAWG_data.jpg"];
+    await builder.addIngredients(
+      files.map((file) => ({
+        ingredient: { title: file, relationship: "componentOf" },
+        asset: { path: `./tests/fixtures/${file}` },
+      })),
+      { concurrency: 2 },
+    );
+    const { ingredients } = builder.getManifestDefinition();
+    expect(ingredients!.map((ingredient) => ingredient.title)).toEqual(files);
+    expect(ingredients![0].validation_results).toBeDefined();
+  });
+
+  it("should add no ingredients if one cannot be read", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    });
+    await expect(
+      builder.addIngredients([
+        { ingredient: parent_json, asset: source },
+        { ingredient: "{}", asset: { path: "./tests/fixtures/missing.jpg" } },
+      ]),
+    ).rejects.toThrow();
+    expect(builder.getManifestDefinition().ingredients).toEqual([]);
+  });
+
+  it("should reference an ingredient manifest with a hashed URI", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    });
+    await builder.addIngredient(
+      JSON.stringify({ title: "CA.jpg", relationship: "componentOf" }),
+      { path: "./tests/fixtures/CA.jpg" },
+    );
+    const { active_manifest } = builder.getManifestDefinition().ingredients![0];
+    const manifest = builder.ingredientHashedUri({
+      ingredient: active_manifest!,
+    });
+    const actions = builder.ingredientHashedUri({
+      ingredient: active_manifest!,
+      assertion: "c2pa.actions",
+      alg: "sha512",
+    });
+    expect(manifest.url).toBe(`self#jumbf=/c2pa/${active_manifest}`);
+    expect(manifest.hash).toHaveLength(32);
+    expect(actions.url).toBe(
+      `self#jumbf=/c2pa/${active_manifest}/c2pa.assertions/c2pa.actions`,
+    );
+    expect(actions.hash).toHaveLength(64);
+    expect(() =>
+      builder.ingredientHashedUri({ ingredient: "missing" }),
+    ).toThrow("No ingredient");
+
+    builder.addAssertion("org.test.review", { reviewed: manifest });
+    const dest = { path: path.join(tempDir, "hashed-uri.jpg") };
+    const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+    builder.sign(signer, source, dest);
+
+    // Besides the review, the ingredient assertion c2pa-rs writes carries the
+    // same hash
+    const signed = await fs.readFile(dest.path);
+    const first = signed.indexOf(manifest.hash);
+    expect(signed.indexOf(manifest.hash, first + 1)).not.toBe(-1);
+    const review = (await Reader.fromAsset(dest))!
+      .getActive()!
+      .assertions!.find((assertion) => assertion.label === "org.test.review");
+    expect(review!.data).toEqual({
+      reviewed: {
+        url: manifest.url,
+        alg: "sha256",
+        hash: manifest.hash.toString("base64"),
+      },
+    });
+  });
+
+  it("should read a manifest definition object as JSON.stringify would", () => {
+    const definition = {
+      claim_generator_info: [{ name: "c2pa-js tests", version: undefined }],
+      format: "image/jpeg",
+      title: "object-definition",
+      vendor: undefined,
+      assertions: [
+        {
+          label: "org.test.created",
+          data: { when: new Date(0), count: 2, ratio: 0.5 },
+        },
+      ],
+    } as unknown as Manifest;
+    const fromObject = Builder.withJson(definition).getManifestDefinition();
+    const fromString = Builder.withJson(
+      JSON.parse(JSON.stringify(definition)),
+    ).getManifestDefinition();
+
+    expect(fromObject).toEqual(fromString);
+    expect(fromObject.assertions![0].data).toEqual({
+      when: "1970-01-01T00:00:00.000Z",
+      count: 2,
+      ratio: 0.5,
+    });
+  });
+
+  it("should reject a circular manifest definition", () => {
+    const definition: Record<string, unknown> = { title: "circular" };
+    definition.self = definition;
+
+    expect(() => Builder.withJson(definition as Manifest)).toThrow(
+      "circular",
+    );
+  });
+
+  it("should take resources from buffers in the definition", async () => {
+    const builder = Builder.withJson({
+      ...manifestDefinition,
+      thumbnail: { format: "image/jpeg", buffer: testThumbnail },
+      ingredients: [
+        {
+          title: "Test",
+          format: "image/jpeg",
+          relationship: "componentOf",
+          thumbnail: {
+            format: "image/jpeg",
+            identifier: "ingredient-thumb.jpg",
+            buffer: testThumbnail,
+          },
+        },
+      ],
+    });
+    expect(builder.getManifestDefinition().thumbnail).toEqual({
+      format: "image/jpeg",
+      identifier: "thumbnail",
+    });
+
+    const dest = { path: path.join(tempDir, "buffer-resources.jpg") };
+    const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+    builder.sign(signer, source, dest);
+
+    const active = (await Reader.fromAsset(dest))!.getActive()!;
+    expect(active.thumbnail).toBeDefined();
+    expect(active.ingredients![0].thumbnail).toBeDefined();
+  });
+
+  it("should reject buffers outside resource references", () => {
+    const definition = {
+      ...manifestDefinition,
+      assertions: [{ label: "org.test.bytes", data: testThumbnail }],
+    } as unknown as Manifest;
+
+    expect(() => Builder.withJson(definition)).toThrow(
+      "must be the buffer of a resource reference",
+    );
+  });
+
   describe("Sign and Archive", () => {
     let builder: BuilderInterface;
 
@@ -221,6 +403,406 @@ describe("Builder", () => {
       expect(activeManifest?.title).toBe("Test_Manifest");
     });
 
+    it("should keep the manifest label from the definition", async () => {
+      // The label is the only identifier c2pa-rs takes from the caller;
+      // instance IDs, salts, and signing times change on every signature.
+      const label = "urn:c2pa:1b3b1ad8-4c2b-4b43-9b6b-2d5e4f6a7b8c";
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const definition: Manifest = {
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        title: "Labeled",
+        format: "image/jpeg",
+        label,
+        ingredients: [],
+        assertions: [],
+        resources: { resources: {} },
+      };
+      const labels: (string | undefined)[] = [];
+      for (let i = 0; i < 2; i++) {
+        const dest: DestinationBufferAsset = { buffer: null };
+        Builder.withJson(definition).sign(signer, source, dest);
+        const reader = await Reader.fromAsset({
+          buffer: dest.buffer!,
+          mimeType: "image/jpeg",
+        });
+        labels.push(reader!.activeLabel());
+      }
+      expect(labels).toEqual([label, label]);
+    });
+
+    it("should re-sign an asset with its manifest as the parent", async () => {
+      const original = await Reader.fromAsset({
+        path: "./tests/fixtures/CA.jpg",
+      });
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const dest: DestinationBufferAsset = { buffer: null };
+      const bytes = await Builder.resign(
+        {
+          claim_generator_info: [{ name: "platform", version: "1.0.0" }],
+          title: "Resigned",
+          format: "image/jpeg",
+        },
+        signer,
+        { path: "./tests/fixtures/CA.jpg" },
+        dest,
+      );
+      expect(bytes.length).toBeGreaterThan(0);
+
+      const reader = await Reader.fromAsset({
+        buffer: dest.buffer!,
+        mimeType: "image/jpeg",
+      });
+      const active = reader!.getActive()!;
+      expect(active.title).toBe("Resigned");
+      expect(active.ingredients).toHaveLength(1);
+      expect(active.ingredients![0]).toMatchObject({
+        relationship: "parentOf",
+        active_manifest: original!.activeLabel(),
+      });
+      expect(reader!.json().validation_state).toBe("Valid");
+    });
+
+    it("should report invalid arguments with a code", () => {
+      const builder = Builder.new();
+      let error: unknown;
+      try {
+        builder.setIntent("bogus" as any);
+      } catch (err) {
+        error = err;
+      }
+      expect(error).toMatchObject({
+        code: "INVALID_ARGUMENT",
+        operation: "Builder.setIntent",
+      });
+    });
+
+    it("should hash with the algorithm asked for", async () => {
+      const builder = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      });
+      builder.setIntent("edit");
+      builder.setHashAlgorithm("sha384");
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const dest: DestinationBufferAsset = { buffer: null };
+      builder.sign(signer, source, dest);
+
+      const signed = { buffer: dest.buffer!, mimeType: "image/jpeg" };
+      const binding = await readDataHash(signed);
+      expect(binding!.alg).toBe("sha384");
+      expect(binding!.hash).toHaveLength(48);
+      const reader = await Reader.fromAsset(signed);
+      expect(reader!.json().validation_state).toBe("Valid");
+      expect(() =>
+        builder.setHashAlgorithm("md5" as HashAlgorithm),
+      ).toThrow("Unknown hash algorithm");
+    });
+
+    it("should write the BMFF hash version asked for", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      for (const version of [2, 3] as const) {
+        const builder = Builder.withJson({
+          claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        });
+        builder.setIntent("edit");
+        builder.setBmffHashVersion(version);
+        const dest: DestinationBufferAsset = { buffer: null };
+        builder.sign(signer, { path: "./tests/fixtures/sample.m4a" }, dest);
+
+        expect(dest.buffer!.includes(`c2pa.hash.bmff.v${version}`)).toBe(true);
+        const reader = await Reader.fromAsset({
+          buffer: dest.buffer!,
+          mimeType: "audio/mp4",
+        });
+        expect(reader!.json().validation_state).toBe("Valid");
+      }
+      expect(() => Builder.new().setBmffHashVersion(1 as 2)).toThrow(
+        "Unsupported BMFF hash version",
+      );
+
+      const claimV2 = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        claim_version: 2,
+      });
+      expect(() => claimV2.setBmffHashVersion(2)).toThrow(
+        "needs claim version 1",
+      );
+      claimV2.setBmffHashVersion(3);
+    });
+
+    it("should require a time authority to re-timestamp", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      await expect(
+        Builder.retimestamp(
+          { claim_generator_info: [{ name: "archive", version: "1.0.0" }] },
+          signer,
+          { path: "./tests/fixtures/CA.jpg" },
+          { buffer: null },
+        ),
+      ).rejects.toThrow("time authority");
+    });
+
+    it("should fetch timestamps when re-timestamping", async () => {
+      // Nothing listens here, so the fetch fails once signing asks for it.
+      const signer = LocalSigner.newSigner(
+        publicKey,
+        privateKey,
+        "es256",
+        "http://127.0.0.1:9/",
+      );
+      const definition = {
+        claim_generator_info: [{ name: "archive", version: "1.0.0" }],
+      };
+      await expect(
+        Builder.retimestamp(
+          definition,
+          signer,
+          { path: "./tests/fixtures/A.jpg" },
+          { buffer: null },
+        ),
+      ).rejects.toThrow("no manifest");
+      await expect(
+        Builder.retimestamp(
+          definition,
+          signer,
+          { path: "./tests/fixtures/CA.jpg" },
+          { buffer: null },
+          { scope: "all" },
+        ),
+      ).rejects.toThrow("timestamp");
+    });
+
+    it("should place the manifest store as asked", () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const remoteUrl = "https://example.com/manifests/test.c2pa";
+      const placed = (placement: ManifestPlacement) => {
+        const builder = Builder.withJson({
+          claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+          title: "Placed",
+          format: "image/jpeg",
+        });
+        builder.setPlacement(placement);
+        const dest: DestinationBufferAsset = { buffer: null };
+        builder.sign(signer, source, dest);
+        const signed = dest.buffer! as Buffer;
+        return {
+          embedded: signed.includes("c2pa.signature"),
+          remote: signed.includes(remoteUrl),
+        };
+      };
+
+      expect(placed("embedded")).toEqual({ embedded: true, remote: false });
+      expect(placed("sidecar")).toEqual({ embedded: false, remote: false });
+      expect(placed({ remoteUrl })).toEqual({ embedded: false, remote: true });
+      expect(placed({ remoteUrl, embed: true })).toEqual({
+        embedded: true,
+        remote: true,
+      });
+      expect(() => placed({ remoteUrl: "manifests/test.c2pa" })).toThrow(
+        "Invalid remote URL",
+      );
+    });
+
+    it("should sign an animated GIF", async () => {
+      const gif = await fs.readFile("./tests/fixtures/animated.gif");
+      const dest = { path: path.join(tempDir, "signed.gif") };
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+
+      expect(Builder.supportedMimeTypes()).toContain("image/gif");
+      builder.sign(signer, { buffer: gif, mimeType: "image/gif" }, dest);
+
+      const signed = await fs.readFile(dest.path);
+      // The loop extension survives alongside the manifest's own block
+      expect(signed.includes("NETSCAPE2.0")).toBe(true);
+      expect(signed.includes("C2PA_GIF")).toBe(true);
+
+      const reader = await Reader.fromAsset(dest);
+      expect(reader).not.toBeNull();
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
+    it("should sign a DNG detected from its content", async () => {
+      const dng = await fs.readFile("./tests/fixtures/camera.dng");
+      const dest = { path: path.join(tempDir, "signed.dng") };
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+
+      builder.sign(signer, { buffer: dng }, dest);
+
+      expect(builder.getManifestDefinition().format).toBe("image/x-adobe-dng");
+      const reader = await Reader.fromAsset(dest);
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
+    it("should sign an SVG without disturbing its XML structure", async () => {
+      const svg = await fs.readFile("./tests/fixtures/design.svg");
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const dest: DestinationBufferAsset = { buffer: null };
+
+      builder.sign(signer, { buffer: svg }, dest);
+
+      const signed = dest.buffer!.toString("utf8");
+      const prolog = svg.toString("utf8").split("<svg")[0];
+      expect(signed.startsWith(prolog)).toBe(true);
+      expect(signed).toContain("<title>Square &amp; circle</title>");
+      expect(signed.match(/<c2pa:manifest\b/g)).toHaveLength(1);
+
+      expect(builder.getManifestDefinition().format).toBe("image/svg+xml");
+      const reader = await Reader.fromAsset({
+        buffer: dest.buffer!,
+        mimeType: "image/svg+xml",
+      });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+
+      // Signing again replaces the manifest rather than adding another
+      const resigned: DestinationBufferAsset = { buffer: null };
+      builder.sign(signer, { buffer: dest.buffer! }, resigned);
+      expect(
+        resigned.buffer!.toString("utf8").match(/<c2pa:manifest\b/g),
+      ).toHaveLength(1);
+    });
+
+    it("should not sign XML documents that are not SVG", () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const xml = Buffer.from('<?xml version="1.0"?><note><svg/></note>');
+
+      expect(() =>
+        builder.sign(signer, { buffer: xml }, { buffer: null }),
+      ).toThrow("Input asset must have a mime type");
+    });
+
+    it("should not detect an SVG with a long prolog", () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const svg = Buffer.from(
+        `<?xml version="1.0"?><!-- ${"x".repeat(1024)} -->` +
+          '<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"></svg>',
+      );
+
+      expect(() =>
+        builder.sign(signer, { buffer: svg }, { buffer: null }),
+      ).toThrow("Input asset must have a mime type");
+      const dest: DestinationBufferAsset = { buffer: null };
+      builder.sign(signer, { buffer: svg, mimeType: "image/svg+xml" }, dest);
+      expect(dest.buffer!.toString("utf8")).toContain("<c2pa:manifest");
+    });
+
+    it("should sign a HEIC image sequence", async () => {
+      const fixture = "./tests/fixtures/burst.heics";
+      const bytes = await fs.readFile(fixture);
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      // Identified by its file extension, its MIME type, and its content
+      const inputs: SourceAsset[] = [
+        { path: fixture },
+        { buffer: bytes, mimeType: "image/heic-sequence" },
+        { buffer: bytes },
+      ];
+
+      for (const [index, input] of inputs.entries()) {
+        const dest = { path: path.join(tempDir, `sequence-${index}.heics`) };
+        builder.sign(signer, input, dest);
+
+        expect(builder.getManifestDefinition().format).toBe("image/heic");
+        const reader = await Reader.fromAsset(dest);
+        expect(reader!.getActive()?.title).toBe("Test_Manifest");
+      }
+    });
+
+    it.each([
+      ["sample.wav", "audio/wav"],
+      ["sample.mp3", "audio/mpeg"],
+      ["sample.m4a", "audio/mp4"],
+    ])(
+      "should embed a manifest in %s signed from a stream",
+      async (fixture, mimeType) => {
+        const dest: FileAsset = {
+          path: path.join(tempDir, `signed-${fixture}`),
+        };
+        const signerConfig: JsCallbackSignerConfig = {
+          alg: "es256",
+          certs: [publicKey],
+          reserveSize: 10000,
+          tsaUrl: undefined,
+          directCoseHandling: false,
+        };
+        const signer = new TestSigner(privateKey);
+
+        await builder.signConfigAsync(
+          signer.sign,
+          signerConfig,
+          {
+            stream: fs.createReadStream(path.join("./tests/fixtures", fixture)),
+            mimeType,
+          },
+          dest,
+        );
+
+        // No sidecar is written, so the manifest must be read from the asset
+        expect(await fs.pathExists(dest.path.replace(/\.\w+$/, ".c2pa"))).toBe(
+          false,
+        );
+        expect(builder.getManifestDefinition().format).toBe(mimeType);
+        const reader = await Reader.fromAsset(dest);
+        expect(reader!.getActive()?.title).toBe("Test_Manifest");
+        expect(reader!.remoteUrl()).toBe("");
+      },
+    );
+
+    it("should write the manifest store to a sidecar", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const dest: FileAsset & SidecarDestination = {
+        path: path.join(tempDir, "sidecar.jpg"),
+        sidecar: {},
+      };
+
+      const manifest = builder.sign(signer, source, dest);
+
+      expect(dest.sidecar!.path).toBe(path.join(tempDir, "sidecar.c2pa"));
+      const sidecar = await fs.readFile(dest.sidecar!.path!);
+      expect(sidecar.equals(manifest)).toBe(true);
+      // Only the sidecar holds the manifest store
+      expect(await Reader.fromAsset({ path: dest.path })).toBeNull();
+      const reader = await Reader.fromManifestDataAndAsset(sidecar, {
+        path: dest.path,
+      });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+
+      // An existing sidecar is only replaced when asked to
+      let error: unknown;
+      try {
+        builder.sign(signer, source, dest);
+      } catch (err) {
+        error = err;
+      }
+      expect(error).toMatchObject({
+        code: "SIDECAR_EXISTS",
+        operation: "Builder.sign",
+      });
+      builder.sign(signer, source, {
+        path: dest.path,
+        sidecar: { overwrite: true },
+      });
+    });
+
+    it("should embed the manifest store and write a named sidecar", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const dest: DestinationBufferAsset & SidecarDestination = {
+        buffer: null,
+        sidecar: {
+          directory: tempDir,
+          fileName: "upload.manifest",
+          embed: true,
+        },
+      };
+
+      const manifest = builder.sign(signer, source, dest);
+
+      const sidecar = await fs.readFile(path.join(tempDir, "upload.manifest"));
+      expect(sidecar.equals(manifest)).toBe(true);
+      const reader = await Reader.fromAsset({
+        buffer: dest.buffer!,
+        mimeType: "image/jpeg",
+      });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
     it("should add a CBOR assertion, sign, and verify it in the signed manifest", async () => {
       // Add the c2pa.watermarked action as a CBOR assertion
       const actionsAssertion = {
@@ -379,6 +961,31 @@ describe("Builder", () => {
       expect(activeManifest?.title).toBe("Test_Manifest");
     });
 
+    it("should sign data with callback to a Writable stream", async () => {
+      const outputPath = path.join(tempDir, "callback_signed_stream.jpg");
+      const stream = fs.createWriteStream(outputPath);
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        directCoseHandling: false,
+      };
+      const signer = new TestSigner(privateKey);
+
+      const bytes = await builder.signConfigAsync(
+        signer.sign,
+        signerConfig,
+        source,
+        { stream },
+      );
+      expect(bytes.length).toBeGreaterThan(0);
+      expect(stream.writableFinished).toBe(true);
+
+      const reader = await Reader.fromAsset({ path: outputPath });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
     it("should sign data with callback to buffer", async () => {
       const dest: DestinationBufferAsset = {
         buffer: null,
@@ -445,6 +1052,309 @@ describe("Builder", () => {
       expect(activeManifest?.title).toBe("Test_Manifest");
     });
 
+    it("should sign a file in place", async () => {
+      const filePath = path.join(tempDir, "in_place.jpg");
+      await fs.copy("./tests/fixtures/A.jpg", filePath);
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+
+      builder.sign(signer, { path: filePath }, { path: filePath });
+
+      const reader = await Reader.fromAsset({ path: filePath });
+      expect(reader).not.toBeNull();
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+      expect(
+        (await fs.readdir(tempDir)).filter((name) => name.endsWith(".tmp")),
+      ).toHaveLength(0);
+    });
+
+    it("should keep the file mode when signing in place", async () => {
+      const filePath = path.join(tempDir, "in_place_mode.jpg");
+      await fs.copy("./tests/fixtures/A.jpg", filePath);
+      await fs.chmod(filePath, 0o600);
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+
+      builder.sign(signer, { path: filePath }, { path: filePath });
+
+      expect((await fs.stat(filePath)).mode & 0o777).toBe(0o600);
+    });
+
+    it("should sign data with callback signer to a Writable stream", async () => {
+      const outputPath = path.join(tempDir, "signed_stream.jpg");
+      const stream = fs.createWriteStream(outputPath);
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        directCoseHandling: false,
+      };
+      const testSigner = new TestSigner(privateKey);
+      const signer = CallbackSigner.newSigner(signerConfig, testSigner.sign);
+
+      const bytes = await builder.signAsync(signer, source, { stream });
+      expect(bytes.length).toBeGreaterThan(0);
+      expect(stream.writableFinished).toBe(true);
+
+      const reader = await Reader.fromAsset({ path: outputPath });
+      expect(reader).not.toBeNull();
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
+    it("should sign between assets backed by IO callbacks", async () => {
+      // An in-memory store standing in for a custom storage backend
+      const memoryIo = (data: Buffer) => {
+        let position = 0;
+        const io: AssetIo = {
+          read: async (length) => {
+            const chunk = data.subarray(position, position + length);
+            position += chunk.length;
+            return chunk;
+          },
+          seek: (offset, origin) => {
+            const base = { start: 0, current: position, end: data.length };
+            position = base[origin] + offset;
+            return position;
+          },
+          write: async (chunk) => {
+            const end = position + chunk.length;
+            if (end > data.length) {
+              data = Buffer.concat([data, Buffer.alloc(end - data.length)]);
+            }
+            chunk.copy(data, position);
+            position = end;
+          },
+        };
+        return { io, contents: () => data };
+      };
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        directCoseHandling: false,
+      };
+      const testSigner = new TestSigner(privateKey);
+      const signer = CallbackSigner.newSigner(signerConfig, testSigner.sign);
+      const input = memoryIo(await fs.readFile("./tests/fixtures/A.jpg"));
+      const output = memoryIo(Buffer.alloc(0));
+
+      await builder.signAsync(
+        signer,
+        { io: input.io, mimeType: "image/jpeg" },
+        { io: output.io },
+      );
+
+      const reader = await Reader.fromAsset({
+        buffer: output.contents(),
+        mimeType: "image/jpeg",
+      });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+
+      const { read, seek } = memoryIo(Buffer.alloc(0)).io;
+      await expect(
+        builder.signAsync(signer, source, { io: { read, seek } }),
+      ).rejects.toThrow("can only be used as sources");
+    });
+
+    it("should sign between file descriptors", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const outputPath = path.join(tempDir, "signed_fd.jpg");
+      const input = await fs.open("./tests/fixtures/A.jpg", "r");
+      const output = await fs.open(outputPath, "w+");
+      try {
+        builder.sign(
+          signer,
+          { fd: input, mimeType: "image/jpeg" },
+          { fd: output },
+        );
+      } finally {
+        await fs.close(input);
+        await fs.close(output);
+      }
+
+      const reader = await Reader.fromAsset({ path: outputPath });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
+    it("should leave a file descriptor untouched when signing fails", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const outputPath = path.join(tempDir, "kept_fd.jpg");
+      await fs.writeFile(outputPath, "original");
+      const output = await fs.open(outputPath, "r+");
+      try {
+        expect(() =>
+          builder.sign(
+            signer,
+            { buffer: Buffer.from("not an image"), mimeType: "image/jpeg" },
+            { fd: output },
+          ),
+        ).toThrow();
+      } finally {
+        await fs.close(output);
+      }
+
+      expect(await fs.readFile(outputPath, "utf8")).toBe("original");
+    });
+
+    it("should sign to a temporary file", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const output: DestinationAsset = { tempFile: true, extension: "jpg" };
+
+      builder.sign(signer, source, output);
+
+      const outputPath = (output as { path: string }).path;
+      expect(path.dirname(outputPath)).toBe(path.resolve(os.tmpdir()));
+      const reader = await Reader.fromAsset({ path: outputPath });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+      await fs.remove(outputPath);
+    });
+
+    it("should not leave a temporary file when signing fails", async () => {
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      const output: DestinationAsset = { tempFile: true, extension: "jpg" };
+
+      expect(() =>
+        builder.sign(
+          signer,
+          { buffer: Buffer.from("not an image"), mimeType: "image/jpeg" },
+          output,
+        ),
+      ).toThrow();
+      expect(
+        await fs.pathExists((output as { path: string }).path),
+      ).toBeFalsy();
+    });
+
+    it("should bound the data queued on a slow Writable stream", async () => {
+      const chunks: Buffer[] = [];
+      let maxQueued = 0;
+      const stream = new Writable({
+        highWaterMark: 1024,
+        write(chunk, _encoding, callback) {
+          maxQueued = Math.max(maxQueued, this.writableLength);
+          chunks.push(chunk);
+          setTimeout(callback, 1);
+        },
+      });
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        directCoseHandling: false,
+      };
+      const testSigner = new TestSigner(privateKey);
+      const signer = CallbackSigner.newSigner(signerConfig, testSigner.sign);
+
+      await builder.signAsync(signer, source, { stream });
+      expect(stream.writableFinished).toBe(true);
+      expect(maxQueued).toBeLessThanOrEqual(16 * 64 * 1024);
+
+      const reader = await Reader.fromAsset({
+        buffer: Buffer.concat(chunks),
+        mimeType: "image/jpeg",
+      });
+      expect(reader!.getActive()?.title).toBe("Test_Manifest");
+    });
+
+    it("should reject signing that exceeds its timeout", async () => {
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        directCoseHandling: false,
+      };
+      // A signing service that never answers
+      const signer = CallbackSigner.newSigner(
+        signerConfig,
+        () => new Promise<Buffer>(() => {}),
+      );
+      const start = Date.now();
+
+      await expect(
+        builder.signAsync(signer, source, { buffer: null }, { timeoutMs: 200 }),
+      ).rejects.toMatchObject({
+        message: "The operation timed out after 200 ms",
+        code: "TIMEOUT",
+        operation: "Builder.signAsync",
+      });
+      expect(Date.now() - start).toBeLessThan(5000);
+    });
+
+    it("should retry time stamp requests", async () => {
+      let requests = 0;
+      const server = http.createServer((req, res) => {
+        requests++;
+        res.statusCode = 503;
+        res.end();
+      });
+      await new Promise<void>((resolve) => server.listen(0, resolve));
+      const { port } = server.address() as AddressInfo;
+      const signerConfig: JsCallbackSignerConfig = {
+        alg: "es256",
+        certs: [publicKey],
+        reserveSize: 20000,
+        tsaUrl: `http://127.0.0.1:${port}/tsa`,
+        directCoseHandling: false,
+      };
+      const testSigner = new TestSigner(privateKey);
+      const signer = CallbackSigner.newSigner(signerConfig, testSigner.sign);
+
+      try {
+        await expect(
+          builder.signAsync(
+            signer,
+            source,
+            { buffer: null },
+            { network: { timestamp: { retries: 2 } } },
+          ),
+        ).rejects.toMatchObject({
+          code: "TIME_STAMP_FAILED",
+          network: {
+            endpoint: "timestamp",
+            url: `http://127.0.0.1:${port}/tsa`,
+            reason: "status",
+            status: 503,
+            attempts: 3,
+          },
+        });
+        expect(requests).toBe(3);
+      } finally {
+        server.close();
+      }
+    });
+
+    it("should reject Writable stream output for synchronous signing", () => {
+      const stream = fs.createWriteStream(
+        path.join(tempDir, "signed_stream_sync.jpg"),
+      );
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+
+      expect(() => builder.sign(signer, source, { stream })).toThrow(
+        "asynchronous",
+      );
+      stream.destroy();
+    });
+
+    it("should list signable formats and refuse read-only ones", () => {
+      expect(Builder.supportedMimeTypes()).toContain("image/jpeg");
+      expect(Builder.supportedMimeTypes()).not.toContain("application/pdf");
+      expect(Reader.supportedMimeTypes()).toContain("application/pdf");
+
+      const pdf = Buffer.from("%PDF-1.7\n");
+      const output = path.join(tempDir, "read_only.pdf");
+      const signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+      expect(() =>
+        builder.sign(
+          signer,
+          { buffer: pdf, mimeType: "application/pdf" },
+          { path: output },
+        ),
+      ).toThrow("application/pdf assets can be read but not signed");
+      expect(fs.existsSync(output)).toBe(false);
+    });
+
     it("should preserve JSON assertion characters without escaping", async () => {
       const fingerprintAssertion = JSON.stringify({
         alg: "sha256",
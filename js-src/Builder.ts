@@ -18,59 +18,183 @@ import type {
 } from "@contentauth/c2pa-types";
 
 import { getNeonBinary } from "./binary.js";
+import { networkArgument, settingsArgument } from "./Context.js";
+import type { Context } from "./Context.js";
 import type {
+  AddIngredientsOptions,
+  BmffHashVersion,
   BuilderInterface,
   C2paSettings,
   CallbackSignerInterface,
   ClaimVersion,
   DestinationAsset,
   FileAsset,
+  HashAlgorithm,
+  HashedUri,
+  HashedUriOptions,
   IdentityAssertionSignerInterface,
+  IngredientAsset,
   JsCallbackSignerConfig,
   LocalSignerInterface,
   ManifestAssertionKind,
+  ManifestDefinitionWithBuffers,
+  ManifestPlacement,
+  OperationOptions,
+  PreparedIngredient,
   ReaderInterface,
+  RetimestampOptions,
+  SoftBindingPluginInterface,
+  SignOptions,
   SourceAsset,
   NeonBuilderHandle,
 } from "./types.d.ts";
 import { IdentityAssertionSigner } from "./IdentityAssertion.js";
+import { Reader } from "./Reader.js";
+import { CallbackSigner } from "./Signer.js";
 
 export class Builder implements BuilderInterface {
   constructor(private builder: NeonBuilderHandle) {}
 
-  static new(settings?: C2paSettings): Builder {
-    const settingsStr = settings
-      ? typeof settings === "string"
-        ? settings
-        : JSON.stringify(settings)
-      : undefined;
-    const builder: NeonBuilderHandle = getNeonBinary().builderNew(settingsStr);
+  /**
+   * The MIME types the native library can sign. Formats such as PDF can be
+   * read but not signed, and signing them throws before the output is written.
+   */
+  static supportedMimeTypes(): string[] {
+    return getNeonBinary().builderSupportedMimeTypes();
+  }
+
+  static new(settings?: C2paSettings | Context): Builder {
+    const builder: NeonBuilderHandle = getNeonBinary().builderNew(
+      settingsArgument(settings),
+    );
     return new Builder(builder);
   }
 
-  static withJson(json: Manifest, settings?: C2paSettings): Builder {
-    let jsonString: string;
+  /**
+   * Create a Builder for an endorsement: an update manifest that adds a
+   * signature, such as an approver's, to an asset's existing manifest. The
+   * signed asset becomes the parent ingredient with a `c2pa.opened` action;
+   * the asset's content is not rebound, so it must not have changed.
+   * @param json Optional manifest definition, such as claim generator info
+   * @param settings Optional settings or Context
+   */
+  static endorsement(
+    json?: Manifest | ManifestDefinitionWithBuffers,
+    settings?: C2paSettings | Context,
+  ): Builder {
+    const builder = json
+      ? Builder.withJson(json, settings)
+      : Builder.new(settings);
+    builder.setIntent("update");
+    return builder;
+  }
+
+  /**
+   * Re-sign an asset under your own credential while keeping its provenance.
+   * The asset's manifest store is removed and becomes the parent ingredient
+   * of a new manifest, with a `c2pa.opened` action, signed by `signer`.
+   * @param json The new manifest definition, such as claim generator info
+   * @param signer A LocalSigner, or a CallbackSigner to sign asynchronously
+   * @param input The asset to re-sign
+   * @param output Where to write the re-signed asset
+   * @param settings Optional settings or Context
+   * @returns The bytes of the new manifest
+   */
+  static async resign(
+    json: Manifest | ManifestDefinitionWithBuffers,
+    signer: LocalSignerInterface | CallbackSignerInterface,
+    input: SourceAsset,
+    output: DestinationAsset,
+    settings?: C2paSettings | Context,
+  ): Promise<Buffer> {
+    const builder = Builder.withJson(json, settings);
+    try {
+      // The edit intent turns the source asset into the parent ingredient.
+      builder.setIntent("edit");
+      return signer instanceof CallbackSigner
+        ? await builder.signAsync(signer, input, output)
+        : builder.sign(signer as LocalSignerInterface, input, output);
+    } finally {
+      builder.dispose();
+    }
+  }
+
+  /**
+   * Renew the timestamps of an asset's manifests, so archived assets stay
+   * verifiable after the original time-stamping authority's certificate
+   * expires. The asset is verified, then an update manifest is signed with a
+   * `c2pa.time-stamp` assertion holding fresh timestamps, from the signer's
+   * time-stamping authority, for the signatures of the existing manifests.
+   * The asset is read twice, so it can't be a stream.
+   * @param json The update manifest definition, such as claim generator info
+   * @param signer A LocalSigner or CallbackSigner with a time authority URL
+   * @param input The asset to re-timestamp
+   * @param output Where to write the re-timestamped asset
+   * @param options Which manifests to timestamp
+   * @param settings Optional settings or Context, such as the trust anchors
+   * to verify the asset with
+   * @returns The bytes of the new manifest
+   */
+  static async retimestamp(
+    json: Manifest | ManifestDefinitionWithBuffers,
+    signer: LocalSignerInterface | CallbackSignerInterface,
+    input: SourceAsset,
+    output: DestinationAsset,
+    options?: RetimestampOptions,
+    settings?: C2paSettings | Context,
+  ): Promise<Buffer> {
+    if (!signer.timeAuthorityUrl()) {
+      throw new Error("Re-timestamping needs a signer with a time authority");
+    }
+    const reader = await Reader.fromAsset(input, settings);
+    if (!reader) {
+      throw new Error("The asset has no manifest to timestamp");
+    }
+    let labels: string[];
     try {
-      jsonString = JSON.stringify(json);
-    } catch (error) {
-      // TODO: errors should be standardized across JS and Node
-      if (error instanceof Error) {
-        throw new Error(
-          `Failed to stringify JSON Manifest Definition: ${error.message}`,
-        );
+      const store = reader.json();
+      if (store.validation_state === "Invalid") {
+        const codes = reader.validationMessages("errors").map((m) => m.code);
+        throw new Error(`The asset's manifest is invalid: ${codes.join(", ")}`);
       }
-      throw new Error(
-        "Failed to stringify JSON Manifest Definition: Unknown error",
-      );
+      labels = Object.keys(store.manifests);
+      if (options?.scope !== "all") {
+        labels = labels.filter((label) => label === store.active_manifest);
+      }
+    } finally {
+      reader.dispose();
+    }
+
+    // Time-stamp assertions belong in update manifests, which leave the
+    // asset's content binding alone.
+    const builder = Builder.endorsement(json, settings);
+    try {
+      for (const label of labels) {
+        builder.addTimestamp(label);
+      }
+      return signer instanceof CallbackSigner
+        ? await builder.signAsync(signer, input, output)
+        : builder.sign(signer as LocalSignerInterface, input, output);
+    } finally {
+      builder.dispose();
     }
-    const settingsStr = settings
-      ? typeof settings === "string"
-        ? settings
-        : JSON.stringify(settings)
-      : undefined;
+  }
+
+  /**
+   * Create a Builder from a manifest definition. Resource references in the
+   * definition, such as `thumbnail`, can carry their bytes in a `buffer`
+   * field instead of being added with `addResource`.
+   * @param json The manifest definition
+   * @param settings Optional settings or Context
+   */
+  static withJson(
+    json: Manifest | ManifestDefinitionWithBuffers,
+    settings?: C2paSettings | Context,
+  ): Builder {
+    // The definition is converted natively, as JSON.stringify would.
     const builder: NeonBuilderHandle = getNeonBinary().builderWithJson(
-      jsonString,
-      settingsStr,
+      json,
+      settingsArgument(settings),
     );
     return new Builder(builder);
   }
@@ -80,6 +204,26 @@ export class Builder implements BuilderInterface {
     getNeonBinary().builderSetIntent.call(this.builder, intentString);
   }
 
+  setHashAlgorithm(alg: HashAlgorithm): void {
+    getNeonBinary().builderUpdateManifestProperty.call(
+      this.builder,
+      "hash_alg",
+      alg,
+    );
+  }
+
+  setBmffHashVersion(version: BmffHashVersion): void {
+    getNeonBinary().builderUpdateManifestProperty.call(
+      this.builder,
+      "bmff_hash_version",
+      version,
+    );
+  }
+
+  addTimestamp(manifestLabel: string): void {
+    getNeonBinary().builderAddTimestamp.call(this.builder, manifestLabel);
+  }
+
   setNoEmbed(noEmbed = true): void {
     getNeonBinary().builderSetNoEmbed.call(this.builder, noEmbed);
   }
@@ -88,6 +232,21 @@ export class Builder implements BuilderInterface {
     getNeonBinary().builderSetRemoteUrl.call(this.builder, remoteUrl);
   }
 
+  setPlacement(placement: ManifestPlacement): void {
+    if (typeof placement === "string") {
+      getNeonBinary().builderSetPlacement.call(
+        this.builder,
+        placement === "embedded",
+      );
+    } else {
+      getNeonBinary().builderSetPlacement.call(
+        this.builder,
+        placement.embed ?? false,
+        placement.remoteUrl,
+      );
+    }
+  }
+
   addAction(actionJson: string): void {
     return getNeonBinary().builderAddAction.call(this.builder, actionJson);
   }
@@ -105,6 +264,15 @@ export class Builder implements BuilderInterface {
     );
   }
 
+  ingredientHashedUri(options: HashedUriOptions): HashedUri {
+    return getNeonBinary().builderHashedUri.call(
+      this.builder,
+      options.ingredient,
+      options.assertion,
+      options.alg,
+    );
+  }
+
   async addResource(uri: string, resource: SourceAsset): Promise<void> {
     return getNeonBinary().builderAddResource.call(this.builder, uri, resource);
   }
@@ -136,21 +304,52 @@ export class Builder implements BuilderInterface {
     return JSON.parse(result);
   }
 
+  async addPreparedIngredient(prepared: PreparedIngredient): Promise<void> {
+    getNeonBinary().builderAddIngredient.call(
+      this.builder,
+      JSON.stringify(prepared.ingredient),
+    );
+    for (const [identifier, resource] of Object.entries(prepared.resources)) {
+      await this.addResource(identifier, resource);
+    }
+  }
+
+  async addIngredients(
+    ingredients: IngredientAsset[],
+    options?: AddIngredientsOptions,
+  ): Promise<void> {
+    return getNeonBinary().builderAddIngredientsFromAssets.call(
+      this.builder,
+      ingredients.map(({ ingredient }) =>
+        typeof ingredient === "string"
+          ? ingredient
+          : JSON.stringify(ingredient),
+      ),
+      ingredients.map(({ asset }) => asset),
+      options?.concurrency,
+    );
+  }
+
+  addSoftBindingPlugin(plugin: SoftBindingPluginInterface): void {
+    getNeonBinary().builderAddSoftBindingPlugin.call(
+      this.builder,
+      plugin.getHandle(),
+    );
+  }
+
   async toArchive(asset: DestinationAsset): Promise<void> {
     return getNeonBinary().builderToArchive.call(this.builder, asset);
   }
 
   static async fromArchive(
     asset: SourceAsset,
-    settings?: C2paSettings,
+    settings?: C2paSettings | Context,
   ): Promise<Builder> {
-    const settingsStr = settings
-      ? typeof settings === "string"
-        ? settings
-        : JSON.stringify(settings)
-      : undefined;
     return new Builder(
-      await getNeonBinary().builderFromArchive(asset, settingsStr),
+      await getNeonBinary().builderFromArchive(
+        asset,
+        settingsArgument(settings),
+      ),
     );
   }
 
@@ -186,6 +385,7 @@ export class Builder implements BuilderInterface {
     signerConfig: JsCallbackSignerConfig,
     input: SourceAsset,
     output: DestinationAsset,
+    options?: OperationOptions,
   ): Promise<Buffer> {
     return getNeonBinary()
       .builderSignConfigAsync.call(
@@ -194,6 +394,8 @@ export class Builder implements BuilderInterface {
         signerConfig,
         input,
         output,
+        options?.timeoutMs,
+        options?.priority,
       )
       .then((result: Buffer | { manifest: Buffer; signedAsset: Buffer }) => {
         // output is a buffer and result is the manifest and the signed asset.
@@ -218,14 +420,24 @@ export class Builder implements BuilderInterface {
     signer: CallbackSignerInterface | IdentityAssertionSignerInterface,
     input: SourceAsset,
     output: DestinationAsset,
+    options?: SignOptions,
   ): Promise<Buffer> {
     const neonHandle = signer.getHandle();
     const isIdentity = signer instanceof IdentityAssertionSigner;
+    const network = networkArgument(options?.network);
     const neonFn = isIdentity
       ? getNeonBinary().builderIdentitySignAsync
       : getNeonBinary().builderSignAsync;
     return neonFn
-      .call(this.builder, neonHandle, input, output)
+      .call(
+        this.builder,
+        neonHandle,
+        input,
+        output,
+        options?.timeoutMs,
+        options?.priority,
+        network && JSON.stringify(network),
+      )
       .then((result: Buffer | { manifest: Buffer; signedAsset: Buffer }) => {
         // output is a buffer and result is the manifest and the signed asset.
         if ("buffer" in output) {
@@ -259,6 +471,14 @@ export class Builder implements BuilderInterface {
     );
   }
 
+  dispose(): void {
+    getNeonBinary().builderDispose.call(this.builder);
+  }
+
+  memoryUsage(): number {
+    return getNeonBinary().builderMemoryUsage.call(this.builder);
+  }
+
   getHandle(): NeonBuilderHandle {
     return this.builder;
   }
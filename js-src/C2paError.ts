@@ -0,0 +1,68 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type {
+  C2paErrorCode,
+  C2paErrorContext,
+  NetworkFailure,
+} from "./types.d.ts";
+
+/**
+ * An error thrown by a native operation. The native layer creates plain
+ * `Error` objects carrying these properties, so `instanceof C2paError`
+ * checks for them rather than for the prototype.
+ */
+export class C2paError extends Error {
+  /** Stable identifier to branch on instead of the message */
+  readonly code: C2paErrorCode;
+  /** The operation that failed, e.g. "Reader.fromAsset" */
+  readonly operation: string;
+  /** The underlying error variant, from c2pa-rs when it raised the error */
+  readonly variant: string;
+  /** Where the error happened, as far as the native layer knows */
+  readonly context: C2paErrorContext;
+  /** The request that failed, when the operation failed because of one */
+  readonly network?: NetworkFailure;
+
+  constructor(
+    message: string,
+    code: C2paErrorCode,
+    operation: string,
+    variant: string,
+  ) {
+    super(message);
+    this.code = code;
+    this.operation = operation;
+    this.variant = variant;
+    this.context = { operation };
+  }
+
+  static [Symbol.hasInstance](value: unknown): boolean {
+    return (
+      value instanceof Error &&
+      typeof (value as Partial<C2paError>).code === "string" &&
+      typeof (value as Partial<C2paError>).operation === "string" &&
+      typeof (value as Partial<C2paError>).variant === "string"
+    );
+  }
+}
+
+/**
+ * Whether `error` came from a native operation, optionally with the given code.
+ */
+export function isC2paError(
+  error: unknown,
+  code?: C2paErrorCode,
+): error is C2paError {
+  return error instanceof C2paError && (!code || error.code === code);
+}
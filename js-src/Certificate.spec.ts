@@ -0,0 +1,58 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import * as fs from "fs-extra";
+
+import { parseCertificates } from "./Certificate.js";
+import { isC2paError } from "./C2paError.js";
+
+describe("Certificate", () => {
+  it("describes each certificate in a PEM chain", async () => {
+    const pem = await fs.readFile("./tests/fixtures/certs/es256.pub", "utf8");
+    const [signer, intermediate] = parseCertificates(pem);
+    expect(signer).toMatchObject({
+      commonName: "C2PA Signer",
+      issuer:
+        "C=US, ST=CA, L=Somewhere, O=C2PA Test Intermediate Root CA, OU=FOR TESTING_ONLY, CN=Intermediate CA",
+      extendedKeyUsages: ["emailProtection"],
+      isCa: false,
+      selfSigned: false,
+      c2paSigning: { suitable: true, reason: null },
+    });
+    expect(signer.subject).toContain("O=C2PA Test Signing Cert");
+    expect(signer.notBefore).toEqual(new Date("2022-06-10T18:46:40Z"));
+    expect(signer.notAfter.getTime()).toBeGreaterThan(Date.now());
+    expect(intermediate).toMatchObject({
+      commonName: "Intermediate CA",
+      isCa: true,
+      c2paSigning: { suitable: false },
+    });
+  });
+
+  it("accepts DER certificates", async () => {
+    const pem = await fs.readFile("./tests/fixtures/certs/ed25519.pub", "utf8");
+    const base64 = pem.split("-----")[2].replace(/\s/g, "");
+    const [fromDer] = parseCertificates(Buffer.from(base64, "base64"));
+    expect(fromDer).toEqual(parseCertificates(pem)[0]);
+  });
+
+  it("rejects data without certificates", () => {
+    let error: unknown;
+    try {
+      parseCertificates(Buffer.from("not a certificate"));
+    } catch (err) {
+      error = err;
+    }
+    expect(isC2paError(error, "CERTIFICATE_INVALID")).toBe(true);
+  });
+});
@@ -0,0 +1,39 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type { CertificateInfo } from "./types.d.ts";
+
+type NativeCertificateInfo = Omit<CertificateInfo, "notBefore" | "notAfter"> & {
+  notBefore: number;
+  notAfter: number;
+};
+
+/**
+ * Describe the certificates in a PEM chain, or a single DER certificate: their
+ * subject, issuer, alternative names, extended key usages, validity, and
+ * whether they are suitable for signing C2PA manifests.
+ * @param input PEM text, or a buffer with PEM or DER data
+ * @returns One entry per certificate, in the order given
+ * @throws C2paError with code CERTIFICATE_INVALID when a certificate cannot be parsed
+ */
+export function parseCertificates(input: string | Buffer): CertificateInfo[] {
+  const parsed: NativeCertificateInfo[] = JSON.parse(
+    getNeonBinary().parseCertificates(input),
+  );
+  return parsed.map((info) => ({
+    ...info,
+    notBefore: new Date(info.notBefore * 1000),
+    notAfter: new Date(info.notAfter * 1000),
+  }));
+}
@@ -0,0 +1,99 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+
+import {
+  knownClaimGenerators,
+  recognizeClaimGenerator,
+} from "./ClaimGenerator.js";
+import { Reader } from "./Reader.js";
+
+describe("recognizeClaimGenerator", () => {
+  it("recognizes a v1 claim generator string", () => {
+    expect(
+      recognizeClaimGenerator(
+        "Adobe_Photoshop/25.3.1 adobe_c2pa/0.7.6 c2pa-rs/0.25.2",
+      ),
+    ).toEqual({
+      name: "Photoshop",
+      version: "25.3.1",
+      displayName: "Photoshop 25.3.1",
+      generator: "Adobe_Photoshop",
+      known: true,
+      vendor: "Adobe",
+    });
+  });
+
+  it("prefers claim generator info", () => {
+    const manifest = {
+      claim_generator: "Adobe_Lightroom/7.0",
+      claim_generator_info: [
+        { name: "c2pa-rs", version: "0.78.4" },
+        { name: "Adobe Photoshop", version: "25.3" },
+      ],
+    } as unknown as Manifest;
+    const recognized = recognizeClaimGenerator(manifest);
+    expect(recognized?.displayName).toBe("Photoshop 25.3");
+    expect(recognized?.generator).toBe("Adobe Photoshop");
+  });
+
+  it("returns unrecognized generators as is", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    const recognized = recognizeClaimGenerator(reader!.getActive()!);
+    expect(recognized).toEqual({
+      name: "make test images",
+      version: "0.33.1",
+      displayName: "make test images 0.33.1",
+      generator: "make_test_images",
+      known: false,
+    });
+  });
+
+  it("tries the caller's patterns first", () => {
+    const recognized = recognizeClaimGenerator("make_test_images/0.33.1", {
+      generators: [
+        {
+          match: "MAKE_TEST_IMAGES",
+          name: "Test Image Maker",
+          icon: "https://example.com/icon.svg",
+        },
+      ],
+    });
+    expect(recognized).toMatchObject({
+      name: "Test Image Maker",
+      displayName: "Test Image Maker 0.33.1",
+      known: true,
+      icon: "https://example.com/icon.svg",
+    });
+
+    const overridden = recognizeClaimGenerator("Adobe_Photoshop/25.3", {
+      generators: [{ match: /photoshop/gi, name: "PS" }],
+    });
+    expect(overridden?.displayName).toBe("PS 25.3");
+  });
+
+  it("returns null when there is no generator", () => {
+    expect(recognizeClaimGenerator("")).toBeNull();
+    expect(recognizeClaimGenerator({} as Manifest)).toBeNull();
+  });
+
+  it("ships frozen patterns", () => {
+    expect(Object.isFrozen(knownClaimGenerators)).toBe(true);
+    expect(
+      knownClaimGenerators.some((pattern) => pattern.name === "Firefly"),
+    ).toBe(true);
+  });
+});
@@ -0,0 +1,166 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+import type {
+  ClaimGeneratorPattern,
+  RecognizeClaimGeneratorOptions,
+  RecognizedClaimGenerator,
+} from "./types.d.ts";
+
+/**
+ * Products known to sign C2PA manifests, matched against the name in
+ * `claim_generator_info` or the product token of a v1 `claim_generator`
+ * string, e.g. "Adobe_Photoshop/25.3.1 adobe_c2pa/0.7.6 c2pa-rs/0.25.2".
+ */
+export const knownClaimGenerators: readonly Readonly<ClaimGeneratorPattern>[] =
+  Object.freeze(
+    [
+      { match: /^adobe[ _]?photoshop\b/i, name: "Photoshop", vendor: "Adobe" },
+      {
+        match: /^adobe[ _]?lightroom\b/i,
+        name: "Lightroom",
+        vendor: "Adobe",
+      },
+      { match: /^adobe[ _]?firefly\b/i, name: "Firefly", vendor: "Adobe" },
+      {
+        match: /^adobe[ _]?illustrator\b/i,
+        name: "Illustrator",
+        vendor: "Adobe",
+      },
+      {
+        match: /^adobe[ _]?premiere[ _]?pro\b/i,
+        name: "Premiere Pro",
+        vendor: "Adobe",
+      },
+      { match: /^adobe[ _]?express\b/i, name: "Express", vendor: "Adobe" },
+      { match: /^(openai[ _])?chatgpt\b/i, name: "ChatGPT", vendor: "OpenAI" },
+      { match: /^(openai[ _])?dall[-·]?e\b/i, name: "DALL·E", vendor: "OpenAI" },
+      {
+        match: /^(microsoft[ _])?designer\b/i,
+        name: "Designer",
+        vendor: "Microsoft",
+      },
+      {
+        match: /^(google[ _])?pixel[ _]camera\b/i,
+        name: "Pixel Camera",
+        vendor: "Google",
+      },
+      { match: /^leica\b/i, name: "Leica Camera", vendor: "Leica" },
+      { match: /^nikon\b/i, name: "Nikon Camera", vendor: "Nikon" },
+      { match: /^sony\b/i, name: "Sony Camera", vendor: "Sony" },
+      { match: /^truepic\b/i, name: "Truepic", vendor: "Truepic" },
+      {
+        match: /^c2patool$/i,
+        name: "c2patool",
+        vendor: "Content Authenticity Initiative",
+      },
+    ].map((pattern) => Object.freeze(pattern)),
+  );
+
+interface Generator {
+  name: string;
+  version?: string;
+  icon?: RecognizedClaimGenerator["manifestIcon"];
+}
+
+/**
+ * The generators a manifest names, in order: each `claim_generator_info`
+ * entry, or each `name/version` token of a `claim_generator` string.
+ */
+function generators(manifest: Manifest | string): Generator[] {
+  if (typeof manifest !== "string" && manifest.claim_generator_info?.length) {
+    return manifest.claim_generator_info
+      .filter((info) => typeof info.name === "string" && info.name !== "")
+      .map((info) => ({
+        name: info.name,
+        version: info.version ?? undefined,
+        icon: info.icon ?? undefined,
+      }));
+  }
+  const claimGenerator =
+    typeof manifest === "string" ? manifest : manifest.claim_generator;
+  return (claimGenerator ?? "")
+    .split(/\s+/)
+    .filter((token) => token !== "")
+    .map((token) => {
+      const slash = token.indexOf("/");
+      return slash === -1
+        ? { name: token }
+        : {
+            name: token.slice(0, slash),
+            version: token.slice(slash + 1) || undefined,
+          };
+    });
+}
+
+function matches(pattern: ClaimGeneratorPattern, name: string): boolean {
+  // `search` ignores `lastIndex`, so global patterns match every time.
+  return typeof pattern.match === "string"
+    ? pattern.match.toLowerCase() === name.toLowerCase()
+    : name.search(pattern.match) !== -1;
+}
+
+/**
+ * Work out which product signed a manifest, for showing e.g. "Signed with
+ * Photoshop 25.3.1". The first generator the manifest names that matches a
+ * pattern wins; the caller's patterns are tried before `knownClaimGenerators`.
+ * When none match, the first generator is returned as is, with `known` false.
+ * @param manifest A manifest from a Reader, or a v1 `claim_generator` string
+ * @param options Additional patterns to recognize
+ * @returns The product, or null when the manifest names no generator
+ */
+export function recognizeClaimGenerator(
+  manifest: Manifest | string,
+  options: RecognizeClaimGeneratorOptions = {},
+): RecognizedClaimGenerator | null {
+  const found = generators(manifest);
+  if (found.length === 0) {
+    return null;
+  }
+  const patterns = [...(options.generators ?? []), ...knownClaimGenerators];
+  for (const generator of found) {
+    const pattern = patterns.find((p) => matches(p, generator.name));
+    if (pattern) {
+      return describe(generator, pattern);
+    }
+  }
+  return describe(found[0]);
+}
+
+function describe(
+  generator: Generator,
+  pattern?: ClaimGeneratorPattern,
+): RecognizedClaimGenerator {
+  // v1 product tokens use underscores for spaces, e.g. "Adobe_Photoshop".
+  const name = pattern?.name ?? generator.name.replace(/_/g, " ");
+  const recognized: RecognizedClaimGenerator = {
+    name,
+    displayName: generator.version ? `${name} ${generator.version}` : name,
+    generator: generator.name,
+    known: pattern !== undefined,
+  };
+  if (generator.version) {
+    recognized.version = generator.version;
+  }
+  if (pattern?.vendor) {
+    recognized.vendor = pattern.vendor;
+  }
+  if (pattern?.icon) {
+    recognized.icon = pattern.icon;
+  }
+  if (generator.icon) {
+    recognized.manifestIcon = generator.icon;
+  }
+  return recognized;
+}
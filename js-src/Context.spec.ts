@@ -0,0 +1,69 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import * as fs from "fs-extra";
+
+import { Builder } from "./Builder.js";
+import { Context } from "./Context.js";
+import { Reader } from "./Reader.js";
+
+describe("Context", () => {
+  it("should resolve settings once at creation", () => {
+    const context = Context.new({
+      verify: { verify_after_reading: false },
+    });
+
+    const settings = context.settings() as any;
+    expect(settings.verify.verify_after_reading).toBe(false);
+    expect(settings).not.toHaveProperty("signer");
+  });
+
+  it("should leave out undefined settings", () => {
+    const context = Context.new({
+      verify: { verify_after_reading: false, verify_trust: undefined },
+    });
+
+    const settings = context.settings() as any;
+    expect(settings.verify.verify_after_reading).toBe(false);
+    expect(settings.verify.verify_trust).toBe(
+      (Context.new().settings() as any).verify.verify_trust,
+    );
+  });
+
+  it("should throw on invalid settings", () => {
+    expect(() => Context.new("{ invalid json")).toThrow();
+  });
+
+  it("should be shared by multiple readers", async () => {
+    const context = Context.new();
+    const buffer = await fs.readFile("./tests/fixtures/CA.jpg");
+
+    const first = await Reader.fromAsset(
+      { buffer, mimeType: "image/jpeg" },
+      context,
+    );
+    const second = await Reader.fromAsset(
+      { path: "./tests/fixtures/CA.jpg" },
+      context,
+    );
+
+    expect(first!.json()).toEqual(second!.json());
+  });
+
+  it("should be accepted by Builder", () => {
+    const context = Context.new({ builder: { thumbnail: { enabled: false } } });
+    const builder = Builder.new(context);
+
+    expect(builder.getManifestDefinition()).toBeDefined();
+  });
+});
@@ -0,0 +1,82 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  C2paSettings,
+  ContextInterface,
+  NeonContextHandle,
+  NetworkOptions,
+  NetworkPolicy,
+} from "./types.d.ts";
+
+export class Context implements ContextInterface {
+  constructor(private context: NeonContextHandle) {}
+
+  /**
+   * Create a Context from settings. The settings (including any trust lists
+   * loaded with `configureTrustSources`) are resolved once, here.
+   * @param settings Optional settings as a JSON/TOML string or object
+   */
+  static new(settings?: C2paSettings): Context {
+    return new Context(getNeonBinary().contextNew(settingsArgument(settings)));
+  }
+
+  settings(): Record<string, unknown> {
+    return JSON.parse(getNeonBinary().contextSettings.call(this.context));
+  }
+
+  getHandle(): NeonContextHandle {
+    return this.context;
+  }
+}
+
+/**
+ * Convert the settings accepted by Reader and Builder into the native argument:
+ * a Context handle, or a settings string or object.
+ */
+export function settingsArgument(
+  settings?: C2paSettings | Context,
+): C2paSettings | NeonContextHandle | undefined {
+  if (!settings) {
+    return undefined;
+  }
+  if (settings instanceof Context) {
+    return settings.getHandle();
+  }
+  return settings;
+}
+
+function networkPolicyArgument(policy?: NetworkPolicy) {
+  return (
+    policy && {
+      connect_timeout_ms: policy.connectTimeoutMs,
+      read_timeout_ms: policy.readTimeoutMs,
+      retries: policy.retries,
+    }
+  );
+}
+
+/**
+ * Convert network options into the form the native layer reads.
+ */
+export function networkArgument(options?: NetworkOptions) {
+  return (
+    options && {
+      ...networkPolicyArgument(options),
+      remote_manifest: networkPolicyArgument(options.remoteManifest),
+      ocsp: networkPolicyArgument(options.ocsp),
+      timestamp: networkPolicyArgument(options.timestamp),
+    }
+  );
+}
@@ -0,0 +1,62 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { createHash } from "node:crypto";
+import * as fs from "fs-extra";
+
+import { isC2paError } from "./C2paError.js";
+import { hashAsset, readDataHash } from "./Hashing.js";
+
+describe("Hashing", () => {
+  it("recomputes the data hash of a signed asset", async () => {
+    const asset = { path: "./tests/fixtures/CA.jpg" };
+    const binding = await readDataHash(asset);
+    expect(binding).toMatchObject({
+      alg: "sha256",
+      exclusions: [{ start: 20, length: 117273 }],
+    });
+    expect(await hashAsset(asset, binding!)).toEqual(binding!.hash);
+
+    const buffer = await fs.readFile(asset.path);
+    const hash = await hashAsset({ buffer, mimeType: "image/jpeg" }, binding!);
+    expect(hash).toEqual(binding!.hash);
+
+    // Changing a byte outside the exclusions changes the hash.
+    buffer[buffer.length - 3] ^= 0xff;
+    const tampered = await hashAsset({ buffer, mimeType: "image/jpeg" }, binding!);
+    expect(tampered).not.toEqual(binding!.hash);
+  });
+
+  it("hashes the whole asset without exclusions", async () => {
+    const buffer = await fs.readFile("./tests/fixtures/A.jpg");
+    const expected = createHash("sha384").update(buffer).digest();
+    expect(await hashAsset({ buffer }, { alg: "sha384" })).toEqual(expected);
+  });
+
+  it("returns null for an asset without a manifest", async () => {
+    expect(await readDataHash({ path: "./tests/fixtures/A.jpg" })).toBeNull();
+  });
+
+  it("rejects exclusions past the end of the asset", async () => {
+    const error = await hashAsset(
+      { path: "./tests/fixtures/A.jpg" },
+      { exclusions: [{ start: 0, length: 1e9 }] },
+    ).catch((err) => err);
+    expect(isC2paError(error, "INVALID_ARGUMENT")).toBe(true);
+    await expect(
+      hashAsset({ path: "./tests/fixtures/A.jpg" }, {
+        alg: "md5" as "sha256",
+      }),
+    ).rejects.toThrow('Unknown hash algorithm "md5"');
+  });
+});
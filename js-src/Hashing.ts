@@ -0,0 +1,61 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  DataHashBinding,
+  HashAssetOptions,
+  OperationOptions,
+  SourceAsset,
+} from "./types.d.ts";
+
+/**
+ * Hash an asset the way a C2PA data hash assertion does, leaving out the
+ * excluded byte ranges. Pass the `DataHashBinding` from `readDataHash` as the
+ * options to recompute the hash recorded in a manifest.
+ * @param asset The asset to hash
+ * @param options.alg The hash algorithm, sha256 by default
+ * @param options.exclusions Byte ranges to leave out of the hash
+ * @returns The hash
+ */
+export async function hashAsset(
+  asset: SourceAsset,
+  options?: HashAssetOptions & OperationOptions,
+): Promise<Buffer> {
+  return getNeonBinary().hashAsset(
+    asset,
+    options?.alg,
+    options?.exclusions ? JSON.stringify(options.exclusions) : undefined,
+    options?.timeoutMs,
+    options?.priority,
+  );
+}
+
+/**
+ * Read the data hash assertion of the active manifest embedded in an asset,
+ * without validating the manifest.
+ * @param asset The asset to read
+ * @returns The algorithm, hash, and exclusions, or null when the asset has no
+ * embedded manifest or its manifest is bound to the asset another way, such as
+ * with a BMFF hash for MP4 files
+ */
+export async function readDataHash(
+  asset: SourceAsset,
+  options?: OperationOptions,
+): Promise<DataHashBinding | null> {
+  return getNeonBinary().readDataHash(
+    asset,
+    options?.timeoutMs,
+    options?.priority,
+  );
+}
@@ -188,4 +188,223 @@ describe("IdentityAssertionBuilder", () => {
       mimeType: "image/jpeg",
     });
   });
+
+  it("should bind a verifiable credential to the claim", async () => {
+    const { CallbackSigner } = await import("./Signer");
+    const { Reader } = await import("./Reader");
+    const { Builder } = await import("./Builder");
+    const {
+      IdentityAssertionBuilder,
+      IdentityAssertionSigner,
+      VerifiableCredentialHolder,
+    } = await import("./IdentityAssertion");
+
+    const c2paTestSigner = new TestSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+    );
+    const c2paSigner = CallbackSigner.newSigner(
+      {
+        alg: "es256" as SigningAlg,
+        certs: [await fs.readFile("./tests/fixtures/certs/es256.pub")],
+        reserveSize: 10000,
+        directCoseHandling: true,
+      },
+      c2paTestSigner.sign,
+    );
+
+    // The aggregator's Ed25519 key, identified by a did:jwk issuer
+    const { publicKey, privateKey } = crypto.generateKeyPairSync("ed25519");
+    const jwk = JSON.stringify(publicKey.export({ format: "jwk" }));
+    const issuer = `did:jwk:${Buffer.from(jwk)
+      .toString("base64")
+      .replace(/\+/g, "-")
+      .replace(/\//g, "_")}`;
+    const verifiedAt = new Date(Date.now() - 60_000).toISOString();
+    const credentialHolder =
+      VerifiableCredentialHolder.newVerifiableCredentialHolder(
+        {
+          "@context": [
+            "https://www.w3.org/ns/credentials/v2",
+            "https://cawg.io/identity/1.1/ica/context/",
+          ],
+          type: ["VerifiableCredential", "IdentityClaimsAggregationCredential"],
+          issuer,
+          validFrom: verifiedAt,
+          credentialSubject: {
+            verifiedIdentities: [
+              {
+                type: "cawg.social_media",
+                username: "jsmith",
+                uri: "https://example.com/jsmith",
+                verifiedAt,
+                provider: { id: "https://example.com", name: "Example" },
+              },
+            ],
+          },
+        },
+        10000,
+        async (data: Buffer) => crypto.sign(null, data, privateKey),
+      );
+
+    const builder = Builder.withJson(manifestDefinition);
+    await builder.addResource("thumbnail.jpg", {
+      mimeType: "image/jpeg",
+      buffer: await fs.readFile("./tests/fixtures/thumbnail.jpg"),
+    });
+    await builder.addResource("ingredient-thumb.jpg", {
+      mimeType: "image/jpeg",
+      buffer: await fs.readFile("./tests/fixtures/thumbnail.jpg"),
+    });
+
+    const iaSigner = IdentityAssertionSigner.new(c2paSigner.getHandle());
+    const iab =
+      await IdentityAssertionBuilder.identityBuilderForCredentialHolder(
+        credentialHolder,
+      );
+    iab.addReferencedAssertions(["cawg.training-mining"]);
+    iab.addRoles(["cawg.creator"]);
+    iaSigner.addIdentityAssertion(iab);
+
+    const dest: DestinationBufferAsset = { buffer: null };
+    await builder.signAsync(
+      iaSigner,
+      {
+        buffer: await fs.readFile("./tests/fixtures/CA.jpg"),
+        mimeType: "image/jpeg",
+      },
+      dest,
+    );
+
+    const reader = await Reader.fromAsset({
+      buffer: dest.buffer! as Buffer,
+      mimeType: "image/jpeg",
+    });
+    const [identity] = reader!.report().manifests[0]!.identities;
+    expect(identity).toMatchObject({
+      sigType: "cawg.identity_claims_aggregation",
+      valid: true,
+      issuer,
+      failures: [],
+    });
+    expect(identity!.verifiedIdentities[0]!.username).toBe("jsmith");
+  });
+
+  it("should sign an X.509 identity assertion with a callback", async () => {
+    const { CallbackSigner } = await import("./Signer");
+    const { Reader } = await import("./Reader");
+    const { Builder } = await import("./Builder");
+    const {
+      IdentityAssertionBuilder,
+      IdentityAssertionSigner,
+      X509CredentialHolder,
+    } = await import("./IdentityAssertion");
+
+    const config: JsCallbackSignerConfig = {
+      alg: "es256" as SigningAlg,
+      certs: [await fs.readFile("./tests/fixtures/certs/es256.pub")],
+      reserveSize: 10000,
+      directCoseHandling: true,
+    };
+    const testSigner = new TestSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+    );
+    const c2paSigner = CallbackSigner.newSigner(config, testSigner.sign);
+    // The identity service only sees the bytes to sign
+    const signed: Buffer[] = [];
+    const identitySigner = CallbackSigner.newSigner(
+      config,
+      async (data: Buffer) => {
+        signed.push(data);
+        return testSigner.sign(data);
+      },
+    );
+
+    const builder = Builder.withJson(manifestDefinition);
+    await builder.addResource("thumbnail.jpg", {
+      mimeType: "image/jpeg",
+      buffer: await fs.readFile("./tests/fixtures/thumbnail.jpg"),
+    });
+    await builder.addResource("ingredient-thumb.jpg", {
+      mimeType: "image/jpeg",
+      buffer: await fs.readFile("./tests/fixtures/thumbnail.jpg"),
+    });
+
+    const iaSigner = IdentityAssertionSigner.new(c2paSigner.getHandle());
+    const iab =
+      await IdentityAssertionBuilder.identityBuilderForCredentialHolder(
+        X509CredentialHolder.newX509CredentialHolder(identitySigner),
+      );
+    iab.addReferencedAssertions(["cawg.training-mining"]);
+    iaSigner.addIdentityAssertion(iab);
+
+    const dest: DestinationBufferAsset = { buffer: null };
+    await builder.signAsync(
+      iaSigner,
+      {
+        buffer: await fs.readFile("./tests/fixtures/CA.jpg"),
+        mimeType: "image/jpeg",
+      },
+      dest,
+    );
+    expect(signed).toHaveLength(1);
+
+    const reader = await Reader.fromAsset({
+      buffer: dest.buffer! as Buffer,
+      mimeType: "image/jpeg",
+    });
+    const [identity] = reader!.report().manifests[0]!.identities;
+    expect(identity!.sigType).toBe("cawg.x509.cose");
+    expect(identity!.signer!.issuer).toBe("C2PA Test Signing Cert");
+    // The test certificate is not in the CAWG trust list
+    expect(identity!.failures.map((failure) => failure.code)).toEqual([
+      "signingCredential.untrusted",
+    ]);
+  });
+
+  it("should name identity signing as the failed operation", async () => {
+    const { CallbackSigner } = await import("./Signer");
+    const { Builder } = await import("./Builder");
+    const { IdentityAssertionSigner } = await import("./IdentityAssertion");
+    const c2paPrivateKey = await fs.readFile(
+      "./tests/fixtures/certs/es256.pem",
+    );
+    const c2paSigner = CallbackSigner.newSigner(
+      {
+        alg: "es256" as SigningAlg,
+        certs: [await fs.readFile("./tests/fixtures/certs/es256.pub")],
+        reserveSize: 10000,
+        tsaUrl: undefined,
+        tsaHeaders: undefined,
+        tsaBody: undefined,
+        directCoseHandling: true,
+      },
+      new TestSigner(c2paPrivateKey).sign,
+    );
+    const iaSigner = IdentityAssertionSigner.new(c2paSigner.getHandle());
+    const builder = Builder.withJson(manifestDefinition);
+
+    await expect(
+      builder.signAsync(
+        iaSigner,
+        { path: "./tests/fixtures/CA.jpg" },
+        { buffer: null },
+        { timeoutMs: -1 },
+      ),
+    ).rejects.toMatchObject({
+      code: "SETTINGS_INVALID",
+      operation: "Builder.identitySignAsync",
+    });
+  });
+
+  it("should reject a credential without a credential subject", async () => {
+    const { VerifiableCredentialHolder } = await import("./IdentityAssertion");
+
+    expect(() =>
+      VerifiableCredentialHolder.newVerifiableCredentialHolder(
+        { issuer: "did:web:example.com" } as any,
+        10000,
+        async (data: Buffer) => data,
+      ),
+    ).toThrow(/credentialSubject/);
+  });
 });
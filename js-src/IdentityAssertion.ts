@@ -14,13 +14,19 @@
 import { getNeonBinary } from "./binary.js";
 import type {
   CallbackCredentialHolderInterface,
+  CallbackSignerInterface,
   IdentityAssertionBuilderInterface,
   IdentityAssertionSignerInterface,
   NeonCallbackCredentialHolderHandle,
   NeonIdentityAssertionSignerHandle,
   NeonIdentityAssertionBuilderHandle,
+  NeonVerifiableCredentialHolderHandle,
+  NeonX509CredentialHolderHandle,
   SignerPayload,
   NeonCallbackSignerHandle,
+  VerifiableCredential,
+  VerifiableCredentialHolderInterface,
+  X509CredentialHolderInterface,
 } from "./types.d.ts";
 
 export class IdentityAssertionBuilder
@@ -29,7 +35,10 @@ export class IdentityAssertionBuilder
   constructor(private _builder: NeonIdentityAssertionBuilderHandle) {}
 
   static async identityBuilderForCredentialHolder(
-    credentialHolder: CallbackCredentialHolderInterface,
+    credentialHolder:
+      | CallbackCredentialHolderInterface
+      | VerifiableCredentialHolderInterface
+      | X509CredentialHolderInterface,
   ): Promise<IdentityAssertionBuilder> {
     const builder = getNeonBinary().identityBuilderForCredentialHolder(
       credentialHolder.getHandle(),
@@ -120,3 +129,62 @@ export class CallbackCredentialHolder
     );
   }
 }
+
+export class VerifiableCredentialHolder
+  implements VerifiableCredentialHolderInterface
+{
+  constructor(
+    private verifiableCredentialHolder: NeonVerifiableCredentialHolderHandle,
+  ) {}
+
+  getHandle(): NeonVerifiableCredentialHolderHandle {
+    return this.verifiableCredentialHolder;
+  }
+
+  /**
+   * Create a credential holder for an identity claims aggregation credential.
+   * When signing, the signer payload is bound to the credential as
+   * `credentialSubject.c2paAsset` and the credential is wrapped in a
+   * COSE_Sign1 signed by the issuer.
+   * @param credential The W3C verifiable credential, without `c2paAsset`
+   * @param reserveSize The space to reserve for the signed credential
+   * @param callback Signs the COSE data with the issuer's Ed25519 key
+   */
+  static newVerifiableCredentialHolder(
+    credential: VerifiableCredential,
+    reserveSize: number,
+    callback: (data: Buffer) => Promise<Buffer>,
+  ) {
+    const credentialHolder = getNeonBinary().newVerifiableCredentialHolder(
+      JSON.stringify(credential),
+      reserveSize,
+      callback,
+    );
+    return new VerifiableCredentialHolder(credentialHolder);
+  }
+}
+
+export class X509CredentialHolder implements X509CredentialHolderInterface {
+  constructor(private x509CredentialHolder: NeonX509CredentialHolderHandle) {}
+
+  getHandle(): NeonX509CredentialHolderHandle {
+    return this.x509CredentialHolder;
+  }
+
+  /**
+   * Create a credential holder that signs identity assertions as
+   * `cawg.x509.cose` with a callback signer. The signer payload is bound to
+   * the claim and wrapped in a COSE_Sign1 with the signer's certificate
+   * chain; the signer's callback only signs the bytes it is given, so an
+   * external identity service can hold the key.
+   * @param signer The callback signer holding the identity certificate
+   */
+  static newX509CredentialHolder(
+    signer: CallbackSignerInterface,
+  ): X509CredentialHolder {
+    const credentialHolder = getNeonBinary().newX509CredentialHolder(
+      signer.getHandle(),
+    );
+    return new X509CredentialHolder(credentialHolder);
+  }
+}
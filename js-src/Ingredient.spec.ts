@@ -0,0 +1,158 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+
+import { createHash } from "node:crypto";
+import * as fs from "fs-extra";
+
+import { Builder } from "./Builder.js";
+import {
+  clearIngredientCache,
+  configureIngredientCache,
+  createIngredient,
+  getIngredientCacheStats,
+} from "./Ingredient.js";
+import { Reader } from "./Reader.js";
+import { LocalSigner } from "./Signer.js";
+
+describe("createIngredient", () => {
+  it("extracts the ingredient and its resources from a signed asset", async () => {
+    const buffer = await fs.readFile("./tests/fixtures/CA.jpg");
+    const { ingredient, resources } = await createIngredient(
+      { buffer, mimeType: "image/jpeg" },
+      undefined,
+      { ingredient: { title: "CA.jpg", relationship: "componentOf" } },
+    );
+    expect(ingredient).toMatchObject({
+      title: "CA.jpg",
+      format: "image/jpeg",
+      relationship: "componentOf",
+      active_manifest:
+        "contentauth:urn:uuid:c2677d4b-0a93-4444-876f-ed2f2d40b8cf",
+      hash: createHash("sha256").update(buffer).digest("hex"),
+    });
+    expect(ingredient.validation_results?.activeManifest?.failure).toEqual([
+      expect.objectContaining({ code: "signingCredential.untrusted" }),
+    ]);
+    expect(resources[ingredient.thumbnail!.identifier].mimeType).toBe(
+      "image/jpeg",
+    );
+    expect(resources[ingredient.manifest_data!.identifier]).toMatchObject({
+      mimeType: "application/c2pa",
+    });
+  });
+
+  it("has no manifest data for an asset without a manifest", async () => {
+    const { ingredient, resources } = await createIngredient({
+      path: "./tests/fixtures/A.jpg",
+    });
+    expect(ingredient.active_manifest).toBeUndefined();
+    expect(ingredient.manifest_data).toBeUndefined();
+    expect(Object.keys(resources)).toEqual([ingredient.thumbnail!.identifier]);
+  });
+
+  it("can be added to a Builder later", async () => {
+    const prepared = await createIngredient(
+      { path: "./tests/fixtures/CA.jpg" },
+      undefined,
+      { ingredient: { title: "CA.jpg", relationship: "parentOf" } },
+    );
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    });
+    await builder.addPreparedIngredient(prepared);
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const dest = { buffer: null };
+    builder.sign(signer, { path: "./tests/fixtures/A.jpg" }, dest);
+
+    const reader = await Reader.fromAsset({
+      buffer: dest.buffer!,
+      mimeType: "image/jpeg",
+    });
+    const [ingredient] = reader!.getActive()!.ingredients!;
+    expect(ingredient.title).toBe("CA.jpg");
+    expect(ingredient.active_manifest).toBe(
+      prepared.ingredient.active_manifest,
+    );
+    expect(reader!.json().manifests[ingredient.active_manifest!]).toBeDefined();
+  });
+});
+
+describe("configureIngredientCache", () => {
+  const ingredientJson = JSON.stringify({
+    title: "CA.jpg",
+    relationship: "parentOf",
+  });
+
+  afterEach(() => {
+    configureIngredientCache(null);
+  });
+
+  it("reads an ingredient shared by many Builders once", async () => {
+    configureIngredientCache({});
+    const before = getIngredientCacheStats();
+    const ingredients = [];
+    for (let i = 0; i < 3; i++) {
+      const builder = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      });
+      await builder.addIngredient(ingredientJson, {
+        path: "./tests/fixtures/CA.jpg",
+      });
+      ingredients.push(builder.getManifestDefinition().ingredients![0]);
+    }
+
+    const stats = getIngredientCacheStats();
+    expect(stats).toMatchObject({ enabled: true, entries: 1 });
+    expect(stats.hits).toEqual(before.hits + 2);
+    expect(stats.misses).toEqual(before.misses + 1);
+    expect(ingredients[1]).toEqual(ingredients[0]);
+    expect(ingredients[2]).toEqual(ingredients[0]);
+  });
+
+  it("reads an asset again for different ingredient JSON", async () => {
+    configureIngredientCache({});
+    for (const title of ["first.jpg", "second.jpg"]) {
+      const { ingredient } = await createIngredient(
+        { path: "./tests/fixtures/CA.jpg" },
+        undefined,
+        { ingredient: { title } },
+      );
+      expect(ingredient.title).toBe(title);
+    }
+    expect(getIngredientCacheStats().entries).toEqual(2);
+
+    clearIngredientCache();
+    expect(getIngredientCacheStats()).toMatchObject({
+      enabled: true,
+      entries: 0,
+      memoryBytes: 0,
+    });
+  });
+
+  it("is off by default", async () => {
+    const before = getIngredientCacheStats();
+    await createIngredient({ path: "./tests/fixtures/CA.jpg" });
+    expect(getIngredientCacheStats()).toEqual({
+      ...before,
+      enabled: false,
+      entries: 0,
+      memoryBytes: 0,
+    });
+  });
+});
@@ -0,0 +1,82 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import { settingsArgument } from "./Context.js";
+import type { Context } from "./Context.js";
+import type {
+  C2paSettings,
+  CreateIngredientOptions,
+  IngredientCacheConfig,
+  IngredientCacheStats,
+  OperationOptions,
+  PreparedIngredient,
+  SourceAsset,
+} from "./types.d.ts";
+
+/**
+ * Create an ingredient from an asset without a Builder, validating any
+ * manifest it carries and extracting its thumbnail and manifest data. The
+ * result can be stored and added to a Builder later with
+ * `builder.addPreparedIngredient`.
+ * @param asset The ingredient asset, which must have a mime type
+ * @param settings Settings or a Context to read the asset with
+ * @param options.ingredient Fields that take precedence over those read from
+ * the asset, such as `title` and `relationship`
+ * @returns The ingredient and the resources it refers to
+ */
+export async function createIngredient(
+  asset: SourceAsset,
+  settings?: C2paSettings | Context,
+  options?: CreateIngredientOptions & OperationOptions,
+): Promise<PreparedIngredient> {
+  const { json, resources } = await getNeonBinary().createIngredient(
+    asset,
+    options?.ingredient ? JSON.stringify(options.ingredient) : undefined,
+    settingsArgument(settings),
+    options?.timeoutMs,
+    options?.priority,
+  );
+  return { ingredient: JSON.parse(json), resources };
+}
+
+/**
+ * Cache ingredients read from assets, so a batch of Builders adding the same
+ * ingredient validates its manifest and extracts its thumbnail once instead of
+ * once per Builder. Ingredients are looked up by the SHA-256 of the asset
+ * along with the ingredient JSON and settings they are read with, and apply to
+ * `builder.addIngredient` with an asset and to `createIngredient`. Replaces
+ * any previous cache configuration, dropping the cached ingredients.
+ * @param config The cache configuration, or null to turn caching off
+ */
+export function configureIngredientCache(
+  config: IngredientCacheConfig | null,
+): void {
+  getNeonBinary().configureIngredientCache(
+    config ? JSON.stringify({ max_memory_bytes: config.maxMemoryBytes }) : null,
+  );
+}
+
+/**
+ * Drop every cached ingredient, keeping the cache on.
+ */
+export function clearIngredientCache(): void {
+  getNeonBinary().clearIngredientCache();
+}
+
+/**
+ * Get the size of the ingredient cache and how many lookups it answered.
+ */
+export function getIngredientCacheStats(): IngredientCacheStats {
+  return JSON.parse(getNeonBinary().ingredientCacheStats());
+}
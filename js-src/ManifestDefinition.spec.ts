@@ -0,0 +1,102 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+
+import { validateManifestDefinition } from "./ManifestDefinition.js";
+
+describe("validateManifestDefinition", () => {
+  const definition: Manifest = {
+    claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    title: "Test_Manifest",
+    format: "image/jpeg",
+    thumbnail: { format: "image/jpeg", identifier: "thumbnail.jpg" },
+    ingredients: [
+      {
+        title: "Test",
+        format: "image/jpeg",
+        instance_id: "12345",
+        relationship: "parentOf",
+        thumbnail: { format: "image/jpeg", identifier: "ingredient-thumb.jpg" },
+      },
+    ],
+    assertions: [
+      {
+        label: "c2pa.actions.v2",
+        data: {
+          actions: [
+            { action: "c2pa.opened", parameters: { ingredientIds: ["12345"] } },
+          ],
+        },
+      },
+      {
+        label: "org.life.meaning__1",
+        data: { answer: 42 },
+        kind: "Json",
+      },
+    ],
+  } as Manifest;
+
+  it("accepts a valid definition", () => {
+    expect(validateManifestDefinition(definition)).toEqual([]);
+    expect(validateManifestDefinition(JSON.stringify(definition))).toEqual([]);
+  });
+
+  it("reports each problem with its path", () => {
+    const errors = validateManifestDefinition({
+      ...definition,
+      claim_version: 3,
+      ingredients: [
+        ...definition.ingredients!,
+        { title: "Other", relationship: "parentOf", label: "other" },
+      ],
+      assertions: [
+        {
+          label: "c2pa.actions",
+          data: {
+            actions: [
+              { action: "c2pa.placed", parameters: { ingredientIds: ["nope"] } },
+              { when: "2025-01-01T00:00:00Z" },
+            ],
+          },
+        },
+        { label: "not a label", data: {} },
+      ],
+    } as Manifest);
+    expect(errors).toEqual([
+      { path: "claim_version", message: "Expected 1 or 2" },
+      {
+        path: "ingredients[1].relationship",
+        message: "ingredients[0] is already the parent",
+      },
+      {
+        path: "assertions[0].data.actions[0].parameters.ingredientIds[0]",
+        message: 'No ingredient has the label or instance_id "nope"',
+      },
+      {
+        path: "assertions[0].data.actions[1].action",
+        message: "Missing action name",
+      },
+      {
+        path: "assertions[1].label",
+        message: expect.stringContaining("is not a dot-separated label"),
+      },
+    ]);
+  });
+
+  it("reports malformed JSON at the root", () => {
+    const [error] = validateManifestDefinition("{");
+    expect(error.path).toBe("");
+    expect(error.message).toMatch(/^Invalid JSON/);
+  });
+});
@@ -0,0 +1,33 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  ManifestDefinitionError,
+  ManifestDefinitionWithBuffers,
+} from "./types.d.ts";
+
+/**
+ * Check a manifest definition without creating a Builder or signing: its
+ * field types, assertion labels, the shape of actions assertions, ingredient
+ * relationships, resource references, and the ingredients actions refer to.
+ * @param definition The definition, or its JSON
+ * @returns The problems found, empty when there are none
+ */
+export function validateManifestDefinition(
+  definition: Manifest | ManifestDefinitionWithBuffers | string,
+): ManifestDefinitionError[] {
+  return JSON.parse(getNeonBinary().validateManifestDefinition(definition));
+}
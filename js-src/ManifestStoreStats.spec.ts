@@ -0,0 +1,81 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+import * as fs from "fs-extra";
+
+import { Builder } from "./Builder.js";
+import { getManifestStoreStats } from "./ManifestStoreStats.js";
+import { LocalSigner } from "./Signer.js";
+
+describe("getManifestStoreStats", () => {
+  it("measures an embedded manifest store", async () => {
+    const stats = (await getManifestStoreStats({
+      path: "./tests/fixtures/CA.jpg",
+    }))!;
+    expect(stats.manifestCount).toBe(1);
+    expect(stats.ingredientDepth).toBe(0);
+
+    const [manifest] = stats.manifests;
+    expect(manifest.label).toBe(stats.activeManifest);
+    expect(manifest.depth).toBe(0);
+    expect(manifest.ingredientCount).toBe(1);
+    expect(manifest.assertionCount).toBe(manifest.assertions.length);
+    expect(manifest.resources.map((resource) => resource.label)).toEqual([
+      "c2pa.thumbnail.claim.jpeg",
+      "c2pa.thumbnail.ingredient.jpeg",
+    ]);
+    const parts =
+      manifest.claimBytes +
+      manifest.signatureBytes +
+      manifest.assertionBytes +
+      manifest.resources.reduce((sum, resource) => sum + resource.bytes, 0);
+    expect(parts).toBeLessThan(manifest.bytes);
+    expect(manifest.bytes).toBeLessThan(stats.totalBytes);
+  });
+
+  it("follows ingredient manifests", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "CA.jpg",
+    });
+    await builder.addIngredient(
+      JSON.stringify({ title: "CA.jpg", relationship: "parentOf" }),
+      { path: "./tests/fixtures/CA.jpg" },
+    );
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const output = { buffer: null };
+    builder.sign(signer, { path: "./tests/fixtures/CA.jpg" }, output);
+
+    const stats = (await getManifestStoreStats({
+      buffer: output.buffer!,
+      mimeType: "image/jpeg",
+    }))!;
+    expect(stats.manifestCount).toBe(2);
+    expect(stats.ingredientDepth).toBe(1);
+    expect(stats.manifests.map((manifest) => manifest.depth)).toEqual([1, 0]);
+    expect(stats.assertionCount).toBe(
+      stats.manifests[0].assertionCount + stats.manifests[1].assertionCount,
+    );
+  });
+
+  it("returns null for an asset without a manifest", async () => {
+    expect(
+      await getManifestStoreStats({ path: "./tests/fixtures/A.jpg" }),
+    ).toBeNull();
+  });
+});
@@ -0,0 +1,40 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  ManifestStoreStats,
+  OperationOptions,
+  SourceAsset,
+} from "./types.d.ts";
+
+/**
+ * Measure the manifest store embedded in an asset: its total size, the size
+ * of each manifest and of its claim, signature, assertions, and resources,
+ * and how deep its ingredients go. The manifest store is not validated, so
+ * this is cheap enough to check assets against size limits before reading
+ * them.
+ * @param asset The asset to measure
+ * @returns The statistics, or null when the asset has no embedded manifest store
+ */
+export async function getManifestStoreStats(
+  asset: SourceAsset,
+  options?: OperationOptions,
+): Promise<ManifestStoreStats | null> {
+  const json = await getNeonBinary().manifestStoreStats(
+    asset,
+    options?.timeoutMs,
+    options?.priority,
+  );
+  return json === null ? null : JSON.parse(json);
+}
@@ -0,0 +1,172 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import * as fs from "fs-extra";
+import * as os from "os";
+import * as path from "path";
+import { pathToFileURL } from "url";
+
+import { Builder } from "./Builder.js";
+import { verifyPresentation } from "./Presentation.js";
+import { LocalSigner } from "./Signer.js";
+
+describe("verifyPresentation", () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), "c2pa-presentation-"));
+  });
+
+  afterEach(async () => {
+    await fs.remove(tempDir);
+  });
+
+  const fileUrl = (name: string) =>
+    pathToFileURL(path.join(tempDir, name)).href;
+
+  it("verifies a representation that is a single file", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "sample.m4a",
+      assertions: [
+        {
+          label: "c2pa.actions",
+          data: {
+            actions: [
+              {
+                action: "c2pa.created",
+                digitalSourceType:
+                  "http://cv.iptc.org/newscodes/digitalsourcetype/digitalCapture",
+              },
+            ],
+          },
+        },
+      ],
+    });
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    await fs.ensureDir(path.join(tempDir, "audio"));
+    builder.sign(
+      signer,
+      { path: "./tests/fixtures/sample.m4a" },
+      { path: path.join(tempDir, "audio", "signed.m4a") },
+    );
+    await fs.writeFile(
+      path.join(tempDir, "audio.mpd"),
+      `<?xml version="1.0"?>
+      <MPD xmlns="urn:mpeg:dash:schema:mpd:2011" type="static">
+        <BaseURL>audio/</BaseURL>
+        <Period>
+          <AdaptationSet mimeType="audio/mp4">
+            <Representation id="a1">
+              <BaseURL>signed.m4a</BaseURL>
+            </Representation>
+          </AdaptationSet>
+        </Period>
+      </MPD>`,
+    );
+
+    const report = await verifyPresentation(path.join(tempDir, "audio.mpd"));
+    expect(report.kind).toBe("dash");
+    expect(report.validationState).toBe("Valid");
+    const [representation] = report.representations;
+    expect(representation).toMatchObject({
+      id: "a1",
+      mimeType: "audio/mp4",
+      init: null,
+      validationState: "Valid",
+    });
+    expect(representation.activeManifest).toMatch(/^urn:c2pa:/);
+    expect(representation.segments).toEqual([
+      expect.objectContaining({
+        uri: fileUrl("audio/signed.m4a"),
+        validationState: "Valid",
+      }),
+    ]);
+  });
+
+  it("resolves the segments of a SegmentTemplate", async () => {
+    await fs.writeFile(
+      path.join(tempDir, "video.mpd"),
+      `<MPD mediaPresentationDuration="PT5S">
+        <Period>
+          <AdaptationSet mimeType="video/mp4">
+            <SegmentTemplate timescale="1000" duration="2000" startNumber="1"
+              initialization="$RepresentationID$/init.mp4"
+              media="$RepresentationID$/seg-$Number%03d$.m4s" />
+            <Representation id="720p" bandwidth="3000000" />
+          </AdaptationSet>
+        </Period>
+      </MPD>`,
+    );
+
+    const report = await verifyPresentation(path.join(tempDir, "video.mpd"));
+    const [representation] = report.representations;
+    expect(representation.init).toBe(fileUrl("720p/init.mp4"));
+    expect(representation.segments.map((segment) => segment.uri)).toEqual([
+      fileUrl("720p/seg-001.m4s"),
+      fileUrl("720p/seg-002.m4s"),
+      fileUrl("720p/seg-003.m4s"),
+    ]);
+    // Segments that can't be loaded are invalid, with the reason.
+    expect(report.validationState).toBe("Invalid");
+    expect(representation.segments[0].error).toMatch(/initialization segment/);
+  });
+
+  it("follows the variants of an HLS playlist", async () => {
+    await fs.writeFile(
+      path.join(tempDir, "main.m3u8"),
+      "#EXTM3U\n" +
+        '#EXT-X-STREAM-INF:BANDWIDTH=800000,CODECS="avc1.4d401e"\n' +
+        "low/index.m3u8\n",
+    );
+    await fs.outputFile(
+      path.join(tempDir, "low", "index.m3u8"),
+      "#EXTM3U\n" +
+        '#EXT-X-MAP:URI="init.mp4"\n' +
+        "#EXTINF:4.0,\nsegment0.m4s\n" +
+        "#EXTINF:4.0,\nsegment1.m4s\n" +
+        "#EXT-X-ENDLIST\n",
+    );
+
+    const report = await verifyPresentation(path.join(tempDir, "main.m3u8"));
+    expect(report.kind).toBe("hls");
+    expect(report.representations).toEqual([
+      expect.objectContaining({
+        id: "low/index.m3u8",
+        init: fileUrl("low/init.mp4"),
+      }),
+    ]);
+    expect(
+      report.representations[0].segments.map((segment) => segment.uri),
+    ).toEqual([fileUrl("low/segment0.m4s"), fileUrl("low/segment1.m4s")]);
+  });
+
+  it("rejects a remote presentation that refers to local files", async () => {
+    await fs.writeFile(
+      path.join(tempDir, "remote.mpd"),
+      `<MPD><BaseURL>https://cdn.example.com/</BaseURL><Period>
+        <AdaptationSet><Representation id="r">
+          <BaseURL>file:///etc/passwd</BaseURL>
+        </Representation></AdaptationSet>
+      </Period></MPD>`,
+    );
+
+    await expect(
+      verifyPresentation(path.join(tempDir, "remote.mpd")),
+    ).rejects.toThrow(/can't refer to/);
+  });
+});
@@ -0,0 +1,48 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import { settingsArgument } from "./Context.js";
+import type { Context } from "./Context.js";
+import type {
+  C2paSettings,
+  OperationOptions,
+  PresentationReport,
+} from "./types.d.ts";
+
+/**
+ * Verify a fragmented MP4 presentation from its DASH MPD or HLS playlist.
+ * The segment URLs are resolved from the MPD's `SegmentTemplate` or
+ * `SegmentList` elements, or from the playlist and, for a multivariant
+ * playlist, the media playlists of its variants. Each media segment is then
+ * verified with its initialization segment.
+ *
+ * A segment that can't be loaded or read is reported as invalid, with the
+ * reason in its `error`, rather than rejecting the whole verification.
+ * @param source The path or http(s) URL of the MPD or playlist
+ * @param settings Settings or a Context to verify the segments with
+ * @returns The validation state of each representation and of each of its
+ * segments
+ */
+export async function verifyPresentation(
+  source: string,
+  settings?: C2paSettings | Context,
+  options?: OperationOptions,
+): Promise<PresentationReport> {
+  return getNeonBinary().verifyPresentation(
+    source,
+    settingsArgument(settings),
+    options?.timeoutMs,
+    options?.priority,
+  );
+}
@@ -15,9 +15,16 @@
 // import native objects from built native code
 import type { ManifestStore } from "@contentauth/c2pa-types";
 import path from "path";
+import http from "http";
+import type { AddressInfo } from "net";
+import { createHash, X509Certificate } from "crypto";
 import * as fs from "fs-extra";
 
+import { Builder } from "./Builder.js";
+import { C2paError, isC2paError } from "./C2paError.js";
 import { Reader } from "./Reader.js";
+import { LocalSigner } from "./Signer.js";
+import type { DestinationBufferAsset } from "./types.d.ts";
 
 const tempDir = path.join(__dirname, "tmp");
 
@@ -175,6 +182,56 @@ describe("Reader", () => {
     );
   });
 
+  it("reports whether manifest data matches the embedded store", async () => {
+    const manifestData = await fs.readFile(
+      "./tests/fixtures/CA/manifest_data.c2pa",
+    );
+    const same = await Reader.fromManifestDataAndAsset(manifestData, {
+      path: "./tests/fixtures/CA.jpg",
+    });
+    expect(same.embeddedManifestMatch()).toBe("same");
+    const absent = await Reader.fromManifestDataAndAsset(manifestData, {
+      path: "./tests/fixtures/A.jpg",
+    });
+    expect(absent.embeddedManifestMatch()).toBe("absent");
+    const fromAsset = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    expect(fromAsset!.embeddedManifestMatch()).toBeNull();
+  });
+
+  it("chooses between differing manifest data and embedded store", async () => {
+    const manifestData = await fs.readFile(
+      "./tests/fixtures/CA/manifest_data.c2pa",
+    );
+    const asset = { path: "./tests/fixtures/C_with_CAWG_data.jpg" };
+    const embedded = (await Reader.fromAsset(asset))!.json().active_manifest;
+
+    const provided = await Reader.fromManifestDataAndAsset(
+      manifestData,
+      asset,
+    );
+    expect(provided.embeddedManifestMatch()).toBe("different");
+    expect(provided.json().active_manifest).toBe(
+      manifestStore.active_manifest,
+    );
+
+    const preferEmbedded = await Reader.fromManifestDataAndAsset(
+      manifestData,
+      asset,
+      undefined,
+      { precedence: "embedded" },
+    );
+    expect(preferEmbedded.embeddedManifestMatch()).toBe("different");
+    expect(preferEmbedded.json().active_manifest).toBe(embedded);
+
+    await expect(
+      Reader.fromManifestDataAndAsset(manifestData, asset, undefined, {
+        precedence: "error",
+      }),
+    ).rejects.toMatchObject({ code: "MANIFEST_DATA_MISMATCH" });
+  });
+
   it("should read from manifest data and file with settings context", async () => {
     const manifestData = await fs.readFile(
       "./tests/fixtures/CA/manifest_data.c2pa",
@@ -226,6 +283,532 @@ describe("Reader", () => {
     expect(fs.existsSync(outputPath));
   });
 
+  it("should extract every thumbnail in one call", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    const label = reader!.json().active_manifest!;
+    const thumbnails = await reader!.extractThumbnails();
+
+    expect(Object.keys(thumbnails)).toEqual([
+      label,
+      `${label}/c2pa.ingredient`,
+    ]);
+    expect(thumbnails[label].mimeType).toBe("image/jpeg");
+    expect(thumbnails[label].buffer.length).toBe(49690);
+    const ingredient = thumbnails[`${label}/c2pa.ingredient`];
+    expect(ingredient.buffer.subarray(0, 2)).toEqual(Buffer.from([0xff, 0xd8]));
+  });
+
+  it("should apply per-call verify options", async () => {
+    const asset = { path: "./tests/fixtures/CA.jpg" };
+    const untrusted = (reader: Reader | null) =>
+      JSON.stringify(reader!.json().validation_status ?? []).includes(
+        "signingCredential.untrusted",
+      );
+
+    const trusted = await Reader.fromAsset(asset, undefined, {
+      verifyTrust: false,
+    });
+    expect(untrusted(trusted)).toBeFalsy();
+
+    const overridden = await Reader.fromAsset(
+      asset,
+      { verify: { verify_trust: false } },
+      { verifyTrust: true },
+    );
+    expect(untrusted(overridden)).toBeTruthy();
+  });
+
+  it("should trust only the certificates in a per-call allow-list", async () => {
+    const chain = await fs.readFile("./tests/fixtures/certs/es256.pub", "utf8");
+    const signer = LocalSigner.newSigner(
+      Buffer.from(chain),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const output: DestinationBufferAsset = { buffer: null };
+    Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "Allowed",
+      format: "image/jpeg",
+    }).sign(signer, { path: "./tests/fixtures/A.jpg" }, output);
+    const asset = { buffer: output.buffer! as Buffer, mimeType: "image/jpeg" };
+    // The settings trust the root of the signing certificate
+    const settings = {
+      trust: { trust_anchors: chain.split(/(?=-----BEGIN)/).pop() },
+    };
+    const signingCodes = async (allowedCertificates?: string[]) => {
+      const reader = await Reader.fromAsset(asset, settings, {
+        allowedCertificates,
+      });
+      const { success, failure } =
+        reader!.json().validation_results!.activeManifest!;
+      return [...success, ...failure]
+        .map(({ code }) => code)
+        .filter((code) => code.startsWith("signingCredential"));
+    };
+
+    expect(await signingCodes()).toEqual(["signingCredential.trusted"]);
+    const other = await fs.readFile(
+      "./tests/fixtures/certs/ed25519.pub",
+      "utf8",
+    );
+    expect(await signingCodes([other])).toEqual([
+      "signingCredential.untrusted",
+    ]);
+    expect(await signingCodes([])).toEqual(["signingCredential.untrusted"]);
+
+    const der = new X509Certificate(chain.split(/(?=-----BEGIN)/)[0]).raw;
+    const hash = createHash("sha256").update(der).digest("hex");
+    expect(await signingCodes([hash])).toEqual(["signingCredential.trusted"]);
+    await expect(signingCodes(["not a certificate"])).rejects.toThrow(
+      "must be PEM or SHA-256 hashes",
+    );
+  });
+
+  it("should read from a memory-mapped file", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+      mmap: true,
+    });
+    expect(reader).not.toBeNull();
+    const json = reader!.json();
+    expect(json.manifests).toEqual(manifestStore.manifests);
+    expect(json.active_manifest).toEqual(manifestStore.active_manifest);
+  });
+
+  it("should read from a slice of a larger buffer", async () => {
+    const data = await fs.readFile("./tests/fixtures/CA.jpg");
+    const padded = Buffer.concat([Buffer.alloc(17), data, Buffer.alloc(5)]);
+    const reader = await Reader.fromAsset({
+      buffer: padded.subarray(17, 17 + data.length),
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should read from a URL with range requests", async () => {
+    const data = await fs.readFile("./tests/fixtures/CA.jpg");
+    const ranges: string[] = [];
+    const server = http.createServer((req, res) => {
+      const match = /bytes=(\d+)-(\d+)/.exec(req.headers.range ?? "");
+      if (!match) {
+        res.end(data);
+        return;
+      }
+      ranges.push(match[0]);
+      const start = Number(match[1]);
+      const end = Math.min(Number(match[2]), data.length - 1);
+      res.writeHead(206, {
+        "Content-Range": `bytes ${start}-${end}/${data.length}`,
+      });
+      res.end(data.subarray(start, end + 1));
+    });
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+
+    try {
+      const reader = await Reader.fromAsset({
+        url: `http://127.0.0.1:${port}/CA.jpg`,
+      });
+      expect(reader!.json().active_manifest).toEqual(
+        manifestStore.active_manifest,
+      );
+      expect(ranges.length).toBeGreaterThan(0);
+    } finally {
+      server.close();
+    }
+  });
+
+  it("should follow a Link header to a remote manifest", async () => {
+    const data = await fs.readFile("./tests/fixtures/A.jpg");
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "A.jpg",
+    });
+    builder.setNoEmbed(true);
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const manifest = builder.sign(
+      signer,
+      { buffer: data, mimeType: "image/jpeg" },
+      { buffer: null },
+    );
+    const server = http.createServer((req, res) => {
+      if (req.url === "/manifests/A.c2pa") {
+        res.end(manifest);
+        return;
+      }
+      if (req.url === "/assets/A.jpg") {
+        res.setHeader("Link", '<../manifests/A.c2pa>; rel="c2pa-manifest"');
+      }
+      res.end(req.method === "HEAD" ? undefined : data);
+    });
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+
+    try {
+      const reader = await Reader.fromUrl(
+        `http://127.0.0.1:${port}/assets/A.jpg`,
+      );
+      expect(reader!.getActive()!.title).toEqual("A.jpg");
+      expect(reader!.isEmbedded()).toBe(false);
+
+      // Without a Link header there is nothing to read.
+      expect(
+        await Reader.fromUrl(`http://127.0.0.1:${port}/plain/A.jpg`),
+      ).toBeNull();
+      expect(
+        await Reader.fromUrl(`http://127.0.0.1:${port}/assets/A.jpg`, undefined, {
+          fetchRemoteManifests: false,
+        }),
+      ).toBeNull();
+    } finally {
+      server.close();
+    }
+  });
+
+  it("should retry remote manifest fetches", async () => {
+    const data = await fs.readFile("./tests/fixtures/A.jpg");
+    let failures = 0;
+    let requests = 0;
+    let manifest: Buffer | null = null;
+    const server = http.createServer((req, res) => {
+      requests++;
+      if (failures > 0) {
+        failures--;
+        res.statusCode = 503;
+        res.end();
+        return;
+      }
+      res.end(manifest);
+    });
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+    const url = `http://127.0.0.1:${port}/manifests/A.c2pa`;
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "A.jpg",
+    });
+    builder.setNoEmbed(true);
+    builder.setRemoteUrl(url);
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const dest: DestinationBufferAsset = { buffer: null };
+    manifest = builder.sign(
+      signer,
+      { buffer: data, mimeType: "image/jpeg" },
+      dest,
+    );
+    const asset = { buffer: dest.buffer as Buffer, mimeType: "image/jpeg" };
+
+    try {
+      failures = 2;
+      const reader = await Reader.fromAsset(asset, undefined, {
+        network: { remoteManifest: { retries: 2 } },
+      });
+      expect(reader!.getActive()!.title).toEqual("A.jpg");
+      expect(requests).toBe(3);
+
+      failures = 2;
+      requests = 0;
+      await expect(
+        Reader.fromAsset(asset, undefined, { network: { retries: 1 } }),
+      ).rejects.toMatchObject({
+        code: "REMOTE_FETCH_FAILED",
+        network: {
+          endpoint: "remoteManifest",
+          url,
+          reason: "status",
+          status: 503,
+          attempts: 2,
+        },
+      });
+      expect(requests).toBe(2);
+    } finally {
+      server.close();
+    }
+  });
+
+  it("reports each fragment read with an initialization segment", async () => {
+    const fragmentDir = path.join(tempDir, "fragments");
+    await fs.ensureDir(fragmentDir);
+    for (const name of ["seg-10.m4s", "seg-2.m4s", "seg-1.m4s", "other.mp4"]) {
+      await fs.copy(
+        "./tests/fixtures/sample.m4a",
+        path.join(fragmentDir, name),
+      );
+    }
+
+    // The matches are taken in natural order.
+    const { reader, fragments, merkleTree } = await Reader.fromFragments(
+      { path: "./tests/fixtures/sample.m4a" },
+      path.join(fragmentDir, "seg-*.m4s"),
+    );
+    expect(
+      fragments.map((fragment) => path.basename(fragment.path!)),
+    ).toEqual(["seg-1.m4s", "seg-2.m4s", "seg-10.m4s"]);
+    // Without a manifest in the initialization segment no fragment is valid.
+    expect(reader).toBeNull();
+    expect(merkleTree).toBeNull();
+    expect(fragments[0]).toMatchObject({
+      index: 0,
+      validationState: "Invalid",
+      error: expect.any(String),
+    });
+
+    await expect(
+      Reader.fromFragments(
+        { path: "./tests/fixtures/sample.m4a" },
+        path.join(fragmentDir, "*.m4v"),
+      ),
+    ).rejects.toThrow("No fragments match");
+  });
+
+  it("reports which Merkle leaf of a fragment failed", async () => {
+    // An unsigned manifest whose BMFF hash has a three-leaf tree storing only
+    // its root; the last byte of seg1 was changed after hashing.
+    const { fragments, merkleTree } = await Reader.fromFragments(
+      { path: "./tests/fixtures/merkle/init.mp4" },
+      "./tests/fixtures/merkle/seg*.m4s",
+    );
+    const [map] = merkleTree!.maps;
+    expect(map).toMatchObject({
+      count: 3,
+      layers: [3, 2, 1],
+      storedLayer: 2,
+    });
+    expect(map!.initHash!.matches).toBe(true);
+    expect(fragments.map((fragment) => fragment.merkle![0]!.valid)).toEqual([
+      true,
+      false,
+      true,
+    ]);
+    const corrupted = fragments[1]!.merkle![0]!;
+    expect(corrupted.location).toBe(1);
+    expect(corrupted.expected).toBe(map!.hashes[0]);
+    expect(corrupted.computed).not.toBe(corrupted.expected);
+  });
+
+  it("should reject a source larger than maxBytes", async () => {
+    await expect(
+      Reader.fromAsset({ path: "./tests/fixtures/CA.jpg", maxBytes: 1024 }),
+    ).rejects.toThrow("maximum size of 1024 bytes");
+    await expect(
+      Reader.fromAsset({
+        stream: fs.createReadStream("./tests/fixtures/CA.jpg"),
+        mimeType: "image/jpeg",
+        maxBytes: 1024,
+      }),
+    ).rejects.toThrow("maximum size of 1024 bytes");
+  });
+
+  it("should limit a URL that ignores ranges to maxBytes", async () => {
+    const data = await fs.readFile("./tests/fixtures/CA.jpg");
+    const server = http.createServer((req, res) => res.end(data));
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+    const url = `http://127.0.0.1:${port}/CA.jpg`;
+
+    try {
+      await expect(Reader.fromAsset({ url, maxBytes: 1024 })).rejects.toThrow(
+        "maximum size of 1024 bytes",
+      );
+      const reader = await Reader.fromAsset({ url, maxBytes: data.length });
+      expect(reader!.json().active_manifest).toEqual(
+        manifestStore.active_manifest,
+      );
+    } finally {
+      server.close();
+    }
+  });
+
+  it("should reject a resource larger than maxBytes", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const uri = reader!.getActive()!.thumbnail!.identifier;
+    await expect(
+      reader!.resourceToAsset(uri, { buffer: null, maxBytes: 16 }),
+    ).rejects.toThrow("maximum size of 16 bytes");
+  });
+
+  it("should ignore changes to the buffer after the call", async () => {
+    const buffer = await fs.readFile("./tests/fixtures/CA.jpg");
+    const pending = Reader.fromAsset({ buffer, mimeType: "image/jpeg" });
+    buffer.fill(0);
+    const reader = await pending;
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should detect the MIME type of a buffer", async () => {
+    const buffer = await fs.readFile("./tests/fixtures/CA.jpg");
+    const reader = await Reader.fromAsset({ buffer });
+    expect(reader).not.toBeNull();
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should detect the MIME type of a file without an extension", async () => {
+    const filePath = path.join(tempDir, "upload");
+    await fs.copy("./tests/fixtures/CA.jpg", filePath);
+    const reader = await Reader.fromAsset({ path: filePath });
+    expect(reader).not.toBeNull();
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should report invalid settings with a code", async () => {
+    const settings = { verify: 5 } as any;
+    await expect(
+      Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" }, settings),
+    ).rejects.toMatchObject({
+      code: "SETTINGS_INVALID",
+      operation: "Reader.fromAsset",
+    });
+  });
+
+  it("should report font files as unsupported", async () => {
+    const buffer = Buffer.concat([Buffer.from("wOF2"), Buffer.alloc(60)]);
+    await expect(Reader.fromAsset({ buffer })).rejects.toMatchObject({
+      code: "UNSUPPORTED_FORMAT",
+    });
+  });
+
+  it("should report Office documents as unsupported", async () => {
+    // A ZIP local file header for the main part of a Word document
+    const buffer = Buffer.concat([
+      Buffer.from([0x50, 0x4b, 0x03, 0x04]),
+      Buffer.alloc(26),
+      Buffer.from("word/document.xml"),
+    ]);
+    await expect(Reader.fromAsset({ buffer })).rejects.toMatchObject({
+      code: "UNSUPPORTED_FORMAT",
+    });
+  });
+
+  it("should report MPEG transport streams as unsupported", async () => {
+    const packet = Buffer.alloc(188);
+    packet[0] = 0x47;
+    const buffer = Buffer.concat([packet, packet, packet]);
+    await expect(Reader.fromAsset({ buffer })).rejects.toMatchObject({
+      code: "UNSUPPORTED_FORMAT",
+    });
+  });
+
+  it("should read from a Readable stream", async () => {
+    const reader = await Reader.fromAsset({
+      stream: fs.createReadStream("./tests/fixtures/CA.jpg", {
+        highWaterMark: 1024,
+      }),
+      mimeType: "image/jpeg",
+    });
+    expect(reader).not.toBeNull();
+    const json = reader!.json();
+    expect(json.manifests).toEqual(manifestStore.manifests);
+    expect(json.active_manifest).toEqual(manifestStore.active_manifest);
+  });
+
+  it("should reject a stream that fails", async () => {
+    async function* failing() {
+      yield Buffer.from([0xff, 0xd8]);
+      throw new Error("upload aborted");
+    }
+    await expect(
+      Reader.fromAsset({ stream: failing(), mimeType: "image/jpeg" }),
+    ).rejects.toThrow("upload aborted");
+  });
+
+  it("should read from an async generator", async () => {
+    async function* generate() {
+      const data = await fs.readFile("./tests/fixtures/CA.jpg");
+      for (let offset = 0; offset < data.length; offset += 4096) {
+        yield data.subarray(offset, offset + 4096);
+      }
+    }
+    const reader = await Reader.fromAsset({
+      stream: generate(),
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should read from a pull callback", async () => {
+    const data = await fs.readFile("./tests/fixtures/CA.jpg");
+    let offset = 0;
+    const reader = await Reader.fromAsset({
+      pull: async () => {
+        if (offset >= data.length) {
+          return null;
+        }
+        const chunk = data.subarray(offset, offset + 4096);
+        offset += chunk.length;
+        return chunk;
+      },
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+  });
+
+  it("should read through IO callbacks", async () => {
+    const data = await fs.readFile("./tests/fixtures/CA.jpg");
+    let position = 0;
+    const reader = await Reader.fromAsset({
+      io: {
+        read: (length) => {
+          const chunk = data.subarray(position, position + length);
+          position += chunk.length;
+          return chunk;
+        },
+        seek: async (offset, origin) => {
+          const base = { start: 0, current: position, end: data.length };
+          position = base[origin] + offset;
+          return position;
+        },
+      },
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+
+    await expect(
+      Reader.fromAsset({
+        io: {
+          read: () => Promise.reject(new Error("Blob store unavailable")),
+          seek: () => 0,
+        },
+        mimeType: "image/jpeg",
+      }),
+    ).rejects.toThrow("Blob store unavailable");
+  });
+
+  it("should write a resource to a Writable stream", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const outputPath = path.join(tempDir, "thumbnail_stream.jpg");
+    const stream = fs.createWriteStream(outputPath);
+
+    const uri = reader!.getActive()!.thumbnail!.identifier;
+    const result = await reader!.resourceToAsset(uri, { stream });
+    expect(result.bytes_written).toBeGreaterThan(0);
+    expect((await fs.stat(outputPath)).size).toBe(result.bytes_written);
+  });
+
   it("should report manifest is embedded", async () => {
     const reader = await Reader.fromAsset({
       path: "./tests/fixtures/CA.jpg",
@@ -246,6 +829,306 @@ describe("Reader", () => {
     expect(reader!.isEmbedded()).toBeFalsy();
   });
 
+  it("should fail after the reader is disposed", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    expect(reader).not.toBeNull();
+    reader!.dispose();
+    reader!.dispose();
+
+    expect(() => reader!.json()).toThrow("The Reader has been disposed");
+    await expect(reader!.jsonAsync()).rejects.toThrow("disposed");
+    const builder = Builder.new();
+    expect(() => builder.addIngredientFromReader(reader!)).toThrow(
+      "The Reader has been disposed",
+    );
+  });
+
+  it("should share a reader without reading it again", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    // What another worker thread receives through postMessage
+    const shared = structuredClone(reader!.share());
+    const bytes = reader!.memoryUsage();
+    reader!.dispose();
+
+    const opened = Reader.fromShared(shared);
+    // The size measured when the Reader was shared, without locking it
+    expect(opened.memoryUsage()).toBe(bytes);
+    expect(opened.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+    expect(Reader.releaseShared(shared)).toBe(true);
+    expect(Reader.releaseShared(shared)).toBe(false);
+    expect(opened.json().active_manifest).toEqual(
+      manifestStore.active_manifest,
+    );
+    expect(() => Reader.fromShared(shared)).toThrow("No Reader is shared");
+  });
+
+  it("should report a stable code with native errors", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    reader!.dispose();
+
+    const error = await reader!.jsonAsync().catch((err) => err);
+    expect(error).toBeInstanceOf(C2paError);
+    expect(error).toMatchObject({
+      code: "DISPOSED",
+      operation: "Reader",
+      variant: "Disposed",
+    });
+    expect(isC2paError(error, "DISPOSED")).toBe(true);
+    expect(isC2paError(error, "TIMEOUT")).toBe(false);
+    expect(isC2paError(new Error("plain"))).toBe(false);
+  });
+
+  it("should report where in the manifest store an error happened", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const uri =
+      "self#jumbf=/c2pa/urn:c2pa:missing/c2pa.assertions/c2pa.thumbnail.claim.jpeg";
+
+    await expect(
+      reader!.resourceToAsset(uri, { buffer: null }),
+    ).rejects.toMatchObject({
+      code: "RESOURCE_NOT_FOUND",
+      context: {
+        operation: "Reader.resourceToAsset",
+        manifestLabel: "urn:c2pa:missing",
+        assertion: "c2pa.thumbnail.claim.jpeg",
+        uri,
+      },
+    });
+  });
+
+  it("should summarize the manifest store in a report", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    expect(reader).not.toBeNull();
+    const report = reader!.report();
+    expect(report.activeManifest).toEqual(manifestStore.active_manifest);
+    expect(report.manifests).toHaveLength(1);
+    const [active] = report.manifests;
+    expect(active.title).toEqual("CA.jpg");
+    expect(active.signer).toMatchObject({
+      commonName: "C2PA Signer",
+      issuer: "C2PA Test Signing Cert",
+      alg: "ps256",
+      time: "2024-08-06T21:53:37+00:00",
+    });
+    expect(active.generators).toEqual([
+      { name: "make_test_images", version: "0.33.1" },
+      { name: "c2pa-rs", version: "0.33.1" },
+    ]);
+    expect(active.actions.map(({ action }) => action)).toEqual([
+      "c2pa.opened",
+      "c2pa.color_adjustments",
+    ]);
+    expect(active.ingredients).toEqual([
+      { title: "A.jpg", relationship: "parentOf", activeManifest: null },
+    ]);
+    expect(report.failures.map(({ code }) => code)).toEqual([
+      "signingCredential.untrusted",
+    ]);
+
+    const markdown = reader!.renderReport("markdown");
+    expect(markdown).toContain("## CA.jpg (active manifest)");
+    expect(markdown).toContain("- **Signed by:** C2PA Signer");
+    expect(markdown).toContain("- c2pa.color\\_adjustments");
+    const html = reader!.renderReport("html");
+    expect(html).toContain("<dt>Signed at</dt><dd>2024-08-06T21:53:37+00:00</dd>");
+    expect(html).toContain("<li>signingCredential.untrusted: signing certificate untrusted</li>");
+    expect(() => reader!.renderReport("pdf" as "html")).toThrow(
+      'Unknown report format "pdf"',
+    );
+  });
+
+  it("should summarize identity claims aggregation credentials", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/cawg_ica.jpg",
+    });
+    const [active] = reader!.report().manifests;
+    expect(active!.identities).toHaveLength(1);
+    const [identity] = active!.identities;
+    expect(identity).toMatchObject({
+      label: "cawg.identity",
+      sigType: "cawg.identity_claims_aggregation",
+      valid: true,
+      issuer: expect.stringMatching(/^did:jwk:/),
+      failures: [],
+    });
+    expect(identity!.verifiedIdentities.map(({ type }) => type)).toEqual([
+      "cawg.document_verification",
+      "cawg.affiliation",
+      "cawg.social_media",
+      "cawg.crypto_wallet",
+    ]);
+    expect(identity!.verifiedIdentities[2]).toMatchObject({
+      username: "username",
+      uri: "https://example-social-network.com/username",
+      provider: { name: "Example Social Network" },
+    });
+    // CAWG success codes aren't reported as failures.
+    expect(reader!.validationMessages()).toContainEqual(
+      expect.objectContaining({
+        code: "cawg.ica.credential_valid",
+        kind: "success",
+      }),
+    );
+    expect(reader!.renderReport("markdown")).toContain(
+      "- username on Example Social Network (cawg.social\\_media",
+    );
+
+    const x509 = await Reader.fromAsset({
+      path: "./tests/fixtures/C_with_CAWG_data.jpg",
+    });
+    const [x509Identity] = x509!.report().manifests[0]!.identities;
+    expect(x509Identity).toMatchObject({
+      sigType: "cawg.x509.cose",
+      valid: false,
+      signer: { issuer: "C2PA Test Signing Cert", alg: "Ed25519" },
+    });
+    expect(x509Identity!.failures.map(({ code }) => code)).toEqual([
+      "signingCredential.untrusted",
+    ]);
+  });
+
+  it("should list the signatures of an endorsed asset", async () => {
+    const builder = Builder.endorsement({
+      claim_generator_info: [{ name: "approver", version: "1.0.0" }],
+    });
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const output: DestinationBufferAsset = { buffer: null };
+    builder.sign(
+      signer,
+      {
+        buffer: await fs.readFile("./tests/fixtures/CA.jpg"),
+        mimeType: "image/jpeg",
+      },
+      output,
+    );
+
+    const reader = await Reader.fromAsset({
+      buffer: output.buffer! as Buffer,
+      mimeType: "image/jpeg",
+    });
+    const [endorsement, original] = reader!.signatures();
+    expect(endorsement).toMatchObject({
+      manifestLabel: reader!.report().activeManifest,
+      kind: "claim",
+      updateManifest: true,
+      signer: { alg: "es256" },
+      validationState: "Valid",
+    });
+    expect(original).toMatchObject({
+      kind: "claim",
+      updateManifest: false,
+      signer: { alg: "ps256", time: "2024-08-06T21:53:37+00:00" },
+      validationState: "Valid",
+    });
+    expect(original!.failures.map(({ code }) => code)).toEqual([
+      "signingCredential.untrusted",
+    ]);
+
+    const cawg = await Reader.fromAsset({
+      path: "./tests/fixtures/C_with_CAWG_data.jpg",
+    });
+    expect(
+      cawg!.signatures().map(({ kind, sigType }) => [kind, sigType]),
+    ).toEqual([
+      ["claim", null],
+      ["identity", "cawg.x509.cose"],
+    ]);
+  });
+
+  it("should export the provenance graph", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    expect(reader).not.toBeNull();
+    const active = manifestStore.active_manifest!;
+    const ingredient = `${active}/ingredients/0`;
+    expect(reader!.provenanceGraph()).toEqual({
+      nodes: [
+        {
+          id: active,
+          kind: "manifest",
+          title: "CA.jpg",
+          format: "image/jpeg",
+          signer: "C2PA Signer",
+          active: true,
+        },
+        {
+          id: ingredient,
+          kind: "ingredient",
+          title: "A.jpg",
+          format: "image/jpeg",
+          signer: null,
+          active: false,
+        },
+      ],
+      edges: [
+        { from: ingredient, to: active, relationship: "parentOf", title: "A.jpg" },
+      ],
+    });
+
+    const dot = reader!.provenanceGraphDot();
+    expect(dot).toMatch(/^digraph provenance \{/);
+    expect(dot).toContain(
+      `"${active}" [label="CA.jpg\\nsigned by C2PA Signer", shape=box, style=bold];`,
+    );
+    expect(dot).toContain(`"${ingredient}" -> "${active}" [label="parentOf"];`);
+  });
+
+  it("should check C2PA 2.x conformance", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/CA.jpg",
+    });
+    expect(reader).not.toBeNull();
+    const active = manifestStore.active_manifest!;
+    const report = reader!.conformance();
+    expect(report.result).toEqual("fail");
+    expect(report.checks[0]).toEqual({
+      id: "validation",
+      status: "warn",
+      message: "Manifest store is valid, but its signer is not on a trust list",
+      manifest: null,
+    });
+    const status = Object.fromEntries(
+      report.checks
+        .filter((check) => check.manifest === active)
+        .map((check) => [check.id, check.status]),
+    );
+    expect(status).toEqual({
+      "claim.version": "fail",
+      "claim.generatorInfo": "pass",
+      "assertion.actions": "pass",
+      "assertion.deprecated": "warn",
+      "signature.algorithm": "pass",
+      "signature.timeStamp": "pass",
+      "ingredient.relationship": "pass",
+    });
+    expect(report.checks).toContainEqual({
+      id: "assertion.deprecated",
+      status: "warn",
+      message:
+        '"stds.schema-org.CreativeWork" is deprecated in C2PA 2.x; use c2pa.metadata',
+      manifest: active,
+    });
+  });
+
+  it("should read manifest properties without blocking", async () => {
+    const reader = await Reader.fromAsset({
+      path: "./tests/fixtures/cloud.jpg",
+    });
+    expect(reader).not.toBeNull();
+    expect(await reader!.jsonAsync()).toEqual(reader!.json());
+    expect(await reader!.remoteUrlAsync()).toEqual(reader!.remoteUrl());
+    expect(await reader!.isEmbeddedAsync()).toBeFalsy();
+  });
+
   it("should decode CAWG identity assertion with signature_info", async () => {
     // This test verifies that postValidateCawg() properly decodes CAWG assertions
     // and extracts signature_info from the signature data, matching c2pa-js behavior
@@ -14,20 +14,67 @@
 import type { Manifest, ManifestStore } from "@contentauth/c2pa-types";
 
 import { getNeonBinary } from "./binary.js";
+import { networkArgument, settingsArgument } from "./Context.js";
+import type { Context } from "./Context.js";
+import {
+  applyValidationVerdicts,
+  hasValidationRules,
+  runValidationRules,
+  verdictMessages,
+} from "./ValidationRules.js";
 import type {
   C2paSettings,
+  ConformanceReport,
   DestinationAsset,
+  EmbeddedManifestMatch,
+  FragmentReport,
+  ManifestDataOptions,
+  MerkleTreeReport,
+  OperationOptions,
+  ProvenanceGraph,
   ReaderInterface,
   ResourceAsset,
   SourceAsset,
+  SourceBufferAsset,
   NeonReaderHandle,
+  UrlReadOptions,
+  ReportFormat,
+  SharedReader,
+  SignatureSummary,
+  ValidationMessage,
+  ValidationMessageFilter,
+  ValidationRuleVerdict,
+  VerificationReport,
+  VerifyOptions,
 } from "./types.d.ts";
 
+function verifyOptionsArgument(options?: VerifyOptions): string | undefined {
+  if (!options) {
+    return undefined;
+  }
+  return JSON.stringify({
+    ocsp_fetch: options.ocsp,
+    remote_manifest_fetch: options.fetchRemoteManifests,
+    verify_trust: options.verifyTrust,
+    verify_timestamp_trust: options.verifyTimestamps,
+    allowed_certificates: options.allowedCertificates,
+    network: networkArgument(options.network),
+  });
+}
+
 export class Reader implements ReaderInterface {
+  // Findings of the rules registered with `registerValidationRule`
+  private verdicts: ValidationRuleVerdict[] = [];
+  // How the embedded manifest store compared with the provided manifest data
+  private embeddedMatch: EmbeddedManifestMatch | null = null;
+
   constructor(private reader: NeonReaderHandle) {}
 
   json(): ManifestStore {
-    return JSON.parse(getNeonBinary().readerJson.call(this.reader));
+    return applyValidationVerdicts(
+      getNeonBinary().readerJson.call(this.reader),
+      this.verdicts,
+    );
   }
 
   remoteUrl(): string {
@@ -38,26 +85,204 @@ export class Reader implements ReaderInterface {
     return getNeonBinary().readerIsEmbedded.call(this.reader);
   }
 
-  async resourceToAsset(uri: string, asset: DestinationAsset): Promise<ResourceAsset> {
-    return getNeonBinary().readerResourceToAsset.call(this.reader, uri, asset);
+  embeddedManifestMatch(): EmbeddedManifestMatch | null {
+    return this.embeddedMatch;
+  }
+
+  validationMessages(filter?: ValidationMessageFilter): ValidationMessage[] {
+    const messages = getNeonBinary().readerValidationMessages.call(
+      this.reader,
+      filter,
+    );
+    const verdicts = verdictMessages(this.verdicts).filter(
+      ({ kind }) =>
+        filter === "errors"
+          ? kind === "failure"
+          : filter !== "warnings" || kind !== "success",
+    );
+    return [...messages, ...verdicts];
+  }
+
+  report(): VerificationReport {
+    return getNeonBinary().readerReport.call(this.reader, "json");
+  }
+
+  renderReport(format: ReportFormat): string {
+    return getNeonBinary().readerReport.call(this.reader, format);
+  }
+
+  signatures(): SignatureSummary[] {
+    return getNeonBinary().readerSignatures.call(this.reader);
+  }
+
+  provenanceGraph(): ProvenanceGraph {
+    return getNeonBinary().readerProvenanceGraph.call(this.reader, "json");
+  }
+
+  provenanceGraphDot(): string {
+    return getNeonBinary().readerProvenanceGraph.call(this.reader, "dot");
+  }
+
+  conformance(): ConformanceReport {
+    return getNeonBinary().readerConformance.call(this.reader);
+  }
+
+  async jsonAsync(): Promise<ManifestStore> {
+    return applyValidationVerdicts(
+      await getNeonBinary().readerJsonAsync.call(this.reader),
+      this.verdicts,
+    );
+  }
+
+  async remoteUrlAsync(): Promise<string> {
+    return getNeonBinary().readerRemoteUrlAsync.call(this.reader);
+  }
+
+  async isEmbeddedAsync(): Promise<boolean> {
+    return getNeonBinary().readerIsEmbeddedAsync.call(this.reader);
+  }
+
+  async resourceToAsset(
+    uri: string,
+    asset: DestinationAsset,
+    options?: OperationOptions,
+  ): Promise<ResourceAsset> {
+    return getNeonBinary().readerResourceToAsset.call(
+      this.reader,
+      uri,
+      asset,
+      options?.timeoutMs,
+      options?.priority,
+    );
+  }
+
+  async extractThumbnails(
+    options?: OperationOptions,
+  ): Promise<Record<string, SourceBufferAsset>> {
+    return getNeonBinary().readerExtractThumbnails.call(
+      this.reader,
+      options?.timeoutMs,
+      options?.priority,
+    );
+  }
+
+  /**
+   * The MIME types the native library can read manifests from
+   */
+  static supportedMimeTypes(): string[] {
+    return getNeonBinary().readerSupportedMimeTypes();
+  }
+
+  static async fromAsset(
+    asset: SourceAsset,
+    settings?: C2paSettings | Context,
+    options?: VerifyOptions & OperationOptions,
+  ): Promise<Reader | null> {
+    const reader: NeonReaderHandle | null =
+      await getNeonBinary().readerFromAsset(asset, settingsArgument(settings), verifyOptionsArgument(options), options?.timeoutMs, options?.priority);
+    return reader ? Reader.validated(reader) : null;
   }
 
-  static async fromAsset(asset: SourceAsset, settings?: C2paSettings): Promise<Reader | null> {
-    const settingsStr = settings ? (typeof settings === 'string' ? settings : JSON.stringify(settings)) : undefined;
+  /**
+   * Read an asset like `fromAsset`, blocking until the read completes. The
+   * read runs on the native worker threads while the calling thread waits,
+   * so this suits CLI tools and build scripts rather than servers.
+   * Validation rules registered with `registerValidationRule` are async and
+   * can't run here, so this throws while any are registered. Stream, pull, and
+   * IO assets throw too, and a remote manifest resolver fails the fetch, since
+   * their callbacks would need the blocked thread.
+   */
+  static fromAssetSync(
+    asset: SourceAsset,
+    settings?: C2paSettings | Context,
+    options?: VerifyOptions & OperationOptions,
+  ): Reader | null {
+    if (hasValidationRules()) {
+      throw new Error(
+        "Validation rules are async; use Reader.fromAsset to apply them",
+      );
+    }
     const reader: NeonReaderHandle | null =
-      await getNeonBinary().readerFromAsset(asset, settingsStr);
+      getNeonBinary().readerFromAssetSync(
+        asset,
+        settingsArgument(settings),
+        verifyOptionsArgument(options),
+        options?.timeoutMs,
+        options?.priority,
+      );
     return reader ? new Reader(reader) : null;
   }
 
+  static async fromUrl(
+    url: string,
+    settings?: C2paSettings | Context,
+    options?: UrlReadOptions & VerifyOptions & OperationOptions,
+  ): Promise<Reader | null> {
+    const reader: NeonReaderHandle | null =
+      await getNeonBinary().readerFromUrl(url, options?.mimeType, settingsArgument(settings), verifyOptionsArgument(options), options?.timeoutMs, options?.priority);
+    return reader ? Reader.validated(reader) : null;
+  }
+
+  /**
+   * Read fragmented MP4 content from its initialization segment and its
+   * fragments, validating each fragment against the Merkle tree of the
+   * manifest's BMFF hash.
+   * @param init The initialization segment, which holds the manifest store
+   * @param fragments The fragments in order, or a glob such as
+   * `"segments/seg-*.m4s"` whose matches are taken in natural order. Only the
+   * file name of a glob can have wildcards.
+   * @returns A report for each fragment, and a Reader with the validation
+   * results of the first fragment that failed, or of the last fragment when
+   * none did. The Reader is null when no fragment could be read. The Merkle
+   * trees of the BMFF hash are returned too, and each fragment's report says
+   * which leaf it is and which hash didn't match; `merkleTree` is null when
+   * the initialization segment has no Merkle trees.
+   */
+  static async fromFragments(
+    init: SourceAsset,
+    fragments: SourceAsset[] | string,
+    settings?: C2paSettings | Context,
+    options?: VerifyOptions & OperationOptions,
+  ): Promise<{
+    reader: Reader | null;
+    fragments: FragmentReport[];
+    merkleTree: MerkleTreeReport | null;
+  }> {
+    const result = await getNeonBinary().readerFromFragments(
+      init,
+      fragments,
+      settingsArgument(settings),
+      verifyOptionsArgument(options),
+      options?.timeoutMs,
+      options?.priority,
+    );
+    return {
+      reader: result.reader ? await Reader.validated(result.reader) : null,
+      fragments: result.fragments,
+      merkleTree: result.merkleTree,
+    };
+  }
+
   static async fromManifestDataAndAsset(
     manifestData: Buffer,
     asset: SourceAsset,
-    settings?: C2paSettings,
+    settings?: C2paSettings | Context,
+    options?: VerifyOptions & OperationOptions & ManifestDataOptions,
   ): Promise<Reader> {
-    const settingsStr = settings ? (typeof settings === 'string' ? settings : JSON.stringify(settings)) : undefined;
-    const reader: NeonReaderHandle =
-      await getNeonBinary().readerFromManifestDataAndAsset(manifestData, asset, settingsStr);
-    return new Reader(reader);
+    const { reader, embedded } =
+      await getNeonBinary().readerFromManifestDataAndAsset(manifestData, asset, settingsArgument(settings), verifyOptionsArgument(options), options?.timeoutMs, options?.priority, options?.precedence);
+    const validated = await Reader.validated(reader);
+    validated.embeddedMatch = embedded;
+    return validated;
+  }
+
+  // Runs the registered validation rules on a newly read manifest store
+  private static async validated(handle: NeonReaderHandle): Promise<Reader> {
+    const reader = new Reader(handle);
+    if (hasValidationRules()) {
+      reader.verdicts = await runValidationRules(reader.json());
+    }
+    return reader;
   }
 
   // Non-neon methods, copied from c2pa-js
@@ -78,6 +303,44 @@ export class Reader implements ReaderInterface {
     return manifestStore.manifests[activeManifest];
   }
 
+  share(): SharedReader {
+    return {
+      key: getNeonBinary().readerShare.call(this.reader),
+      verdicts: this.verdicts,
+      embeddedManifestMatch: this.embeddedMatch,
+    };
+  }
+
+  /**
+   * Open a Reader shared by `share`, possibly in another worker thread. The
+   * new Reader uses the same native Reader, so it is neither read nor
+   * verified again, and disposing either one leaves the other usable.
+   */
+  static fromShared(shared: SharedReader): Reader {
+    const reader = new Reader(getNeonBinary().readerFromShared(shared.key));
+    reader.verdicts = shared.verdicts;
+    reader.embeddedMatch = shared.embeddedManifestMatch ?? null;
+    return reader;
+  }
+
+  /**
+   * Remove a shared Reader from the registry. Readers already opened from it
+   * keep working; its native memory is freed once they are all disposed or
+   * garbage collected.
+   * @returns Whether the Reader was still registered
+   */
+  static releaseShared(shared: SharedReader): boolean {
+    return getNeonBinary().readerReleaseShared(shared.key);
+  }
+
+  dispose(): void {
+    getNeonBinary().readerDispose.call(this.reader);
+  }
+
+  memoryUsage(): number {
+    return getNeonBinary().readerMemoryUsage.call(this.reader);
+  }
+
   getHandle(): NeonReaderHandle {
     return this.reader;
   }
@@ -0,0 +1,299 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+import { execFileSync } from "child_process";
+import http from "http";
+import type { AddressInfo } from "net";
+import path from "path";
+import { Worker } from "worker_threads";
+
+import {
+  configureRuntime,
+  configureTelemetry,
+  flushTelemetry,
+  getMemoryUsage,
+  getMetrics,
+  getPrometheusMetrics,
+  setLogger,
+  shutdown,
+  withTraceParent,
+} from "./Runtime.js";
+import { Reader } from "./Reader.js";
+import type { NativeSpan } from "./types.d.ts";
+
+describe("Runtime", () => {
+  it("rejects invalid thread counts", () => {
+    expect(() => configureRuntime({ workerThreads: 0 })).toThrow(
+      "at least 1",
+    );
+    expect(() => configureRuntime({ batchConcurrency: 0 })).toThrow(
+      "at least 1",
+    );
+  });
+
+  it("refuses to reconfigure a running runtime", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    expect(reader).not.toBeNull();
+    expect(() => configureRuntime({ workerThreads: 4 })).toThrow(
+      "already started",
+    );
+  });
+
+  it("counts reads and their outcomes", async () => {
+    const before = getMetrics().operations.read;
+    await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    await expect(
+      Reader.fromAsset({ path: "./tests/fixtures/missing.jpg" }),
+    ).rejects.toThrow();
+
+    const metrics = getMetrics();
+    const after = metrics.operations.read;
+    expect(after.succeeded - before.succeeded).toBe(1);
+    expect(after.failed - before.failed).toBe(1);
+    expect(after.latency.count - before.latency.count).toBe(2);
+    expect(metrics.runtime?.workers).toBeGreaterThan(0);
+  });
+
+  it("formats metrics for Prometheus", () => {
+    const text = getPrometheusMetrics();
+    expect(text).toContain("# TYPE c2pa_operation_duration_seconds histogram");
+    expect(text).toMatch(
+      /c2pa_operations_total\{operation="read",outcome="success"\} \d+/,
+    );
+  });
+
+  it("reports the memory held by live readers", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const size = reader!.memoryUsage();
+    // The manifest store includes a thumbnail
+    expect(size).toBeGreaterThan(1000);
+
+    const before = getMemoryUsage().readers;
+    expect(before.bytes).toBeGreaterThanOrEqual(size);
+    reader!.dispose();
+    const after = getMemoryUsage().readers;
+    expect(reader!.memoryUsage()).toBe(0);
+    expect(before.count - after.count).toBe(1);
+    expect(before.bytes - after.bytes).toBe(size);
+  });
+
+  it("runs batch operations alongside interactive ones", async () => {
+    const asset = { path: "./tests/fixtures/CA.jpg" };
+    const batch = Array.from({ length: 8 }, () =>
+      Reader.fromAsset(asset, undefined, { priority: "batch" }),
+    );
+    const interactive = await Reader.fromAsset(asset, undefined, {
+      priority: "interactive",
+    });
+    expect(interactive).not.toBeNull();
+    for (const reader of await Promise.all(batch)) {
+      expect(reader).not.toBeNull();
+    }
+    expect(getMetrics().runtime?.batchWaiting).toBe(0);
+
+    await expect(
+      Reader.fromAsset(asset, undefined, {
+        priority: "urgent" as "interactive",
+      }),
+    ).rejects.toThrow("Unknown priority");
+  });
+
+  it("validates logger arguments", () => {
+    expect(() =>
+      setLogger(() => {}, { level: "verbose" as "trace" }),
+    ).toThrow("Unknown log level");
+    expect(() =>
+      setLogger("console" as unknown as null),
+    ).toThrow("must be a function");
+  });
+
+  it("installs and removes a logger", async () => {
+    const events: unknown[] = [];
+    setLogger((event) => events.push(event), { level: "trace" });
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    expect(reader).not.toBeNull();
+    setLogger(null);
+    for (const event of events) {
+      expect(event).toMatchObject({
+        level: expect.any(String),
+        target: expect.any(String),
+        message: expect.any(String),
+      });
+    }
+  });
+
+  it("exports native spans in the caller's trace", async () => {
+    const traceparent =
+      "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    const spans: NativeSpan[] = [];
+    configureTelemetry({ onSpan: (span) => spans.push(span) });
+    try {
+      await withTraceParent(traceparent, () =>
+        Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" }),
+      );
+      await expect(
+        Reader.fromAsset({ path: "./tests/fixtures/missing.jpg" }),
+      ).rejects.toThrow();
+      await new Promise((resolve) => setTimeout(resolve, 50));
+    } finally {
+      configureTelemetry(null);
+    }
+
+    expect(spans).toEqual([
+      expect.objectContaining({
+        name: "c2pa.read",
+        traceId: "4bf92f3577b34da6a3ce929d0e0e4736",
+        parentSpanId: "00f067aa0ba902b7",
+        status: "ok",
+        attributes: { "c2pa.operation": "read" },
+      }),
+      expect.objectContaining({ parentSpanId: null, status: "error" }),
+    ]);
+    expect(spans[0].endTime).toBeGreaterThanOrEqual(spans[0].startTime);
+    expect(() => withTraceParent("not-a-trace", () => {})).toThrow(
+      "Invalid traceparent",
+    );
+  });
+
+  it("sends spans to an OTLP collector", async () => {
+    const requests: { url?: string; body: string }[] = [];
+    const server = http.createServer((req, res) => {
+      let body = "";
+      req.on("data", (chunk) => (body += chunk));
+      req.on("end", () => {
+        requests.push({ url: req.url, body });
+        res.end("{}");
+      });
+    });
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+    configureTelemetry({
+      otlp: { endpoint: `http://127.0.0.1:${port}`, serviceName: "test" },
+    });
+    try {
+      await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+      await flushTelemetry();
+    } finally {
+      configureTelemetry(null);
+      server.close();
+    }
+
+    expect(requests).toHaveLength(1);
+    expect(requests[0].url).toBe("/v1/traces");
+    const [resourceSpans] = JSON.parse(requests[0].body).resourceSpans;
+    expect(resourceSpans.resource.attributes).toEqual([
+      { key: "service.name", value: { stringValue: "test" } },
+    ]);
+    expect(resourceSpans.scopeSpans[0].spans).toEqual([
+      expect.objectContaining({ name: "c2pa.read", status: { code: 1 } }),
+    ]);
+  });
+
+  it("can be loaded by worker threads", async () => {
+    const instances = getMetrics().instances;
+    const worker = new Worker(
+      `
+      const { parentPort } = require("worker_threads");
+      const neon = require(${JSON.stringify(path.join(__dirname, "index.node"))});
+      neon.onSettingsChange(() => {});
+      neon.readerFromAsset({ path: "./tests/fixtures/CA.jpg" }).then(
+        (reader) => parentPort.postMessage(neon.readerJson.call(reader).length),
+        (err) => parentPort.postMessage(err.message),
+      );
+      `,
+      { eval: true },
+    );
+    const message = await new Promise((resolve, reject) => {
+      worker.once("message", resolve);
+      worker.once("error", reject);
+    });
+    expect(message).toBeGreaterThan(0);
+    expect(getMetrics().instances).toBe(instances + 1);
+    await expect(shutdown()).rejects.toThrow("shared by");
+
+    await worker.terminate();
+    expect(getMetrics().instances).toBe(instances);
+    // Listeners from the terminated worker are dropped.
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    expect(reader).not.toBeNull();
+  });
+
+  it("runs work on a single thread with the currentThread flavor", () => {
+    // The runtime is configured once per process, so this runs in a new one.
+    const script = `
+      const neon = require(${JSON.stringify(path.join(__dirname, "index.node"))});
+      neon.configureRuntime(JSON.stringify({ flavor: "currentThread" }));
+      const asset = { path: "./tests/fixtures/CA.jpg" };
+      (async () => {
+        const readers = await Promise.all([
+          neon.readerFromAsset(asset),
+          neon.readerFromAsset(asset),
+        ]);
+        neon.readerFromAssetSync(asset);
+        const { workers } = JSON.parse(neon.getMetrics()).runtime;
+        const drained = await neon.shutdown(5000);
+        console.log(JSON.stringify({ read: readers.length, workers, drained }));
+      })();
+    `;
+    const output = execFileSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+    });
+    expect(JSON.parse(output)).toEqual({ read: 2, workers: 1, drained: true });
+  });
+
+  it("rejects worker threads for the currentThread flavor", () => {
+    expect(() =>
+      configureRuntime({ flavor: "currentThread", workerThreads: 2 }),
+    ).toThrow("workerThreads can't be set");
+  });
+
+  it("drains running work and rejects new work after shutdown", () => {
+    // Shutting down is permanent, so it runs in a separate process.
+    const script = `
+      const neon = require(${JSON.stringify(path.join(__dirname, "index.node"))});
+      const asset = { path: "./tests/fixtures/CA.jpg" };
+      (async () => {
+        const pending = neon.readerFromAsset(asset);
+        const drained = await neon.shutdown(5000);
+        const read = await pending.then(() => "ok", (err) => err.message);
+        const after = await Promise.resolve()
+          .then(() => neon.readerFromAsset(asset))
+          .then(() => "ok", (err) => err.message);
+        console.log(JSON.stringify({ drained, read, after }));
+      })();
+    `;
+    const output = execFileSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+    });
+    const result = JSON.parse(output);
+    expect(result.drained).toBe(true);
+    expect(result.read).toBe("ok");
+    expect(result.after).toContain("shut down");
+  });
+
+  it("starts again from the environment after reinitialize", () => {
+    // The runtime is started once per process, so this runs in a new one.
+    const script = `
+      const neon = require(${JSON.stringify(path.join(__dirname, "index.node"))});
+      const asset = { path: "./tests/fixtures/CA.jpg" };
+      const workers = () => JSON.parse(neon.getMetrics()).runtime.workers;
+      (async () => {
+        await neon.readerFromAsset(asset);
+        const before = workers();
+        await neon.shutdown(5000);
+        process.env.C2PA_WORKER_THREADS = "3";
+        neon.reinitialize();
+        await neon.readerFromAsset(asset);
+        console.log(JSON.stringify({ before, after: workers() }));
+      })();
+    `;
+    const output = execFileSync(process.execPath, ["-e", script], {
+      encoding: "utf8",
+      env: { ...process.env, C2PA_WORKER_THREADS: "2" },
+    });
+    expect(JSON.parse(output)).toEqual({ before: 2, after: 3 });
+  });
+});
@@ -0,0 +1,210 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  LogLevel,
+  NativeLogEvent,
+  NativeMemoryUsage,
+  NativeMetrics,
+  RuntimeConfig,
+  TelemetryConfig,
+} from "./types.d.ts";
+
+/**
+ * Configure the native thread pool that runs asynchronous reads and signs.
+ * Must be called before any other operation starts the pool; afterwards it throws.
+ * @param config The thread pool configuration
+ */
+export function configureRuntime(config: RuntimeConfig): void {
+  getNeonBinary().configureRuntime(
+    JSON.stringify({
+      flavor: config.flavor,
+      worker_threads: config.workerThreads,
+      max_blocking_threads: config.maxBlockingThreads,
+      thread_name: config.threadName,
+      thread_stack_size: config.threadStackSize,
+      batch_concurrency: config.batchConcurrency,
+    }),
+  );
+}
+
+/**
+ * Shut down the native thread pool, for instance on SIGTERM. New operations are
+ * rejected from this point on, while running ones get up to `timeoutMs`
+ * (30 seconds by default) to finish. The pool cannot be restarted afterwards,
+ * except by `reinitialize`. The pool is shared with every Worker that loaded
+ * the module, so this rejects while any other Worker is still running.
+ * @param options.timeoutMs How long to wait for running operations
+ * @returns Whether every running operation finished in time
+ */
+export async function shutdown(options?: {
+  timeoutMs?: number;
+}): Promise<boolean> {
+  return getNeonBinary().shutdown(options?.timeoutMs);
+}
+
+/**
+ * Return the native layer to its state before first use, for processes
+ * restored from a snapshot such as Lambda SnapStart. The thread pool is
+ * stopped and started again on the next operation, and the `C2PA_*`
+ * environment variables are read again, so no threads or configuration from
+ * before the snapshot carry over. Operations still running are abandoned, and
+ * trust sources stop refreshing until `configureTrustSources` is called again.
+ * Like `shutdown`, this throws while any other Worker is still running.
+ */
+export function reinitialize(): void {
+  getNeonBinary().reinitialize();
+}
+
+/**
+ * Get counters from the native layer: the thread pool's tasks, and for reads,
+ * signs, and resource extractions, how many started, succeeded, failed, or are
+ * still running, with a latency histogram.
+ * @returns A snapshot of the counters
+ */
+export function getMetrics(): NativeMetrics {
+  return JSON.parse(getNeonBinary().getMetrics());
+}
+
+/**
+ * Get the approximate native memory held by the Readers and Builders that
+ * have not been disposed or garbage collected.
+ * @returns The number of objects and their size in bytes, for each kind
+ */
+export function getMemoryUsage(): NativeMemoryUsage {
+  return JSON.parse(getNeonBinary().getMemoryUsage());
+}
+
+/**
+ * Get the native counters in the Prometheus text exposition format, to serve
+ * from a `/metrics` endpoint.
+ * @param prefix Prefix for the metric names
+ * @returns The metrics as text
+ */
+export function getPrometheusMetrics(prefix = "c2pa"): string {
+  const metrics = getMetrics();
+  const lines: string[] = [];
+  const metric = (name: string, type: string, help: string) => {
+    lines.push(`# HELP ${prefix}_${name} ${help}`);
+    lines.push(`# TYPE ${prefix}_${name} ${type}`);
+  };
+
+  if (metrics.runtime) {
+    metric("runtime_workers", "gauge", "Worker threads in the native pool.");
+    lines.push(`${prefix}_runtime_workers ${metrics.runtime.workers}`);
+    metric("runtime_alive_tasks", "gauge", "Tasks running or waiting.");
+    lines.push(`${prefix}_runtime_alive_tasks ${metrics.runtime.aliveTasks}`);
+    metric("runtime_queued_tasks", "gauge", "Tasks queued for a worker.");
+    lines.push(`${prefix}_runtime_queued_tasks ${metrics.runtime.queuedTasks}`);
+  }
+
+  const operations = Object.entries(metrics.operations);
+  metric("operations_total", "counter", "Completed native operations.");
+  for (const [operation, counts] of operations) {
+    lines.push(
+      `${prefix}_operations_total{operation="${operation}",outcome="success"} ${counts.succeeded}`,
+    );
+    lines.push(
+      `${prefix}_operations_total{operation="${operation}",outcome="failure"} ${counts.failed}`,
+    );
+  }
+  metric("operations_active", "gauge", "Native operations in progress.");
+  for (const [operation, counts] of operations) {
+    lines.push(
+      `${prefix}_operations_active{operation="${operation}"} ${counts.active}`,
+    );
+  }
+  metric(
+    "operation_duration_seconds",
+    "histogram",
+    "Duration of native operations.",
+  );
+  for (const [operation, counts] of operations) {
+    for (const [le, count] of counts.latency.buckets) {
+      lines.push(
+        `${prefix}_operation_duration_seconds_bucket{operation="${operation}",le="${le}"} ${count}`,
+      );
+    }
+    lines.push(
+      `${prefix}_operation_duration_seconds_sum{operation="${operation}"} ${counts.latency.sumSeconds}`,
+    );
+    lines.push(
+      `${prefix}_operation_duration_seconds_count{operation="${operation}"} ${counts.latency.count}`,
+    );
+  }
+  return lines.join("\n") + "\n";
+}
+
+/**
+ * Forward log records from the native layer, including c2pa-rs, to `logger`.
+ * Only one logger is active at a time; calling this again replaces it, and
+ * passing `null` stops forwarding. Records are delivered asynchronously.
+ * @param logger Called with each record
+ * @param options.level The least severe level forwarded, "warn" by default
+ */
+export function setLogger(
+  logger: ((event: NativeLogEvent) => void) | null,
+  options?: { level?: LogLevel },
+): void {
+  getNeonBinary().setLogger(logger, options?.level);
+}
+
+/**
+ * Export spans for native reads, signs, resource extraction, and network
+ * requests, so traces show the time spent in native C2PA work. Spans go to
+ * `onSpan`, to an OTLP/HTTP collector, or both. Calling this again replaces
+ * the configuration, and passing `null` turns telemetry off.
+ * @param config Where to export spans
+ */
+export function configureTelemetry(config: TelemetryConfig | null): void {
+  getNeonBinary().configureTelemetry(
+    config?.otlp
+      ? JSON.stringify({
+          otlp: {
+            endpoint: config.otlp.endpoint,
+            headers: config.otlp.headers,
+            service_name: config.otlp.serviceName,
+          },
+        })
+      : null,
+    config?.onSpan ?? null,
+  );
+}
+
+/**
+ * Run `fn` with native spans it starts joining the trace of `traceparent`, a
+ * W3C trace context header such as OpenTelemetry's propagator injects. Only
+ * native operations `fn` starts before its first `await` are covered. Spans
+ * for network requests made by c2pa-rs start their own traces.
+ * @param traceparent The parent span, e.g.
+ * "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+ * @param fn Starts the native operations
+ * @returns What `fn` returns
+ */
+export function withTraceParent<T>(traceparent: string, fn: () => T): T {
+  getNeonBinary().setTraceParent(traceparent);
+  try {
+    return fn();
+  } finally {
+    getNeonBinary().setTraceParent(null);
+  }
+}
+
+/**
+ * Send spans waiting for the OTLP collector now, rather than with the next
+ * batch, such as before the process exits.
+ */
+export async function flushTelemetry(): Promise<void> {
+  return getNeonBinary().flushTelemetry();
+}
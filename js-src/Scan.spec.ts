@@ -0,0 +1,153 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+import * as fs from "fs-extra";
+import * as os from "os";
+import * as path from "path";
+
+import { Builder } from "./Builder.js";
+import { readDataHash } from "./Hashing.js";
+import { Reader } from "./Reader.js";
+import { findProvenanceReference, scanC2paRanges } from "./Scan.js";
+import { LocalSigner } from "./Signer.js";
+
+describe("scanC2paRanges", () => {
+  it("finds the segments holding a manifest store", async () => {
+    const asset = { path: "./tests/fixtures/CA.jpg" };
+    const result = await scanC2paRanges(asset);
+    expect(result.format).toBe("image/jpeg");
+    expect(result.ranges.map((range) => range.container)).toEqual([
+      "APP11",
+      "APP11",
+    ]);
+
+    // Together they are what the data hash leaves out.
+    const [first, last] = result.ranges;
+    const [exclusion] = (await readDataHash(asset))!.exclusions;
+    expect(first.start).toBe(exclusion.start);
+    expect(last.start + last.length - first.start).toBe(exclusion.length);
+  });
+
+  it("finds XMP referring to a remote manifest", async () => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "A.jpg",
+    });
+    builder.setNoEmbed(true);
+    builder.setRemoteUrl("https://manifests.example.com/remote.c2pa");
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const output = { buffer: null };
+    builder.sign(signer, { path: "./tests/fixtures/A.jpg" }, output);
+
+    const { ranges } = await scanC2paRanges({
+      buffer: output.buffer!,
+      mimeType: "image/jpeg",
+    });
+    expect(ranges).toEqual([
+      expect.objectContaining({
+        kind: "xmp",
+        container: "APP1",
+        provenance: "https://manifests.example.com/remote.c2pa",
+      }),
+    ]);
+  });
+
+  it("finds nothing in an asset without C2PA data", async () => {
+    const result = await scanC2paRanges({ path: "./tests/fixtures/A.jpg" });
+    expect(result.ranges).toEqual([]);
+  });
+});
+
+describe("findProvenanceReference", () => {
+  let tempDir: string;
+
+  beforeEach(async () => {
+    tempDir = await fs.mkdtemp(path.join(os.tmpdir(), "c2pa-provenance-"));
+  });
+
+  afterEach(async () => {
+    await fs.remove(tempDir);
+  });
+
+  // Sign A.jpg into the temp dir without embedding the manifest store.
+  const signUnembedded = async (name: string, remoteUrl?: string) => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "A.jpg",
+    });
+    builder.setNoEmbed(true);
+    if (remoteUrl) {
+      builder.setRemoteUrl(remoteUrl);
+    }
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const assetPath = path.join(tempDir, name);
+    const manifest = builder.sign(
+      signer,
+      { path: "./tests/fixtures/A.jpg" },
+      { path: assetPath },
+    );
+    return { assetPath, manifest };
+  };
+
+  it("reads a manifest store referenced from an XMP sidecar", async () => {
+    const { assetPath, manifest } = await signUnembedded("photo.jpg");
+    await fs.writeFile(path.join(tempDir, "photo.c2pa"), manifest);
+    await fs.writeFile(
+      path.join(tempDir, "photo.xmp"),
+      `<x:xmpmeta xmlns:x="adobe:ns:meta/">
+        <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+          <rdf:Description xmlns:dcterms="http://purl.org/dc/terms/"
+            dcterms:provenance="photo.c2pa"/>
+        </rdf:RDF>
+      </x:xmpmeta>`,
+    );
+
+    expect(await findProvenanceReference({ path: assetPath })).toEqual({
+      url: "photo.c2pa",
+      source: "sidecar",
+    });
+    const reader = await Reader.fromAsset({ path: assetPath });
+    expect(reader?.getActive()?.title).toBe("A.jpg");
+  });
+
+  it("does not fetch a remote reference when fetching is off", async () => {
+    const url = "https://manifests.example.com/remote.c2pa";
+    const { assetPath } = await signUnembedded("remote.jpg", url);
+
+    expect(await findProvenanceReference({ path: assetPath })).toEqual({
+      url,
+      source: "embedded",
+    });
+    await expect(
+      Reader.fromAsset(
+        { path: assetPath },
+        { verify: { remote_manifest_fetch: false } },
+      ),
+    ).rejects.toMatchObject({ code: "REMOTE_MANIFEST_NOT_FETCHED" });
+  });
+
+  it("finds nothing in an asset without XMP", async () => {
+    expect(
+      await findProvenanceReference({ path: "./tests/fixtures/A.jpg" }),
+    ).toBeNull();
+  });
+});
@@ -0,0 +1,62 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  C2paScanResult,
+  OperationOptions,
+  ProvenanceReference,
+  SourceAsset,
+} from "./types.d.ts";
+
+/**
+ * Find the bytes of an asset that hold C2PA data: the JPEG segments, PNG
+ * chunks, BMFF boxes, or RIFF chunks holding the manifest store, and XMP that
+ * refers to a manifest store in `dcterms:provenance`. Nothing is validated.
+ * Formats that encode the manifest store, such as GIF and SVG, can't be
+ * scanned.
+ * @param asset The asset to scan
+ * @returns The byte ranges, which are empty when the asset has no C2PA data
+ */
+export async function scanC2paRanges(
+  asset: SourceAsset,
+  options?: OperationOptions,
+): Promise<C2paScanResult> {
+  return JSON.parse(
+    await getNeonBinary().scanC2paRanges(
+      asset,
+      options?.timeoutMs,
+      options?.priority,
+    ),
+  );
+}
+
+/**
+ * Find the manifest store an asset refers to in the `dcterms:provenance` of
+ * its XMP, embedded or in an `.xmp` sidecar file next to a file asset. The
+ * reference is not followed; `Reader.fromAsset` does that when the asset has
+ * no manifest store of its own.
+ * @param asset The asset to look in
+ * @returns The reference, or null when there is none outside the asset
+ */
+export async function findProvenanceReference(
+  asset: SourceAsset,
+  options?: OperationOptions,
+): Promise<ProvenanceReference | null> {
+  const json = await getNeonBinary().findProvenanceReference(
+    asset,
+    options?.timeoutMs,
+    options?.priority,
+  );
+  return json === null ? null : JSON.parse(json);
+}
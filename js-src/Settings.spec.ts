@@ -12,11 +12,30 @@ import {
   settingsToJson,
   loadSettingsFromFile,
   loadSettingsFromUrl,
+  getEffectiveSettings,
+  configureTrustSources,
+  clearTrustSources,
+  onSettingsChange,
+  setOfflineMode,
+  isOfflineMode,
+  configureProxy,
+  clearProxy,
+  setRemoteManifestResolver,
+  configureNetworkCache,
+  clearNetworkCache,
+  getNetworkCacheStats,
+  configureNetwork,
 } from "./Settings.js";
+import { Builder } from "./Builder.js";
+import { Reader } from "./Reader.js";
+import { LocalSigner } from "./Signer.js";
 import type { TrustConfig, VerifyConfig, SettingsContext } from "./types.d.ts";
 import * as fs from "fs-extra";
 import * as path from "path";
 import * as os from "os";
+import { execFileSync } from "child_process";
+import http from "http";
+import type { AddressInfo } from "net";
 import { vi } from "vitest";
 
 // Mock node-fetch
@@ -228,6 +247,83 @@ describe("Settings", () => {
     expect(merged.verify?.ocspFetch).toBe(true); // overridden by settings2
   });
 
+  it("returns effective settings with defaults filled in", () => {
+    const defaults = getEffectiveSettings() as any;
+    expect(defaults.verify.verify_after_reading).toBe(true);
+    expect(defaults.trust).toBeDefined();
+    expect(defaults.core).toBeDefined();
+
+    const effective = getEffectiveSettings(
+      settingsToJson(createVerifySettings({ verifyAfterReading: false })),
+    ) as any;
+    expect(effective.verify.verify_after_reading).toBe(false);
+    // Unspecified values keep their defaults
+    expect(effective.verify.remote_manifest_fetch).toBe(true);
+    expect("signer" in effective).toBe(false);
+  });
+
+  it("throws on invalid settings when resolving effective settings", () => {
+    expect(() => getEffectiveSettings("{ not valid")).toThrow();
+  });
+
+  describe("environment variables", () => {
+    // The environment is read once per process, on first use, so each case
+    // runs in a separate process.
+    const load = (env: Record<string, string>, script: string) => {
+      const addon = JSON.stringify(path.join(__dirname, "index.node"));
+      return execFileSync(
+        process.execPath,
+        ["-e", `const neon = require(${addon});\n${script}`],
+        { encoding: "utf8", env: { ...process.env, ...env }, stdio: "pipe" },
+      );
+    };
+
+    it("configures settings, signers, and the runtime", () => {
+      const output = load(
+        {
+          C2PA_TRUST_ANCHORS: "./tests/fixtures/certs/es256.pub",
+          C2PA_REMOTE_MANIFEST_FETCH: "false",
+          C2PA_TSA_URL: "http://timestamp.example.com",
+          C2PA_WORKER_THREADS: "2",
+        },
+        `const fs = require("fs");
+        const settings = JSON.parse(neon.getSettingsJson());
+        const signer = neon.localSignerNew(
+          fs.readFileSync("./tests/fixtures/certs/es256.pub"),
+          fs.readFileSync("./tests/fixtures/certs/es256.pem"),
+          "es256",
+        );
+        neon.readerFromAsset({ path: "./tests/fixtures/CA.jpg" }).then(() => {
+          console.log(JSON.stringify({
+            anchors: settings.trust.trust_anchors,
+            remote: settings.verify.remote_manifest_fetch,
+            tsa: neon.localSignerTimeAuthorityUrl.call(signer),
+            workers: JSON.parse(neon.getMetrics()).runtime.workers,
+          }));
+        });`,
+      );
+      const result = JSON.parse(output);
+      expect(result.anchors).toContain("BEGIN CERTIFICATE");
+      expect(result.remote).toBe(false);
+      expect(result.tsa).toBe("http://timestamp.example.com");
+      expect(result.workers).toBe(2);
+    });
+
+    it("fails the first operation with an invalid value", () => {
+      const output = load(
+        { C2PA_WORKER_THREADS: "many" },
+        `try {
+          neon.getSettingsJson();
+        } catch (error) {
+          console.log(error.message);
+        }`,
+      );
+      expect(output).toContain(
+        'C2PA_WORKER_THREADS must be a whole number of at least 1, got "many"',
+      );
+    });
+  });
+
   describe("loadSettingsFromFile", () => {
     let tempDir: string;
 
@@ -275,6 +371,406 @@ verify_after_sign = false`;
     });
   });
 
+  describe("configureTrustSources", () => {
+    afterEach(() => {
+      clearTrustSources();
+    });
+
+    it("applies trust anchors loaded from a file", async () => {
+      await configureTrustSources({
+        trust: { trustAnchors: "./tests/fixtures/certs/es256.pub" },
+      });
+
+      const effective = getEffectiveSettings() as any;
+      expect(effective.trust.trust_anchors).toContain("BEGIN CERTIFICATE");
+    });
+
+    it("applies trust lists given as strings", async () => {
+      const trustAnchors = await fs.readFile(
+        "./tests/fixtures/certs/es256.pub",
+        "utf8",
+      );
+      const trustConfig = "1.3.6.1.5.5.7.3.4\n1.3.6.1.5.5.7.3.36\n";
+      await configureTrustSources({ trust: { trustAnchors, trustConfig } });
+
+      const effective = getEffectiveSettings() as any;
+      expect(effective.trust.trust_anchors).toBe(trustAnchors);
+      expect(effective.trust.trust_config).toBe(trustConfig);
+    });
+
+    it("lets per-call settings override loaded trust lists", async () => {
+      await configureTrustSources({
+        trust: { trustAnchors: "./tests/fixtures/certs/es256.pub" },
+      });
+      const userAnchors = await fs.readFile(
+        "./tests/fixtures/certs/ed25519.pub",
+        "utf8",
+      );
+
+      const effective = getEffectiveSettings(
+        JSON.stringify({ trust: { trust_anchors: userAnchors } }),
+      ) as any;
+      expect(effective.trust.trust_anchors).toBe(userAnchors);
+    });
+
+    it("removes loaded trust lists when cleared", async () => {
+      await configureTrustSources({
+        trust: { trustAnchors: "./tests/fixtures/certs/es256.pub" },
+        refreshIntervalSecs: 60,
+      });
+      clearTrustSources();
+
+      const effective = getEffectiveSettings() as any;
+      expect(effective.trust.trust_anchors).toBeNull();
+    });
+
+    it("rejects plain HTTP sources", async () => {
+      await expect(
+        configureTrustSources({
+          trust: { trustAnchors: "http://example.com/anchors.pem" },
+        }),
+      ).rejects.toThrow("HTTPS");
+    });
+
+    it("falls back to cached downloads", async () => {
+      const cacheDir = await fs.mkdtemp(path.join(os.tmpdir(), "c2pa-trust-"));
+      await fs.copy(
+        "./tests/fixtures/certs/es256.pub",
+        path.join(cacheDir, "example.com_trust_anchors.pem"),
+      );
+      const trust = { trustAnchors: "https://example.com/trust/anchors.pem" };
+      setOfflineMode(true);
+      try {
+        await configureTrustSources({ trust, cacheDir });
+        const effective = getEffectiveSettings() as any;
+        expect(effective.trust.trust_anchors).toContain("BEGIN CERTIFICATE");
+
+        await expect(
+          configureTrustSources({
+            trust: { trustAnchors: "https://example.com/other.pem" },
+            cacheDir,
+          }),
+        ).rejects.toThrow("offline mode");
+      } finally {
+        setOfflineMode(false);
+        await fs.remove(cacheDir);
+      }
+    });
+
+    it("rejects sources that are not trust lists", async () => {
+      await expect(
+        configureTrustSources({
+          trust: { trustAnchors: "./tests/fixtures/certs/es256.pem" },
+        }),
+      ).rejects.toThrow();
+    });
+  });
+
+  describe("onSettingsChange", () => {
+    it("notifies listeners when trust sources change", async () => {
+      const events: string[] = [];
+      const unsubscribe = onSettingsChange((event) => events.push(event.reason));
+
+      await configureTrustSources({
+        trust: { trustAnchors: "./tests/fixtures/certs/es256.pub" },
+      });
+      clearTrustSources();
+      await vi.waitFor(() => expect(events).toHaveLength(2));
+      expect(events).toEqual(["trustSourcesConfigured", "trustSourcesCleared"]);
+
+      unsubscribe();
+      await configureTrustSources({
+        trust: { trustAnchors: "./tests/fixtures/certs/es256.pub" },
+      });
+      clearTrustSources();
+      await new Promise((resolve) => setTimeout(resolve, 50));
+      expect(events).toHaveLength(2);
+    });
+  });
+
+  describe("offline mode", () => {
+    afterEach(() => {
+      setOfflineMode(false);
+    });
+
+    it("can be turned on and off", () => {
+      expect(isOfflineMode()).toBe(false);
+      setOfflineMode(true);
+      expect(isOfflineMode()).toBe(true);
+      setOfflineMode(false);
+      expect(isOfflineMode()).toBe(false);
+    });
+
+    it("refuses to fetch trust lists", async () => {
+      setOfflineMode(true);
+      await expect(
+        configureTrustSources({
+          trust: { trustAnchors: "https://example.com/anchors.pem" },
+        }),
+      ).rejects.toThrow("offline mode");
+    });
+
+    it("refuses to fetch remote manifests", async () => {
+      setOfflineMode(true);
+      await expect(
+        Reader.fromAsset({ path: "./tests/fixtures/cloud.jpg" }),
+      ).rejects.toMatchObject({ name: expect.stringMatching(/^Offline/) });
+    });
+
+    it("still reads embedded manifests", async () => {
+      setOfflineMode(true);
+      const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+      expect(reader).not.toBeNull();
+    });
+  });
+
+  describe("configureProxy", () => {
+    afterEach(() => {
+      clearProxy();
+    });
+
+    it("accepts a proxy configuration", async () => {
+      configureProxy({
+        httpProxy: "http://proxy.example.com:8080",
+        httpsProxy: "http://proxy.example.com:8080",
+        noProxy: ["localhost", ".internal.example.com"],
+      });
+
+      const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+      expect(reader).not.toBeNull();
+    });
+
+    it("rejects an invalid proxy URL", () => {
+      expect(() => configureProxy({ httpsProxy: "not a url" })).toThrow(
+        "Invalid proxy",
+      );
+    });
+
+    it("notifies settings change listeners", async () => {
+      const events: string[] = [];
+      const unsubscribe = onSettingsChange((event) => events.push(event.reason));
+
+      configureProxy({ httpsProxy: "http://proxy.example.com:8080" });
+      await vi.waitFor(() => expect(events).toEqual(["proxyChanged"]));
+      unsubscribe();
+    });
+  });
+
+  describe("setRemoteManifestResolver", () => {
+    const remoteUrl = "https://manifests.example.com/A.c2pa";
+
+    async function signRemote(): Promise<{ asset: Buffer; manifest: Buffer }> {
+      const builder = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        title: "A.jpg",
+      });
+      builder.setNoEmbed(true);
+      builder.setRemoteUrl(remoteUrl);
+      const signer = LocalSigner.newSigner(
+        await fs.readFile("./tests/fixtures/certs/es256.pub"),
+        await fs.readFile("./tests/fixtures/certs/es256.pem"),
+        "es256",
+      );
+      const output = { buffer: null };
+      const manifest = builder.sign(
+        signer,
+        { path: "./tests/fixtures/A.jpg" },
+        output,
+      );
+      return { asset: output.buffer!, manifest };
+    }
+
+    afterEach(() => {
+      setRemoteManifestResolver(null);
+    });
+
+    it("reads remote manifests from the resolver", async () => {
+      const { asset, manifest } = await signRemote();
+      const urls: string[] = [];
+      setRemoteManifestResolver(async (url) => {
+        urls.push(url);
+        return manifest;
+      });
+
+      const reader = await Reader.fromAsset({
+        buffer: asset,
+        mimeType: "image/jpeg",
+      });
+      expect(urls).toEqual([remoteUrl]);
+      expect(reader!.remoteUrl()).toEqual(remoteUrl);
+      expect(reader!.getActive()!.title).toEqual("A.jpg");
+    });
+
+    it("fails to read when the resolver throws", async () => {
+      const { asset } = await signRemote();
+      setRemoteManifestResolver(() => {
+        throw new Error("not authorized");
+      });
+
+      await expect(
+        Reader.fromAsset({ buffer: asset, mimeType: "image/jpeg" }),
+      ).rejects.toMatchObject({
+        code: "REMOTE_FETCH_FAILED",
+        message: expect.stringContaining("not authorized"),
+      });
+    });
+
+    it("is not asked for embedded manifests", async () => {
+      const resolver = vi.fn(async () => null);
+      setRemoteManifestResolver(resolver);
+
+      const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+      expect(reader).not.toBeNull();
+      expect(resolver).not.toHaveBeenCalled();
+    });
+  });
+
+  describe("configureNetworkCache", () => {
+    let cacheDir: string;
+
+    beforeEach(async () => {
+      cacheDir = await fs.mkdtemp(path.join(os.tmpdir(), "c2pa-network-cache-"));
+    });
+
+    afterEach(async () => {
+      configureNetworkCache(null);
+      setRemoteManifestResolver(null);
+      await fs.remove(cacheDir);
+    });
+
+    async function signRemote(): Promise<Buffer> {
+      const builder = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        title: "A.jpg",
+      });
+      builder.setNoEmbed(true);
+      builder.setRemoteUrl("https://manifests.example.com/cached.c2pa");
+      const signer = LocalSigner.newSigner(
+        await fs.readFile("./tests/fixtures/certs/es256.pub"),
+        await fs.readFile("./tests/fixtures/certs/es256.pem"),
+        "es256",
+      );
+      const output = { buffer: null };
+      const manifest = builder.sign(
+        signer,
+        { path: "./tests/fixtures/A.jpg" },
+        output,
+      );
+      const resolver = vi.fn(async () => manifest);
+      setRemoteManifestResolver(resolver);
+      return output.buffer!;
+    }
+
+    it("fetches a remote manifest once", async () => {
+      const asset = await signRemote();
+      configureNetworkCache({});
+      const before = getNetworkCacheStats().resources.remoteManifest;
+
+      for (let i = 0; i < 3; i++) {
+        const reader = await Reader.fromAsset({
+          buffer: asset,
+          mimeType: "image/jpeg",
+        });
+        expect(reader!.getActive()!.title).toEqual("A.jpg");
+      }
+      const stats = getNetworkCacheStats();
+      expect(stats.enabled).toBe(true);
+      expect(stats.entries).toEqual(1);
+      expect(stats.resources.remoteManifest).toEqual({
+        hits: before.hits + 2,
+        misses: before.misses + 1,
+      });
+    });
+
+    it("keeps responses on disk until cleared", async () => {
+      const asset = await signRemote();
+      configureNetworkCache({ dir: cacheDir });
+      await Reader.fromAsset({ buffer: asset, mimeType: "image/jpeg" });
+      expect(await fs.readdir(path.join(cacheDir, "remoteManifest"))).toHaveLength(1);
+
+      // A new configuration starts with an empty memory cache.
+      configureNetworkCache({ dir: cacheDir });
+      const before = getNetworkCacheStats().resources.remoteManifest.hits;
+      await Reader.fromAsset({ buffer: asset, mimeType: "image/jpeg" });
+      expect(getNetworkCacheStats().resources.remoteManifest.hits).toEqual(before + 1);
+
+      clearNetworkCache();
+      expect(getNetworkCacheStats().entries).toEqual(0);
+      expect(await fs.pathExists(path.join(cacheDir, "remoteManifest"))).toBe(false);
+    });
+
+    it("does not cache with a TTL of zero", async () => {
+      const asset = await signRemote();
+      configureNetworkCache({ remoteManifestTtlSecs: 0 });
+      await Reader.fromAsset({ buffer: asset, mimeType: "image/jpeg" });
+      expect(getNetworkCacheStats().entries).toEqual(0);
+    });
+
+    it("is off by default", () => {
+      expect(getNetworkCacheStats()).toMatchObject({
+        enabled: false,
+        entries: 0,
+        memoryBytes: 0,
+      });
+    });
+  });
+
+  describe("configureNetwork", () => {
+    afterEach(() => {
+      configureNetwork(null);
+    });
+
+    it("rejects invalid values", () => {
+      expect(() => configureNetwork({ retries: -1 })).toThrow(
+        "Invalid network configuration",
+      );
+    });
+
+    it("times out requests to a server that doesn't respond", async () => {
+      // Accepts connections but never answers
+      const server = http.createServer(() => {});
+      await new Promise<void>((resolve) => server.listen(0, resolve));
+      const { port } = server.address() as AddressInfo;
+      const url = `http://127.0.0.1:${port}/manifests/A.c2pa`;
+      const builder = Builder.withJson({
+        claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+        title: "A.jpg",
+      });
+      builder.setNoEmbed(true);
+      builder.setRemoteUrl(url);
+      const signer = LocalSigner.newSigner(
+        await fs.readFile("./tests/fixtures/certs/es256.pub"),
+        await fs.readFile("./tests/fixtures/certs/es256.pem"),
+        "es256",
+      );
+      const output = { buffer: null };
+      builder.sign(signer, { path: "./tests/fixtures/A.jpg" }, output);
+      const asset = { buffer: output.buffer!, mimeType: "image/jpeg" };
+
+      configureNetwork({ readTimeoutMs: 200 });
+      try {
+        const start = Date.now();
+        await expect(Reader.fromAsset(asset)).rejects.toMatchObject({
+          code: "REMOTE_FETCH_FAILED",
+          network: { endpoint: "remoteManifest", url, reason: "timeout" },
+        });
+        expect(Date.now() - start).toBeLessThan(5000);
+
+        // Options given for the call take precedence.
+        await expect(
+          Reader.fromAsset(asset, undefined, {
+            network: { remoteManifest: { readTimeoutMs: 100, retries: 1 } },
+          }),
+        ).rejects.toMatchObject({
+          network: { reason: "timeout", attempts: 2 },
+        });
+      } finally {
+        server.closeAllConnections();
+        server.close();
+      }
+    });
+  });
+
   describe("loadSettingsFromUrl", () => {
     beforeEach(() => {
       vi.clearAllMocks();
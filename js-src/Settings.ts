@@ -14,10 +14,25 @@
 import * as fs from "fs-extra";
 import fetch from "node-fetch";
 
-import type { TrustConfig, VerifyConfig, SettingsContext } from "./types.d.ts";
+import { getNeonBinary } from "./binary.js";
+import { networkArgument } from "./Context.js";
+import type {
+  C2paSettings,
+  TrustConfig,
+  TrustSourceConfig,
+  NetworkCacheConfig,
+  NetworkCacheStats,
+  NetworkOptions,
+  ProxyConfig,
+  RemoteManifestResolver,
+  SettingsChangeEvent,
+  SettingsChangeReason,
+  VerifyConfig,
+  SettingsContext,
+} from "./types.d.ts";
 
 type SettingsObjectType = {
-  [k: string]: string | boolean | undefined | SettingsObjectType;
+  [k: string]: string | boolean | number | undefined | SettingsObjectType;
 };
 
 function snakeCaseify(object: SettingsObjectType): SettingsObjectType {
@@ -119,6 +134,9 @@ export async function loadSettingsFromFile(filePath: string): Promise<string> {
  * @returns Settings as a string
  */
 export async function loadSettingsFromUrl(url: string): Promise<string> {
+  if (isOfflineMode()) {
+    throw new Error(`Network access is disabled in offline mode: ${url}`);
+  }
   const res = await fetch(url);
   if (!res.ok) {
     throw new Error(
@@ -127,3 +145,167 @@ export async function loadSettingsFromUrl(url: string): Promise<string> {
   }
   return await res.text();
 }
+
+/**
+ * Get the settings a Reader or Builder would use, with the c2pa-rs defaults
+ * filled in. Useful for logging the resolved configuration of a service.
+ * Signer settings are omitted since they may contain private keys.
+ * @param settings Optional settings, as accepted by the Reader and Builder
+ * @returns The resolved settings with snake_case keys
+ */
+export function getEffectiveSettings(
+  settings?: C2paSettings,
+): Record<string, unknown> {
+  return JSON.parse(getNeonBinary().getSettingsJson(settings || undefined));
+}
+
+/**
+ * Load trust lists from file paths or HTTPS URLs, or take them as PEM or text
+ * strings, and apply them to every Reader and Builder created afterwards.
+ * Settings passed to a Reader or Builder still take precedence. When
+ * `refreshIntervalSecs` is set, the sources are reloaded on that interval; a
+ * failed reload keeps the previous lists. Calling this again replaces the
+ * previous configuration.
+ * @param config The trust list sources
+ */
+export async function configureTrustSources(
+  config: TrustSourceConfig,
+): Promise<void> {
+  return getNeonBinary().configureTrustSources(
+    JSON.stringify(snakeCaseify(config as SettingsObjectType)),
+  );
+}
+
+/**
+ * Stop refreshing and remove the trust lists loaded by `configureTrustSources`.
+ */
+export function clearTrustSources(): void {
+  getNeonBinary().clearTrustSources();
+}
+
+/**
+ * Register a listener that is called whenever the process-wide settings change,
+ * for example when trust lists are configured, refreshed with new contents, or
+ * cleared. Use it to drop cached Readers, Builders, or Contexts created under the
+ * previous settings. Listeners do not keep the process alive.
+ * @param listener Called with the reason for the change
+ * @returns A function that removes the listener
+ */
+export function onSettingsChange(
+  listener: (event: SettingsChangeEvent) => void,
+): () => void {
+  const id: number = getNeonBinary().onSettingsChange(
+    (reason: SettingsChangeReason) => listener({ reason }),
+  );
+  return () => getNeonBinary().offSettingsChange(id);
+}
+
+/**
+ * Turn offline mode on or off. While on, the native layer makes no network
+ * requests: fetching remote manifests, OCSP responses, timestamps, trust lists,
+ * and Trustmark models fails with an error named `Offline` instead. This applies
+ * to every Reader, Builder, and Context, including ones created earlier.
+ * @param offline Whether to disable network access
+ */
+export function setOfflineMode(offline: boolean): void {
+  getNeonBinary().setOfflineMode(offline);
+}
+
+/**
+ * Whether offline mode is on.
+ */
+export function isOfflineMode(): boolean {
+  return getNeonBinary().isOfflineMode();
+}
+
+/**
+ * Send requests made by the native layer (remote manifests, OCSP, timestamps,
+ * and trust lists) through an HTTP proxy. Without a configured proxy, the
+ * standard `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`, and `NO_PROXY` environment
+ * variables are used. Applies to Readers, Builders, and Contexts created afterwards.
+ * @param config The proxy configuration
+ */
+export function configureProxy(config: ProxyConfig): void {
+  getNeonBinary().configureProxy(
+    JSON.stringify({
+      http_proxy: config.httpProxy,
+      https_proxy: config.httpsProxy,
+      no_proxy: config.noProxy ?? [],
+    }),
+  );
+}
+
+/**
+ * Remove the proxy set by `configureProxy`, falling back to the environment variables.
+ */
+export function clearProxy(): void {
+  getNeonBinary().clearProxy();
+}
+
+/**
+ * Set the timeouts and retries for remote manifest, OCSP, and time stamp
+ * requests, replacing any set before. Options passed to a single operation
+ * take precedence. Applies to every Reader, Builder, and Context, including
+ * ones created earlier. When a request still fails, the operation's error has
+ * a `network` property saying which endpoint failed and why.
+ * @param options The timeouts and retries, or null for the HTTP client defaults
+ */
+export function configureNetwork(options: NetworkOptions | null): void {
+  const network = networkArgument(options ?? undefined);
+  getNeonBinary().configureNetwork(network ? JSON.stringify(network) : null);
+}
+
+/**
+ * Fetch remote manifests with your own code instead of the built-in HTTP
+ * client, for example to add authentication, serve them from a cache, or go
+ * through a proxy agent. The resolver is given the manifest URL and returns
+ * the manifest store bytes, or null to let the built-in client fetch it. If it
+ * throws, reading fails with the error code `REMOTE_FETCH_FAILED`. Applies to
+ * every Reader, Builder, and Context, including ones created earlier. Offline
+ * mode and `core.allowed_network_hosts` still apply.
+ * @param resolver The resolver, or null to go back to the built-in client
+ */
+export function setRemoteManifestResolver(
+  resolver: RemoteManifestResolver | null,
+): void {
+  getNeonBinary().setRemoteManifestResolver(
+    resolver ? async (url: string) => resolver(url) : null,
+  );
+}
+
+/**
+ * Cache remote manifests, OCSP responses, and trust lists fetched by the
+ * native layer, so verifying many assets from the same issuer doesn't fetch
+ * the same resources again. Responses are kept in memory, and also on disk
+ * when `dir` is set. Replaces any previous cache configuration, dropping the
+ * responses in memory. Applies to every Reader, Builder, and Context,
+ * including ones created earlier.
+ * @param config The cache configuration, or null to turn caching off
+ */
+export function configureNetworkCache(config: NetworkCacheConfig | null): void {
+  getNeonBinary().configureNetworkCache(
+    config
+      ? JSON.stringify({
+          max_memory_bytes: config.maxMemoryBytes,
+          dir: config.dir,
+          remote_manifest_ttl_secs: config.remoteManifestTtlSecs,
+          ocsp_ttl_secs: config.ocspTtlSecs,
+          trust_ttl_secs: config.trustTtlSecs,
+        })
+      : null,
+  );
+}
+
+/**
+ * Drop every cached response, in memory and in the cache directory.
+ */
+export function clearNetworkCache(): void {
+  getNeonBinary().clearNetworkCache();
+}
+
+/**
+ * Get the size of the network cache and how many lookups it answered.
+ */
+export function getNetworkCacheStats(): NetworkCacheStats {
+  return JSON.parse(getNeonBinary().networkCacheStats());
+}
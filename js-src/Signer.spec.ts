@@ -15,7 +15,7 @@
 import * as fs from "fs-extra";
 import * as crypto from "crypto";
 
-import { CallbackSigner } from "./Signer.js";
+import { CallbackSigner, LocalSigner } from "./Signer.js";
 import { Builder } from "./Builder.js";
 import { Reader } from "./Reader.js";
 import type { JsCallbackSignerConfig, SigningAlg } from "./types.d.ts";
@@ -40,6 +40,67 @@ class TestSigner {
   };
 }
 
+// P-256 ECDSA over a digest as is, the way an HSM signs a precomputed hash.
+// node:crypto always hashes what it signs, so this is done by hand.
+type Point = [bigint, bigint] | null;
+
+const P = BigInt(
+  "0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+);
+const N = BigInt(
+  "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+);
+const G: Point = [
+  BigInt("0x6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+  BigInt("0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+];
+
+const mod = (a: bigint, m: bigint) => ((a % m) + m) % m;
+
+const inverse = (a: bigint, m: bigint) => {
+  let [x, y, r, s] = [mod(a, m), m, BigInt(1), BigInt(0)];
+  while (y > BigInt(0)) {
+    const q = x / y;
+    [x, y, r, s] = [y, x - q * y, s, r - q * s];
+  }
+  return mod(r, m);
+};
+
+const add = (p: Point, q: Point): Point => {
+  if (!p || !q) {
+    return p ?? q;
+  }
+  if (p[0] === q[0] && p[1] !== q[1]) {
+    return null;
+  }
+  const slope =
+    p[0] === q[0]
+      ? (BigInt(3) * p[0] * p[0] - BigInt(3)) * inverse(BigInt(2) * p[1], P)
+      : (q[1] - p[1]) * inverse(q[0] - p[0], P);
+  const x = mod(slope * slope - p[0] - q[0], P);
+  return [x, mod(slope * (p[0] - x) - p[1], P)];
+};
+
+const toBigInt = (bytes: Buffer) => BigInt(`0x${bytes.toString("hex")}`);
+const toBytes = (n: bigint) =>
+  Buffer.from(n.toString(16).padStart(64, "0"), "hex");
+
+const signP256Digest = (digest: Buffer, key: crypto.KeyObject) => {
+  const { d: jwkD } = key.export({ format: "jwk" });
+  const d = toBigInt(Buffer.from(jwkD!, "base64url"));
+  const k = mod(toBigInt(crypto.randomBytes(40)), N - BigInt(1)) + BigInt(1);
+  let [point, base]: [Point, Point] = [null, G];
+  for (let e = k; e > BigInt(0); e >>= BigInt(1)) {
+    if (e & BigInt(1)) {
+      point = add(point, base);
+    }
+    base = add(base, base);
+  }
+  const r = mod(point![0], N);
+  const s = mod(inverse(k, N) * (toBigInt(digest) + r * d), N);
+  return Buffer.concat([toBytes(r), toBytes(s)]);
+};
+
 describe("TestSigner", () => {
   it("should sign data", async () => {
     const signer = new TestSigner(
@@ -67,6 +128,16 @@ describe("CallbackSigner", () => {
     expect(signer).toBeDefined();
   });
 
+  it("rejects an algorithm the certificate's key doesn't use", () => {
+    const es384 = { ...config, alg: "es384" as SigningAlg };
+    expect(() => CallbackSigner.newSigner(es384, async (data) => data)).toThrow(
+      expect.objectContaining({
+        code: "SIGNING_KEY_INVALID",
+        message: expect.stringContaining("P-384 EC"),
+      }),
+    );
+  });
+
   it("should sign data", async () => {
     const signer = CallbackSigner.newSigner(config, async (data) => data);
     const data = Buffer.from("test data");
@@ -183,4 +254,74 @@ describe("CallbackSigner", () => {
     expect(manifestStore.active_manifest).not.toBeUndefined();
     expect(activeManifest?.title).toBe("Test_Manifest_Buffer");
   });
+
+  it("signs a digest in signDigest mode", async () => {
+    const key = crypto.createPrivateKey(
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+    );
+    const hashes: Array<string | undefined> = [];
+    const signer = CallbackSigner.newSigner(
+      { ...config, tsaUrl: undefined, signDigest: true },
+      async (digest, hashAlgorithm) => {
+        hashes.push(hashAlgorithm);
+        expect(digest.length).toBe(32);
+        return signP256Digest(digest, key);
+      },
+    );
+
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "Digest",
+    });
+    const dest = { buffer: null };
+    await builder.signAsync(signer, { path: "./tests/fixtures/A.jpg" }, dest);
+
+    const reader = await Reader.fromAsset({
+      buffer: dest.buffer! as Buffer,
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.getActive()!.title).toBe("Digest");
+    const failures = reader!.validationMessages("errors");
+    expect(failures.map(({ code }) => code)).not.toContain(
+      "claimSignature.mismatch",
+    );
+    expect(hashes).toEqual(["sha256"]);
+  });
+
+  it("rejects signDigest for ed25519 and direct COSE handling", async () => {
+    const ed25519 = {
+      ...config,
+      alg: "ed25519" as SigningAlg,
+      certs: [await fs.readFile("./tests/fixtures/certs/ed25519.pub")],
+      signDigest: true,
+    };
+    expect(() =>
+      CallbackSigner.newSigner(ed25519, async (data) => data),
+    ).toThrow("can't be used with signDigest");
+    const direct = { ...config, directCoseHandling: true, signDigest: true };
+    expect(() =>
+      CallbackSigner.newSigner(direct, async (data) => data),
+    ).toThrow("directCoseHandling");
+  });
+});
+
+describe("LocalSigner", () => {
+  it("signs with the algorithm it was created with", async () => {
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/ed25519.pub"),
+      await fs.readFile("./tests/fixtures/certs/ed25519.pem"),
+      "ed25519",
+    );
+    expect(signer.alg()).toBe("ed25519");
+  });
+
+  it("rejects an algorithm the certificate's key doesn't use", async () => {
+    const certificate = await fs.readFile("./tests/fixtures/certs/es256.pub");
+    const privateKey = await fs.readFile("./tests/fixtures/certs/es256.pem");
+    for (const alg of ["es384", "ps256", "ed25519"] as SigningAlg[]) {
+      expect(() => LocalSigner.newSigner(certificate, privateKey, alg)).toThrow(
+        expect.objectContaining({ code: "SIGNING_KEY_INVALID" }),
+      );
+    }
+  });
 });
@@ -13,6 +13,7 @@
 
 import { getNeonBinary } from "./binary.js";
 import type {
+  CallbackSignerFunction,
   CallbackSignerInterface,
   JsCallbackSignerConfig,
   NeonCallbackSignerHandle,
@@ -73,7 +74,7 @@ export class CallbackSigner implements CallbackSignerInterface {
 
   static newSigner(
     config: JsCallbackSignerConfig,
-    callback: (data: Buffer) => Promise<Buffer>,
+    callback: CallbackSignerFunction,
   ) {
     // Convert the config object to a JsBox<CallbackSignerConfig>
     const configBox = getNeonBinary().callbackSignerConfigFromJs(config);
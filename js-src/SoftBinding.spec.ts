@@ -0,0 +1,156 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import * as fs from "fs-extra";
+
+import { Builder } from "./Builder.js";
+import { isC2paError } from "./C2paError.js";
+import { Reader } from "./Reader.js";
+import { LocalSigner } from "./Signer.js";
+import { SoftBindingPlugin, detectSoftBindings } from "./SoftBinding.js";
+import type {
+  DestinationBufferAsset,
+  JsCallbackSignerConfig,
+} from "./types.d.ts";
+
+describe("SoftBindingPlugin", () => {
+  let signer: LocalSigner;
+  let signerConfig: JsCallbackSignerConfig;
+
+  beforeAll(async () => {
+    const publicKey = await fs.readFile("./tests/fixtures/certs/es256.pub");
+    const privateKey = await fs.readFile("./tests/fixtures/certs/es256.pem");
+    signer = LocalSigner.newSigner(publicKey, privateKey, "es256");
+    signerConfig = {
+      alg: "es256",
+      certs: [publicKey],
+      reserveSize: 10000,
+      directCoseHandling: false,
+    };
+  });
+
+  async function sign(builder: Builder): Promise<Buffer> {
+    const dest: DestinationBufferAsset = { buffer: null };
+    await builder.signConfigAsync(
+      async (data) => signer.sign(data),
+      signerConfig,
+      { path: "./tests/fixtures/A.jpg" },
+      dest,
+    );
+    return dest.buffer as Buffer;
+  }
+
+  it("embeds a watermark and records it when signing", async () => {
+    let embeds = 0;
+    const plugin = SoftBindingPlugin.new({
+      alg: "com.example.watermark",
+      embed: async (asset, mimeType) => {
+        expect(mimeType).toBe("image/jpeg");
+        embeds += 1;
+        return { asset, value: `watermark-${embeds}` };
+      },
+    });
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "Watermarked",
+    });
+    builder.addSoftBindingPlugin(plugin);
+
+    const reader = await Reader.fromAsset({
+      buffer: await sign(builder),
+      mimeType: "image/jpeg",
+    });
+    expect(reader!.getActive()?.assertions).toContainEqual(
+      expect.objectContaining({
+        label: "c2pa.soft-binding",
+        data: expect.objectContaining({
+          alg: "com.example.watermark",
+          blocks: [{ scope: {}, value: "watermark-1" }],
+        }),
+      }),
+    );
+
+    // Each signing embeds a new watermark rather than adding to the last.
+    expect(builder.getManifestDefinition().assertions ?? []).toHaveLength(0);
+    const again = await Reader.fromAsset({
+      buffer: await sign(builder),
+      mimeType: "image/jpeg",
+    });
+    const bindings = again!
+      .getActive()
+      ?.assertions?.filter(({ label }) => label === "c2pa.soft-binding");
+    expect(bindings).toHaveLength(1);
+    expect(embeds).toBe(2);
+  });
+
+  it("detects watermarks and compares them with the manifest", async () => {
+    const plugin = SoftBindingPlugin.new({
+      alg: "com.example.watermark",
+      embed: async (asset) => ({ asset, value: "abc" }),
+      detect: async () => "abc",
+    });
+    const builder = Builder.withJson({ title: "Watermarked" });
+    builder.addSoftBindingPlugin(plugin);
+    const signed = await sign(builder);
+
+    expect(
+      await detectSoftBindings({ buffer: signed, mimeType: "image/jpeg" }, [
+        plugin,
+      ]),
+    ).toEqual([
+      { alg: "com.example.watermark", value: "abc", matchesManifest: true },
+    ]);
+    // Without a manifest, the detected value is a hint for finding it.
+    expect(
+      await detectSoftBindings({ path: "./tests/fixtures/A.jpg" }, [plugin]),
+    ).toEqual([
+      { alg: "com.example.watermark", value: "abc", matchesManifest: null },
+    ]);
+
+    const other = SoftBindingPlugin.new({
+      alg: "com.example.watermark",
+      detect: async () => null,
+    });
+    expect(
+      await detectSoftBindings({ buffer: signed, mimeType: "image/jpeg" }, [
+        other,
+      ]),
+    ).toEqual([
+      { alg: "com.example.watermark", value: null, matchesManifest: false },
+    ]);
+  });
+
+  it("rejects when a plugin fails", async () => {
+    const plugin = SoftBindingPlugin.new({
+      alg: "com.example.watermark",
+      embed: async () => {
+        throw new Error("no watermark for you");
+      },
+    });
+    const builder = Builder.withJson({ title: "Watermarked" });
+    builder.addSoftBindingPlugin(plugin);
+    const error = await sign(builder).catch((err) => err);
+    expect(isC2paError(error, "SOFT_BINDING_FAILED")).toBe(true);
+    expect(error.message).toContain("no watermark for you");
+
+    expect(() =>
+      builder.sign(signer, { path: "./tests/fixtures/A.jpg" }, { buffer: null }),
+    ).toThrow("Soft binding plugins need an async signer");
+  });
+
+  it("needs an embedder or a detector", () => {
+    expect(() => SoftBindingPlugin.new({ alg: "com.example.none" })).toThrow(
+      TypeError,
+    );
+  });
+});
@@ -0,0 +1,76 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import { settingsArgument } from "./Context.js";
+import type { Context } from "./Context.js";
+import type {
+  C2paSettings,
+  NeonSoftBindingHandle,
+  OperationOptions,
+  SoftBindingHint,
+  SoftBindingPluginConfig,
+  SoftBindingPluginInterface,
+  SourceAsset,
+} from "./types.d.ts";
+
+export class SoftBindingPlugin implements SoftBindingPluginInterface {
+  constructor(
+    private plugin: NeonSoftBindingHandle,
+    readonly alg: string,
+  ) {}
+
+  /**
+   * Create a plugin from an embedder, a detector, or both.
+   * @param config The algorithm name and its callbacks
+   */
+  static new(config: SoftBindingPluginConfig): SoftBindingPlugin {
+    const plugin: NeonSoftBindingHandle = getNeonBinary().softBindingNew(
+      config.alg,
+      config.embed,
+      config.detect,
+    );
+    return new SoftBindingPlugin(plugin, config.alg);
+  }
+
+  getHandle(): NeonSoftBindingHandle {
+    return this.plugin;
+  }
+}
+
+/**
+ * Run each plugin's detector over an asset and compare what it finds with the
+ * soft binding assertions of the asset's active manifest. When the asset has
+ * lost its manifest, a detected value is a hint for recovering it, such as
+ * from a manifest repository.
+ * @param asset The asset, which must have a mime type
+ * @param plugins The plugins to detect with; those without a detector are
+ * skipped
+ * @param settings Settings or a Context to read the manifest with
+ * @returns A hint for each plugin with a detector, in order
+ */
+export async function detectSoftBindings(
+  asset: SourceAsset,
+  plugins: SoftBindingPluginInterface[],
+  settings?: C2paSettings | Context,
+  options?: OperationOptions,
+): Promise<SoftBindingHint[]> {
+  const hints = await getNeonBinary().detectSoftBindings(
+    asset,
+    plugins.map((plugin) => plugin.getHandle()),
+    settingsArgument(settings),
+    options?.timeoutMs,
+    options?.priority,
+  );
+  return JSON.parse(hints);
+}
@@ -0,0 +1,84 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+import * as fs from "fs-extra";
+import { Readable } from "stream";
+
+import type { DestinationBufferAsset } from "./types.d.ts";
+import { LocalSigner } from "./Signer.js";
+import { readSync, signSync } from "./Sync.js";
+
+describe("readSync and signSync", () => {
+  const definition: Manifest = {
+    claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+    title: "Sync_Manifest",
+    format: "image/jpeg",
+    ingredients: [],
+    assertions: [],
+    resources: { resources: {} },
+  };
+
+  it("should sign and read an asset without promises", async () => {
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    const output: DestinationBufferAsset = { buffer: null };
+    const manifest = signSync(
+      definition,
+      signer,
+      { path: "./tests/fixtures/A.jpg" },
+      output,
+    );
+    expect(manifest.length).toBeGreaterThan(0);
+
+    const reader = readSync({ buffer: output.buffer!, mimeType: "image/jpeg" });
+    expect(reader?.getActive()?.title).toBe("Sync_Manifest");
+  });
+
+  it("should return null for an asset without a manifest", () => {
+    expect(readSync({ path: "./tests/fixtures/A.jpg" })).toBeNull();
+  });
+
+  it("should reject stream, pull, and IO assets", async () => {
+    const data = await fs.readFile("./tests/fixtures/A.jpg");
+    expect(() =>
+      readSync({ stream: Readable.from([data]), mimeType: "image/jpeg" }),
+    ).toThrow(/asynchronous/);
+    expect(() =>
+      readSync({ pull: async () => null, mimeType: "image/jpeg" }),
+    ).toThrow(/asynchronous/);
+    expect(() =>
+      readSync({
+        io: { read: async () => null, seek: async () => 0 },
+        mimeType: "image/jpeg",
+      }),
+    ).toThrow(/asynchronous/);
+
+    const signer = LocalSigner.newSigner(
+      await fs.readFile("./tests/fixtures/certs/es256.pub"),
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+      "es256",
+    );
+    expect(() =>
+      signSync(
+        definition,
+        signer,
+        { stream: Readable.from([data]), mimeType: "image/jpeg" },
+        { buffer: null },
+      ),
+    ).toThrow(/asynchronous/);
+  });
+});
@@ -0,0 +1,72 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { Manifest } from "@contentauth/c2pa-types";
+
+import { Builder } from "./Builder.js";
+import type { Context } from "./Context.js";
+import { Reader } from "./Reader.js";
+import type {
+  C2paSettings,
+  DestinationAsset,
+  LocalSignerInterface,
+  ManifestDefinitionWithBuffers,
+  OperationOptions,
+  SourceAsset,
+  VerifyOptions,
+} from "./types.d.ts";
+
+/**
+ * Read the manifest store of an asset, blocking until the read completes.
+ * The library's other entry points never block the event loop; this one is
+ * for CLI tools and build scripts that would rather not juggle promises.
+ * Stream, pull, and IO assets are not supported, since their callbacks would
+ * need the event loop this call is blocking.
+ * @param asset The asset to read
+ * @param settings Settings or a Context for the read
+ * @param options Verification and timeout options
+ * @returns A Reader, or null when the asset has no manifest store
+ */
+export function readSync(
+  asset: SourceAsset,
+  settings?: C2paSettings | Context,
+  options?: VerifyOptions & OperationOptions,
+): Reader | null {
+  return Reader.fromAssetSync(asset, settings, options);
+}
+
+/**
+ * Sign an asset with a manifest definition, blocking until signing
+ * completes. Only local signers are supported, since a callback signer would
+ * need the event loop this call is blocking.
+ * @param definition The manifest definition
+ * @param signer The local signer
+ * @param input The asset to sign
+ * @param output Where to write the signed asset
+ * @param settings Settings or a Context for the builder
+ * @returns The manifest bytes
+ */
+export function signSync(
+  definition: Manifest | ManifestDefinitionWithBuffers,
+  signer: LocalSignerInterface,
+  input: SourceAsset,
+  output: DestinationAsset,
+  settings?: C2paSettings | Context,
+): Buffer {
+  const builder = Builder.withJson(definition, settings);
+  try {
+    return builder.sign(signer, input, output);
+  } finally {
+    builder.dispose();
+  }
+}
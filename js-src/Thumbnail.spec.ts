@@ -0,0 +1,44 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { isC2paError } from "./C2paError.js";
+import { Reader } from "./Reader.js";
+import { decodeThumbnail } from "./Thumbnail.js";
+
+describe("decodeThumbnail", () => {
+  it("decodes the thumbnail of a manifest to RGBA pixels", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const thumbnail = reader!.getActive()!.thumbnail!;
+    const { buffer } = await reader!.resourceToAsset(thumbnail.identifier, {
+      buffer: null,
+    });
+
+    const image = await decodeThumbnail({
+      buffer,
+      mimeType: thumbnail.format,
+    });
+    expect(image.width).toBeGreaterThan(0);
+    expect(image.height).toBeGreaterThan(0);
+    expect(image.data.length).toBe(image.width * image.height * 4);
+    // Fully opaque, as JPEG has no alpha channel
+    expect(image.data[3]).toBe(255);
+  });
+
+  it("rejects formats it cannot decode", async () => {
+    const error = await decodeThumbnail({
+      buffer: Buffer.from([0xff, 0x0a, 0x00, 0x00]),
+      mimeType: "image/jxl",
+    }).catch((err: unknown) => err);
+    expect(isC2paError(error, "UNSUPPORTED_FORMAT")).toBe(true);
+  });
+});
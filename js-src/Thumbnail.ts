@@ -0,0 +1,34 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type { DecodedImage, OperationOptions, SourceAsset } from "./types.d.ts";
+
+/**
+ * Decode an image, such as a thumbnail extracted with `resourceToAsset`, to
+ * raw RGBA pixels, so it can be painted without an image decoder of your own.
+ * The format is taken from the asset's MIME type, or detected from its bytes.
+ * @param asset The image to decode
+ * @returns The width and height of the image, and its pixels as 8-bit RGBA,
+ * row by row
+ */
+export async function decodeThumbnail(
+  asset: SourceAsset,
+  options?: OperationOptions,
+): Promise<DecodedImage> {
+  return getNeonBinary().decodeThumbnail(
+    asset,
+    options?.timeoutMs,
+    options?.priority,
+  );
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+import { vi } from "vitest";
+
+import { Reader } from "./Reader.js";
+import { registerValidationRule } from "./ValidationRules.js";
+
+describe("registerValidationRule", () => {
+  const asset = { path: "./tests/fixtures/CA.jpg" };
+
+  it("merges rule failures into the validation results", async () => {
+    const allowed = ["Example Signer"];
+    const unregister = registerValidationRule(({ activeManifest }) =>
+      allowed.includes(activeManifest?.signature_info?.common_name ?? "")
+        ? null
+        : {
+            kind: "failure",
+            code: "com.example.signer.notAllowed",
+            explanation: "Signer is not on the allow-list",
+          },
+    );
+    try {
+      const reader = await Reader.fromAsset(asset);
+      const store = reader!.json();
+      // CA.jpg is otherwise valid
+      expect(store.validation_state).toBe("Invalid");
+      expect(store.validation_status).toContainEqual(
+        expect.objectContaining({ code: "com.example.signer.notAllowed" }),
+      );
+      expect(
+        store.validation_results!.activeManifest!.failure.map(
+          (status) => status.code,
+        ),
+      ).toContain("com.example.signer.notAllowed");
+      expect(reader!.validationMessages()).toContainEqual({
+        code: "com.example.signer.notAllowed",
+        kind: "failure",
+        key: "c2pa.validation.com.example.signer.notAllowed",
+        params: {},
+        message: "Signer is not on the allow-list",
+      });
+      expect((await reader!.jsonAsync()).validation_status).toEqual(
+        store.validation_status,
+      );
+    } finally {
+      unregister();
+    }
+  });
+
+  it("adds informational verdicts without failing validation", async () => {
+    const before = (await Reader.fromAsset(asset))!.json();
+    const unregister = registerValidationRule(async ({ manifestStore }) => [
+      {
+        kind: "informational",
+        code: "com.example.manifests",
+        explanation: `${Object.keys(manifestStore.manifests).length} manifests`,
+      },
+    ]);
+    try {
+      const store = (await Reader.fromAsset(asset))!.json();
+      expect(store.validation_state).toBe(before.validation_state);
+      expect(store.validation_status).toEqual(before.validation_status);
+      expect(
+        store.validation_results!.activeManifest!.informational,
+      ).toContainEqual(
+        expect.objectContaining({ code: "com.example.manifests" }),
+      );
+    } finally {
+      unregister();
+    }
+  });
+
+  it("rejects the read when a rule throws", async () => {
+    const unregister = registerValidationRule(() => {
+      throw new Error("rule failed");
+    });
+    try {
+      await expect(Reader.fromAsset(asset)).rejects.toThrow("rule failed");
+    } finally {
+      unregister();
+    }
+  });
+
+  it("stops running a rule once it is unregistered", async () => {
+    const rule = vi.fn(() => null);
+    const unregister = registerValidationRule(rule);
+    await Reader.fromAsset(asset);
+    expect(rule).toHaveBeenCalledTimes(1);
+    unregister();
+    await Reader.fromAsset(asset);
+    expect(rule).toHaveBeenCalledTimes(1);
+  });
+});
@@ -0,0 +1,118 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import type { ManifestStore } from "@contentauth/c2pa-types";
+import type {
+  ValidationMessage,
+  ValidationRule,
+  ValidationRuleVerdict,
+} from "./types.d.ts";
+
+const rules = new Set<ValidationRule>();
+
+/**
+ * Run `rule` on every manifest store read after this call, once c2pa-rs has
+ * validated it. The verdicts it returns are merged into the Reader's
+ * validation results: failures are added to `validation_status` and make the
+ * store `Invalid`. Use it for policy checks such as requiring the signer to
+ * be on an allow-list. Readers created earlier are not affected.
+ * @param rule The rule
+ * @returns A function that unregisters the rule
+ */
+export function registerValidationRule(rule: ValidationRule): () => void {
+  // Each registration is its own entry, so unregistering one leaves others
+  // of the same function in place.
+  const registered: ValidationRule = (context) => rule(context);
+  rules.add(registered);
+  return () => {
+    rules.delete(registered);
+  };
+}
+
+export function hasValidationRules(): boolean {
+  return rules.size > 0;
+}
+
+/**
+ * Run the registered rules on a manifest store, in the order they were
+ * registered. A rule that throws fails the read.
+ */
+export async function runValidationRules(
+  manifestStore: ManifestStore,
+): Promise<ValidationRuleVerdict[]> {
+  const verdicts: ValidationRuleVerdict[] = [];
+  const activeManifest = manifestStore.active_manifest
+    ? manifestStore.manifests[manifestStore.active_manifest]
+    : undefined;
+  for (const rule of [...rules]) {
+    // Each rule gets its own copy, so one rule can't change what the next sees.
+    const store: ManifestStore = structuredClone(manifestStore);
+    const result = await rule({
+      manifestStore: store,
+      activeManifest: activeManifest && structuredClone(activeManifest),
+    });
+    if (Array.isArray(result)) {
+      verdicts.push(...result);
+    } else if (result) {
+      verdicts.push(result);
+    }
+  }
+  return verdicts;
+}
+
+/**
+ * Merge rule verdicts into a manifest store's validation results for its
+ * active manifest.
+ */
+export function applyValidationVerdicts(
+  manifestStore: ManifestStore,
+  verdicts: ValidationRuleVerdict[],
+): ManifestStore {
+  if (verdicts.length === 0) {
+    return manifestStore;
+  }
+  const results = (manifestStore.validation_results ??= {});
+  const active = (results.activeManifest ??= {
+    success: [],
+    informational: [],
+    failure: [],
+  });
+  for (const verdict of verdicts) {
+    const status = {
+      code: verdict.code,
+      url: verdict.url ?? null,
+      explanation: verdict.explanation ?? null,
+    };
+    active[verdict.kind].push(status);
+    if (verdict.kind === "failure") {
+      (manifestStore.validation_status ??= []).push(status);
+      manifestStore.validation_state = "Invalid";
+    }
+  }
+  return manifestStore;
+}
+
+/**
+ * Rule verdicts in the form of `Reader.validationMessages`.
+ */
+export function verdictMessages(
+  verdicts: ValidationRuleVerdict[],
+): ValidationMessage[] {
+  return verdicts.map((verdict) => ({
+    code: verdict.code,
+    kind: verdict.kind,
+    key: `c2pa.validation.${verdict.code}`,
+    params: verdict.url ? { url: verdict.url } : {},
+    message: verdict.explanation,
+  }));
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+import * as fs from "fs-extra";
+
+import { Reader } from "./Reader.js";
+import {
+  getValidationPolicy,
+  getValidationStatusCodes,
+  getValidationStatusKind,
+  setValidationPolicy,
+} from "./ValidationStatus.js";
+
+describe("ValidationStatus", () => {
+  it("exposes the c2pa-rs validation codes", () => {
+    const codes = getValidationStatusCodes();
+    expect(codes.CLAIM_SIGNATURE_VALIDATED).toEqual({
+      code: "claimSignature.validated",
+      kind: "success",
+    });
+    expect(codes.SIGNING_CREDENTIAL_OCSP_SKIPPED.kind).toBe("informational");
+    expect(codes.SIGNING_CREDENTIAL_UNTRUSTED.kind).toBe("failure");
+    expect(Object.isFrozen(codes)).toBe(true);
+    expect(getValidationStatusCodes()).toBe(codes);
+  });
+
+  it("matches the codes reported by a Reader", async () => {
+    const codes = getValidationStatusCodes();
+    const manifestData = await fs.readFile(
+      "./tests/fixtures/CA/manifest_data.c2pa",
+    );
+    const reader = await Reader.fromManifestDataAndAsset(manifestData, {
+      path: "./tests/fixtures/CA.jpg",
+    });
+    const status = reader!.json().validation_status![0];
+    expect(status.code).toBe(codes.SIGNING_CREDENTIAL_UNTRUSTED.code);
+  });
+
+  it("lists findings with message keys and parameters", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const messages = reader!.validationMessages();
+    const untrusted = messages.find(
+      (message) => message.code === "signingCredential.untrusted",
+    );
+    expect(untrusted).toMatchObject({
+      kind: "failure",
+      key: "c2pa.validation.signingCredential.untrusted",
+      params: { manifestLabel: reader!.activeLabel() },
+    });
+    expect(untrusted!.params.url).toContain("jumbf=");
+    for (const message of messages) {
+      expect(message.key).toBe(`c2pa.validation.${message.code}`);
+      expect(message.kind).toBe(getValidationStatusKind(message.code));
+    }
+  });
+
+  it("filters findings by severity", async () => {
+    const reader = await Reader.fromAsset({ path: "./tests/fixtures/CA.jpg" });
+    const all = reader!.validationMessages();
+    const kinds = (filter: "errors" | "warnings" | "all") =>
+      new Set(reader!.validationMessages(filter).map(({ kind }) => kind));
+
+    expect(reader!.validationMessages("all")).toEqual(all);
+    expect(kinds("warnings")).toEqual(new Set(["informational", "failure"]));
+    expect(reader!.validationMessages("errors")).toEqual(
+      all.filter(({ kind }) => kind === "failure"),
+    );
+    expect(() => reader!.validationMessages("fatal" as "errors")).toThrow(
+      "Unknown filter",
+    );
+  });
+
+  it("classifies codes", () => {
+    expect(getValidationStatusKind("claimSignature.validated")).toBe(
+      "success",
+    );
+    expect(getValidationStatusKind("timeStamp.untrusted")).toBe(
+      "informational",
+    );
+    expect(getValidationStatusKind("assertion.dataHash.mismatch")).toBe(
+      "failure",
+    );
+    expect(getValidationStatusKind("not.a.code")).toBe("failure");
+  });
+});
+
+describe("setValidationPolicy", () => {
+  const asset = { path: "./tests/fixtures/CA.jpg" };
+
+  afterEach(() => {
+    setValidationPolicy(null);
+  });
+
+  it("makes an untrusted signer a hard failure", async () => {
+    const before = (await Reader.fromAsset(asset))!;
+    expect(before.json().validation_state).toBe("Valid");
+
+    setValidationPolicy({ "signingCredential.untrusted": "error" });
+    expect(getValidationPolicy()).toEqual({
+      "signingCredential.untrusted": "error",
+    });
+    const reader = (await Reader.fromAsset(asset))!;
+    expect(reader.json().validation_state).toBe("Invalid");
+    expect((await reader.jsonAsync()).validation_state).toBe("Invalid");
+    expect(reader.report().validationState).toBe("Invalid");
+    expect(before.json().validation_state).toBe("Invalid");
+  });
+
+  it("reclassifies and ignores codes", async () => {
+    setValidationPolicy({
+      "timeStamp.untrusted": "error",
+      "signingCredential.untrusted": "ignore",
+    });
+    const reader = (await Reader.fromAsset(asset))!;
+    const store = reader.json();
+    expect(store.validation_status!.map((status) => status.code)).toEqual([
+      "timeStamp.untrusted",
+    ]);
+    const messages = reader.validationMessages();
+    expect(
+      messages.find((message) => message.code === "timeStamp.untrusted")!.kind,
+    ).toBe("failure");
+    expect(
+      messages.some((message) => message.code === "signingCredential.untrusted"),
+    ).toBe(false);
+
+    setValidationPolicy({ "timeStamp.untrusted": "ignore" });
+    expect(reader.json().validation_state).toBe("Valid");
+  });
+
+  it("rejects unknown severities", () => {
+    expect(() =>
+      setValidationPolicy({
+        "signingCredential.untrusted": "fatal" as "error",
+      }),
+    ).toThrow(RangeError);
+    expect(getValidationPolicy()).toEqual({});
+  });
+});
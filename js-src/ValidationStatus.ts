@@ -0,0 +1,74 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type {
+  ValidationPolicy,
+  ValidationStatusCodeInfo,
+  ValidationStatusKind,
+} from "./types.d.ts";
+
+let codes: Readonly<Record<string, Readonly<ValidationStatusCodeInfo>>> | null =
+  null;
+
+/**
+ * Get every validation status code defined by the bundled c2pa-rs release,
+ * keyed by its c2pa-rs constant name, e.g. `SIGNING_CREDENTIAL_UNTRUSTED`.
+ * Compare `validation_status` entries against these instead of hardcoding
+ * the strings.
+ * @returns The codes, with whether each reports a success, is informational,
+ * or reports a failure
+ */
+export function getValidationStatusCodes(): Readonly<
+  Record<string, Readonly<ValidationStatusCodeInfo>>
+> {
+  if (codes === null) {
+    const parsed: Record<string, ValidationStatusCodeInfo> = JSON.parse(
+      getNeonBinary().validationStatusCodes(),
+    );
+    Object.values(parsed).forEach((info) => Object.freeze(info));
+    codes = Object.freeze(parsed);
+  }
+  return codes;
+}
+
+/**
+ * Classify a validation status code the way c2pa-rs does. Codes it does not
+ * know are failures.
+ * @param code A code from `validation_status`, e.g. "claimSignature.validated"
+ */
+export function getValidationStatusKind(code: string): ValidationStatusKind {
+  return getNeonBinary().validationStatusKind(code) as ValidationStatusKind;
+}
+
+/**
+ * Treat validation status codes as errors, warnings, or not at all, in place
+ * of the kind c2pa-rs gives them. Codes set to "error" are reported as
+ * failures and make the manifest store `Invalid`, even ones the C2PA
+ * specification allows in a valid store, such as
+ * `signingCredential.untrusted`. Codes set to "warning" are reported as
+ * informational, and codes set to "ignore" are left out. The policy applies
+ * to `json`, `validationMessages`, `report`, and `conformance` of every
+ * Reader, including ones created earlier, and replaces any previous policy.
+ * @param policy Severities keyed by status code, or null to clear the policy
+ */
+export function setValidationPolicy(policy: ValidationPolicy | null): void {
+  getNeonBinary().setValidationPolicy(policy ? JSON.stringify(policy) : null);
+}
+
+/**
+ * Get the policy set by `setValidationPolicy`, which is empty when none is set.
+ */
+export function getValidationPolicy(): ValidationPolicy {
+  return JSON.parse(getNeonBinary().getValidationPolicy());
+}
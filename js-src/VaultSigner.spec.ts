@@ -0,0 +1,139 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/* eslint-disable @typescript-eslint/no-non-null-assertion */
+import * as crypto from "crypto";
+import * as fs from "fs-extra";
+import http from "http";
+import type { AddressInfo } from "net";
+
+import { Builder } from "./Builder.js";
+import { Reader } from "./Reader.js";
+import type { CallbackSigner } from "./Signer.js";
+import { createVaultTransitSigner } from "./VaultSigner.js";
+
+interface VaultRequest {
+  url: string;
+  headers: http.IncomingHttpHeaders;
+  body: Record<string, unknown>;
+}
+
+describe("createVaultTransitSigner", () => {
+  let server: http.Server;
+  let address: string;
+  let requests: VaultRequest[];
+
+  // A transit engine mounted at "transit" with an ES256 key named "c2pa",
+  // and AppRole auth that hands out "s.client".
+  beforeEach(async () => {
+    requests = [];
+    const key = crypto.createPrivateKey(
+      await fs.readFile("./tests/fixtures/certs/es256.pem"),
+    );
+    server = http.createServer((req, res) => {
+      let data = "";
+      req.on("data", (chunk) => (data += chunk));
+      req.on("end", () => {
+        const body = JSON.parse(data);
+        requests.push({ url: req.url!, headers: req.headers, body });
+        res.setHeader("Content-Type", "application/json");
+        if (req.url === "/v1/auth/approle/login") {
+          res.end(
+            JSON.stringify({
+              auth: { client_token: "s.client", lease_duration: 3600 },
+            }),
+          );
+        } else if (
+          req.url === "/v1/transit/sign/c2pa" &&
+          req.headers["x-vault-token"] === "s.client"
+        ) {
+          const signature = crypto.sign(
+            "sha256",
+            Buffer.from(body.input, "base64"),
+            { key, dsaEncoding: "ieee-p1363" },
+          );
+          const encoded = signature.toString("base64url");
+          res.end(
+            JSON.stringify({ data: { signature: `vault:v1:${encoded}` } }),
+          );
+        } else {
+          res.statusCode = 403;
+          res.end(JSON.stringify({ errors: ["permission denied"] }));
+        }
+      });
+    });
+    await new Promise<void>((resolve) => server.listen(0, resolve));
+    const { port } = server.address() as AddressInfo;
+    address = `http://127.0.0.1:${port}/`;
+  });
+
+  afterEach(async () => {
+    await new Promise((resolve) => server.close(resolve));
+  });
+
+  const sign = async (signer: CallbackSigner) => {
+    const builder = Builder.withJson({
+      claim_generator_info: [{ name: "c2pa_test", version: "1.0.0" }],
+      title: "Vault",
+    });
+    const dest = { buffer: null };
+    await builder.signAsync(signer, { path: "./tests/fixtures/A.jpg" }, dest);
+    return dest.buffer! as Buffer;
+  };
+
+  it("signs with a transit key after logging in with AppRole", async () => {
+    const signer = createVaultTransitSigner({
+      address,
+      auth: { roleId: "role", secretId: "secret" },
+      namespace: "media",
+      keyName: "c2pa",
+      alg: "es256",
+      certs: [await fs.readFile("./tests/fixtures/certs/es256.pub")],
+    });
+    const buffer = await sign(signer);
+
+    const reader = await Reader.fromAsset({ buffer, mimeType: "image/jpeg" });
+    expect(reader!.getActive()!.title).toBe("Vault");
+    const failures = reader!.validationMessages("errors");
+    expect(failures.map(({ code }) => code)).not.toContain(
+      "claimSignature.mismatch",
+    );
+
+    const [login, signRequest] = requests;
+    expect(login.body).toEqual({ role_id: "role", secret_id: "secret" });
+    expect(signRequest.headers["x-vault-namespace"]).toBe("media");
+    expect(signRequest.body).toMatchObject({
+      hash_algorithm: "sha2-256",
+      marshaling_algorithm: "jws",
+    });
+
+    // The client token is reused until its lease runs out.
+    await sign(signer);
+    expect(requests.map(({ url }) => url)).toEqual([
+      "/v1/auth/approle/login",
+      "/v1/transit/sign/c2pa",
+      "/v1/transit/sign/c2pa",
+    ]);
+  });
+
+  it("reports the errors Vault returns", async () => {
+    const signer = createVaultTransitSigner({
+      address,
+      auth: { token: "s.revoked" },
+      keyName: "c2pa",
+      alg: "es256",
+      certs: [await fs.readFile("./tests/fixtures/certs/es256.pub")],
+    });
+    await expect(sign(signer)).rejects.toThrow("permission denied");
+  });
+});
@@ -0,0 +1,188 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { CallbackSigner } from "./Signer.js";
+import type { SigningAlg, VaultTransitSignerConfig } from "./types.d.ts";
+
+// The transit engine's name for the hash each algorithm signs. Ed25519 signs
+// the message itself.
+const HASH_ALGORITHMS: Partial<Record<SigningAlg, string>> = {
+  es256: "sha2-256",
+  es384: "sha2-384",
+  es512: "sha2-512",
+  ps256: "sha2-256",
+  ps384: "sha2-384",
+  ps512: "sha2-512",
+};
+
+interface VaultToken {
+  value: string;
+  // Milliseconds since the epoch, or undefined for tokens that don't expire
+  expiresAt?: number;
+}
+
+// The parts of Vault API responses used here
+interface VaultResponse {
+  status: number;
+  body: {
+    errors?: string[];
+    data?: { signature?: string };
+    auth?: { client_token: string; lease_duration: number };
+  };
+}
+
+class VaultTransitClient {
+  private address: string;
+  private token?: VaultToken;
+
+  constructor(private config: VaultTransitSignerConfig) {
+    this.address = config.address.replace(/\/+$/, "");
+  }
+
+  // A raw signature of `data`: r || s for ECDSA, as COSE expects, rather than
+  // the ASN.1 encoding the transit engine returns by default.
+  async sign(data: Buffer): Promise<Buffer> {
+    const { alg, keyName, keyVersion } = this.config;
+    const body: Record<string, unknown> = { input: data.toString("base64") };
+    if (keyVersion !== undefined) {
+      body.key_version = keyVersion;
+    }
+    const hashAlgorithm = HASH_ALGORITHMS[alg];
+    if (hashAlgorithm) {
+      body.hash_algorithm = hashAlgorithm;
+    }
+    if (alg.startsWith("es")) {
+      body.marshaling_algorithm = "jws";
+    } else if (alg.startsWith("ps")) {
+      body.signature_algorithm = "pss";
+      body.salt_length = "hash";
+    }
+    const mount = this.config.mount ?? "transit";
+    const { data: result } = await this.request(
+      `${mount}/sign/${encodeURIComponent(keyName)}`,
+      body,
+    );
+    if (!result?.signature) {
+      throw new Error("Vault returned no signature");
+    }
+    // Signatures are prefixed with the key version, as in "vault:v1:..."
+    const { signature } = result;
+    const encoded = signature.slice(signature.lastIndexOf(":") + 1);
+    return Buffer.from(encoded, alg.startsWith("es") ? "base64url" : "base64");
+  }
+
+  // POST to the Vault API, logging in again once if an AppRole token was
+  // revoked before its lease ran out.
+  private async request(
+    path: string,
+    body: object,
+    retry = true,
+  ): Promise<VaultResponse["body"]> {
+    const response = await this.post(path, body, await this.clientToken());
+    if (response.status === 403 && retry && "roleId" in this.config.auth) {
+      this.token = undefined;
+      return this.request(path, body, false);
+    }
+    return checked(path, response);
+  }
+
+  private async clientToken(): Promise<string> {
+    const { auth } = this.config;
+    if ("token" in auth) {
+      return auth.token;
+    }
+    if (
+      this.token &&
+      (this.token.expiresAt === undefined || Date.now() < this.token.expiresAt)
+    ) {
+      return this.token.value;
+    }
+    const path = `auth/${auth.mount ?? "approle"}/login`;
+    const response = await this.post(path, {
+      role_id: auth.roleId,
+      secret_id: auth.secretId,
+    });
+    const login = checked(path, response).auth;
+    if (!login) {
+      throw new Error("Vault returned no client token");
+    }
+    // Log in again a little before the lease runs out.
+    this.token = {
+      value: login.client_token,
+      expiresAt: login.lease_duration
+        ? Date.now() + login.lease_duration * 900
+        : undefined,
+    };
+    return login.client_token;
+  }
+
+  private async post(
+    path: string,
+    body: object,
+    token?: string,
+  ): Promise<VaultResponse> {
+    const headers: Record<string, string> = {
+      "Content-Type": "application/json",
+    };
+    if (token) {
+      headers["X-Vault-Token"] = token;
+    }
+    if (this.config.namespace) {
+      headers["X-Vault-Namespace"] = this.config.namespace;
+    }
+    const response = await fetch(`${this.address}/v1/${path}`, {
+      method: "POST",
+      headers,
+      body: JSON.stringify(body),
+    });
+    return {
+      status: response.status,
+      body: await response.json().catch(() => ({})),
+    };
+  }
+}
+
+// The body of a successful response, or an error with Vault's messages.
+function checked(path: string, response: VaultResponse) {
+  const { status, body } = response;
+  if (status < 200 || status >= 300) {
+    const errors = body.errors?.join("; ") || "no details";
+    throw new Error(
+      `Vault request to ${path} failed with ${status}: ${errors}`,
+    );
+  }
+  return body;
+}
+
+/**
+ * Create a signer that signs with a key in HashiCorp Vault's transit secrets
+ * engine, so the private key never leaves Vault. The certificate chain is
+ * given here, since Vault does not hold certificates. Use the signer with
+ * `Builder.signAsync`.
+ * @param config Where the key is and how to log in to Vault
+ */
+export function createVaultTransitSigner(
+  config: VaultTransitSignerConfig,
+): CallbackSigner {
+  const client = new VaultTransitClient(config);
+  return CallbackSigner.newSigner(
+    {
+      alg: config.alg,
+      certs: config.certs,
+      reserveSize: config.reserveSize ?? 10000,
+      tsaUrl: config.tsaUrl,
+      directCoseHandling: false,
+    },
+    (data) => client.sign(data),
+  );
+}
@@ -0,0 +1,48 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { Builder } from "./Builder.js";
+import { Reader } from "./Reader.js";
+import { nativeFeatures, versionInfo } from "./Version.js";
+
+describe("versionInfo", () => {
+  it("describes the native binary", () => {
+    const info = versionInfo();
+    expect(info.c2paVersion).toMatch(/^\d+\.\d+\.\d+/);
+    expect(info.specVersion).toBe("2.2");
+    expect(info.claimVersions).toEqual([1, 2]);
+    expect(info.features).toEqual(
+      expect.arrayContaining(["file_io", "fetch_remote_manifests", "pdf"]),
+    );
+    expect(info.signingAlgorithms).toContain("ed25519");
+    expect(info.readableFormats).toEqual(Reader.supportedMimeTypes());
+    expect(info.signableFormats).toEqual(Builder.supportedMimeTypes());
+    expect(info.readableFormats).toContain("application/pdf");
+    expect(info.signableFormats).not.toContain("application/pdf");
+  });
+});
+
+describe("nativeFeatures", () => {
+  it("reports the optional capabilities of the default build", () => {
+    expect(nativeFeatures()).toEqual({
+      pdf: true,
+      fetchRemoteManifests: true,
+      thumbnails: true,
+      cawg: true,
+      trustmark: true,
+    });
+    expect(versionInfo().features).toEqual(
+      expect.arrayContaining(["add_thumbnails", "fetch_remote_manifests"]),
+    );
+  });
+});
@@ -0,0 +1,36 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+import { getNeonBinary } from "./binary.js";
+import type { NativeFeatures, VersionInfo } from "./types.d.ts";
+
+/**
+ * Describe the native binary: the c2pa-rs version it bundles, the C2PA
+ * specification and claim versions it supports, the c2pa-rs features it was
+ * built with, and the formats it can read and sign. Use this to detect
+ * capabilities rather than relying on the package version.
+ * @returns The version and capabilities of the native binary
+ */
+export function versionInfo(): VersionInfo {
+  return JSON.parse(getNeonBinary().versionInfo());
+}
+
+/**
+ * Report the optional capabilities of the native binary, which a platform
+ * build can leave out, so an application can degrade gracefully instead of
+ * failing when one is used.
+ * @returns Whether each optional capability is available
+ */
+export function nativeFeatures(): NativeFeatures {
+  return JSON.parse(getNeonBinary().nativeFeatures());
+}
@@ -13,16 +13,37 @@
 
 import { Buffer } from "buffer";
 
+import type { ManifestStore } from "@contentauth/c2pa-types";
+
 import type {
+  BmffHashVersion,
   CallbackSignerConfig,
+  CallbackSignerFunction,
   ClaimVersion,
+  ConformanceReport,
+  DataHashBinding,
+  DecodedImage,
   DestinationAsset,
+  EmbeddedManifestMatch,
+  FragmentReport,
+  HashedUri,
   JsCallbackSignerConfig,
   ManifestAssertionKind,
+  ManifestDataPrecedence,
+  MerkleTreeReport,
+  NativeLogEvent,
+  NativeSpan,
+  PresentationReport,
+  ProvenanceGraph,
+  SignatureSummary,
   SignerPayload,
   SigningAlg,
   SourceAsset,
+  SourceBufferAsset,
   TrustmarkConfig,
+  ValidationMessage,
+  ValidationMessageFilter,
+  VerificationReport,
   NeonCallbackSignerHandle,
   NeonLocalSignerHandle,
   NeonReaderHandle,
@@ -30,7 +51,11 @@ import type {
   NeonIdentityAssertionSignerHandle,
   NeonIdentityAssertionBuilderHandle,
   NeonCallbackCredentialHolderHandle,
+  NeonVerifiableCredentialHolderHandle,
+  NeonX509CredentialHolderHandle,
   NeonTrustmarkHandle,
+  NeonContextHandle,
+  NeonSoftBindingHandle,
 } from "./types.d.ts";
 
 // These functions are not exposed directly, but are called by the Builder, Reader, and Signer, etc. classes
@@ -38,15 +63,28 @@ import type {
 declare module "index.node" {
   // Builder methods
   export function builderNew(): NeonBuilderHandle;
-  export function builderWithJson(json: string): NeonBuilderHandle;
+  export function builderWithJson(
+    json: string | object,
+    settings?: string | object | NeonContextHandle,
+  ): NeonBuilderHandle;
   export function builderSetIntent(intent: string): void;
+  export function builderAddTimestamp(manifestLabel: string): void;
   export function builderSetNoEmbed(noEmbed: boolean): void;
   export function builderSetRemoteUrl(url: string): void;
+  export function builderSetPlacement(embed: boolean, remoteUrl?: string): void;
   export function builderAddAssertion(
     label: string,
     assertion: unknown,
     assertionKind?: ManifestAssertionKind,
   ): void;
+  export function builderHashedUri(
+    ingredient: string,
+    assertion?: string,
+    alg?: string,
+  ): HashedUri;
+  export function builderAddSoftBindingPlugin(
+    plugin: NeonSoftBindingHandle,
+  ): void;
   export function builderAddResource(
     uri: string,
     resource: SourceAsset,
@@ -55,6 +93,11 @@ declare module "index.node" {
     ingredientJson: string,
     resource: SourceAsset,
   ): Promise<void>;
+  export function builderAddIngredientsFromAssets(
+    ingredientJsons: string[],
+    assets: SourceAsset[],
+    concurrency?: number,
+  ): Promise<void>;
   export function builderToArchive(asset: DestinationAsset): Promise<void>;
   export function builderFromArchive(
     asset: SourceAsset,
@@ -69,39 +112,113 @@ declare module "index.node" {
     signerConfig: JsCallbackSignerConfig,
     input: SourceAsset,
     output: DestinationAsset,
+    timeoutMs?: number,
+    priority?: string,
   ): Promise<Buffer | { manifest: Buffer; signedAsset: Buffer }>;
   export function builderSignAsync(
     signer: NeonCallbackSignerHandle | NeonIdentityAssertionSignerHandle,
     input: SourceAsset,
     output: DestinationAsset,
+    timeoutMs?: number,
+    priority?: string,
+    network?: string,
   ): Promise<Buffer | { manifest: Buffer; signedAsset: Buffer }>;
   export function builderIdentitySignAsync(
     signer: NeonIdentityAssertionSignerHandle,
     input: SourceAsset,
     output: DestinationAsset,
+    timeoutMs?: number,
+    priority?: string,
+    network?: string,
   ): Promise<Buffer | { manifest: Buffer; signedAsset: Buffer }>;
   export function builderManifestDefinition(): string;
+  export function builderDispose(): void;
+  export function builderSupportedMimeTypes(): string[];
+  export function builderMemoryUsage(): number;
   export function builderUpdateManifestProperty(
     property: string,
-    value: string | ClaimVersion,
+    value: string | ClaimVersion | BmffHashVersion,
   ): void;
 
   // Reader methods
   export function readerFromAsset(
     asset: SourceAsset,
+    settings?: string | object | NeonContextHandle,
+    verifyOptions?: string,
+    timeoutMs?: number,
+    priority?: string,
   ): Promise<NeonReaderHandle>;
+  export function readerFromAssetSync(
+    asset: SourceAsset,
+    settings?: string | object | NeonContextHandle,
+    verifyOptions?: string,
+    timeoutMs?: number,
+    priority?: string,
+  ): NeonReaderHandle | null;
+  export function readerFromUrl(
+    url: string,
+    mimeType?: string,
+    settings?: string | object | NeonContextHandle,
+    verifyOptions?: string,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<NeonReaderHandle | null>;
+  export function readerFromFragments(
+    init: SourceAsset,
+    fragments: SourceAsset[] | string,
+    settings?: string | object | NeonContextHandle,
+    verifyOptions?: string,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<{
+    reader: NeonReaderHandle | null;
+    fragments: FragmentReport[];
+    merkleTree: MerkleTreeReport | null;
+  }>;
   export function readerFromManifestDataAndAsset(
     manifestData: Buffer,
     asset: SourceAsset,
-  ): Promise<NeonReaderHandle>;
-  export function readerJson(): string;
+    settings?: string | object | NeonContextHandle,
+    verifyOptions?: string,
+    timeoutMs?: number,
+    priority?: string,
+    precedence?: ManifestDataPrecedence,
+  ): Promise<{
+    reader: NeonReaderHandle;
+    embedded: EmbeddedManifestMatch;
+  }>;
+  export function readerJson(): ManifestStore;
   export function readerRemoteUrl(): string;
   export function readerIsEmbedded(): boolean;
+  export function readerSupportedMimeTypes(): string[];
+  export function readerValidationMessages(
+    filter?: ValidationMessageFilter,
+  ): ValidationMessage[];
+  export function readerReport(format: "json"): VerificationReport;
+  export function readerReport(format: string): string;
+  export function readerSignatures(): SignatureSummary[];
+  export function readerProvenanceGraph(format: "json"): ProvenanceGraph;
+  export function readerProvenanceGraph(format: string): string;
+  export function readerConformance(): ConformanceReport;
+  export function readerJsonAsync(): Promise<ManifestStore>;
+  export function readerRemoteUrlAsync(): Promise<string>;
+  export function readerIsEmbeddedAsync(): Promise<boolean>;
   export function readerResourceToAsset(
     uri: string,
     output: DestinationAsset,
+    timeoutMs?: number,
+    priority?: string,
   ): Promise<number>;
+  export function readerExtractThumbnails(
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<Record<string, SourceBufferAsset>>;
   export function readerPostValidateCawg(): Promise<void>;
+  export function readerDispose(): void;
+  export function readerShare(): string;
+  export function readerFromShared(key: string): NeonReaderHandle;
+  export function readerReleaseShared(key: string): boolean;
+  export function readerMemoryUsage(): number;
 
   // Signers
   export function localSignerNew(
@@ -118,7 +235,7 @@ declare module "index.node" {
 
   export function callbackSignerFromConfig(
     config: CallbackSignerConfig,
-    callback: (data: Buffer) => Promise<Buffer>,
+    callback: CallbackSignerFunction,
   ): NeonCallbackSignerHandle;
   export function callbackSignerConfigFromJs(
     config: JsCallbackSignerConfig,
@@ -137,7 +254,10 @@ declare module "index.node" {
     identityAssertionBuilder: NeonIdentityAssertionBuilderHandle,
   ): void;
   export function identityBuilderForCredentialHolder(
-    credentialHolder: NeonCallbackCredentialHolderHandle,
+    credentialHolder:
+      | NeonCallbackCredentialHolderHandle
+      | NeonVerifiableCredentialHolderHandle
+      | NeonX509CredentialHolderHandle,
   ): NeonIdentityAssertionBuilderHandle;
   export function identityBuilderAddReferencedAssertions(
     referencedAssertions: Array<string>,
@@ -150,6 +270,14 @@ declare module "index.node" {
   ): NeonCallbackCredentialHolderHandle;
   export function callbackCredentialHolderReserveSize(): number;
   export function callbackCredentialHolderSigType(): string;
+  export function newVerifiableCredentialHolder(
+    credentialJson: string,
+    reserveSize: number,
+    callback: (data: Buffer) => Promise<Buffer>,
+  ): NeonVerifiableCredentialHolderHandle;
+  export function newX509CredentialHolder(
+    signer: NeonCallbackSignerHandle,
+  ): NeonX509CredentialHolderHandle;
 
   // Trustmark
   export function trustmarkNew(
@@ -162,10 +290,50 @@ declare module "index.node" {
   ): Promise<Buffer>;
   export function trustmarkDecode(image: Buffer): Promise<string>;
 
+  // Soft bindings
+  export function softBindingNew(
+    alg: string,
+    embed?: (
+      asset: Buffer,
+      mimeType: string,
+    ) => Promise<{ asset: Buffer; value: string }>,
+    detect?: (asset: Buffer, mimeType: string) => Promise<string | null>,
+  ): NeonSoftBindingHandle;
+  export function detectSoftBindings(
+    asset: SourceAsset,
+    plugins: NeonSoftBindingHandle[],
+    settings?: string | object | NeonContextHandle,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<string>;
+
+  // Context methods
+  export function contextNew(settings?: string | object): NeonContextHandle;
+  export function contextSettings(): string;
+
+  // Runtime
+  export function configureRuntime(configJson: string): void;
+  export function shutdown(timeoutMs?: number): Promise<boolean>;
+  export function reinitialize(): void;
+  export function getMetrics(): string;
+  export function getMemoryUsage(): string;
+  export function setLogger(
+    logger: ((event: NativeLogEvent) => void) | null,
+    level?: string,
+  ): void;
+  export function configureTelemetry(
+    configJson: string | null,
+    onSpan: ((span: NativeSpan) => void) | null,
+  ): void;
+  export function setTraceParent(traceparent: string | null): void;
+  export function flushTelemetry(): Promise<void>;
+  export function versionInfo(): string;
+  export function nativeFeatures(): string;
+
   // Settings API
   export function loadSettings(json: string): void;
   export function loadSettingsToml(toml: string): void;
-  export function getSettingsJson(): string;
+  export function getSettingsJson(settings?: string | object): string;
   export function loadTrustConfig(trustConfigJson: string): void;
   export function loadCawgTrustConfig(trustConfigJson: string): void;
   export function getTrustConfig(): string;
@@ -173,4 +341,85 @@ declare module "index.node" {
   export function loadVerifyConfig(verifyConfigJson: string): void;
   export function getVerifyConfig(): string;
   export function resetSettings(): void;
+  export function configureTrustSources(configJson: string): Promise<void>;
+  export function clearTrustSources(): void;
+  export function onSettingsChange(callback: (reason: string) => void): number;
+  export function offSettingsChange(id: number): void;
+  export function setOfflineMode(offline: boolean): void;
+  export function isOfflineMode(): boolean;
+  export function configureProxy(configJson: string): void;
+  export function clearProxy(): void;
+  export function configureNetwork(json: string | null): void;
+  export function setRemoteManifestResolver(
+    resolver: ((url: string) => Promise<Uint8Array | null>) | null,
+  ): void;
+  export function configureNetworkCache(configJson: string | null): void;
+  export function clearNetworkCache(): void;
+  export function networkCacheStats(): string;
+
+  // Validation
+  export function validationStatusCodes(): string;
+  export function validationStatusKind(code: string): string;
+  export function setValidationPolicy(policyJson: string | null): void;
+  export function getValidationPolicy(): string;
+
+  // Certificates
+  export function parseCertificates(input: string | Buffer): string;
+
+  // Hashing
+  export function hashAsset(
+    asset: SourceAsset,
+    alg?: string,
+    exclusions?: string,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<Buffer>;
+  export function decodeThumbnail(
+    asset: SourceAsset,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<DecodedImage>;
+  export function readDataHash(
+    asset: SourceAsset,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<DataHashBinding | null>;
+  export function manifestStoreStats(
+    asset: SourceAsset,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<string | null>;
+  export function scanC2paRanges(
+    asset: SourceAsset,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<string>;
+  export function findProvenanceReference(
+    asset: SourceAsset,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<string | null>;
+
+  // Fragmented presentations
+  export function verifyPresentation(
+    source: string,
+    settings?: string | object | NeonContextHandle,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<PresentationReport>;
+
+  // Manifest definitions
+  export function validateManifestDefinition(json: string | object): string;
+
+  // Ingredients
+  export function createIngredient(
+    asset: SourceAsset,
+    ingredientJson?: string,
+    settings?: string | object | NeonContextHandle,
+    timeoutMs?: number,
+    priority?: string,
+  ): Promise<{ json: string; resources: Record<string, SourceBufferAsset> }>;
+  export function configureIngredientCache(configJson: string | null): void;
+  export function clearIngredientCache(): void;
+  export function ingredientCacheStats(): string;
 }
@@ -13,13 +13,33 @@
 
 export type * from "./types.d.ts";
 export { Builder } from "./Builder.js";
+export { C2paError, isC2paError } from "./C2paError.js";
+export { Context } from "./Context.js";
 export { Reader } from "./Reader.js";
 export { LocalSigner, CallbackSigner } from "./Signer.js";
+export { createVaultTransitSigner } from "./VaultSigner.js";
 export {
   IdentityAssertionBuilder,
   IdentityAssertionSigner,
   CallbackCredentialHolder,
+  VerifiableCredentialHolder,
+  X509CredentialHolder,
 } from "./IdentityAssertion.js";
 export { Trustmark } from "./Trustmark.js";
+export { SoftBindingPlugin, detectSoftBindings } from "./SoftBinding.js";
 export { isActionsAssertion } from "./assertions.js";
 export * from "./Settings.js";
+export * from "./Runtime.js";
+export * from "./ValidationStatus.js";
+export { registerValidationRule } from "./ValidationRules.js";
+export * from "./Certificate.js";
+export * from "./Hashing.js";
+export * from "./Thumbnail.js";
+export * from "./ManifestStoreStats.js";
+export * from "./Presentation.js";
+export * from "./Scan.js";
+export * from "./Ingredient.js";
+export * from "./ManifestDefinition.js";
+export * from "./Version.js";
+export * from "./ClaimGenerator.js";
+export * from "./Sync.js";
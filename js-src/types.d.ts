@@ -18,6 +18,7 @@ import type {
   Ingredient,
   Manifest,
   ManifestStore,
+  ResourceRef,
 } from "@contentauth/c2pa-types";
 
 export type { Ingredient } from "@contentauth/c2pa-types";
@@ -65,13 +66,15 @@ export type TrustmarkVariant =
 export type ManifestAssertionKind = "Cbor" | "Json" | "Binary" | "Uri";
 
 /**
- * A buffer for the source asset
+ * A buffer for the source asset. The buffer is copied when the call is made, so
+ * it may be reused as soon as the call returns.
  */
 export interface SourceBufferAsset {
   // A buffer containing the asset data
   buffer: Buffer;
-  // The MIME type of the asset, for instance `image/jpeg`
-  mimeType: string;
+  // The MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied, the MIME type is detected from the content.
+  mimeType?: string;
 }
 
 /**
@@ -83,7 +86,10 @@ export interface DestinationBufferAsset {
 }
 
 /**
- * A file that can be used either the source or destination
+ * A file that can be used either the source or destination.
+ * Files are opened and read or written natively, without passing their contents
+ * through JavaScript. A destination file is replaced only once the operation
+ * succeeds, so it can be the same file as the source.
  */
 export interface FileAsset {
   // The path to the asset
@@ -97,13 +103,230 @@ export interface FileAsset {
  * A source asset that can either be in memory or on disk
  * This is a workaround since Neon does not support streams
  */
-export type SourceAsset = SourceBufferAsset | FileAsset;
+/**
+ * A Node `Readable`, or any async iterable of buffers, used as a source.
+ * Data is read on demand, so the whole asset does not need to be buffered first.
+ * Stream assets can only be used with asynchronous methods.
+ */
+export interface StreamAsset {
+  // The stream to read the asset from, for instance an HTTP upload or S3 object body
+  stream: AsyncIterable<Uint8Array>;
+  // The MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied, the MIME type is detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * A file that is memory-mapped instead of read, for verifying large media without
+ * copying it onto the heap. The file must not be modified while it is being read.
+ * Mapped files can only be used as a source.
+ */
+export interface MappedFileAsset {
+  // The path to the asset
+  path: string;
+  mmap: true;
+  // The optional MIME type of the asset, for instance `video/mp4`.
+  // If not supplied, the MIME type will be inferred from the file extension, if available.
+  mimeType?: string;
+}
+
+/**
+ * Content produced on demand, for instance by a transcoder. `pull` is called
+ * each time more data is needed and returns the next chunk, or a promise of it,
+ * and `null` or `undefined` at the end of the content.
+ * Pull assets can only be used as a source, with asynchronous methods.
+ */
+export interface PullAsset {
+  pull: () =>
+    | Uint8Array
+    | null
+    | undefined
+    | Promise<Uint8Array | null | undefined>;
+  // The MIME type of the asset, for instance `video/mp4`.
+  // If not supplied, the MIME type is detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * Where a seek is relative to: the start of the asset, the current position,
+ * or the end of the asset.
+ */
+export type SeekOrigin = "start" | "current" | "end";
+
+/**
+ * Callbacks implementing a stream over custom storage, such as an encrypted
+ * blob or a chunk store. Each may return its result directly or a promise of
+ * it. The stream keeps its own position, which `read` and `write` advance.
+ */
+export interface AssetIo {
+  // Read up to `length` bytes from the current position. Returns fewer bytes,
+  // or an empty buffer or null, at the end of the asset.
+  read: (
+    length: number,
+  ) =>
+    | Uint8Array
+    | null
+    | undefined
+    | Promise<Uint8Array | null | undefined>;
+  // Move to `offset` relative to `origin` and return the new position.
+  seek: (offset: number, origin: SeekOrigin) => number | Promise<number>;
+  // Write all of `chunk` at the current position. Only needed for destinations.
+  write?: (chunk: Buffer) => void | Promise<void>;
+}
+
+/**
+ * An asset read and written through `read`, `seek`, and `write` callbacks, so
+ * storage backends can take part without staging the bytes. Each read, seek,
+ * and write c2pa-rs makes calls back into JS; reads are made in 64 KiB blocks.
+ * IO assets can only be used with asynchronous methods.
+ */
+export interface IoAsset {
+  io: AssetIo;
+  // The MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied for a source, the MIME type is detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * An HTTP(S) resource, for instance an asset hosted on a CDN. It is read with
+ * range requests, so only the parts needed for verification are downloaded.
+ * URL assets can only be used as a source.
+ */
+export interface UrlAsset {
+  // The URL of the asset
+  url: string;
+  // The optional MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied, the MIME type is inferred from the URL path or detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * Options for `Reader.fromUrl`.
+ */
+export interface UrlReadOptions {
+  // The MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied, the MIME type is inferred from the URL path or detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * A file already opened with `fs.open`, for callers that apply their own locking
+ * or permission checks before handing the file over. The descriptor is
+ * duplicated, so it can be closed once the call returns, but the duplicate shares
+ * its file offset. Source files are read from the start; destination files are
+ * only replaced once the operation succeeds, and are left as they were if it
+ * fails. Only supported on Unix platforms.
+ */
+export interface FileDescriptorAsset {
+  // The open file descriptor
+  fd: number;
+  // The MIME type of the asset, for instance `image/jpeg`.
+  // If not supplied for a source, the MIME type is detected from the content.
+  mimeType?: string;
+}
+
+/**
+ * Options that apply to any asset.
+ */
+export interface AssetLimits {
+  // The maximum number of bytes read from a source asset or written to a
+  // destination asset. Larger assets are rejected with an error instead of being
+  // processed, which protects services that handle untrusted uploads.
+  maxBytes?: number;
+}
+
+export type SourceAsset = (
+  | SourceBufferAsset
+  | FileAsset
+  | MappedFileAsset
+  | StreamAsset
+  | PullAsset
+  | IoAsset
+  | UrlAsset
+  | FileDescriptorAsset
+) &
+  AssetLimits;
+
+/**
+ * A Node `Writable` used as a destination, for instance an HTTP response or a
+ * cloud upload. The output is written once it is complete, with a bounded number
+ * of writes waiting to be accepted at a time. The stream is ended afterwards.
+ * Stream destinations can only be used with asynchronous methods.
+ */
+export interface StreamDestinationAsset {
+  stream: NodeJS.WritableStream;
+}
+
+/**
+ * A destination written to a new file in the system temporary directory.
+ * The file's path is set on the asset as `path` when the operation starts, but
+ * the file only exists once the operation succeeds; a failure leaves no file
+ * behind. The caller is responsible for removing the file when done with it.
+ */
+export interface TempFileDestinationAsset {
+  tempFile: true;
+  // The optional file extension, for instance `jpg`
+  extension?: string;
+  // Set to the path of the temporary file
+  path?: string;
+}
+
+/**
+ * Where to write the manifest store of a sign call as a sidecar file. By default
+ * the sidecar is written next to the destination file, with the same name and a
+ * `.c2pa` extension, and the manifest store is not embedded in the signed asset.
+ */
+/**
+ * Where a signed manifest store goes:
+ * - `"embedded"`: in the asset as JUMBF, the default
+ * - `"sidecar"`: only returned by the sign call, to be stored next to the
+ *   asset, which is left untouched
+ * - `{ remoteUrl }`: referenced from the asset's XMP by `remoteUrl`, where
+ *   the returned manifest store must be published. Set `embed` to also
+ *   embed it as JUMBF. Signing fails for formats without XMP
+ */
+export type ManifestPlacement =
+  | "embedded"
+  | "sidecar"
+  | { remoteUrl: string; embed?: boolean };
+
+export interface SidecarOptions {
+  // The directory to write the sidecar to. Defaults to the destination file's directory.
+  directory?: string;
+  // The file name of the sidecar. Required unless the destination is a file.
+  fileName?: string;
+  // The extension used when the file name is taken from the destination. Defaults to `c2pa`.
+  extension?: string;
+  // Replace an existing sidecar. Otherwise signing fails with `SIDECAR_EXISTS`
+  // before the destination is written.
+  overwrite?: boolean;
+  // Also embed the manifest store in the signed asset.
+  embed?: boolean;
+  // Set to the path of the sidecar
+  path?: string;
+}
+
+/**
+ * Options for the destination of a sign call.
+ */
+export interface SidecarDestination {
+  sidecar?: SidecarOptions;
+}
 
 /**
  * An destination asset that can either be in memory or on disk
  * This is a workaround since Neon does not support streams
  */
-export type DestinationAsset = DestinationBufferAsset | FileAsset;
+export type DestinationAsset = (
+  | DestinationBufferAsset
+  | FileAsset
+  | StreamDestinationAsset
+  | IoAsset
+  | TempFileDestinationAsset
+  | FileDescriptorAsset
+) &
+  AssetLimits &
+  SidecarDestination;
 
 /**
  * The return type of resourceToAsset.
@@ -144,6 +367,29 @@ export interface CallbackCredentialHolderInterface {
   getHandle(): NeonCallbackCredentialHolderHandle;
 }
 
+export interface VerifiableCredentialHolderInterface {
+  getHandle(): NeonVerifiableCredentialHolderHandle;
+}
+
+export interface X509CredentialHolderInterface {
+  getHandle(): NeonX509CredentialHolderHandle;
+}
+
+/**
+ * A W3C verifiable credential issued by an identity claims aggregator. The
+ * `c2paAsset` of the credential subject is filled in when signing.
+ */
+export interface VerifiableCredential {
+  "@context": string[];
+  type: string[];
+  // The aggregator's DID, did:jwk or did:web, whose Ed25519 key signs
+  issuer: string;
+  validFrom?: string;
+  validUntil?: string;
+  credentialSubject: Record<string, unknown> | Record<string, unknown>[];
+  [key: string]: unknown;
+}
+
 /**
  * @internal
  * Internal type used for Rust/Node.js interop
@@ -151,12 +397,16 @@ export interface CallbackCredentialHolderInterface {
 export type CallbackSignerConfig = unknown;
 export type NeonCallbackSignerHandle = unknown;
 export type NeonCallbackCredentialHolderHandle = unknown;
+export type NeonVerifiableCredentialHolderHandle = unknown;
+export type NeonX509CredentialHolderHandle = unknown;
 export type NeonLocalSignerHandle = unknown;
 export type NeonBuilderHandle = unknown;
 export type NeonReaderHandle = unknown;
 export type NeonIdentityAssertionSignerHandle = unknown;
 export type NeonIdentityAssertionBuilderHandle = unknown;
 export type NeonTrustmarkHandle = unknown;
+export type NeonContextHandle = unknown;
+export type NeonSoftBindingHandle = unknown;
 
 /*
  * Configuration for an asynchronous signer.
@@ -172,6 +422,58 @@ export interface JsCallbackSignerConfig {
   // When true, the callback function should return fully-formed COSE data.
   // When false, the callback function should return raw signature data and the c2pa SDK will handle COSE wrapping.
   directCoseHandling: boolean;
+  // When true, the callback is given the digest of the data, hashed with the
+  // algorithm's hash, and the name of that hash, for signers such as HSM SDKs
+  // that sign digests rather than messages. Not supported for ed25519 or with
+  // directCoseHandling.
+  signDigest?: boolean;
+}
+
+/**
+ * Signs the data to be signed, or in `signDigest` mode its digest, returning a
+ * raw signature (or COSE data with `directCoseHandling`).
+ */
+export type CallbackSignerFunction = (
+  data: Buffer,
+  hashAlgorithm?: HashAlgorithm,
+) => Promise<Buffer>;
+
+/**
+ * How `createVaultTransitSigner` logs in to Vault: with a token, or with an
+ * AppRole role ID and secret ID, whose client token is renewed as its lease
+ * runs out.
+ */
+export type VaultAuth =
+  | { token: string }
+  | {
+      roleId: string;
+      secretId: string;
+      // Mount path of the AppRole auth method. Defaults to "approle"
+      mount?: string;
+    };
+
+/**
+ * A signing key in HashiCorp Vault's transit secrets engine, for
+ * `createVaultTransitSigner`.
+ */
+export interface VaultTransitSignerConfig {
+  // The Vault server, e.g. "https://vault.example.com:8200"
+  address: string;
+  auth: VaultAuth;
+  // Vault Enterprise namespace
+  namespace?: string;
+  // Mount path of the transit engine. Defaults to "transit"
+  mount?: string;
+  keyName: string;
+  // The key version to sign with. Defaults to the latest
+  keyVersion?: number;
+  // Must match the type of the transit key
+  alg: SigningAlg;
+  // The certificate chain for the key, starting with the signing certificate
+  certs: Buffer[];
+  // Defaults to 10000
+  reserveSize?: number;
+  tsaUrl?: string;
 }
 
 export interface SignerPayload {
@@ -205,6 +507,35 @@ export interface BuilderInterface {
    */
   setIntent(intent: BuilderIntent): void;
 
+  /**
+   * Set the hash algorithm for the asset's hard binding, such as its data
+   * hash, and for the hashes of the claim's assertions. The claim signature
+   * is hashed as its signing algorithm requires, so use es384 or ps384 for
+   * SHA-384 throughout.
+   * @param alg The algorithm, sha256 by default
+   */
+  setHashAlgorithm(alg: HashAlgorithm): void;
+
+  /**
+   * Choose the BMFF hash assertion written when signing MP4, HEIF, and other
+   * BMFF assets. Version 3, the default, is written in C2PA 2.x claims and
+   * can hold Merkle trees, set with the `core.merkle_tree_chunk_size_in_kb`
+   * setting. Version 2 is for older validators: it is written in a C2PA 1.x
+   * claim and can't hold Merkle trees. When no claim version has been chosen,
+   * this chooses the one the version needs; it throws if a different claim
+   * version has been chosen with `claim_version` or `updateManifestProperty`.
+   * @param version 2 or 3
+   */
+  setBmffHashVersion(version: BmffHashVersion): void;
+
+  /**
+   * Fetch a fresh timestamp from the signer's time-stamping authority for a
+   * manifest when signing, and record it in a `c2pa.time-stamp` assertion.
+   * The manifest must be the parent ingredient's or one it contains.
+   * @param manifestLabel The label of the manifest to timestamp
+   */
+  addTimestamp(manifestLabel: string): void;
+
   /**
    * Set the no embed flag of the manifest
    * @param noEmbed The no embed flag of the manifest
@@ -217,6 +548,13 @@ export interface BuilderInterface {
    */
   setRemoteUrl(url: string): void;
 
+  /**
+   * Set where the manifest store goes when the Builder is signed, replacing
+   * the settings of `setNoEmbed` and `setRemoteUrl`
+   * @param placement Embedded, sidecar only, or referenced by a remote URL
+   */
+  setPlacement(placement: ManifestPlacement): void;
+
   /**
    * Add a single action to the manifest.
    * This is a convenience method for adding an action to the `Actions` assertion.
@@ -235,6 +573,14 @@ export interface BuilderInterface {
     assertion: unknown,
     assertionKind?: ManifestAssertionKind,
   ): void;
+  /**
+   * Make a hashed URI to the manifest of an ingredient already added, or to
+   * one of its assertions, for an assertion that references it. The hash is
+   * a Buffer, which `addAssertion` stores as a CBOR byte string.
+   * @param options The ingredient, and optionally the assertion and algorithm
+   */
+  ingredientHashedUri(options: HashedUriOptions): HashedUri;
+
   /**
    * Add a resource from a buffer or file
    * @param uri The URI of the resource
@@ -258,6 +604,31 @@ export interface BuilderInterface {
    */
   addIngredientFromReader(reader: ReaderInterface): Ingredient;
 
+  /**
+   * Add an ingredient from `createIngredient`, along with its resources
+   * @param prepared The ingredient and its resources
+   */
+  addPreparedIngredient(prepared: PreparedIngredient): Promise<void>;
+
+  /**
+   * Add ingredients read from assets, validating their manifests in parallel.
+   * They are added in the order given, and none are added if any fails.
+   * @param ingredients The ingredients and the assets to read them from
+   * @param options.concurrency How many assets to read at once
+   */
+  addIngredients(
+    ingredients: IngredientAsset[],
+    options?: AddIngredientsOptions,
+  ): Promise<void>;
+
+  /**
+   * Run a soft binding plugin's embedder over the asset whenever the Builder
+   * is signed, adding a `c2pa.soft-binding` assertion for the watermark. Only
+   * the async signing methods support plugins; `sign` throws.
+   * @param plugin The plugin, from `SoftBindingPlugin.new`
+   */
+  addSoftBindingPlugin(plugin: SoftBindingPluginInterface): void;
+
   /**
    * Convert the Builder into a archive formatted buffer or file
    * @param asset The file or buffer for the archive
@@ -284,6 +655,7 @@ export interface BuilderInterface {
    * @param callback The callback function to sign the asset
    * @param source The file or buffer containing the asset
    * @param dest The file or buffer to write the asset to
+   * @param options.timeoutMs Reject if signing takes longer than this
    * @returns the bytes of the c2pa_manifest that was embedded
    */
   signConfigAsync(
@@ -291,6 +663,7 @@ export interface BuilderInterface {
     signerConfig: JsCallbackSignerConfig,
     input: SourceAsset,
     output: DestinationAsset,
+    options?: OperationOptions,
   ): Promise<Buffer>;
 
   /**
@@ -299,12 +672,15 @@ export interface BuilderInterface {
    * @param callbackSigner The CallbackSigner
    * @param source The file or buffer containing the asset
    * @param dest The file or buffer to write the asset to
+   * @param options.timeoutMs Reject if signing takes longer than this
+   * @param options.network Timeouts and retries for time stamp requests
    * @returns the bytes of the c2pa_manifest that was embedded
    */
   signAsync(
     callbackSigner: CallbackSignerInterface | IdentityAssertionSignerInterface,
     input: SourceAsset,
     output: DestinationAsset,
+    options?: SignOptions,
   ): Promise<Buffer>;
 
   /**
@@ -332,12 +708,37 @@ export interface BuilderInterface {
    */
   updateManifestProperty(property: string, value: string | ClaimVersion): void;
 
+  /**
+   * Free the Builder's native memory now rather than when it is garbage
+   * collected. Any later call on the Builder throws.
+   */
+  dispose(): void;
+
+  /**
+   * Approximate native memory held by the Builder, in bytes. This counts
+   * manifest data and binary resources, not allocator overhead, so use it to
+   * compare objects rather than as an exact figure.
+   */
+  memoryUsage(): number;
+
   /**
    * Get the internal handle for use with Neon bindings
    */
   getHandle(): NeonBuilderHandle;
 }
 
+export interface ContextInterface {
+  /**
+   * Get the settings this Context was created with, with signer sections removed
+   */
+  settings(): Record<string, unknown>;
+
+  /**
+   * Get the internal handle for use with Neon bindings
+   */
+  getHandle(): NeonContextHandle;
+}
+
 export interface ReaderInterface {
   /**
    * Get the JSON representation of the manifest
@@ -354,6 +755,73 @@ export interface ReaderInterface {
    */
   isEmbedded(): boolean;
 
+  /**
+   * How the manifest store embedded in the asset compared with the manifest
+   * data given to `Reader.fromManifestDataAndAsset`, or null for Readers
+   * created any other way
+   */
+  embeddedManifestMatch(): EmbeddedManifestMatch | null;
+
+  /**
+   * Get every validation finding, for the active manifest and then for each
+   * ingredient, with a stable key and parameters for localized messages
+   * @param filter Only return failures ("errors"), failures and informational
+   * findings ("warnings"), or everything ("all", the default)
+   */
+  validationMessages(filter?: ValidationMessageFilter): ValidationMessage[];
+
+  /**
+   * Summarize who signed each manifest, when, with what tool, what was done to
+   * the asset, and which validation checks failed
+   */
+  report(): VerificationReport;
+
+  /**
+   * List every signature in the manifest store, with its validation state:
+   * each manifest's claim signature, including the endorsements added by
+   * update manifests, followed by its CAWG identity signatures
+   */
+  signatures(): SignatureSummary[];
+
+  /**
+   * Render the summary from `report` as Markdown or HTML. Values from the
+   * manifests are escaped.
+   */
+  renderReport(format: ReportFormat): string;
+
+  /**
+   * Get the manifests and ingredients in the manifest store as a graph, with
+   * an edge from each ingredient to the manifest that uses it
+   */
+  provenanceGraph(): ProvenanceGraph;
+
+  /**
+   * Render the graph from `provenanceGraph` in Graphviz DOT
+   */
+  provenanceGraphDot(): string;
+
+  /**
+   * Check the manifest store against the C2PA 2.x conformance requirements
+   * for claims, assertions, and signatures
+   */
+  conformance(): ConformanceReport;
+
+  /**
+   * Get the JSON representation of the manifest without blocking the event
+   * loop while a signing or resource task is using the reader
+   */
+  jsonAsync(): Promise<ManifestStore>;
+
+  /**
+   * Like `remoteUrl`, without blocking the event loop
+   */
+  remoteUrlAsync(): Promise<string>;
+
+  /**
+   * Like `isEmbedded`, without blocking the event loop
+   */
+  isEmbeddedAsync(): Promise<boolean>;
+
   /**
    * Write a resource to a buffer or file
    * @param uri The URI of the resource
@@ -364,8 +832,40 @@ export interface ReaderInterface {
   resourceToAsset(
     uri: string,
     output: DestinationAsset,
+    options?: OperationOptions,
   ): Promise<ResourceAsset>;
 
+  /**
+   * Read every manifest and ingredient thumbnail in one call
+   * @returns The thumbnails, keyed by manifest label for claim thumbnails and
+   *          by "<manifest label>/<ingredient label>" for ingredient thumbnails
+   */
+  extractThumbnails(
+    options?: OperationOptions,
+  ): Promise<Record<string, SourceBufferAsset>>;
+
+  /**
+   * Share the verified Reader with other worker threads. Pass the result to
+   * `Reader.fromShared` in another worker, for example with `postMessage`, to
+   * use the same native Reader without reading and verifying the asset again.
+   * The Reader stays registered until `Reader.releaseShared` is called.
+   */
+  share(): SharedReader;
+
+  /**
+   * Free the Reader's native memory, including its manifest store and
+   * resources, now rather than when it is garbage collected. Any later call
+   * on the Reader throws.
+   */
+  dispose(): void;
+
+  /**
+   * Approximate native memory held by the Reader, in bytes. This counts
+   * manifest data and binary resources, not allocator overhead, so use it to
+   * compare objects rather than as an exact figure.
+   */
+  memoryUsage(): number;
+
   /**
    * Get the internal handle for use with Neon bindings
    */
@@ -424,6 +924,43 @@ export interface TrustmarkInterface {
   decode(image: Buffer): Promise<string>;
 }
 
+/**
+ * A soft binding algorithm, such as a watermark, implemented in JS. The
+ * embedder runs when a Builder with the plugin is signed asynchronously, and
+ * the detector runs in `detectSoftBindings`.
+ */
+export interface SoftBindingPluginConfig {
+  // The algorithm's name from the C2PA soft binding algorithm list, e.g.
+  // "com.adobe.trustmark.Q"
+  alg: string;
+  // Watermark the asset, resolving to the watermarked asset and the value to
+  // record in the `c2pa.soft-binding` assertion
+  embed?: (
+    asset: Buffer,
+    mimeType: string,
+  ) => Promise<{ asset: Buffer; value: string }>;
+  // Read the watermark back, resolving to its value or null when there is none
+  detect?: (asset: Buffer, mimeType: string) => Promise<string | null>;
+}
+
+export interface SoftBindingPluginInterface {
+  readonly alg: string;
+  getHandle(): NeonSoftBindingHandle;
+}
+
+/**
+ * What a soft binding detector found in an asset, from `detectSoftBindings`.
+ */
+export interface SoftBindingHint {
+  alg: string;
+  // The detected value, or null when the detector found no watermark
+  value: string | null;
+  // Whether a soft binding assertion of the active manifest has the value, or
+  // null when the asset has no manifest. A value found without a manifest can
+  // be used to look the manifest up, such as with a manifest repository.
+  matchesManifest: boolean | null;
+}
+
 export interface TrustmarkConfig {
   variant: TrustmarkVariant;
   version: TrustmarkVersion;
@@ -441,51 +978,1186 @@ export interface TrustConfig {
   userAnchors?: string;
   /** Trust anchors for validation (PEM format or base64-encoded certificate hashes) */
   trustAnchors?: string;
-  /** Trust configuration file path */
+  /** Trust configuration: the allowed EKU OIDs, one per line */
   trustConfig?: string;
   /** Allowed list of certificates (PEM format or base64-encoded certificate hashes) */
   allowedList?: string;
 }
 
 /**
- * Configuration for verification settings in C2PA.
- * Controls various verification behaviors and options.
+ * Locations to load trust lists from. Each value is a file path, an HTTPS URL,
+ * or the list itself: a value containing a PEM header or a line break is used
+ * as it is.
  */
-export interface VerifyConfig {
-  /** Whether to verify after reading a manifest */
-  verifyAfterReading?: boolean;
-  /** Whether to verify after signing a manifest */
-  verifyAfterSign?: boolean;
-  /** Whether to verify trust during validation */
-  verifyTrust?: boolean;
-  /** Whether to verify timestamp trust */
-  verifyTimestampTrust?: boolean;
-  /** Whether to fetch OCSP responses */
-  ocspFetch?: boolean;
-  /** Whether to fetch remote manifests */
-  remoteManifestFetch?: boolean;
-  /** Whether to skip ingredient conflict resolution */
-  skipIngredientConflictResolution?: boolean;
-  /** Whether to use strict v1 validation */
-  strictV1Validation?: boolean;
+export interface TrustSources {
+  /** Trust anchors (PEM) */
+  trustAnchors?: string;
+  /** User-provided trust anchors (PEM) */
+  userAnchors?: string;
+  /** Allowed list of certificates (PEM or base64-encoded certificate hashes) */
+  allowedList?: string;
+  /** Trust configuration (EKU list) */
+  trustConfig?: string;
 }
 
 /**
- * Settings configuration object that can be passed to Reader and Builder constructors.
- * Only trust, verify, and builder settings are configurable from the Node SDK.
- * Uses snake_case internally to match the c2pa-rs settings format.
+ * Configuration for loading trust lists from files or URLs.
+ * Loaded lists apply to every Reader and Builder created afterwards.
  */
-export interface SettingsContext {
-  /** C2PA trust configuration */
-  trust?: TrustConfig;
-  /** CAWG trust configuration */
-  cawgTrust?: TrustConfig;
-  /** Verification configuration */
-  verify?: VerifyConfig;
-  /** Builder configuration */
-  builder?: {
-    thumbnail?: {
-      enabled?: boolean;
-    };
-  };
+export interface TrustSourceConfig {
+  /** C2PA trust list sources */
+  trust?: TrustSources;
+  /** CAWG trust list sources */
+  cawgTrust?: TrustSources;
+  /** Reload the sources on this interval, in seconds. No refresh when unset. */
+  refreshIntervalSecs?: number;
+  /**
+   * Keep verified downloads from HTTPS sources in this directory, and fall back
+   * to them when a download fails. No cache when unset.
+   */
+  cacheDir?: string;
+  /**
+   * Use cached downloads younger than this, in seconds, instead of downloading
+   * again. Without it, the cache is only used when a download fails.
+   */
+  cacheTtlSecs?: number;
+}
+
+/**
+ * HTTP proxy for requests made by the native layer.
+ */
+export interface ProxyConfig {
+  /** Proxy URL for `http://` requests */
+  httpProxy?: string;
+  /** Proxy URL for `https://` requests */
+  httpsProxy?: string;
+  /** Hosts to reach directly. An entry matches the host and its subdomains; `*` matches all. */
+  noProxy?: string[];
+}
+
+/**
+ * Resolves a remote manifest URL to the bytes of the manifest store, for
+ * `setRemoteManifestResolver`. Returns null to let the built-in HTTP client
+ * fetch it.
+ */
+export type RemoteManifestResolver = (
+  url: string,
+) => Promise<Uint8Array | null> | Uint8Array | null;
+
+/**
+ * Cache for responses fetched by the native layer, for `configureNetworkCache`.
+ */
+export interface NetworkCacheConfig {
+  /** Memory for cached responses, in bytes. The oldest are dropped to make room. Defaults to 64 MiB. */
+  maxMemoryBytes?: number;
+  /** Also keep responses in this directory, so they outlive the process and can be shared with other processes */
+  dir?: string;
+  /** How long remote manifests are cached for. Defaults to a day; 0 turns their caching off. */
+  remoteManifestTtlSecs?: number;
+  /** How long OCSP responses are cached for. Defaults to an hour; 0 turns their caching off. */
+  ocspTtlSecs?: number;
+  /** How long trust lists from `configureTrustSources` are cached for. Defaults to a day; 0 turns their caching off. */
+  trustTtlSecs?: number;
+}
+
+/**
+ * Timeouts and retries for requests. Unset values fall back to the ones set
+ * with `configureNetwork`, then to the HTTP client defaults.
+ */
+export interface NetworkPolicy {
+  /** How long to wait for a connection to the server */
+  connectTimeoutMs?: number;
+  /** How long to wait for each read of the response */
+  readTimeoutMs?: number;
+  /**
+   * How many more times to send a request that failed to connect, timed out,
+   * or got a 408, 429, or 5xx response. Defaults to 0
+   */
+  retries?: number;
+}
+
+/**
+ * Timeouts and retries for every kind of request, with overrides for each,
+ * for `configureNetwork` or a single operation.
+ */
+export interface NetworkOptions extends NetworkPolicy {
+  /** Fetches of remote manifests */
+  remoteManifest?: NetworkPolicy;
+  /** OCSP requests for signing certificates */
+  ocsp?: NetworkPolicy;
+  /** Requests to the time stamp authority when signing */
+  timestamp?: NetworkPolicy;
+}
+
+/** The kinds of request with their own `NetworkPolicy` */
+export type NetworkEndpoint = "remoteManifest" | "ocsp" | "timestamp";
+
+/**
+ * The request that made an operation fail, on the `network` property of its
+ * error.
+ */
+export interface NetworkFailure {
+  endpoint: NetworkEndpoint;
+  url: string;
+  /**
+   * "connect" if no connection could be made, "timeout" if the server didn't
+   * respond in time, "status" for an error response, "disallowed" for hosts
+   * outside `core.allowed_network_hosts`, and "io" for other failures
+   */
+  reason: "connect" | "timeout" | "status" | "disallowed" | "io";
+  /** The response status, for "status" failures */
+  status?: number;
+  /** How many times the request was sent */
+  attempts: number;
+  message: string;
+}
+
+/**
+ * How well the network cache is doing, from `getNetworkCacheStats`.
+ */
+export interface NetworkCacheStats {
+  enabled: boolean;
+  /** Responses held in memory */
+  entries: number;
+  memoryBytes: number;
+  /** Lookups since the process started, by kind of response */
+  resources: Record<
+    "remoteManifest" | "ocsp" | "trust",
+    { hits: number; misses: number }
+  >;
+}
+
+/**
+ * What caused the process-wide settings to change.
+ */
+export type SettingsChangeReason =
+  | "trustSourcesConfigured"
+  | "trustSourcesRefreshed"
+  | "trustSourcesCleared"
+  | "offlineModeChanged"
+  | "proxyChanged";
+
+/**
+ * Passed to `onSettingsChange` listeners. Readers, Builders, and Contexts
+ * created before the change still use the previous settings.
+ */
+export interface SettingsChangeEvent {
+  reason: SettingsChangeReason;
+}
+
+/**
+ * Common verification toggles for a single read. Unset options keep the
+ * values from the settings or Context passed with the call.
+ */
+export interface VerifyOptions {
+  /** Fetch OCSP responses for signing certificates */
+  ocsp?: boolean;
+  /** Fetch remote manifests referenced by the asset */
+  fetchRemoteManifests?: boolean;
+  /** Verify signing certificates against the trust lists */
+  verifyTrust?: boolean;
+  /** Verify timestamp certificates against the trust lists */
+  verifyTimestamps?: boolean;
+  /**
+   * Trust only these end-entity certificates, given as PEM or as SHA-256
+   * hashes of their DER encoding in hex or base64. Manifests signed by any
+   * other certificate are untrusted, whatever the trust anchors in the
+   * settings. Turns on `verifyTrust` unless it is set
+   */
+  allowedCertificates?: string[];
+  /** Timeouts and retries for the remote manifest and OCSP requests */
+  network?: NetworkOptions;
+}
+
+/**
+ * Which manifest store `Reader.fromManifestDataAndAsset` reads when the asset
+ * has one embedded that differs from the provided manifest data: the
+ * provided one, the embedded one, or neither, failing with
+ * `MANIFEST_DATA_MISMATCH`.
+ */
+export type ManifestDataPrecedence = "provided" | "embedded" | "error";
+
+/**
+ * How the manifest store embedded in an asset compares with the manifest data
+ * provided for it. An embedded store that can't be loaded is "different".
+ */
+export type EmbeddedManifestMatch = "absent" | "same" | "different";
+
+/**
+ * Options for `Reader.fromManifestDataAndAsset`.
+ */
+export interface ManifestDataOptions {
+  /** Which manifest store to read when they differ, "provided" by default */
+  precedence?: ManifestDataPrecedence;
+}
+
+/**
+ * Options for a single asynchronous operation.
+ */
+export interface OperationOptions {
+  /**
+   * Reject with a `Timeout` error if the operation has not finished after this
+   * many milliseconds, for instance when a remote manifest or timestamp server
+   * does not respond
+   */
+  timeoutMs?: number;
+  /**
+   * Scheduling lane. "interactive" operations, the default, start right away;
+   * "batch" operations take turns in a limited number of slots so a large
+   * background job cannot delay interactive ones
+   */
+  priority?: OperationPriority;
+}
+
+export type OperationPriority = "interactive" | "batch";
+
+/**
+ * Options for `Builder.signAsync`.
+ */
+export interface SignOptions extends OperationOptions {
+  /**
+   * Timeouts and retries for the request to the time stamp authority of a
+   * `CallbackSigner`
+   */
+  network?: NetworkOptions;
+}
+
+/**
+ * Configuration for verification settings in C2PA.
+ * Controls various verification behaviors and options.
+ */
+export interface VerifyConfig {
+  /** Whether to verify after reading a manifest */
+  verifyAfterReading?: boolean;
+  /** Whether to verify after signing a manifest */
+  verifyAfterSign?: boolean;
+  /** Whether to verify trust during validation */
+  verifyTrust?: boolean;
+  /** Whether to verify timestamp trust */
+  verifyTimestampTrust?: boolean;
+  /** Whether to fetch OCSP responses */
+  ocspFetch?: boolean;
+  /** Whether to fetch remote manifests */
+  remoteManifestFetch?: boolean;
+  /** Whether to skip ingredient conflict resolution */
+  skipIngredientConflictResolution?: boolean;
+  /** Whether to use strict v1 validation */
+  strictV1Validation?: boolean;
+}
+
+/**
+ * Settings configuration object that can be passed to Reader and Builder constructors.
+ * Only trust, verify, and builder settings are configurable from the Node SDK.
+ * Uses snake_case internally to match the c2pa-rs settings format.
+ */
+export interface SettingsContext {
+  /** C2PA trust configuration */
+  trust?: TrustConfig;
+  /** CAWG trust configuration */
+  cawgTrust?: TrustConfig;
+  /** Verification configuration */
+  verify?: VerifyConfig;
+  /** Builder configuration */
+  builder?: {
+    thumbnail?: {
+      enabled?: boolean;
+    };
+  };
+}
+
+/**
+ * How the native runtime schedules work: on a pool of worker threads, or on a
+ * single thread, which starts faster and uses less memory in environments such
+ * as AWS Lambda.
+ */
+export type RuntimeFlavor = "multiThread" | "currentThread";
+
+/**
+ * Configuration of the native thread pool, for `configureRuntime`.
+ */
+export interface RuntimeConfig {
+  // How work is scheduled. Defaults to "multiThread"
+  flavor?: RuntimeFlavor;
+  // Number of worker threads. Defaults to the number of CPU cores. Not
+  // allowed with the "currentThread" flavor
+  workerThreads?: number;
+  // Maximum number of threads used for blocking work
+  maxBlockingThreads?: number;
+  // Name given to the pool's threads, as shown in debuggers and profilers
+  threadName?: string;
+  // Stack size of the pool's threads, in bytes
+  threadStackSize?: number;
+  // Batch operations that may run at once. Defaults to half the worker threads
+  batchConcurrency?: number;
+}
+
+/**
+ * Counters for one kind of native operation, from `getMetrics`.
+ */
+export interface OperationMetrics {
+  started: number;
+  succeeded: number;
+  failed: number;
+  // Operations started but not yet finished
+  active: number;
+  latency: {
+    // Cumulative counts per upper bound in seconds, ending with `+Inf`
+    buckets: Array<[string, number]>;
+    count: number;
+    sumSeconds: number;
+  };
+}
+
+/**
+ * A snapshot of the native layer's counters, from `getMetrics`.
+ */
+export interface NativeMetrics {
+  // Threads, the main thread and Workers, that have loaded the addon
+  instances: number;
+  // The thread pool, or null if it has not started
+  runtime: {
+    workers: number;
+    aliveTasks: number;
+    queuedTasks: number;
+    // Batch operations waiting for a slot
+    batchWaiting: number;
+  } | null;
+  operations: {
+    read: OperationMetrics;
+    sign: OperationMetrics;
+    resource: OperationMetrics;
+  };
+}
+
+/**
+ * Approximate native memory held by live objects, from `getMemoryUsage`.
+ */
+export interface NativeMemoryUsage {
+  readers: { count: number; bytes: number };
+  builders: { count: number; bytes: number };
+}
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+/**
+ * A log record from the native layer, passed to the `setLogger` callback.
+ */
+export interface NativeLogEvent {
+  level: LogLevel;
+  // The module or crate that logged the record, e.g. "c2pa::claim"
+  target: string;
+  message: string;
+  // Where the record was logged, when known
+  fields: {
+    modulePath?: string;
+    file?: string;
+    line?: number;
+  };
+}
+
+/**
+ * A span timed by the native layer, passed to the `onSpan` callback of
+ * `configureTelemetry`. Times are milliseconds since the Unix epoch, as
+ * OpenTelemetry's `startSpan` and `span.end` accept.
+ */
+export interface NativeSpan {
+  // "c2pa.read", "c2pa.sign", "c2pa.resource", or "c2pa.http"
+  name: string;
+  kind: "internal" | "client";
+  // Hex W3C trace context IDs. The parent is the span given to
+  // `withTraceParent`, if any.
+  traceId: string;
+  spanId: string;
+  parentSpanId: string | null;
+  startTime: number;
+  endTime: number;
+  status: "ok" | "error";
+  // Following OpenTelemetry semantic conventions where one applies, e.g.
+  // "url.full" and "http.response.status_code"
+  attributes: Record<string, string>;
+}
+
+/**
+ * Where native spans are exported, for `configureTelemetry`.
+ */
+export interface TelemetryConfig {
+  /** Called with each span, asynchronously, for bridging to a JS tracer */
+  onSpan?: (span: NativeSpan) => void;
+  /** Send spans to an OpenTelemetry collector with OTLP/HTTP, as JSON, in batches */
+  otlp?: {
+    /** The collector, e.g. "http://localhost:4318"; spans go to its /v1/traces path */
+    endpoint: string;
+    /** Sent with every request, e.g. for authentication */
+    headers?: Record<string, string>;
+    /** The service.name resource attribute. Defaults to "c2pa-node". */
+    serviceName?: string;
+  };
+}
+
+/**
+ * Stable identifiers for native failures, set as `code` on errors thrown by
+ * native operations. See `C2paError`.
+ */
+export type C2paErrorCode =
+  | "ASSET_INVALID"
+  | "C2PA_ASSERTION_INVALID"
+  | "C2PA_ASSERTION_MISSING"
+  | "C2PA_CLAIM_INVALID"
+  | "C2PA_CLAIM_MISSING"
+  | "C2PA_CLAIM_VERIFICATION_FAILED"
+  | "C2PA_ERROR"
+  | "C2PA_HASH_MISMATCH"
+  | "C2PA_JUMBF_BOX_NOT_FOUND"
+  | "C2PA_JUMBF_INVALID"
+  | "C2PA_JUMBF_NOT_FOUND"
+  | "C2PA_PROVENANCE_MISSING"
+  | "C2PA_VALIDATION_FAILED"
+  | "CBOR_INVALID"
+  | "CERTIFICATE_INVALID"
+  | "CONVERSION_FAILED"
+  | "DISPOSED"
+  | "FILE_IO"
+  | "IMAGE_INVALID"
+  | "INSUFFICIENT_MEMORY"
+  | "INTERNAL"
+  | "INVALID_ARGUMENT"
+  | "JSON_INVALID"
+  | "LOCK_FAILED"
+  | "MANIFEST_DATA_MISMATCH"
+  | "MODEL_DOWNLOAD_FAILED"
+  | "NETWORK_OFFLINE"
+  | "NOT_FOUND"
+  | "READ_FAILED"
+  | "REMOTE_FETCH_FAILED"
+  | "REMOTE_MANIFEST_NOT_FETCHED"
+  | "RESOURCE_NOT_FOUND"
+  | "RUNTIME_SHUT_DOWN"
+  | "SETTINGS_INVALID"
+  | "SIDECAR_EXISTS"
+  | "SIGNATURE_INVALID"
+  | "SIGNATURE_TOO_LARGE"
+  | "SIGNING_CERT_EXPIRED"
+  | "SIGNING_CERT_INVALID"
+  | "SIGNING_CERT_REVOKED"
+  | "SIGNING_CERT_UNTRUSTED"
+  | "SIGNING_FAILED"
+  | "SIGNING_KEY_INVALID"
+  | "SOFT_BINDING_FAILED"
+  | "TIME_STAMP_FAILED"
+  | "TIMEOUT"
+  | "UNSUPPORTED"
+  | "UNSUPPORTED_FORMAT"
+  | "UTF8_INVALID"
+  | "WATERMARK_CONFIG_INVALID"
+  | "WATERMARK_FAILED";
+
+/**
+ * Whether a validation status code reports a success, is informational, or
+ * reports a failure.
+ */
+export type ValidationStatusKind = "success" | "informational" | "failure";
+
+/**
+ * The least severe validation findings `validationMessages` returns.
+ */
+export type ValidationMessageFilter = "errors" | "warnings" | "all";
+
+/**
+ * How a validation policy treats a status code: as a failure that makes the
+ * manifest store invalid, as informational, or not at all.
+ */
+export type ValidationSeverity = "error" | "warning" | "ignore";
+
+/**
+ * Severities for validation status codes, from `setValidationPolicy`, keyed
+ * by code, e.g. `{ "signingCredential.untrusted": "error" }`.
+ */
+export type ValidationPolicy = Record<string, ValidationSeverity>;
+
+/**
+ * A validation status code defined by c2pa-rs, from `getValidationStatusCodes`.
+ */
+export interface ValidationStatusCodeInfo {
+  // The code as it appears in `validation_status`, e.g. "claimSignature.validated"
+  code: string;
+  kind: ValidationStatusKind;
+}
+
+export type HashAlgorithm = "sha256" | "sha384" | "sha512";
+
+export type BmffHashVersion = 2 | 3;
+
+/**
+ * A byte range of an asset, such as where its manifest store is embedded.
+ */
+export interface HashRange {
+  start: number;
+  length: number;
+}
+
+/**
+ * Sizes and counts for a manifest store, from `getManifestStoreStats`. Sizes
+ * are in bytes and include the JUMBF box headers.
+ */
+export interface ManifestStoreStats {
+  totalBytes: number;
+  manifestCount: number;
+  // Assertions in all manifests, not counting thumbnails
+  assertionCount: number;
+  activeManifest: string | null;
+  // The longest chain of ingredient manifests below the active manifest
+  ingredientDepth: number;
+  // In the order they are stored, which ends with the active manifest
+  manifests: ManifestStats[];
+}
+
+export interface ManifestStats {
+  label: string;
+  bytes: number;
+  claimBytes: number;
+  signatureBytes: number;
+  assertionCount: number;
+  assertionBytes: number;
+  ingredientCount: number;
+  // Ingredient links from the active manifest, or null when the active
+  // manifest doesn't lead to this one
+  depth: number | null;
+  assertions: ManifestBoxStats[];
+  // Thumbnails and data boxes, such as icons
+  resources: ManifestBoxStats[];
+}
+
+export interface ManifestBoxStats {
+  label: string;
+  bytes: number;
+}
+
+/**
+ * Bytes of an asset that hold C2PA data, from `scanC2paRanges`: manifest
+ * store data, or an XMP packet that refers to a manifest store.
+ */
+export interface C2paByteRange {
+  kind: "c2pa" | "xmp";
+  start: number;
+  length: number;
+  // The structure of the format holding the data, e.g. "APP11" for a JPEG
+  // segment, "caBX" for a PNG chunk, or "uuid" for a BMFF box
+  container: string;
+  // For XMP, the manifest store URL in its dcterms:provenance
+  provenance?: string;
+}
+
+export interface C2paScanResult {
+  format: string | null;
+  // In the order they appear in the asset
+  ranges: C2paByteRange[];
+}
+
+/**
+ * A manifest store outside an asset that its XMP refers to, from
+ * `findProvenanceReference`.
+ */
+export interface ProvenanceReference {
+  // The dcterms:provenance value: an HTTP(S) URL, a file: URL, or a path,
+  // which is relative to the asset's directory
+  url: string;
+  // Whether the XMP is embedded in the asset or in an .xmp sidecar file
+  source: "embedded" | "sidecar";
+}
+
+/**
+ * The data hash assertion of a manifest, from `readDataHash`.
+ */
+export interface DataHashBinding {
+  name?: string;
+  alg: HashAlgorithm;
+  // The hash of the asset, skipping the exclusions
+  hash: Buffer;
+  // The byte ranges left out of the hash, such as the manifest store itself
+  exclusions: HashRange[];
+}
+
+/**
+ * The version and capabilities of the native binary, from `versionInfo`.
+ */
+export interface VersionInfo {
+  // The bundled c2pa-rs version, e.g. "0.78.4"
+  c2paVersion: string;
+  // The C2PA specification version c2pa-rs implements, e.g. "2.2"
+  specVersion: string;
+  claimVersions: ClaimVersion[];
+  // The c2pa-rs cargo features enabled in this build, e.g. "pdf" or
+  // "fetch_remote_manifests"
+  features: string[];
+  signingAlgorithms: SigningAlg[];
+  // MIME types that can be read, as from `Reader.supportedMimeTypes`
+  readableFormats: string[];
+  // MIME types that can be signed, as from `Builder.supportedMimeTypes`
+  signableFormats: string[];
+}
+
+/**
+ * An image decoded by `decodeThumbnail`.
+ */
+export interface DecodedImage {
+  width: number;
+  height: number;
+  // 8-bit RGBA pixels, row by row, `width * height * 4` bytes
+  data: Buffer;
+}
+
+/**
+ * The optional capabilities of the native binary, from `nativeFeatures`.
+ */
+export interface NativeFeatures {
+  // Reading manifests from PDF documents
+  pdf: boolean;
+  // Fetching remote manifests referenced by an asset
+  fetchRemoteManifests: boolean;
+  // Generating thumbnails when signing and adding ingredients
+  thumbnails: boolean;
+  // CAWG identity assertions
+  cawg: boolean;
+  // TrustMark watermarks
+  trustmark: boolean;
+}
+
+/**
+ * A resource reference that carries its bytes, for definitions passed to
+ * `Builder.withJson`. The bytes are added to the Builder's resources under
+ * `identifier`, or under the path of the reference, such as
+ * "ingredients[0].thumbnail", when it is omitted.
+ */
+export type BufferResourceRef = Omit<ResourceRef, "identifier"> & {
+  identifier?: string;
+  buffer: Uint8Array;
+};
+
+/**
+ * An ingredient whose resource references may carry their bytes.
+ */
+export type IngredientWithBuffers = Omit<
+  Ingredient,
+  "thumbnail" | "data" | "manifest_data"
+> & {
+  thumbnail?: ResourceRef | BufferResourceRef | null;
+  data?: ResourceRef | BufferResourceRef | null;
+  manifest_data?: ResourceRef | BufferResourceRef | null;
+};
+
+/**
+ * A manifest definition whose resource references may carry their bytes.
+ */
+export type ManifestDefinitionWithBuffers = Omit<
+  Manifest,
+  "thumbnail" | "ingredients"
+> & {
+  thumbnail?: ResourceRef | BufferResourceRef | null;
+  ingredients?: IngredientWithBuffers[];
+};
+
+/**
+ * A problem with a manifest definition, from `validateManifestDefinition`.
+ */
+export interface ManifestDefinitionError {
+  // Where the problem is, e.g. "assertions[0].data.actions[1].action", or ""
+  // for the definition as a whole
+  path: string;
+  message: string;
+}
+
+/**
+ * An ingredient from `createIngredient`, ready to be stored and added to a
+ * Builder later.
+ */
+export interface PreparedIngredient {
+  ingredient: Ingredient;
+  // The thumbnail and manifest data the ingredient refers to, by identifier
+  resources: Record<string, SourceBufferAsset>;
+}
+
+/**
+ * An ingredient to read from an asset, for `builder.addIngredients`.
+ */
+export interface IngredientAsset {
+  // Fields that take precedence over those read from the asset, as an object
+  // or JSON
+  ingredient: string | Partial<Ingredient>;
+  // The ingredient asset, which must have a mime type
+  asset: SourceAsset;
+}
+
+export interface AddIngredientsOptions {
+  // How many assets to read at once. Defaults to the number of CPUs.
+  concurrency?: number;
+}
+
+export interface RetimestampOptions {
+  // Timestamp only the active manifest, the default, or every manifest in
+  // the asset's manifest store
+  scope?: "active" | "all";
+}
+
+/**
+ * What a hashed URI from `builder.ingredientHashedUri` points to.
+ */
+export interface HashedUriOptions {
+  // The ingredient's label, instance ID or active manifest label
+  ingredient: string;
+  // The label of an assertion in the ingredient's manifest, to point to it
+  // rather than to the whole manifest
+  assertion?: string;
+  // Defaults to sha256
+  alg?: HashAlgorithm;
+}
+
+/**
+ * A reference to a manifest or assertion, checked against its hash.
+ */
+export interface HashedUri {
+  url: string;
+  alg: string;
+  hash: Buffer;
+}
+
+export interface CreateIngredientOptions {
+  // Fields that take precedence over those read from the asset
+  ingredient?: Partial<Ingredient>;
+}
+
+/**
+ * Cache for ingredients read from assets, for `configureIngredientCache`.
+ */
+export interface IngredientCacheConfig {
+  /** Memory for cached ingredients and their thumbnails and manifest data, in bytes. The least recently used are dropped to make room. Defaults to 64 MiB. */
+  maxMemoryBytes?: number;
+}
+
+/**
+ * How well the ingredient cache is doing, from `getIngredientCacheStats`.
+ */
+export interface IngredientCacheStats {
+  enabled: boolean;
+  /** Ingredients held in memory */
+  entries: number;
+  memoryBytes: number;
+  /** Lookups since the process started */
+  hits: number;
+  misses: number;
+}
+
+export interface HashAssetOptions {
+  // Defaults to sha256
+  alg?: HashAlgorithm;
+  exclusions?: HashRange[];
+}
+
+/**
+ * An X.509 certificate from `parseCertificates`.
+ */
+export interface CertificateInfo {
+  // The distinguished name, e.g. "C=US, O=Example, CN=Example Signer"
+  subject: string;
+  commonName: string | null;
+  issuer: string;
+  // Colon-separated hex, e.g. "70:24:e6:24"
+  serialNumber: string;
+  notBefore: Date;
+  notAfter: Date;
+  // Prefixed with their type, e.g. "DNS:example.com" or "email:signer@example.com"
+  subjectAltNames: string[];
+  // Names such as "emailProtection" for well-known usages, dotted OIDs otherwise
+  extendedKeyUsages: string[];
+  isCa: boolean;
+  selfSigned: boolean;
+  // Whether the certificate meets the C2PA profile for signing certificates
+  // today, with the reason when it does not
+  c2paSigning: {
+    suitable: boolean;
+    reason: string | null;
+  };
+}
+
+/**
+ * Where a native error happened, set as `context` on `C2paError`.
+ */
+export interface C2paErrorContext {
+  operation: string;
+  // Label of the manifest the error refers to
+  manifestLabel?: string;
+  // Label of the assertion the error refers to, e.g. "c2pa.actions.v2"
+  assertion?: string;
+  // URI of the assertion or resource the error refers to
+  uri?: string;
+}
+
+/**
+ * A validation finding from `Reader.validationMessages`. Look up `key` in an
+ * application's message catalog and fill in `params` to show it in any
+ * language; `message` is the English explanation from c2pa-rs, when it gave one.
+ */
+export interface ValidationMessage {
+  code: string;
+  kind: ValidationStatusKind;
+  // "c2pa.validation." followed by the code, e.g. "c2pa.validation.claimSignature.validated"
+  key: string;
+  params: {
+    url?: string;
+    manifestLabel?: string;
+    assertion?: string;
+    ingredientUri?: string;
+  };
+  message?: string;
+}
+
+/**
+ * A Reader shared with `Reader.share`. It is plain data, so it can be sent to
+ * another worker thread with `postMessage`.
+ */
+export interface SharedReader {
+  // The key of the Reader in the process-wide registry
+  key: string;
+  // The findings of validation rules, which ran on the sharing thread
+  verdicts: ValidationRuleVerdict[];
+  // From Reader.embeddedManifestMatch
+  embeddedManifestMatch?: EmbeddedManifestMatch | null;
+}
+
+/**
+ * A finding from a rule registered with `registerValidationRule`, merged into
+ * the validation results of the active manifest.
+ */
+export interface ValidationRuleVerdict {
+  kind: ValidationStatusKind;
+  // An application-defined code, e.g. "com.example.signer.notAllowed"
+  code: string;
+  explanation?: string;
+  // The URI of the assertion or manifest the finding is about
+  url?: string;
+}
+
+export interface ValidationRuleContext {
+  // The manifest store as validated by c2pa-rs
+  manifestStore: ManifestStore;
+  activeManifest?: Manifest;
+}
+
+/**
+ * A validation rule for `registerValidationRule`. Return nothing when the
+ * rule has no findings.
+ */
+export type ValidationRule = (
+  context: ValidationRuleContext,
+) =>
+  | ValidationRuleVerdict
+  | ValidationRuleVerdict[]
+  | null
+  | undefined
+  | Promise<ValidationRuleVerdict | ValidationRuleVerdict[] | null | undefined>;
+
+/**
+ * A summary of a manifest store from `Reader.report`.
+ */
+export interface VerificationReport {
+  validationState: "Invalid" | "Valid" | "Trusted";
+  activeManifest: string | null;
+  // The active manifest first, then the manifests of its ingredients
+  manifests: ManifestSummary[];
+  // The validation findings that report a failure
+  failures: ValidationMessage[];
+}
+
+export interface ManifestSummary {
+  label: string | null;
+  title: string | null;
+  signer: {
+    commonName: string | null;
+    issuer: string | null;
+    alg: string | null;
+    // When the manifest was signed, from its time-stamp
+    time: string | null;
+  } | null;
+  // The tools that created the manifest, from its claim generator info
+  generators: { name: string; version: string | null }[];
+  actions: {
+    action: string;
+    when: string | null;
+    softwareAgent: string | null;
+    digitalSourceType: string | null;
+    description: string | null;
+  }[];
+  ingredients: {
+    title: string | null;
+    relationship: "parentOf" | "componentOf" | "inputTo";
+    // Label of the ingredient's manifest, when it has one
+    activeManifest: string | null;
+  }[];
+  // The manifest's CAWG identity assertions
+  identities: IdentitySummary[];
+}
+
+/**
+ * A CAWG identity assertion in a `ManifestSummary`: the identities an identity
+ * claims aggregator verified, or the X.509 certificate that signed it.
+ */
+export interface IdentitySummary {
+  label: string;
+  // "cawg.identity_claims_aggregation" or "cawg.x509.cose"
+  sigType: string | null;
+  // Whether the identity signature was validated without failures
+  valid: boolean;
+  // The aggregator's DID, for an identity claims aggregation credential
+  issuer: string | null;
+  validFrom: string | null;
+  validUntil: string | null;
+  verifiedIdentities: VerifiedIdentity[];
+  // The certificate's subject, for an X.509 identity
+  signer: {
+    commonName: string | null;
+    issuer: string | null;
+    alg: string | null;
+    time: string | null;
+  } | null;
+  // The validation findings about the assertion that report a failure
+  failures: ValidationMessage[];
+}
+
+/**
+ * A signature from `Reader.signatures`: a manifest's claim signature, or the
+ * signature of one of its CAWG identity assertions.
+ */
+export interface SignatureSummary {
+  manifestLabel: string | null;
+  kind: "claim" | "identity";
+  // The identity assertion's label, for an identity signature
+  label: string | null;
+  // "cawg.identity_claims_aggregation" or "cawg.x509.cose", for an identity
+  sigType: string | null;
+  // Whether the manifest is an update manifest, such as an endorsement
+  updateManifest: boolean;
+  signer: {
+    commonName: string | null;
+    issuer: string | null;
+    alg: string | null;
+    time: string | null;
+  } | null;
+  // Invalid when a check about the signature failed, other than its
+  // credential being untrusted; Trusted when its credential is on a trust list
+  validationState: "Invalid" | "Valid" | "Trusted";
+  // The validation findings about the signature that report a failure
+  failures: ValidationMessage[];
+}
+
+/**
+ * An identity verified by an identity claims aggregator.
+ */
+export interface VerifiedIdentity {
+  // Such as "cawg.social_media", "cawg.document_verification",
+  // "cawg.affiliation", or "cawg.crypto_wallet"
+  type: string;
+  name: string | null;
+  username: string | null;
+  address: string | null;
+  uri: string | null;
+  verifiedAt: string | null;
+  provider: { id: string | null; name: string | null } | null;
+}
+
+/**
+ * The result of `verifyPresentation` for a DASH or HLS presentation.
+ */
+export interface PresentationReport {
+  kind: "dash" | "hls";
+  // The lowest validation state of any segment
+  validationState: "Invalid" | "Valid" | "Trusted";
+  representations: RepresentationReport[];
+}
+
+export interface RepresentationReport {
+  // The Representation id, prefixed by the Period id when there are several
+  // periods, or the URI of an HLS media playlist
+  id: string;
+  mimeType: string;
+  // The initialization segment, or null when segments are verified alone
+  init: string | null;
+  // The label of the active manifest of the first segment that has one
+  activeManifest: string | null;
+  validationState: "Invalid" | "Valid" | "Trusted";
+  segments: SegmentReport[];
+}
+
+export interface SegmentReport {
+  uri: string;
+  validationState: "Invalid" | "Valid" | "Trusted";
+  // The validation findings that report a failure
+  failures: ValidationMessage[];
+  // Why the segment couldn't be loaded or read
+  error?: string;
+}
+
+/**
+ * The result of validating one fragment with `Reader.fromFragments`.
+ */
+export interface FragmentReport {
+  // The position of the fragment in the list, or in the sorted glob matches
+  index: number;
+  // The matched file, when fragments are given as a glob
+  path?: string;
+  validationState: "Invalid" | "Valid" | "Trusted";
+  // The validation findings that report a failure
+  failures: ValidationMessage[];
+  // Where the fragment's leaves fit in the Merkle trees, when the
+  // initialization segment's BMFF hash has any
+  merkle?: MerkleLeafReport[];
+  // Why the fragment's Merkle map boxes couldn't be found or read
+  merkleError?: string;
+  // Why the fragment couldn't be read, e.g. when it isn't in the Merkle tree
+  error?: string;
+}
+
+/**
+ * Where a fragment's leaf fits in a Merkle tree of the BMFF hash, and the
+ * hash its proof leads to against the one the manifest stores. Hashes are
+ * hex.
+ */
+export interface MerkleLeafReport {
+  uniqueId: number;
+  localId: number;
+  // The leaf's index in the tree
+  location: number;
+  // The hash of the fragment, less exclusions
+  leafHash: string;
+  // The stored node the fragment's proof leads to
+  node?: number;
+  expected?: string;
+  computed?: string;
+  valid: boolean;
+  // Why the leaf couldn't be checked, such as a proof that is too short
+  error?: string;
+}
+
+/**
+ * The Merkle trees of an initialization segment's BMFF hash, from
+ * `Reader.fromFragments`. Hashes are hex.
+ */
+export interface MerkleTreeReport {
+  // The version of the BMFF hash assertion
+  bmffVersion: number;
+  maps: Array<{
+    uniqueId: number;
+    localId: number;
+    alg: string;
+    // The number of leaves, one per fragment
+    count: number;
+    // The number of nodes in each layer, from the leaves to the root
+    layers: number[];
+    // The layer the manifest stores hashes for, counting from the leaves
+    storedLayer?: number;
+    hashes: string[];
+    // The hash of the initialization segment, less exclusions
+    initHash?: { expected: string; computed: string; matches: boolean };
+  }>;
+}
+
+export type ReportFormat = "markdown" | "html";
+
+/**
+ * The manifests of a manifest store and the ingredients they were made from,
+ * from `Reader.provenanceGraph`.
+ */
+export interface ProvenanceGraph {
+  // The active manifest first, then the manifests of its ingredients, then
+  // ingredients without a manifest
+  nodes: ProvenanceNode[];
+  // From an ingredient to the manifest that uses it
+  edges: ProvenanceEdge[];
+}
+
+export interface ProvenanceNode {
+  // The manifest label, or "<manifest label>/ingredients/<index>" for an
+  // ingredient without a manifest
+  id: string;
+  kind: "manifest" | "ingredient";
+  title: string | null;
+  format: string | null;
+  // The signer's common name, or the issuer when it has none
+  signer: string | null;
+  active: boolean;
+}
+
+export interface ProvenanceEdge {
+  from: string;
+  to: string;
+  relationship: "parentOf" | "componentOf" | "inputTo";
+  // The ingredient's title as the manifest using it records it
+  title: string | null;
+}
+
+/**
+ * How a manifest store measures up to the C2PA 2.x conformance requirements,
+ * from `Reader.conformance`.
+ */
+export interface ConformanceReport {
+  // The worst status of any check
+  result: ConformanceStatus;
+  // The checks of the whole store, then of the active manifest and the
+  // manifests of its ingredients
+  checks: ConformanceCheck[];
+}
+
+// "fail" for a requirement that is not met, "warn" for a recommendation or a
+// deprecated feature
+export type ConformanceStatus = "pass" | "warn" | "fail";
+
+export interface ConformanceCheck {
+  // What was checked, e.g. "validation", "claim.version",
+  // "claim.generatorInfo", "assertion.actions", "assertion.deprecated",
+  // "signature.algorithm", "signature.timeStamp", or "ingredient.relationship"
+  id: string;
+  status: ConformanceStatus;
+  message: string;
+  // The label of the manifest checked, or null for the whole store
+  manifest: string | null;
+}
+
+/**
+ * A product to recognize in claim generators, for `recognizeClaimGenerator`.
+ */
+export interface ClaimGeneratorPattern {
+  // A generator name to match exactly, ignoring case, or a pattern to test it
+  // against, e.g. /^adobe[ _]?photoshop\b/i
+  match: string | RegExp;
+  // The product name to show, e.g. "Photoshop"
+  name: string;
+  vendor?: string;
+  // An icon for the product, such as a URL your UI can load
+  icon?: string;
+}
+
+export interface RecognizeClaimGeneratorOptions {
+  // Patterns tried before the built-in ones, so they can override them
+  generators?: ClaimGeneratorPattern[];
+}
+
+/**
+ * The product that signed a manifest, from `recognizeClaimGenerator`.
+ */
+export interface RecognizedClaimGenerator {
+  // The product name, or the generator name as is when not recognized
+  name: string;
+  version?: string;
+  // The name and version, e.g. "Photoshop 25.3.1"
+  displayName: string;
+  // The generator name the manifest gives, e.g. "Adobe_Photoshop"
+  generator: string;
+  // Whether a pattern matched
+  known: boolean;
+  vendor?: string;
+  // The icon of the matching pattern
+  icon?: string;
+  // The icon the manifest gives for the generator, which can be read with
+  // `Reader.resourceToAsset`
+  manifestIcon?: NonNullable<
+    NonNullable<Manifest["claim_generator_info"]>[number]["icon"]
+  >;
 }
@@ -12,33 +12,941 @@
 // each license.
 
 use crate::error::Error;
+use crate::network::RangeClient;
+use crate::sniff::sniff_mime_type;
 use c2pa::format_from_path;
+use memmap2::Mmap;
 use neon::prelude::*;
 use neon::types::buffer::TypedArray;
+use std::collections::VecDeque;
 use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Cursor, Read, Seek, Write};
-use std::path::Path;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, ThreadId};
+use tempfile::SpooledTempFile;
 
 pub(crate) trait NeonReadStreamTrait: Read + Seek + Send {}
-pub(crate) trait NeonWriteStreamTrait: Write + Read + Seek + Send {}
+pub(crate) trait NeonWriteStreamTrait: Write + Read + Seek + Send {
+    /// Called once all output has been written.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
 
 impl NeonReadStreamTrait for Cursor<Vec<u8>> {}
+impl NeonReadStreamTrait for Cursor<JsBytes> {}
 impl NeonReadStreamTrait for BufReader<File> {}
+impl NeonReadStreamTrait for Cursor<Mmap> {}
+impl NeonReadStreamTrait for JsReadableStream {}
+impl NeonReadStreamTrait for BufReader<JsIo> {}
+impl NeonReadStreamTrait for UrlStream {}
+impl<S: Read + Seek + Send> NeonReadStreamTrait for LimitedStream<S> {}
 
 impl NeonWriteStreamTrait for Cursor<Vec<u8>> {}
-impl NeonWriteStreamTrait for File {}
+impl NeonWriteStreamTrait for JsIo {}
+
+/// Size of the chunks passed to `Writable.write`.
+const WRITE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of chunks passed to `Writable.write` that may wait for their callbacks at once.
+const MAX_PENDING_WRITES: usize = 16;
+
+/// Size above which data received from or written to a JS stream is spooled to a
+/// temporary file.
+const SPOOL_MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+
+/// Size of the reads requested from the `read` callback of an IO asset.
+const IO_READ_SIZE: usize = 64 * 1024;
+
+/// Size of the range requests made for URL assets.
+const URL_BLOCK_SIZE: u64 = 256 * 1024;
+
+/// Number of blocks of a URL asset kept in memory.
+const URL_CACHED_BLOCKS: usize = 16;
+
+/// The contents of a JS `Buffer`, copied once into native memory.
+///
+/// JS may modify, transfer, or detach the buffer as soon as the call returns,
+/// so the bytes are not read in place. Clones share the copy.
+#[derive(Clone)]
+pub struct JsBytes(Arc<[u8]>);
+
+impl JsBytes {
+    pub fn new<'a, C: neon::context::Context<'a>>(cx: &mut C, buffer: Handle<JsBuffer>) -> Self {
+        Self(Arc::from(buffer.as_slice(cx)))
+    }
+}
+
+impl AsRef<[u8]> for JsBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Where a readable stream asset gets its chunks from.
+#[derive(Clone)]
+enum ChunkSource {
+    /// An async iterator, such as the one of a Node `Readable`.
+    Iterator(Arc<Root<JsObject>>),
+    /// A function returning the next chunk, or a promise of it, and `null` at the end.
+    Pull(Arc<Root<JsFunction>>),
+}
+
+/// A JS stream object passed as an asset, with what is needed to call it from other threads.
+#[derive(Clone)]
+pub struct JsStream {
+    channel: Channel,
+    stream: Arc<Root<JsObject>>,
+    // Not set for objects that are only writable.
+    source: Option<ChunkSource>,
+    js_thread: ThreadId,
+}
+
+impl JsStream {
+    fn new(cx: &mut FunctionContext, stream: Handle<JsObject>) -> NeonResult<Self> {
+        let async_iterator = cx
+            .global::<JsFunction>("Symbol")?
+            .get::<JsValue, _, _>(cx, "asyncIterator")?;
+        let source = match stream.get_opt::<JsFunction, _, _>(cx, async_iterator)? {
+            Some(iterator_fn) => {
+                let iterator = iterator_fn
+                    .call_with(cx)
+                    .this(stream)
+                    .apply::<JsObject, _>(cx)?;
+                Some(ChunkSource::Iterator(Arc::new(iterator.root(cx))))
+            }
+            None => None,
+        };
+        Ok(Self::with_source(cx, stream, source))
+    }
+
+    fn from_pull(cx: &mut FunctionContext, pull: Handle<JsFunction>) -> Self {
+        let source = Some(ChunkSource::Pull(Arc::new(pull.root(cx))));
+        let stream = pull.upcast::<JsObject>();
+        Self::with_source(cx, stream, source)
+    }
+
+    fn with_source(
+        cx: &mut FunctionContext,
+        stream: Handle<JsObject>,
+        source: Option<ChunkSource>,
+    ) -> Self {
+        let mut channel = cx.channel();
+        channel.unref(cx);
+
+        Self {
+            channel,
+            stream: Arc::new(stream.root(cx)),
+            source,
+            js_thread: thread::current().id(),
+        }
+    }
+
+    fn is_pull(&self) -> bool {
+        matches!(self.source, Some(ChunkSource::Pull(_)))
+    }
+
+    fn ensure_off_js_thread(&self) -> io::Result<()> {
+        // The JS thread would be waiting on itself.
+        if thread::current().id() == self.js_thread {
+            return Err(io::Error::other(
+                "Stream assets can only be used with asynchronous methods",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A Node `Readable`, any async iterable of buffers, or a pull callback, read on demand.
+///
+/// Chunks are pulled from JS only when c2pa-rs reads past the
+/// data received so far, so the source stream is paused in between. Received data
+/// is kept so the stream can seek backwards, in memory up to `SPOOL_MEMORY_LIMIT`
+/// and in a temporary file beyond that, so long recordings can be hashed without
+/// holding them in memory. Seeking relative to the end reads the rest of the source.
+pub struct JsReadableStream {
+    handle: JsStream,
+    source: ChunkSource,
+    data: SpooledTempFile,
+    len: u64,
+    position: u64,
+    done: bool,
+    max_len: Option<u64>,
+}
+
+impl JsReadableStream {
+    fn new(handle: JsStream) -> Result<Self, Error> {
+        let source = handle.source.clone().ok_or_else(|| {
+            Error::Asset("Source streams must be async iterable, like a Node Readable".to_string())
+        })?;
+        Ok(Self {
+            handle,
+            source,
+            data: SpooledTempFile::new(SPOOL_MEMORY_LIMIT),
+            len: 0,
+            position: 0,
+            done: false,
+            max_len: None,
+        })
+    }
+
+    /// Fail once the source produces more than `max_len` bytes, rather than
+    /// receiving all of it, for instance when seeking relative to the end.
+    fn with_limit(mut self, max_len: u64) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Wait for the next chunk from JS. Returns false at the end of the stream.
+    fn pull_chunk(&mut self) -> io::Result<bool> {
+        if self.done {
+            return Ok(false);
+        }
+        self.handle.ensure_off_js_thread()?;
+
+        let (tx, rx) = mpsc::channel::<Result<Option<Vec<u8>>, String>>();
+        let source = self.source.clone();
+        self.handle
+            .channel
+            .try_send(move |mut cx| match source {
+                ChunkSource::Iterator(iterator) => {
+                    let iterator = iterator.to_inner(&mut cx);
+                    let next = iterator
+                        .get::<JsFunction, _, _>(&mut cx, "next")?
+                        .call_with(&cx)
+                        .this(iterator)
+                        .apply::<JsPromise, _>(&mut cx)?;
+                    next.to_future(&mut cx, move |mut cx, result| {
+                        let chunk = match result {
+                            Ok(value) => {
+                                let result = value.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                                let done = result
+                                    .get_opt::<JsBoolean, _, _>(&mut cx, "done")?
+                                    .is_some_and(|done| done.value(&mut cx));
+                                let value = result.get_value(&mut cx, "value")?;
+                                if done {
+                                    Ok(None)
+                                } else {
+                                    chunk_bytes(&mut cx, value).map(Some)
+                                }
+                            }
+                            Err(err) => Err(err.to_string(&mut cx)?.value(&mut cx)),
+                        };
+                        let _ = tx.send(chunk);
+                        Ok(())
+                    })?;
+                    Ok(())
+                }
+                ChunkSource::Pull(pull) => {
+                    let value = pull
+                        .to_inner(&mut cx)
+                        .call_with(&cx)
+                        .apply::<JsValue, _>(&mut cx)?;
+                    // The callback may return a chunk directly or a promise of one.
+                    match value.downcast::<JsPromise, _>(&mut cx) {
+                        Ok(promise) => {
+                            promise.to_future(&mut cx, move |mut cx, result| {
+                                let chunk = match result {
+                                    Ok(value) => pulled_chunk(&mut cx, value),
+                                    Err(err) => Err(err.to_string(&mut cx)?.value(&mut cx)),
+                                };
+                                let _ = tx.send(chunk);
+                                Ok(())
+                            })?;
+                        }
+                        Err(_) => {
+                            let _ = tx.send(pulled_chunk(&mut cx, value));
+                        }
+                    }
+                    Ok(())
+                }
+            })
+            .map_err(io::Error::other)?;
+
+        match rx.recv() {
+            Ok(Ok(Some(chunk))) => {
+                self.data.seek(SeekFrom::End(0))?;
+                self.data.write_all(&chunk)?;
+                self.len += chunk.len() as u64;
+                Ok(true)
+            }
+            Ok(Ok(None)) => {
+                self.done = true;
+                Ok(false)
+            }
+            Ok(Err(err)) => Err(io::Error::other(err)),
+            Err(_) => Err(io::Error::other("Stream asset could not be read")),
+        }
+    }
+
+    fn fill_to(&mut self, len: u64) -> io::Result<()> {
+        while self.len < len && self.pull_chunk()? {
+            if let Some(max_len) = self.max_len.filter(|max| self.len > *max) {
+                return Err(size_limit_error(max_len));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copy a chunk received from JS.
+fn chunk_bytes<'a, C: neon::context::Context<'a>>(
+    cx: &mut C,
+    value: Handle<JsValue>,
+) -> Result<Vec<u8>, String> {
+    match value.downcast::<JsTypedArray<u8>, _>(cx) {
+        Ok(bytes) => Ok(bytes.as_slice(cx).to_vec()),
+        Err(_) => Err("Stream chunks must be Buffers or Uint8Arrays".to_string()),
+    }
+}
+
+/// A chunk returned by a pull callback, where `null` or `undefined` ends the stream.
+fn pulled_chunk<'a, C: neon::context::Context<'a>>(
+    cx: &mut C,
+    value: Handle<JsValue>,
+) -> Result<Option<Vec<u8>>, String> {
+    if value.is_a::<JsNull, _>(cx) || value.is_a::<JsUndefined, _>(cx) {
+        Ok(None)
+    } else {
+        chunk_bytes(cx, value).map(Some)
+    }
+}
+
+impl Read for JsReadableStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill_to(self.position + buf.len() as u64)?;
+        let available = self.len.saturating_sub(self.position);
+        let len = buf.len().min(available.try_into().unwrap_or(usize::MAX));
+        self.data.seek(SeekFrom::Start(self.position))?;
+        self.data.read_exact(&mut buf[..len])?;
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+impl Seek for JsReadableStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                self.fill_to(u64::MAX)?;
+                self.len.checked_add_signed(offset)
+            }
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// A Node `Writable` destination.
+///
+/// c2pa-rs may seek back over its output, so writes go to a spool that is kept
+/// in memory up to `SPOOL_MEMORY_LIMIT` and moved to a temporary file beyond that.
+/// Once the output is complete it is passed to `Writable.write` in chunks, with at
+/// most `MAX_PENDING_WRITES` chunks waiting for their write callbacks, so memory
+/// stays bounded when the destination is slow.
+pub struct JsWritableStream {
+    handle: JsStream,
+    spool: SpooledTempFile,
+}
+
+impl JsWritableStream {
+    fn new(handle: JsStream) -> Self {
+        Self {
+            handle,
+            spool: SpooledTempFile::new(SPOOL_MEMORY_LIMIT),
+        }
+    }
+
+    /// Call `method` on the writable, with an optional chunk. The result passed to
+    /// its callback is sent to `done`.
+    fn call(
+        &self,
+        method: &'static str,
+        chunk: Option<Vec<u8>>,
+        done: mpsc::Sender<Result<(), String>>,
+    ) -> io::Result<()> {
+        let stream = Arc::clone(&self.handle.stream);
+        self.handle
+            .channel
+            .try_send(move |mut cx| {
+                let stream = stream.to_inner(&mut cx);
+                let method = stream.get::<JsFunction, _, _>(&mut cx, method)?;
+                let done = Mutex::new(Some(done));
+                let callback = JsFunction::new(&mut cx, move |mut cx| {
+                    let result = match cx.argument_opt(0) {
+                        Some(err)
+                            if !err.is_a::<JsNull, _>(&mut cx)
+                                && !err.is_a::<JsUndefined, _>(&mut cx) =>
+                        {
+                            Err(err.to_string(&mut cx)?.value(&mut cx))
+                        }
+                        _ => Ok(()),
+                    };
+                    if let Some(done) = done.lock().ok().and_then(|mut done| done.take()) {
+                        let _ = done.send(result);
+                    }
+                    Ok(cx.undefined())
+                })?;
+
+                let mut call = method.call_with(&cx);
+                call.this(stream);
+                if let Some(chunk) = chunk {
+                    call.arg(JsBuffer::from_slice(&mut cx, &chunk)?);
+                }
+                call.arg(callback).exec(&mut cx)
+            })
+            .map(|_| ())
+            .map_err(io::Error::other)
+    }
+}
+
+/// Wait for the callback of a call made with `JsWritableStream::call`.
+fn wait_for_callback(done: &mpsc::Receiver<Result<(), String>>) -> io::Result<()> {
+    match done.recv() {
+        Ok(result) => result.map_err(io::Error::other),
+        Err(_) => Err(io::Error::other("Stream asset could not be written")),
+    }
+}
+
+impl Read for JsWritableStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.spool.read(buf)
+    }
+}
+
+impl Write for JsWritableStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.spool.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for JsWritableStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.spool.seek(pos)
+    }
+}
+
+impl NeonWriteStreamTrait for JsWritableStream {
+    fn finish(&mut self) -> io::Result<()> {
+        self.handle.ensure_off_js_thread()?;
+        self.spool.seek(SeekFrom::Start(0))?;
+
+        let (tx, rx) = mpsc::channel();
+        let mut pending = 0;
+        loop {
+            let mut chunk = Vec::with_capacity(WRITE_CHUNK_SIZE);
+            (&mut self.spool)
+                .take(WRITE_CHUNK_SIZE as u64)
+                .read_to_end(&mut chunk)?;
+            if chunk.is_empty() {
+                break;
+            }
+            if pending == MAX_PENDING_WRITES {
+                wait_for_callback(&rx)?;
+                pending -= 1;
+            }
+            self.call("write", Some(chunk), tx.clone())?;
+            pending += 1;
+        }
+        for _ in 0..pending {
+            wait_for_callback(&rx)?;
+        }
+
+        self.call("end", None, tx)?;
+        wait_for_callback(&rx)
+    }
+}
+
+/// An argument passed to a callback of an IO asset.
+enum IoArg {
+    Number(f64),
+    String(&'static str),
+    Bytes(Vec<u8>),
+}
+
+/// An object whose `read`, `seek`, and `write` callbacks implement the stream,
+/// for storage backends such as encrypted blobs or chunk stores.
+///
+/// Each call c2pa-rs makes is forwarded to the JS thread, and waits for the
+/// callback's result or the promise of it, so no data is staged in between.
+/// Reads of sources are buffered in `IO_READ_SIZE` blocks.
+#[derive(Clone)]
+pub struct JsIo {
+    handle: JsStream,
+    writable: bool,
+}
+
+impl JsIo {
+    fn new(cx: &mut FunctionContext, io: Handle<JsObject>) -> NeonResult<Self> {
+        for method in ["read", "seek"] {
+            if io.get_opt::<JsFunction, _, _>(cx, method)?.is_none() {
+                return cx.throw_type_error(format!("IO assets must have a {method} function"));
+            }
+        }
+        let writable = io.get_opt::<JsFunction, _, _>(cx, "write")?.is_some();
+        Ok(Self {
+            handle: JsStream::with_source(cx, io, None),
+            writable,
+        })
+    }
+
+    /// Call `method` with `args` and wait for its result, or the promise of it,
+    /// converted by `convert`.
+    fn call<T: Send + 'static>(
+        &self,
+        method: &'static str,
+        args: Vec<IoArg>,
+        convert: fn(&mut TaskContext, Handle<JsValue>) -> Result<T, String>,
+    ) -> io::Result<T> {
+        self.handle.ensure_off_js_thread()?;
+
+        let (tx, rx) = mpsc::channel::<Result<T, String>>();
+        let io = Arc::clone(&self.handle.stream);
+        self.handle
+            .channel
+            .try_send(move |mut cx| {
+                let called = cx.try_catch(|cx| {
+                    let io = io.to_inner(cx);
+                    let mut call = io.get::<JsFunction, _, _>(cx, method)?.call_with(cx);
+                    call.this(io);
+                    for arg in args {
+                        match arg {
+                            IoArg::Number(value) => call.arg(cx.number(value)),
+                            IoArg::String(value) => call.arg(cx.string(value)),
+                            IoArg::Bytes(value) => call.arg(JsBuffer::from_slice(cx, &value)?),
+                        };
+                    }
+                    call.apply::<JsValue, _>(cx)
+                });
+                let value = match called {
+                    Ok(value) => value,
+                    Err(err) => {
+                        let _ = tx.send(Err(err.to_string(&mut cx)?.value(&mut cx)));
+                        return Ok(());
+                    }
+                };
+                match value.downcast::<JsPromise, _>(&mut cx) {
+                    Ok(promise) => {
+                        promise.to_future(&mut cx, move |mut cx, result| {
+                            let result = match result {
+                                Ok(value) => convert(&mut cx, value),
+                                Err(err) => Err(err.to_string(&mut cx)?.value(&mut cx)),
+                            };
+                            let _ = tx.send(result);
+                            Ok(())
+                        })?;
+                    }
+                    Err(_) => {
+                        let _ = tx.send(convert(&mut cx, value));
+                    }
+                }
+                Ok(())
+            })
+            .map_err(io::Error::other)?;
+
+        match rx.recv() {
+            Ok(result) => result.map_err(io::Error::other),
+            Err(_) => Err(io::Error::other(format!(
+                "IO asset {method} could not be called"
+            ))),
+        }
+    }
+}
+
+impl Read for JsIo {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let chunk = self.call(
+            "read",
+            vec![IoArg::Number(buf.len() as f64)],
+            |cx, value| pulled_chunk(cx, value).map(Option::unwrap_or_default),
+        )?;
+        if chunk.len() > buf.len() {
+            return Err(io::Error::other(format!(
+                "read returned {} bytes when {} were requested",
+                chunk.len(),
+                buf.len()
+            )));
+        }
+        buf[..chunk.len()].copy_from_slice(&chunk);
+        Ok(chunk.len())
+    }
+}
+
+impl Write for JsIo {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.call("write", vec![IoArg::Bytes(buf.to_vec())], |_, _| Ok(()))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for JsIo {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as f64, "start"),
+            SeekFrom::Current(offset) => (offset as f64, "current"),
+            SeekFrom::End(offset) => (offset as f64, "end"),
+        };
+        self.call(
+            "seek",
+            vec![IoArg::Number(offset), IoArg::String(whence)],
+            |cx, value| match value.downcast::<JsNumber, _>(cx) {
+                Ok(position) if position.value(cx) >= 0.0 => Ok(position.value(cx) as u64),
+                _ => Err("seek must return the new position".to_string()),
+            },
+        )
+    }
+}
+
+/// An HTTP(S) resource read with range requests.
+///
+/// Only the blocks c2pa-rs reads are downloaded, so verifying an asset whose
+/// manifest can be located from its headers does not fetch the whole file.
+/// Recently read blocks are cached so nearby reads and short seeks stay local.
+pub struct UrlStream {
+    client: RangeClient,
+    position: u64,
+    len: Option<u64>,
+    blocks: VecDeque<(u64, Vec<u8>)>,
+}
+
+impl UrlStream {
+    fn new(url: &str) -> Result<Self, Error> {
+        Ok(Self {
+            client: RangeClient::new(url)?,
+            position: 0,
+            len: None,
+            blocks: VecDeque::new(),
+        })
+    }
+
+    /// Fail once the resource turns out to be larger than `max_len` bytes.
+    fn with_limit(mut self, max_len: u64) -> Self {
+        self.client = self.client.with_limit(max_len);
+        self
+    }
+
+    /// Index of the cached block containing `position`, fetching it if needed.
+    /// Returns `None` at the end of the resource.
+    fn block_at(&mut self, position: u64) -> io::Result<Option<usize>> {
+        let cached = self
+            .blocks
+            .iter()
+            .position(|(start, data)| (*start..*start + data.len() as u64).contains(&position));
+        if cached.is_some() {
+            return Ok(cached);
+        }
+        if self.len.is_some_and(|len| position >= len) {
+            return Ok(None);
+        }
+
+        let start = position - position % URL_BLOCK_SIZE;
+        let response = self
+            .client
+            .fetch(start, URL_BLOCK_SIZE)
+            .map_err(|err| match err {
+                Error::FileIO(err) => err,
+                err => io::Error::other(err),
+            })?;
+        if response.total.is_some() {
+            self.len = response.total;
+        }
+        let end = response.start + response.data.len() as u64;
+        if !(response.start..end).contains(&position) {
+            return Ok(None);
+        }
+        if self.blocks.len() == URL_CACHED_BLOCKS {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((response.start, response.data));
+        Ok(Some(self.blocks.len() - 1))
+    }
+}
+
+impl Read for UrlStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(index) = self.block_at(self.position)? else {
+            return Ok(0);
+        };
+        let (start, data) = &self.blocks[index];
+        let offset = (self.position - start) as usize;
+        let count = buf.len().min(data.len() - offset);
+        buf[..count].copy_from_slice(&data[offset..offset + count]);
+        self.position += count as u64;
+        Ok(count)
+    }
+}
+
+impl Seek for UrlStream {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                if self.len.is_none() {
+                    self.block_at(0)?;
+                }
+                let len = self.len.ok_or_else(|| {
+                    io::Error::other("The server did not report the size of the asset")
+                })?;
+                len.checked_add_signed(offset)
+            }
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+pub(crate) fn size_limit_error(max_len: u64) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::FileTooLarge,
+        format!("Asset exceeds the maximum size of {max_len} bytes"),
+    )
+}
+
+/// A stream that fails when more than `max_len` bytes are read from or written to it.
+pub struct LimitedStream<S> {
+    inner: S,
+    max_len: u64,
+    position: u64,
+}
+
+impl<S> LimitedStream<S> {
+    fn new(inner: S, max_len: u64) -> Self {
+        Self {
+            inner,
+            max_len,
+            position: 0,
+        }
+    }
+}
+
+impl<S: Read> Read for LimitedStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.position += count as u64;
+        if self.position > self.max_len {
+            return Err(size_limit_error(self.max_len));
+        }
+        Ok(count)
+    }
+}
+
+impl<S: Write> Write for LimitedStream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.position + buf.len() as u64 > self.max_len {
+            return Err(size_limit_error(self.max_len));
+        }
+        let count = self.inner.write(buf)?;
+        self.position += count as u64;
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: Seek> Seek for LimitedStream<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        // Seeking to the end reveals the size, so oversized sources fail early.
+        if matches!(pos, SeekFrom::End(_)) && self.position > self.max_len {
+            return Err(size_limit_error(self.max_len));
+        }
+        Ok(self.position)
+    }
+}
+
+impl NeonWriteStreamTrait for LimitedStream<Box<dyn NeonWriteStreamTrait>> {
+    fn finish(&mut self) -> io::Result<()> {
+        self.inner.finish()
+    }
+}
+
+/// A file destination opened natively. Output goes to a temporary file next to
+/// `path` that replaces it in `finish`, so a failed operation leaves no partial
+/// file behind and the destination can be the same file as the source.
+pub struct FileDestination {
+    file: File,
+    path: PathBuf,
+    temp_path: PathBuf,
+    finished: bool,
+}
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl FileDestination {
+    fn create(path: &Path) -> io::Result<Self> {
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid file path"))?;
+        let temp_path = path.with_file_name(format!(
+            ".{}.{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)?;
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+            temp_path,
+            finished: false,
+        })
+    }
+}
+
+impl Read for FileDestination {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Write for FileDestination {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Seek for FileDestination {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
+
+impl NeonWriteStreamTrait for FileDestination {
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.finished {
+            // Keep the mode of a file signed in place instead of the temporary file's.
+            if let Ok(metadata) = std::fs::metadata(&self.path) {
+                self.file.set_permissions(metadata.permissions())?;
+            }
+            self.file.sync_all()?;
+            std::fs::rename(&self.temp_path, &self.path)?;
+            self.finished = true;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FileDestination {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// A file descriptor destination. Output is spooled and only copied into the
+/// file in `finish`, so a failed operation leaves the file as it was.
+pub struct DescriptorDestination {
+    file: File,
+    spool: SpooledTempFile,
+}
+
+impl DescriptorDestination {
+    fn new(file: File) -> Self {
+        Self {
+            file,
+            spool: SpooledTempFile::new(SPOOL_MEMORY_LIMIT),
+        }
+    }
+}
+
+impl Read for DescriptorDestination {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.spool.read(buf)
+    }
+}
+
+impl Write for DescriptorDestination {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.spool.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.spool.flush()
+    }
+}
+
+impl Seek for DescriptorDestination {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.spool.seek(pos)
+    }
+}
+
+impl NeonWriteStreamTrait for DescriptorDestination {
+    fn finish(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.rewind()?;
+        self.spool.rewind()?;
+        io::copy(&mut self.spool, &mut self.file)?;
+        self.file.sync_all()
+    }
+}
 
 pub enum Asset {
-    SourceBuffer(Vec<u8>, String),
+    SourceBuffer(JsBytes, Option<String>),
     File(String, Option<String>),
+    MappedFile(String, Option<String>),
+    Stream(JsStream, Option<String>),
+    /// An object implementing the stream with `read`, `seek`, and `write` callbacks.
+    Io(JsIo, Option<String>),
+    Url(String, Option<String>),
+    /// A duplicate of a file descriptor opened by Node.
+    Descriptor(File, Option<String>),
+    /// An asset with a `maxBytes` limit.
+    Limited(Box<Asset>, u64),
     DestinationBuffer(Vec<u8>),
 }
 
 impl Asset {
     pub fn mime_type(&self) -> Option<String> {
         match self {
-            Asset::SourceBuffer(_, mime_type) => Some(mime_type.to_string()),
-            Asset::File(path, mime_type) => match mime_type {
+            Asset::SourceBuffer(_, mime_type)
+            | Asset::Stream(_, mime_type)
+            | Asset::Io(_, mime_type)
+            | Asset::Descriptor(_, mime_type) => mime_type.clone(),
+            Asset::Limited(asset, _) => asset.mime_type(),
+            Asset::Url(url, mime_type) => mime_type.clone().or_else(|| {
+                url.parse::<c2pa::http::http::Uri>()
+                    .ok()
+                    .and_then(|uri| format_from_path(Path::new(uri.path())))
+            }),
+            Asset::File(path, mime_type) | Asset::MappedFile(path, mime_type) => match mime_type {
                 Some(mime_type) => Some(mime_type.to_string()),
                 None => format_from_path(Path::new(&path)),
             },
@@ -52,63 +960,248 @@ impl Asset {
                 let file = File::open(Path::new(&path)).map_err(Error::from)?;
                 Ok(Box::new(BufReader::new(file)))
             }
+            Asset::MappedFile(path, _) => {
+                let file = File::open(Path::new(&path)).map_err(Error::from)?;
+                // SAFETY: the mapping is only read. The caller opted in to mapping and
+                // must not modify or truncate the file while it is being read.
+                let mmap = unsafe { Mmap::map(&file) }.map_err(Error::from)?;
+                Ok(Box::new(Cursor::new(mmap)))
+            }
+            Asset::Stream(handle, _) => Ok(Box::new(JsReadableStream::new(handle)?)),
+            Asset::Io(io, _) => Ok(Box::new(BufReader::with_capacity(IO_READ_SIZE, io))),
+            Asset::Url(url, _) => Ok(Box::new(UrlStream::new(&url)?)),
+            Asset::Descriptor(mut file, _) => {
+                file.rewind().map_err(Error::from)?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Asset::Limited(asset, max_len) => match *asset {
+                Asset::Stream(handle, _) => Ok(Box::new(LimitedStream::new(
+                    JsReadableStream::new(handle)?.with_limit(max_len),
+                    max_len,
+                ))),
+                Asset::Url(url, _) => Ok(Box::new(LimitedStream::new(
+                    UrlStream::new(&url)?.with_limit(max_len),
+                    max_len,
+                ))),
+                asset => Ok(Box::new(LimitedStream::new(
+                    asset.into_read_stream()?,
+                    max_len,
+                ))),
+            },
             _ => Err(Error::Asset("Cannot write to source buffer".to_string())),
         }
     }
 
+    /// Open the asset for reading, along with its MIME type. When no MIME type was
+    /// given or inferred from the path, it is detected from the content.
+    pub fn into_typed_read_stream(
+        self,
+    ) -> Result<(Option<String>, Box<dyn NeonReadStreamTrait>), Error> {
+        let mime_type = self.mime_type();
+        let mut stream = self.into_read_stream()?;
+        let mime_type = match mime_type {
+            Some(mime_type) => Some(handler_mime_type(mime_type)),
+            // Streams that cannot be read yet, like JS streams on the JS thread,
+            // are left undetected.
+            None => sniff_mime_type(&mut stream)
+                .ok()
+                .flatten()
+                .map(str::to_string),
+        };
+        Ok((mime_type, stream))
+    }
+
     pub fn write_stream(&self) -> Result<Box<dyn NeonWriteStreamTrait>, Error> {
         match self {
             Asset::File(path, _) => {
-                let file = OpenOptions::new()
-                    .read(true)
-                    .write(true)
-                    .create(true)
-                    .truncate(true)
-                    .open(Path::new(&path))
-                    .map_err(Error::from)?;
+                let file = FileDestination::create(Path::new(&path)).map_err(Error::from)?;
                 Ok(Box::new(file))
             }
             Asset::DestinationBuffer(buffer) => Ok(Box::new(Cursor::new(buffer.to_owned()))),
+            Asset::Stream(handle, _) if handle.is_pull() => Err(Error::Asset(
+                "Pull assets can only be used as sources".to_string(),
+            )),
+            Asset::Stream(handle, _) => Ok(Box::new(JsWritableStream::new(handle.clone()))),
+            Asset::Io(io, _) if io.writable => Ok(Box::new(io.clone())),
+            Asset::Io(_, _) => Err(Error::Asset(
+                "IO assets without a write function can only be used as sources".to_string(),
+            )),
+            Asset::MappedFile(_, _) => Err(Error::Asset(
+                "Memory-mapped assets are read-only".to_string(),
+            )),
+            Asset::Url(_, _) => Err(Error::Asset("URL assets are read-only".to_string())),
+            Asset::Descriptor(file, _) => {
+                let file = file.try_clone().map_err(Error::from)?;
+                Ok(Box::new(DescriptorDestination::new(file)))
+            }
+            Asset::Limited(asset, max_len) => Ok(Box::new(LimitedStream::new(
+                asset.write_stream()?,
+                *max_len,
+            ))),
             _ => Err(Error::Asset("Cannot write to source buffer".to_string())),
         }
     }
 
+    /// Whether reading the asset calls back into JS, which rules out
+    /// synchronous methods that block the JS thread.
+    pub fn is_js_backed(&self) -> bool {
+        match self {
+            Asset::Stream(_, _) | Asset::Io(_, _) => true,
+            Asset::Limited(asset, _) => asset.is_js_backed(),
+            _ => false,
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             Asset::File(_, _) => "file",
+            Asset::MappedFile(_, _) => "mapped_file",
             Asset::DestinationBuffer(_) => "destination_buffer",
             Asset::SourceBuffer(_, _) => "source_buffer",
+            Asset::Stream(_, _) => "stream",
+            Asset::Io(_, _) => "io",
+            Asset::Url(_, _) => "url",
+            Asset::Descriptor(_, _) => "descriptor",
+            Asset::Limited(asset, _) => asset.name(),
+        }
+    }
+
+    /// The path of a file asset.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Asset::File(path, _) | Asset::MappedFile(path, _) => Some(Path::new(path)),
+            Asset::Limited(asset, _) => asset.path(),
+            _ => None,
         }
     }
 }
 
 pub fn parse_asset(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Asset> {
+    let asset = parse_asset_kind(cx, obj)?;
+    match obj.get_opt::<JsNumber, _, _>(cx, "maxBytes")? {
+        Some(max_bytes) => {
+            let max_bytes = max_bytes.value(cx);
+            if !(max_bytes >= 0.0 && max_bytes.fract() == 0.0) {
+                return cx.throw_range_error("maxBytes must be a non-negative integer");
+            }
+            Ok(Asset::Limited(Box::new(asset), max_bytes as u64))
+        }
+        None => Ok(asset),
+    }
+}
+
+/// Duplicate a file descriptor opened by Node, so the asset keeps working even
+/// if the caller closes theirs. The duplicate shares the file offset.
+#[cfg(unix)]
+fn duplicate_fd(fd: f64) -> io::Result<File> {
+    use std::os::fd::BorrowedFd;
+
+    if !(fd >= 0.0 && fd.fract() == 0.0 && fd <= i32::MAX as f64) {
+        return Err(io::Error::from(io::ErrorKind::InvalidInput));
+    }
+    // SAFETY: the descriptor is only borrowed for the duration of the call to
+    // duplicate it, and `dup` fails cleanly if it is not open.
+    let fd = unsafe { BorrowedFd::borrow_raw(fd as i32) };
+    Ok(File::from(fd.try_clone_to_owned()?))
+}
+
+#[cfg(not(unix))]
+fn duplicate_fd(_fd: f64) -> io::Result<File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "File descriptor assets are only supported on Unix platforms",
+    ))
+}
+
+/// The MIME type c2pa-rs handles a format under. Image sequences and bursts, and
+/// their file extensions, use the same container and handler as single images.
+fn handler_mime_type(mime_type: String) -> String {
+    match mime_type.to_ascii_lowercase().as_str() {
+        "heics" | "image/heic-sequence" => "image/heic".to_string(),
+        "heifs" | "image/heif-sequence" => "image/heif".to_string(),
+        "avifs" | "image/avif-sequence" => "image/avif".to_string(),
+        _ => mime_type,
+    }
+}
+
+/// A new path in the system temporary directory for a `tempFile` destination.
+fn temp_file_path(extension: Option<&str>) -> PathBuf {
+    let mut name = format!(
+        "c2pa-{}-{}-{:016x}",
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        rand::random::<u64>()
+    );
+    if let Some(extension) = extension.map(|ext| ext.trim_start_matches('.')) {
+        name.push('.');
+        name.push_str(extension);
+    }
+    std::env::temp_dir().join(name)
+}
+
+fn parse_asset_kind(cx: &mut FunctionContext, obj: Handle<JsObject>) -> NeonResult<Asset> {
     let mime_type = obj
         .get_opt::<JsString, _, _>(cx, "mimeType")?
         .map(|val| val.value(cx))
         .or(None);
+    let temp_file = obj
+        .get_opt::<JsBoolean, _, _>(cx, "tempFile")?
+        .is_some_and(|val| val.value(cx));
+    if temp_file {
+        // The path is reported on the asset object. Output is only moved there
+        // once the operation succeeds, so a failure leaves nothing to clean up.
+        let extension = obj
+            .get_opt::<JsString, _, _>(cx, "extension")?
+            .map(|val| val.value(cx));
+        let path = temp_file_path(extension.as_deref())
+            .to_string_lossy()
+            .into_owned();
+        let path_value = cx.string(&path);
+        obj.set(cx, "path", path_value)?;
+        return Ok(Asset::File(path, mime_type));
+    }
     let path = obj
         .get_opt::<JsString, _, _>(cx, "path")?
         .map(|val| val.value(cx))
         .or(None);
+    if let Some(stream) = obj.get_opt::<JsObject, _, _>(cx, "stream")? {
+        return Ok(Asset::Stream(JsStream::new(cx, stream)?, mime_type));
+    }
+    if let Some(pull) = obj.get_opt::<JsFunction, _, _>(cx, "pull")? {
+        return Ok(Asset::Stream(JsStream::from_pull(cx, pull), mime_type));
+    }
+    if let Some(io) = obj.get_opt::<JsObject, _, _>(cx, "io")? {
+        return Ok(Asset::Io(JsIo::new(cx, io)?, mime_type));
+    }
+    if let Some(fd) = obj.get_opt::<JsNumber, _, _>(cx, "fd")? {
+        let fd = fd.value(cx);
+        return match duplicate_fd(fd) {
+            Ok(file) => Ok(Asset::Descriptor(file, mime_type)),
+            Err(err) => cx.throw_error(format!("Invalid file descriptor {fd}: {err}")),
+        };
+    }
+    if let Some(url) = obj.get_opt::<JsString, _, _>(cx, "url")? {
+        return Ok(Asset::Url(url.value(cx), mime_type));
+    }
     let buffer_value = obj.get::<JsValue, _, _>(cx, "buffer")?;
-    let buffer = if buffer_value.is_a::<JsBuffer, _>(cx) {
-        Some(
-            buffer_value
-                .downcast_or_throw::<JsBuffer, _>(cx)?
-                .as_slice(cx)
-                .to_vec(),
-        )
-    } else {
-        None
+    let buffer = match buffer_value.downcast::<JsBuffer, _>(cx) {
+        Ok(buffer) => Some(JsBytes::new(cx, buffer)),
+        Err(_) => None,
     };
 
+    let mmap = obj
+        .get_opt::<JsBoolean, _, _>(cx, "mmap")?
+        .is_some_and(|val| val.value(cx));
+    if mmap {
+        return match path {
+            Some(path) => Ok(Asset::MappedFile(path, mime_type)),
+            None => cx.throw_error("Memory-mapped assets must have a path"),
+        };
+    }
+
     match (buffer, mime_type.clone(), path) {
-        (Some(buffer), Some(mime_type), _) => Ok(Asset::SourceBuffer(buffer, mime_type)),
+        (Some(buffer), mime_type, _) => Ok(Asset::SourceBuffer(buffer, mime_type)),
         (None, _, Some(path)) => Ok(Asset::File(path, mime_type)),
         (None, _, None) => Ok(Asset::DestinationBuffer(Vec::new())),
-        _ => cx.throw_error(
-            "Invalid asset data passed. Must contain either a buffer and mimeType or a path.",
-        ),
     }
 }
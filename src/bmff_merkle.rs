@@ -0,0 +1,577 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! The Merkle trees of a fragmented BMFF asset's hash, and where each
+//! fragment's leaf fits in them. c2pa-rs only says whether a fragment is
+//! valid; this replays its checks to show which hash didn't match.
+
+use std::io::Cursor;
+
+use c2pa::assertions::labels::{BMFF_HASH, CLAIM};
+use c2pa::assertions::{BmffHash, BmffMerkleMap, ExclusionsMap, MerkleMap, VecByteBuf};
+use c2pa::jumbf_io::load_jumbf_from_memory;
+use c2pa::{hash_stream_by_alg, HashRange};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::jumbf::{jumbf_boxes, superboxes};
+use crate::scan::C2PA_UUID;
+
+/// Boxes whose children are walked, as c2pa-rs walks them.
+const CONTAINER_TYPES: &[&[u8; 4]] = &[
+    b"moov", b"trak", b"mdia", b"minf", b"stbl", b"moof", b"traf", b"edts", b"udta", b"dinf",
+    b"tref", b"treg", b"mvex", b"mfra", b"meta", b"schi",
+];
+
+/// Boxes with a version and flags after their header, which exclusions can
+/// match on.
+const FULL_BOX_TYPES: &[&[u8; 4]] = &[
+    b"pdin", b"mvhd", b"tkhd", b"mdhd", b"hdlr", b"nmhd", b"elng", b"stsd", b"stdp", b"stts",
+    b"ctts", b"cslg", b"stss", b"stsh", b"elst", b"dref", b"stsz", b"stz2", b"stsc", b"stco",
+    b"co64", b"padb", b"subs", b"saiz", b"saio", b"mehd", b"trex", b"mfhd", b"tfhd", b"trun",
+    b"tfra", b"mfro", b"tfdt", b"leva", b"trep", b"assp", b"sbgp", b"sgpd", b"csgp", b"cprt",
+    b"tsel", b"kind", b"meta", b"xml ", b"bxml", b"iloc", b"pitm", b"ipro", b"infe", b"iinf",
+    b"iref", b"ipma", b"schm", b"fiin", b"fpar", b"fecr", b"gitn", b"fire", b"stri", b"stsg",
+    b"stvi", b"csch", b"sidx", b"ssix", b"prft", b"srpp", b"vmhd", b"smhd", b"srat", b"chnl",
+    b"dmix", b"txtC", b"mime", b"uri ", b"uriI", b"hmhd", b"sthd", b"vvhd", b"medc",
+];
+
+const MAX_BOX_DEPTH: usize = 32;
+
+/// A box in a BMFF asset, at a path like `/moof/traf/tfhd`.
+struct BmffBox {
+    path: String,
+    offset: usize,
+    size: usize,
+    /// Where the box's contents start, after its header and any user type.
+    content: usize,
+    user_type: Option<[u8; 16]>,
+    version: Option<u8>,
+    flags: Option<u32>,
+}
+
+fn malformed(message: &str) -> Error {
+    Error::Reading(format!("The BMFF asset is malformed: {message}"))
+}
+
+/// A FullBox version and flags at `at`.
+fn full_box_header(data: &[u8], at: usize) -> Result<(u8, u32)> {
+    let bytes = data
+        .get(at..at + 4)
+        .ok_or_else(|| malformed("a box header is truncated"))?;
+    Ok((
+        bytes[0],
+        u32::from_be_bytes([0, bytes[1], bytes[2], bytes[3]]),
+    ))
+}
+
+fn walk_boxes(
+    data: &[u8],
+    (start, end): (usize, usize),
+    parent: &str,
+    depth: usize,
+    boxes: &mut Vec<BmffBox>,
+) -> Result<()> {
+    if depth > MAX_BOX_DEPTH {
+        return Err(malformed("boxes are too deeply nested"));
+    }
+    let mut offset = start;
+    while offset < end {
+        let header = data
+            .get(offset..offset + 8)
+            .ok_or_else(|| malformed("a box header is truncated"))?;
+        let kind: [u8; 4] = header[4..8].try_into().unwrap_or_default();
+        let (mut content, size) =
+            match u32::from_be_bytes(header[..4].try_into().unwrap_or_default()) {
+                0 => break,
+                1 => {
+                    let size = data
+                        .get(offset + 8..offset + 16)
+                        .ok_or_else(|| malformed("a box header is truncated"))?;
+                    (
+                        offset + 16,
+                        u64::from_be_bytes(size.try_into().unwrap_or_default()),
+                    )
+                }
+                size => (offset + 8, size as u64),
+            };
+        let size = usize::try_from(size).map_err(|_| malformed("a box is too large"))?;
+        if size < content - offset || offset + size > end {
+            return Err(malformed("a box extends beyond its parent"));
+        }
+        let path = format!("{parent}/{}", String::from_utf8_lossy(&kind));
+        let mut bmff_box = BmffBox {
+            path,
+            offset,
+            size,
+            content,
+            user_type: None,
+            version: None,
+            flags: None,
+        };
+        if &kind == b"uuid" {
+            let user_type: [u8; 16] = data
+                .get(content..content + 16)
+                .and_then(|user_type| user_type.try_into().ok())
+                .ok_or_else(|| malformed("a uuid box is truncated"))?;
+            content += 16;
+            // A C2PA box is a FullBox.
+            if user_type == C2PA_UUID {
+                let (version, flags) = full_box_header(data, content)?;
+                bmff_box.version = Some(version);
+                bmff_box.flags = Some(flags);
+            }
+            bmff_box.user_type = Some(user_type);
+            bmff_box.content = content;
+        } else if FULL_BOX_TYPES.contains(&&kind) {
+            // A QuickTime `meta` box has no version and flags, and starts
+            // right away with its `hdlr` box.
+            let quicktime_meta =
+                &kind == b"meta" && data.get(content + 4..content + 8) == Some(b"hdlr".as_slice());
+            if !quicktime_meta {
+                let (version, flags) = full_box_header(data, content)?;
+                bmff_box.version = Some(version);
+                bmff_box.flags = Some(flags);
+                content += 4;
+            }
+        }
+        let path = bmff_box.path.clone();
+        boxes.push(bmff_box);
+        if CONTAINER_TYPES.contains(&&kind) {
+            walk_boxes(data, (content, offset + size), &path, depth + 1, boxes)?;
+        }
+        offset += size;
+    }
+    Ok(())
+}
+
+fn bmff_boxes(data: &[u8]) -> Result<Vec<BmffBox>> {
+    let mut boxes = Vec::new();
+    walk_boxes(data, (0, data.len()), "", 0, &mut boxes)?;
+    Ok(boxes)
+}
+
+/// The byte ranges the BMFF hash leaves out of `data`. For version 2 and up,
+/// the offset of each top-level box that isn't wholly excluded is hashed in
+/// too, which c2pa-rs marks with a one-byte range.
+fn hash_exclusions(
+    data: &[u8],
+    boxes: &[BmffBox],
+    exclusions: &[ExclusionsMap],
+    offsets_hashed: bool,
+) -> Vec<HashRange> {
+    let mut top_level: Vec<usize> = boxes
+        .iter()
+        .filter(|bmff_box| bmff_box.path.matches('/').count() == 1)
+        .map(|bmff_box| bmff_box.offset)
+        .collect();
+    let mut ranges = Vec::new();
+    for exclusion in exclusions {
+        for bmff_box in boxes
+            .iter()
+            .filter(|bmff_box| bmff_box.path == exclusion.xpath)
+        {
+            let (start, length) = (bmff_box.offset as u64, bmff_box.size as u64);
+            if exclusion.length.is_some_and(|wanted| wanted != length) {
+                continue;
+            }
+            if let (Some(wanted), Some(version)) = (exclusion.version, bmff_box.version) {
+                if wanted != version {
+                    continue;
+                }
+            }
+            if let (Some(wanted), Some(flags)) = (&exclusion.flags, bmff_box.flags) {
+                // c2pa-rs reads the wanted flags into the high bytes, and
+                // hashes must agree with its checks.
+                let wanted = match wanted.get(..3) {
+                    Some(bytes) => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], 0]),
+                    None => 0,
+                };
+                let matches = if exclusion.exact.unwrap_or(true) {
+                    wanted == flags
+                } else {
+                    wanted | flags == wanted
+                };
+                if !matches {
+                    continue;
+                }
+            }
+            let data_matches = exclusion.data.iter().flatten().all(|data_map| {
+                let at = bmff_box.offset + data_map.offset as usize;
+                data.get(at..at + data_map.value.len()) == Some(data_map.value.as_slice())
+            });
+            if !data_matches {
+                continue;
+            }
+            match &exclusion.subset {
+                Some(subsets) => {
+                    for subset in subsets.iter().filter(|subset| subset.offset <= length) {
+                        let left = length - subset.offset;
+                        let subset_length = match subset.length {
+                            0 => left,
+                            subset_length => subset_length.min(left),
+                        };
+                        ranges.push(HashRange::new(start + subset.offset, subset_length));
+                    }
+                }
+                None => {
+                    ranges.push(HashRange::new(start, length));
+                    top_level.retain(|&offset| offset != bmff_box.offset);
+                }
+            }
+        }
+    }
+    if offsets_hashed {
+        top_level.sort();
+        for offset in top_level {
+            let mut range = HashRange::new(offset as u64, 1);
+            range.set_bmff_offset(offset as u64);
+            ranges.push(range);
+        }
+    }
+    ranges
+}
+
+/// The Merkle map boxes of a fragment: C2PA `uuid` boxes whose purpose is
+/// `merkle`.
+fn fragment_merkle_maps(data: &[u8], boxes: &[BmffBox]) -> Result<Vec<BmffMerkleMap>> {
+    let mut maps = Vec::new();
+    for bmff_box in boxes {
+        if bmff_box.path != "/uuid" || bmff_box.user_type != Some(C2PA_UUID) {
+            continue;
+        }
+        // The version and flags, then the purpose as a null-terminated string.
+        let contents = &data[bmff_box.content + 4..bmff_box.offset + bmff_box.size];
+        let Some(end) = contents.iter().position(|&byte| byte == 0) else {
+            continue;
+        };
+        if &contents[..end] != b"merkle" {
+            continue;
+        }
+        let map = ciborium::from_reader(&contents[end + 1..])
+            .map_err(|err| Error::Reading(format!("A Merkle map box is malformed: {err}")))?;
+        maps.push(map);
+    }
+    Ok(maps)
+}
+
+fn hash(alg: &str, data: &[u8], exclusions: Option<Vec<HashRange>>) -> Result<Vec<u8>> {
+    Ok(hash_stream_by_alg(
+        alg,
+        &mut Cursor::new(data),
+        exclusions,
+        true,
+    )?)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// The number of nodes in each layer of a tree, from the leaves to the root.
+/// A node without a sibling moves up a layer as it is.
+fn layer_sizes(leaves: usize) -> Vec<usize> {
+    let mut layers = vec![leaves];
+    let mut layer = leaves;
+    while layer > 1 {
+        layer = layer.div_ceil(2);
+        layers.push(layer);
+    }
+    layers
+}
+
+#[derive(Deserialize)]
+struct ClaimAlg {
+    alg: Option<String>,
+}
+
+/// The BMFF hash assertion and claim hash algorithm of the active manifest in
+/// a manifest store, which is the last manifest in the store.
+fn active_bmff_hash(store: &[u8]) -> Result<Option<(BmffHash, Option<String>)>> {
+    let Some((_, manifests)) = superboxes(store).next() else {
+        return Err(Error::Reading(
+            "The manifest store is malformed".to_string(),
+        ));
+    };
+    let Some((_, manifest)) = superboxes(manifests).last() else {
+        return Ok(None);
+    };
+    let claim_alg = superboxes(manifest)
+        .find(|(label, _)| label.starts_with(CLAIM))
+        .and_then(|(_, claim)| jumbf_boxes(claim).find(|b| b.kind == b"cbor"))
+        .and_then(|cbor| ciborium::from_reader::<ClaimAlg, _>(cbor.payload).ok())
+        .and_then(|claim| claim.alg);
+    let Some(assertions) = superboxes(manifest)
+        .find(|(label, _)| label == "c2pa.assertions")
+        .map(|(_, assertions)| assertions)
+    else {
+        return Ok(None);
+    };
+    let Some((label, bmff_hash)) =
+        superboxes(assertions).find(|(label, _)| label.starts_with(BMFF_HASH))
+    else {
+        return Ok(None);
+    };
+    let Some(cbor) = jumbf_boxes(bmff_hash).find(|b| b.kind == b"cbor") else {
+        return Ok(None);
+    };
+    let mut bmff_hash: BmffHash = ciborium::from_reader(cbor.payload)
+        .map_err(|err| Error::Reading(format!("The BMFF hash assertion is malformed: {err}")))?;
+    // Labels look like `c2pa.hash.bmff.v3`, maybe with an instance suffix.
+    let version = label[BMFF_HASH.len()..]
+        .split("__")
+        .next()
+        .and_then(|suffix| suffix.strip_prefix(".v"))
+        .and_then(|version| version.parse().ok())
+        .unwrap_or(1);
+    bmff_hash.set_bmff_version(version);
+    Ok(Some((bmff_hash, claim_alg)))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitHashReport {
+    expected: String,
+    computed: String,
+    matches: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleMapReport {
+    unique_id: usize,
+    local_id: usize,
+    alg: String,
+    /// The number of leaves, one per fragment.
+    count: usize,
+    /// The number of nodes in each layer, from the leaves to the root.
+    layers: Vec<usize>,
+    /// The layer the manifest stores hashes for, counting from the leaves.
+    /// Fragments carry proofs up to it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stored_layer: Option<usize>,
+    hashes: Vec<String>,
+    /// The hash of the initialization segment, less exclusions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    init_hash: Option<InitHashReport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleTreeReport {
+    /// The version of the BMFF hash assertion.
+    bmff_version: usize,
+    maps: Vec<MerkleMapReport>,
+}
+
+/// Where a fragment's leaf fits in a Merkle tree, and whether its hash
+/// matched.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleLeafReport {
+    unique_id: usize,
+    local_id: usize,
+    /// The leaf's index in the tree.
+    location: usize,
+    /// The hash of the fragment, less exclusions.
+    leaf_hash: String,
+    /// The stored node the fragment's proof leads to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    computed: Option<String>,
+    valid: bool,
+    /// Why the leaf couldn't be checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The Merkle trees of an initialization segment's BMFF hash, to check
+/// fragments against.
+pub struct MerkleTrees {
+    bmff_hash: BmffHash,
+    alg: String,
+    report: MerkleTreeReport,
+}
+
+impl MerkleTrees {
+    /// The trees in the active manifest of `init`, or None when it has no
+    /// manifest or its BMFF hash has no Merkle trees.
+    pub fn from_init(format: &str, init: &[u8]) -> Result<Option<Self>> {
+        let store = match load_jumbf_from_memory(format, init) {
+            Ok(store) => store,
+            Err(c2pa::Error::JumbfNotFound) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let Some((bmff_hash, claim_alg)) = active_bmff_hash(&store)? else {
+            return Ok(None);
+        };
+        let Some(maps) = bmff_hash.merkle() else {
+            return Ok(None);
+        };
+        let alg = bmff_hash
+            .alg()
+            .cloned()
+            .or(claim_alg)
+            .unwrap_or_else(|| "sha256".to_string());
+        let init_exclusions = hash_exclusions(
+            init,
+            &bmff_boxes(init)?,
+            bmff_hash.exclusions(),
+            bmff_hash.bmff_version() > 1,
+        );
+        let mut reports = Vec::with_capacity(maps.len());
+        for map in maps {
+            let map_alg = map.alg.as_deref().unwrap_or(&alg);
+            let init_hash = match &map.init_hash {
+                Some(expected) => {
+                    let computed = hash(map_alg, init, Some(init_exclusions.clone()))?;
+                    Some(InitHashReport {
+                        expected: hex(expected),
+                        computed: hex(&computed),
+                        matches: computed == expected.as_slice(),
+                    })
+                }
+                None => None,
+            };
+            let layers = layer_sizes(map.count);
+            reports.push(MerkleMapReport {
+                unique_id: map.unique_id,
+                local_id: map.local_id,
+                alg: map_alg.to_string(),
+                count: map.count,
+                stored_layer: layers.iter().position(|&size| size == map.hashes.len()),
+                layers,
+                hashes: map.hashes.iter().map(|hash| hex(hash)).collect(),
+                init_hash,
+            });
+        }
+        let report = MerkleTreeReport {
+            bmff_version: bmff_hash.bmff_version(),
+            maps: reports,
+        };
+        Ok(Some(Self {
+            bmff_hash,
+            alg,
+            report,
+        }))
+    }
+
+    pub fn into_report(self) -> MerkleTreeReport {
+        self.report
+    }
+
+    /// Check each Merkle map box in a fragment against its tree.
+    pub fn check(&self, fragment: &[u8]) -> Result<Vec<MerkleLeafReport>> {
+        let boxes = bmff_boxes(fragment)?;
+        let leaves = fragment_merkle_maps(fragment, &boxes)?;
+        if leaves.is_empty() {
+            return Err(Error::Reading(
+                "The fragment has no Merkle map box".to_string(),
+            ));
+        }
+        let exclusions = hash_exclusions(
+            fragment,
+            &boxes,
+            self.bmff_hash.exclusions(),
+            self.bmff_hash.bmff_version() > 1,
+        );
+        let maps = self
+            .bmff_hash
+            .merkle()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut reports = Vec::with_capacity(leaves.len());
+        for leaf in leaves {
+            let map = maps
+                .iter()
+                .find(|map| map.unique_id == leaf.unique_id && map.local_id == leaf.local_id);
+            let alg = map.and_then(|map| map.alg.as_deref()).unwrap_or(&self.alg);
+            let leaf_hash = hash(alg, fragment, Some(exclusions.clone()))?;
+            let mut report = MerkleLeafReport {
+                unique_id: leaf.unique_id,
+                local_id: leaf.local_id,
+                location: leaf.location,
+                leaf_hash: hex(&leaf_hash),
+                node: None,
+                expected: None,
+                computed: None,
+                valid: false,
+                error: None,
+            };
+            let Some(map) = map else {
+                report.error = Some(format!(
+                    "The manifest has no Merkle map with uniqueId {} and localId {}",
+                    leaf.unique_id, leaf.local_id
+                ));
+                reports.push(report);
+                continue;
+            };
+            match replay_proof(alg, map, leaf_hash, leaf.location, leaf.hashes.as_ref()) {
+                Ok((node, computed)) => {
+                    let expected = map.hashes.get(node);
+                    report.valid = expected.is_some_and(|expected| **expected == computed);
+                    report.node = Some(node);
+                    report.expected = expected.map(|expected| hex(expected));
+                    report.computed = Some(hex(&computed));
+                }
+                Err(err) => report.error = Some(err),
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+}
+
+/// Hash a leaf up the tree with its proof until the layer the manifest
+/// stores, giving the node reached and its hash. A node without a sibling
+/// moves up as it is, taking no proof hash, and so does every node when the
+/// fragment has no proof.
+fn replay_proof(
+    alg: &str,
+    map: &MerkleMap,
+    leaf_hash: Vec<u8>,
+    location: usize,
+    proof: Option<&VecByteBuf>,
+) -> std::result::Result<(usize, Vec<u8>), String> {
+    if location >= map.count {
+        return Err(format!(
+            "Location {location} is outside a tree of {} leaves",
+            map.count
+        ));
+    }
+    let mut proof = proof.map(|proof| proof.iter());
+    let mut index = location;
+    let mut hash_value = leaf_hash;
+    for layer in layer_sizes(map.count) {
+        if layer == map.hashes.len() {
+            break;
+        }
+        let sibling = if index % 2 == 1 { index - 1 } else { index + 1 };
+        if let (true, Some(proof)) = (sibling < layer, proof.as_mut()) {
+            let sibling_hash = proof
+                .next()
+                .ok_or_else(|| "The fragment's proof is too short".to_string())?;
+            let pair = if index % 2 == 1 {
+                [sibling_hash.as_slice(), &hash_value].concat()
+            } else {
+                [&hash_value, sibling_hash.as_slice()].concat()
+            };
+            hash_value = hash(alg, &pair, None).map_err(|err| err.to_string())?;
+        }
+        index /= 2;
+    }
+    Ok((index, hash_value))
+}
@@ -0,0 +1,241 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use c2pa::crypto::cose::{check_end_entity_certificate_profile, CertificateTrustPolicy};
+use c2pa::status_tracker::StatusTracker;
+use c2pa::SigningAlg;
+use neon::prelude::*;
+use neon::types::buffer::TypedArray;
+use serde::Serialize;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::FromDer;
+
+use crate::error::{as_js_error_fn, Error, Result};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CertificateInfo {
+    subject: String,
+    common_name: Option<String>,
+    issuer: String,
+    /// Colon-separated hex, as OpenSSL shows it.
+    serial_number: String,
+    /// Seconds since the Unix epoch.
+    not_before: i64,
+    not_after: i64,
+    subject_alt_names: Vec<String>,
+    extended_key_usages: Vec<String>,
+    is_ca: bool,
+    self_signed: bool,
+    c2pa_signing: C2paSigning,
+}
+
+/// Whether a certificate meets the C2PA profile for signing certificates, and
+/// why not when it does not.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct C2paSigning {
+    suitable: bool,
+    reason: Option<String>,
+}
+
+impl CertificateInfo {
+    fn new(der: &[u8], policy: &CertificateTrustPolicy) -> Result<Self> {
+        let (_, cert) =
+            X509Certificate::from_der(der).map_err(|err| Error::Certificate(err.to_string()))?;
+        let common_name = cert
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(str::to_string);
+        let subject_alt_names = match cert.subject_alternative_name() {
+            Ok(Some(san)) => san.value.general_names.iter().map(general_name).collect(),
+            _ => Vec::new(),
+        };
+        let extended_key_usages = match cert.extended_key_usage() {
+            Ok(Some(eku)) => {
+                let eku = eku.value;
+                [
+                    (eku.any, "any"),
+                    (eku.server_auth, "serverAuth"),
+                    (eku.client_auth, "clientAuth"),
+                    (eku.code_signing, "codeSigning"),
+                    (eku.email_protection, "emailProtection"),
+                    (eku.time_stamping, "timeStamping"),
+                    (eku.ocsp_signing, "OCSPSigning"),
+                ]
+                .into_iter()
+                .filter(|(present, _)| *present)
+                .map(|(_, name)| name.to_string())
+                .chain(eku.other.iter().map(|oid| oid.to_id_string()))
+                .collect()
+            }
+            _ => Vec::new(),
+        };
+        let is_ca = cert.is_ca();
+        let c2pa_signing = if is_ca {
+            C2paSigning {
+                suitable: false,
+                reason: Some("the certificate is a CA certificate".to_string()),
+            }
+        } else {
+            let mut log = StatusTracker::default();
+            match check_end_entity_certificate_profile(der, policy, &mut log, None) {
+                Ok(()) => C2paSigning {
+                    suitable: true,
+                    reason: None,
+                },
+                Err(err) => C2paSigning {
+                    suitable: false,
+                    reason: Some(err.to_string()),
+                },
+            }
+        };
+        Ok(Self {
+            subject: cert.subject().to_string(),
+            common_name,
+            issuer: cert.issuer().to_string(),
+            serial_number: cert.raw_serial_as_string(),
+            not_before: cert.validity().not_before.timestamp(),
+            not_after: cert.validity().not_after.timestamp(),
+            subject_alt_names,
+            extended_key_usages,
+            is_ca,
+            self_signed: cert.subject() == cert.issuer(),
+            c2pa_signing,
+        })
+    }
+}
+
+fn general_name(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DNSName(name) => format!("DNS:{name}"),
+        GeneralName::RFC822Name(email) => format!("email:{email}"),
+        GeneralName::URI(uri) => format!("URI:{uri}"),
+        GeneralName::IPAddress(ip) => match <[u8; 4]>::try_from(*ip) {
+            Ok(ip) => format!("IP:{}", std::net::Ipv4Addr::from(ip)),
+            Err(_) => match <[u8; 16]>::try_from(*ip) {
+                Ok(ip) => format!("IP:{}", std::net::Ipv6Addr::from(ip)),
+                Err(_) => name.to_string(),
+            },
+        },
+        GeneralName::DirectoryName(dn) => format!("DirName:{dn}"),
+        _ => name.to_string(),
+    }
+}
+
+/// The DER encoding of each certificate in PEM text, or of a single DER
+/// certificate.
+fn certificate_ders(bytes: &[u8]) -> Result<Vec<Vec<u8>>> {
+    if !bytes.trim_ascii_start().starts_with(b"-----BEGIN") {
+        return Ok(vec![bytes.to_vec()]);
+    }
+    let ders: Vec<Vec<u8>> = pem::parse_many(bytes)
+        .map_err(|err| Error::Certificate(err.to_string()))?
+        .into_iter()
+        .filter(|pem| pem.tag() == "CERTIFICATE")
+        .map(pem::Pem::into_contents)
+        .collect();
+    if ders.is_empty() {
+        return Err(Error::Certificate(
+            "No CERTIFICATE block found in the PEM data".to_string(),
+        ));
+    }
+    Ok(ders)
+}
+
+/// The kind of public key a certificate holds, as named in errors.
+fn key_kind(cert: &X509Certificate) -> String {
+    let algorithm = &cert.public_key().algorithm;
+    match algorithm.algorithm.to_id_string().as_str() {
+        "1.2.840.113549.1.1.1" | "1.2.840.113549.1.1.10" => "RSA".to_string(),
+        "1.3.101.112" => "Ed25519".to_string(),
+        "1.2.840.10045.2.1" => {
+            let curve = algorithm
+                .parameters
+                .as_ref()
+                .and_then(|params| params.as_oid().ok())
+                .map(|oid| oid.to_id_string());
+            match curve.as_deref() {
+                Some("1.2.840.10045.3.1.7") => "P-256 EC".to_string(),
+                Some("1.3.132.0.34") => "P-384 EC".to_string(),
+                Some("1.3.132.0.35") => "P-521 EC".to_string(),
+                Some(curve) => format!("EC ({curve})"),
+                None => "EC".to_string(),
+            }
+        }
+        oid => oid.to_string(),
+    }
+}
+
+/// Check that the end-entity certificate, the first in a PEM chain or a DER
+/// certificate, holds the kind of key `alg` signs with, so a signer set up
+/// with the wrong algorithm fails when it is created rather than when its
+/// manifests are verified. Certificates that can't be parsed are left to
+/// signing to reject.
+pub fn check_signing_alg(certs: &[u8], alg: SigningAlg) -> Result<()> {
+    let Some(der) = certificate_ders(certs)
+        .ok()
+        .and_then(|ders| ders.into_iter().next())
+    else {
+        return Ok(());
+    };
+    let Ok((_, cert)) = X509Certificate::from_der(&der) else {
+        return Ok(());
+    };
+    let expected = match alg {
+        SigningAlg::Es256 => "P-256 EC",
+        SigningAlg::Es384 => "P-384 EC",
+        SigningAlg::Es512 => "P-521 EC",
+        SigningAlg::Ps256 | SigningAlg::Ps384 | SigningAlg::Ps512 => "RSA",
+        SigningAlg::Ed25519 => "Ed25519",
+    };
+    let key = key_kind(&cert);
+    if key != expected {
+        return Err(Error::SigningKey(format!(
+            "{alg} needs a key of type {expected}, but the signing certificate's key is {key}"
+        )));
+    }
+    Ok(())
+}
+
+/// Describe each certificate in a PEM chain, or a single DER certificate, as
+/// JSON. The C2PA profile check uses the default extended key usages and the
+/// current time.
+pub fn parse_certificates(mut cx: FunctionContext) -> JsResult<JsString> {
+    let input = cx.argument::<JsValue>(0)?;
+    let bytes = if let Ok(text) = input.downcast::<JsString, _>(&mut cx) {
+        text.value(&mut cx).into_bytes()
+    } else {
+        input
+            .downcast_or_throw::<JsBuffer, _>(&mut cx)?
+            .as_slice(&cx)
+            .to_vec()
+    };
+    let policy = CertificateTrustPolicy::default();
+    let infos = certificate_ders(&bytes).and_then(|ders| {
+        ders.iter()
+            .map(|der| CertificateInfo::new(der, &policy))
+            .collect::<Result<Vec<_>>>()
+    });
+    let json = infos.and_then(|infos| Ok(serde_json::to_string(&infos)?));
+    match json {
+        Ok(json) => Ok(cx.string(json)),
+        Err(err) => {
+            let err = as_js_error_fn(&mut cx, "parseCertificates", err)?;
+            cx.throw(err)
+        }
+    }
+}
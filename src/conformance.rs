@@ -0,0 +1,325 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use c2pa::assertions::labels::parse_label;
+use c2pa::assertions::Actions;
+use c2pa::{Manifest, Reader, Relationship, ValidationState};
+use serde::Serialize;
+
+use crate::validation::validation_failures;
+use crate::validation_policy;
+
+/// Assertions C2PA 2.x replaces with `c2pa.metadata`.
+const DEPRECATED_ASSERTIONS: [&str; 4] = [
+    "stds.exif",
+    "stds.iptc",
+    "stds.iptc.photo-metadata",
+    "stds.schema-org.CreativeWork",
+];
+
+/// The actions a C2PA 2.x manifest's actions must start with.
+const FIRST_ACTIONS: [&str; 2] = ["c2pa.created", "c2pa.opened"];
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// How a Reader's manifest store measures up to the C2PA 2.x requirements on
+/// claims, assertions, and signatures. Checks that fail are requirements of
+/// the specification; checks that warn are recommendations or deprecations.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceReport {
+    /// The worst status of any check.
+    result: Status,
+    checks: Vec<Check>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Check {
+    /// What was checked, such as `claim.version`.
+    id: &'static str,
+    status: Status,
+    message: String,
+    /// The label of the manifest checked, or None for the whole store.
+    manifest: Option<String>,
+}
+
+/// The checks for one manifest.
+struct Checks<'a> {
+    checks: &'a mut Vec<Check>,
+    manifest: Option<String>,
+}
+
+impl Checks<'_> {
+    fn push(&mut self, id: &'static str, status: Status, message: impl Into<String>) {
+        self.checks.push(Check {
+            id,
+            status,
+            message: message.into(),
+            manifest: self.manifest.clone(),
+        });
+    }
+
+    fn manifest(&mut self, manifest: &Manifest, active: bool) {
+        self.claim_version(manifest, active);
+        self.generator_info(manifest);
+        self.actions(manifest);
+        self.deprecated_assertions(manifest);
+        self.signature(manifest);
+        self.parent(manifest);
+    }
+
+    fn claim_version(&mut self, manifest: &Manifest, active: bool) {
+        match manifest.claim_version() {
+            Some(2) => self.push("claim.version", Status::Pass, "Claim version 2"),
+            // Ingredients signed before C2PA 2.0 keep their v1 claims.
+            Some(1) if !active => self.push(
+                "claim.version",
+                Status::Warn,
+                "Ingredient manifest has a version 1 claim from C2PA 1.x",
+            ),
+            Some(version) => self.push(
+                "claim.version",
+                Status::Fail,
+                format!("Claim version {version}; C2PA 2.x requires version 2"),
+            ),
+            None => self.push("claim.version", Status::Fail, "Claim version is unknown"),
+        }
+    }
+
+    fn generator_info(&mut self, manifest: &Manifest) {
+        let named = manifest
+            .claim_generator_info
+            .iter()
+            .flatten()
+            .any(|info| !info.name.is_empty());
+        if named {
+            self.push(
+                "claim.generatorInfo",
+                Status::Pass,
+                "Claim names its generator",
+            );
+        } else {
+            self.push(
+                "claim.generatorInfo",
+                Status::Fail,
+                "Claim has no claim_generator_info with a name",
+            );
+        }
+    }
+
+    fn actions(&mut self, manifest: &Manifest) {
+        let label = manifest
+            .assertions()
+            .iter()
+            .map(|assertion| assertion.label())
+            .find(|label| parse_label(label).0 == Actions::LABEL);
+        let Some(label) = label else {
+            self.push(
+                "assertion.actions",
+                Status::Fail,
+                "C2PA 2.x requires a c2pa.actions assertion",
+            );
+            return;
+        };
+        let Ok(actions) = manifest.find_assertion::<Actions>(Actions::LABEL) else {
+            self.push(
+                "assertion.actions",
+                Status::Fail,
+                format!("\"{label}\" could not be read"),
+            );
+            return;
+        };
+        match actions.actions().first() {
+            Some(first) if FIRST_ACTIONS.contains(&first.action()) => {
+                if first.action() == "c2pa.created" && first.source_type().is_none() {
+                    self.push(
+                        "assertion.actions",
+                        Status::Warn,
+                        "The c2pa.created action has no digitalSourceType",
+                    );
+                } else {
+                    self.push(
+                        "assertion.actions",
+                        Status::Pass,
+                        format!("Actions start with {}", first.action()),
+                    );
+                }
+            }
+            Some(first) => self.push(
+                "assertion.actions",
+                Status::Fail,
+                format!(
+                    "Actions start with {}; the first must be c2pa.created or c2pa.opened",
+                    first.action()
+                ),
+            ),
+            None => self.push("assertion.actions", Status::Fail, "Actions are empty"),
+        }
+    }
+
+    fn deprecated_assertions(&mut self, manifest: &Manifest) {
+        let mut found = false;
+        for assertion in manifest.assertions() {
+            let label = assertion.label();
+            let (base, version, _) = parse_label(label);
+            let replacement = if DEPRECATED_ASSERTIONS.contains(&base) {
+                "c2pa.metadata"
+            } else if base == Actions::LABEL && version < 2 && manifest.claim_version() == Some(2) {
+                // Version 1 actions belong in version 1 claims.
+                "c2pa.actions.v2"
+            } else {
+                continue;
+            };
+            found = true;
+            self.push(
+                "assertion.deprecated",
+                Status::Warn,
+                format!("\"{label}\" is deprecated in C2PA 2.x; use {replacement}"),
+            );
+        }
+        if !found {
+            self.push(
+                "assertion.deprecated",
+                Status::Pass,
+                "No deprecated assertions",
+            );
+        }
+    }
+
+    fn signature(&mut self, manifest: &Manifest) {
+        let Some(info) = manifest.signature_info() else {
+            self.push(
+                "signature.algorithm",
+                Status::Fail,
+                "Manifest is not signed",
+            );
+            return;
+        };
+        // c2pa-rs only recognizes the algorithms C2PA allows.
+        match info.alg {
+            Some(alg) => self.push(
+                "signature.algorithm",
+                Status::Pass,
+                format!("Signed with {alg}"),
+            ),
+            None => self.push(
+                "signature.algorithm",
+                Status::Fail,
+                "Signed with an algorithm C2PA doesn't allow",
+            ),
+        }
+        if info.time.is_some() {
+            self.push(
+                "signature.timeStamp",
+                Status::Pass,
+                "Signature is time-stamped",
+            );
+        } else {
+            self.push(
+                "signature.timeStamp",
+                Status::Warn,
+                "Signature has no time-stamp, so it can't be validated once the certificate expires",
+            );
+        }
+    }
+
+    fn parent(&mut self, manifest: &Manifest) {
+        let parents = manifest
+            .ingredients()
+            .iter()
+            .filter(|ingredient| *ingredient.relationship() == Relationship::ParentOf)
+            .count();
+        if parents > 1 {
+            self.push(
+                "ingredient.relationship",
+                Status::Fail,
+                format!("{parents} ingredients are parentOf; at most one is allowed"),
+            );
+        } else {
+            self.push(
+                "ingredient.relationship",
+                Status::Pass,
+                "At most one parentOf ingredient",
+            );
+        }
+    }
+}
+
+impl ConformanceReport {
+    pub fn new(reader: &Reader) -> Self {
+        let mut checks = Vec::new();
+        let mut store = Checks {
+            checks: &mut checks,
+            manifest: None,
+        };
+        match validation_policy::validation_state(reader) {
+            ValidationState::Invalid => {
+                let codes: Vec<String> = validation_failures(reader)
+                    .into_iter()
+                    .map(|failure| failure.code)
+                    .collect();
+                store.push(
+                    "validation",
+                    Status::Fail,
+                    format!("Validation failed: {}", codes.join(", ")),
+                );
+            }
+            ValidationState::Valid => store.push(
+                "validation",
+                Status::Warn,
+                "Manifest store is valid, but its signer is not on a trust list",
+            ),
+            ValidationState::Trusted => {
+                store.push("validation", Status::Pass, "Manifest store is trusted")
+            }
+        }
+
+        // The active manifest, then the manifests of its ingredients.
+        let mut labels: Vec<&str> = reader.active_label().into_iter().collect();
+        let mut next = 0;
+        while let Some(label) = labels.get(next).copied() {
+            next += 1;
+            let Some(manifest) = reader.get_manifest(label) else {
+                continue;
+            };
+            for label in manifest
+                .ingredients()
+                .iter()
+                .filter_map(|ingredient| ingredient.active_manifest())
+            {
+                if !labels.contains(&label) {
+                    labels.push(label);
+                }
+            }
+            Checks {
+                checks: &mut checks,
+                manifest: Some(label.to_string()),
+            }
+            .manifest(manifest, next == 1);
+        }
+
+        let result = checks
+            .iter()
+            .map(|check| check.status)
+            .max()
+            .unwrap_or(Status::Pass);
+        Self { result, checks }
+    }
+}
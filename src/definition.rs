@@ -0,0 +1,400 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::HashSet;
+
+use c2pa::assertions::labels::parse_label;
+use c2pa::assertions::Actions;
+use c2pa::{ClaimGeneratorInfo, ResourceRef};
+use neon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::js_value::from_js_with_buffers;
+
+const CLAIM_VERSIONS: [u64; 2] = [1, 2];
+const HASH_ALGORITHMS: [&str; 3] = ["sha256", "sha384", "sha512"];
+const ASSERTION_KINDS: [&str; 4] = ["Cbor", "Json", "Binary", "Uri"];
+const RELATIONSHIPS: [&str; 3] = ["parentOf", "componentOf", "inputTo"];
+
+/// A problem with a manifest definition and where it is, such as
+/// `assertions[0].label`.
+#[derive(Serialize)]
+struct DefinitionError {
+    path: String,
+    message: String,
+}
+
+#[derive(Default)]
+struct Checker {
+    errors: Vec<DefinitionError>,
+}
+
+impl Checker {
+    fn error(&mut self, path: &str, message: impl Into<String>) {
+        self.errors.push(DefinitionError {
+            path: path.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn string<'a>(&mut self, value: &'a Value, path: &str) -> Option<&'a str> {
+        let text = value.as_str();
+        if text.is_none() {
+            self.error(path, "Expected a string");
+        }
+        text
+    }
+
+    fn array<'a>(&mut self, value: &'a Value, path: &str) -> &'a [Value] {
+        match value.as_array() {
+            Some(items) => items,
+            None => {
+                self.error(path, "Expected an array");
+                &[]
+            }
+        }
+    }
+
+    fn object<'a>(&mut self, value: &'a Value, path: &str) -> Option<&'a Map<String, Value>> {
+        let obj = value.as_object();
+        if obj.is_none() {
+            self.error(path, "Expected an object");
+        }
+        obj
+    }
+
+    /// Deserialize `value` as c2pa-rs would, recording why it can't be.
+    fn parse<T: DeserializeOwned>(&mut self, value: &Value, path: &str) -> Option<T> {
+        serde_json::from_value(value.clone())
+            .map_err(|err| self.error(path, err.to_string()))
+            .ok()
+    }
+
+    fn one_of(&mut self, value: &Value, path: &str, allowed: &[&str]) {
+        if let Some(text) = self.string(value, path) {
+            if !allowed.contains(&text) {
+                self.error(
+                    path,
+                    format!("Unknown value \"{text}\". Expected {}", expected(allowed)),
+                );
+            }
+        }
+    }
+
+    fn resource_ref(&mut self, value: &Value, path: &str) {
+        if let Some(resource) = self.parse::<ResourceRef>(value, path) {
+            if resource.identifier.is_empty() {
+                self.error(&format!("{path}.identifier"), "Must not be empty");
+            }
+            if resource.format.is_empty() {
+                self.error(&format!("{path}.format"), "Must not be empty");
+            }
+        }
+    }
+
+    fn definition(&mut self, value: &Value) {
+        let Some(definition) = self.object(value, "") else {
+            return;
+        };
+        let field = |name: &str| definition.get(name).filter(|value| !value.is_null());
+
+        if let Some(version) = field("claim_version") {
+            if !version
+                .as_u64()
+                .is_some_and(|version| CLAIM_VERSIONS.contains(&version))
+            {
+                self.error("claim_version", "Expected 1 or 2");
+            }
+        }
+        for name in ["vendor", "title", "format", "instance_id", "label"] {
+            if let Some(value) = field(name) {
+                self.string(value, name);
+            }
+        }
+        if let Some(alg) = field("hash_alg") {
+            self.one_of(alg, "hash_alg", &HASH_ALGORITHMS);
+        }
+        if let Some(generators) = field("claim_generator_info") {
+            for (index, generator) in self
+                .array(generators, "claim_generator_info")
+                .iter()
+                .enumerate()
+            {
+                self.parse::<ClaimGeneratorInfo>(
+                    generator,
+                    &format!("claim_generator_info[{index}]"),
+                );
+            }
+        }
+        if let Some(thumbnail) = field("thumbnail") {
+            self.resource_ref(thumbnail, "thumbnail");
+        }
+        if let Some(redactions) = field("redactions") {
+            for (index, uri) in self.array(redactions, "redactions").iter().enumerate() {
+                self.string(uri, &format!("redactions[{index}]"));
+            }
+        }
+
+        let mut ingredient_ids = HashSet::new();
+        if let Some(ingredients) = field("ingredients") {
+            let mut parent = None;
+            for (index, ingredient) in self.array(ingredients, "ingredients").iter().enumerate() {
+                let path = format!("ingredients[{index}]");
+                if let Some(id) = self.ingredient(ingredient, &path) {
+                    ingredient_ids.insert(id);
+                }
+                if ingredient.get("relationship").and_then(Value::as_str) == Some("parentOf") {
+                    match parent {
+                        Some(first) => self.error(
+                            &format!("{path}.relationship"),
+                            format!("ingredients[{first}] is already the parent"),
+                        ),
+                        None => parent = Some(index),
+                    }
+                }
+            }
+        }
+
+        if let Some(assertions) = field("assertions") {
+            for (index, assertion) in self.array(assertions, "assertions").iter().enumerate() {
+                self.assertion(assertion, &format!("assertions[{index}]"), &ingredient_ids);
+            }
+        }
+    }
+
+    /// Check an ingredient, returning the ID actions can refer to it by.
+    fn ingredient(&mut self, value: &Value, path: &str) -> Option<String> {
+        let ingredient = self.object(value, path)?;
+        for name in ["title", "format", "instance_id", "document_id", "label"] {
+            if let Some(value) = ingredient.get(name).filter(|value| !value.is_null()) {
+                self.string(value, &format!("{path}.{name}"));
+            }
+        }
+        if let Some(relationship) = ingredient.get("relationship") {
+            self.one_of(
+                relationship,
+                &format!("{path}.relationship"),
+                &RELATIONSHIPS,
+            );
+        }
+        for name in ["thumbnail", "manifest_data", "data"] {
+            if let Some(resource) = ingredient.get(name).filter(|value| !value.is_null()) {
+                self.resource_ref(resource, &format!("{path}.{name}"));
+            }
+        }
+        // c2pa-rs matches `ingredientIds` against the label, falling back to
+        // the instance ID.
+        ["label", "instance_id"]
+            .into_iter()
+            .filter_map(|name| ingredient.get(name).and_then(Value::as_str))
+            .find(|id| !id.is_empty())
+            .map(str::to_string)
+    }
+
+    fn assertion(&mut self, value: &Value, path: &str, ingredient_ids: &HashSet<String>) {
+        let Some(assertion) = self.object(value, path) else {
+            return;
+        };
+        let label_path = format!("{path}.label");
+        let label = match assertion.get("label") {
+            Some(label) => self.string(label, &label_path),
+            None => {
+                self.error(&label_path, "Missing assertion label");
+                None
+            }
+        };
+        if let Some(label) = label {
+            if !is_valid_label(label) {
+                self.error(
+                    &label_path,
+                    format!("\"{label}\" is not a dot-separated label like com.example.rating"),
+                );
+            }
+        }
+        if let Some(kind) = assertion.get("kind").filter(|value| !value.is_null()) {
+            self.one_of(kind, &format!("{path}.kind"), &ASSERTION_KINDS);
+        }
+        let data_path = format!("{path}.data");
+        let Some(data) = assertion.get("data") else {
+            self.error(&data_path, "Missing assertion data");
+            return;
+        };
+        if label.is_some_and(|label| parse_label(label).0.starts_with(Actions::LABEL)) {
+            self.actions(data, &data_path, ingredient_ids);
+        }
+    }
+
+    fn actions(&mut self, value: &Value, path: &str, ingredient_ids: &HashSet<String>) {
+        let errors = self.errors.len();
+        let Some(actions) = value.get("actions") else {
+            self.error(&format!("{path}.actions"), "Missing actions");
+            return;
+        };
+        for (index, action) in self
+            .array(actions, &format!("{path}.actions"))
+            .iter()
+            .enumerate()
+        {
+            let path = format!("{path}.actions[{index}]");
+            match action.get("action") {
+                Some(name) => {
+                    if self
+                        .string(name, &format!("{path}.action"))
+                        .is_some_and(str::is_empty)
+                    {
+                        self.error(&format!("{path}.action"), "Must not be empty");
+                    }
+                }
+                None => self.error(&format!("{path}.action"), "Missing action name"),
+            }
+            let ids_path = format!("{path}.parameters.ingredientIds");
+            if let Some(ids) = action.pointer("/parameters/ingredientIds") {
+                for (index, id) in self.array(ids, &ids_path).iter().enumerate() {
+                    let id_path = format!("{ids_path}[{index}]");
+                    if let Some(id) = self.string(id, &id_path) {
+                        if !ingredient_ids.contains(id) {
+                            self.error(
+                                &id_path,
+                                format!("No ingredient has the label or instance_id \"{id}\""),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        // Anything else wrong with the actions, reported only when the
+        // checks above found nothing more specific.
+        if self.errors.len() == errors {
+            self.parse::<Actions>(value, path);
+        }
+    }
+}
+
+/// `allowed` as a list for an error message, like "a, b, or c".
+fn expected(allowed: &[&str]) -> String {
+    match allowed {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [first, second] => format!("{first} or {second}"),
+        [rest @ .., last] => format!("{}, or {last}", rest.join(", ")),
+    }
+}
+
+/// Whether a label is dot-separated components of letters, digits, `_`, and
+/// `-`, with an optional `__N` instance suffix.
+fn is_valid_label(label: &str) -> bool {
+    let label = match label.rsplit_once("__") {
+        Some((base, instance)) if instance.parse::<u32>().is_ok() => base,
+        _ => label,
+    };
+    !label.is_empty()
+        && label.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
+
+/// The fields of an ingredient that hold resource references.
+const INGREDIENT_RESOURCES: [&str; 3] = ["thumbnail", "data", "manifest_data"];
+
+/// Take the bytes of resource references whose `buffer` field is an index into
+/// `buffers`, as left by `from_js_with_buffers`, returning them with their
+/// identifiers. A reference without an identifier is given its path, such as
+/// `ingredients[0].thumbnail`. Fails if a buffer is anywhere else.
+pub fn take_buffer_resources(
+    definition: &mut Value,
+    buffers: Vec<Vec<u8>>,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let mut buffers: Vec<Option<Vec<u8>>> = buffers.into_iter().map(Some).collect();
+    let mut resources = Vec::new();
+    take_resource(
+        definition.get_mut("thumbnail"),
+        "thumbnail",
+        &mut buffers,
+        &mut resources,
+    );
+    if let Some(ingredients) = definition
+        .get_mut("ingredients")
+        .and_then(Value::as_array_mut)
+    {
+        for (index, ingredient) in ingredients.iter_mut().enumerate() {
+            for field in INGREDIENT_RESOURCES {
+                take_resource(
+                    ingredient.get_mut(field),
+                    &format!("ingredients[{index}].{field}"),
+                    &mut buffers,
+                    &mut resources,
+                );
+            }
+        }
+    }
+    if buffers.iter().any(Option::is_some) {
+        return Err(
+            "Buffers in a manifest definition must be the buffer of a resource reference"
+                .to_string(),
+        );
+    }
+    Ok(resources)
+}
+
+fn take_resource(
+    reference: Option<&mut Value>,
+    path: &str,
+    buffers: &mut [Option<Vec<u8>>],
+    resources: &mut Vec<(String, Vec<u8>)>,
+) {
+    let Some(reference) = reference.and_then(Value::as_object_mut) else {
+        return;
+    };
+    let Some(bytes) = reference
+        .get("buffer")
+        .and_then(Value::as_u64)
+        .and_then(|index| buffers.get_mut(index as usize))
+        .and_then(Option::take)
+    else {
+        return;
+    };
+    reference.remove("buffer");
+    let identifier = reference
+        .entry("identifier")
+        .or_insert_with(|| Value::from(path));
+    resources.push((identifier.as_str().unwrap_or(path).to_string(), bytes));
+}
+
+/// Check a manifest definition without creating a Builder, returning JSON
+/// `{ path, message }` errors, empty when the definition is valid. Takes the
+/// definition as JSON or as an object, which may hold resources in buffers
+/// like the one given to `Builder.withJson`.
+pub fn validate_manifest_definition(mut cx: FunctionContext) -> JsResult<JsString> {
+    let definition = cx.argument::<JsValue>(0)?;
+    let mut checker = Checker::default();
+    if let Ok(json) = definition.downcast::<JsString, _>(&mut cx) {
+        match serde_json::from_str::<Value>(&json.value(&mut cx)) {
+            Ok(value) => checker.definition(&value),
+            Err(err) => checker.error("", format!("Invalid JSON: {err}")),
+        }
+    } else {
+        let (mut value, buffers) = from_js_with_buffers(&mut cx, definition)?;
+        match take_buffer_resources(&mut value, buffers) {
+            Ok(_) => checker.definition(&value),
+            Err(err) => checker.error("", err),
+        }
+    }
+    let errors =
+        serde_json::to_string(&checker.errors).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(errors))
+}
@@ -0,0 +1,160 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Configuration from `C2PA_*` environment variables, read once per process
+//! when settings or the runtime are first needed, and again after
+//! `reinitialize`. It sits under everything configured from JavaScript: trust
+//! sources, `configureRuntime`, and per-call settings all take precedence.
+
+use std::env;
+use std::fs;
+use std::sync::{Arc, RwLock};
+
+use c2pa::Settings;
+
+use crate::error::{Error, Result};
+use crate::runtime::{RuntimeConfig, RuntimeFlavor};
+use crate::settings::{is_inline_trust_list, TrustLists, VerifyOptions};
+
+#[derive(Debug)]
+struct EnvConfig {
+    trust: TrustLists,
+    verify: VerifyOptions,
+    tsa_url: Option<String>,
+    runtime: RuntimeConfig,
+}
+
+// The configuration, or the message of the error reading it, once read.
+static ENV_CONFIG: RwLock<Option<std::result::Result<Arc<EnvConfig>, String>>> = RwLock::new(None);
+
+/// The value of `name`, or None when it is unset or empty.
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+/// The trust list in `name`, or in the file it names.
+fn trust_list(name: &str) -> Result<Option<String>> {
+    var(name)
+        .map(|source| {
+            if is_inline_trust_list(&source) {
+                return Ok(source);
+            }
+            fs::read_to_string(&source)
+                .map_err(|err| Error::Settings(format!("Can't read {name} file {source}: {err}")))
+        })
+        .transpose()
+}
+
+fn flag(name: &str) -> Result<Option<bool>> {
+    var(name)
+        .map(|value| match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Ok(true),
+            "0" | "false" | "no" | "off" => Ok(false),
+            _ => Err(Error::Settings(format!(
+                "{name} must be true or false, got \"{value}\""
+            ))),
+        })
+        .transpose()
+}
+
+fn count(name: &str) -> Result<Option<usize>> {
+    var(name)
+        .map(|value| match value.trim().parse::<usize>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(Error::Settings(format!(
+                "{name} must be a whole number of at least 1, got \"{value}\""
+            ))),
+        })
+        .transpose()
+}
+
+fn flavor(name: &str) -> Result<RuntimeFlavor> {
+    match var(name) {
+        Some(value) => RuntimeFlavor::parse(value.trim()).ok_or_else(|| {
+            Error::Settings(format!(
+                "{name} must be multiThread or currentThread, got \"{value}\""
+            ))
+        }),
+        None => Ok(RuntimeFlavor::default()),
+    }
+}
+
+impl EnvConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            trust: TrustLists {
+                trust_anchors: trust_list("C2PA_TRUST_ANCHORS")?,
+                user_anchors: trust_list("C2PA_USER_ANCHORS")?,
+                allowed_list: trust_list("C2PA_ALLOWED_LIST")?,
+                trust_config: trust_list("C2PA_TRUST_CONFIG")?,
+            },
+            verify: VerifyOptions {
+                verify_trust: flag("C2PA_VERIFY_TRUST")?,
+                remote_manifest_fetch: flag("C2PA_REMOTE_MANIFEST_FETCH")?,
+                ocsp_fetch: flag("C2PA_OCSP_FETCH")?,
+                ..Default::default()
+            },
+            tsa_url: var("C2PA_TSA_URL"),
+            runtime: RuntimeConfig {
+                flavor: flavor("C2PA_RUNTIME_FLAVOR")?,
+                worker_threads: count("C2PA_WORKER_THREADS")?,
+                max_blocking_threads: count("C2PA_MAX_BLOCKING_THREADS")?,
+                batch_concurrency: count("C2PA_BATCH_CONCURRENCY")?,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+/// The configuration from the environment, read on first use. Fails, every
+/// time, when a variable is invalid or names a file that can't be read.
+fn config() -> Result<Arc<EnvConfig>> {
+    if let Some(config) = ENV_CONFIG.read()?.as_ref() {
+        return config.clone().map_err(Error::Settings);
+    }
+    let mut config = ENV_CONFIG.write()?;
+    let config = config.get_or_insert_with(|| {
+        EnvConfig::from_env()
+            .map(Arc::new)
+            .map_err(|err| match err {
+                Error::Settings(message) => message,
+                err => err.to_string(),
+            })
+    });
+    config.clone().map_err(Error::Settings)
+}
+
+/// Forget the configuration, so the environment is read again on next use.
+pub fn reset() -> Result<()> {
+    *ENV_CONFIG.write()? = None;
+    Ok(())
+}
+
+/// Layer the trust lists and verify toggles from the environment over
+/// `settings`.
+pub fn apply(settings: &mut Settings) -> Result<()> {
+    let config = config()?;
+    config.trust.apply(&mut settings.trust);
+    config.verify.apply(settings)
+}
+
+/// The time stamp authority for signers created without one. None when the
+/// environment is invalid, which fails the operations that use settings.
+pub fn tsa_url() -> Option<String> {
+    config().ok().and_then(|config| config.tsa_url.clone())
+}
+
+/// The runtime configuration to use when `configureRuntime` wasn't called.
+pub fn runtime_config() -> Result<RuntimeConfig> {
+    Ok(config()?.runtime.clone())
+}
@@ -11,11 +11,18 @@
 // specific language governing permissions and limitations under
 // each license.
 
+use std::any::Any;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::PoisonError;
 
+use futures::FutureExt;
 use neon::prelude::*;
 use thiserror::Error;
 
+use crate::network::{classify_offline, NetworkFailure};
+use crate::validation::jumbf_labels;
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Asset parsing failed: {0}")]
@@ -27,18 +34,33 @@ pub enum Error {
     #[error(transparent)]
     C2pa(#[from] c2pa::Error),
 
+    #[error("Certificate parsing failed: {0}")]
+    Certificate(String),
+
+    #[error("The {0} has been disposed")]
+    Disposed(&'static str),
+
     #[error(transparent)]
     FileIO(#[from] std::io::Error),
 
     #[error(transparent)]
     Image(#[from] image::ImageError),
 
+    #[error("Internal error: {0}")]
+    Internal(String),
+
+    #[error("{0}")]
+    InvalidArgument(String),
+
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 
     #[error("Lock acquisition failed: {0}")]
     Lock(String),
 
+    #[error("The manifest data differs from the manifest store embedded in the asset")]
+    ManifestDataMismatch,
+
     #[error(transparent)]
     NeonSerde(#[from] neon_serde4::errors::Error),
 
@@ -48,15 +70,42 @@ pub enum Error {
     #[error(transparent)]
     RemoteManifestFetch(#[from] reqwest::Error),
 
+    #[error("{source}")]
+    Network {
+        source: Box<Error>,
+        failure: NetworkFailure,
+    },
+
+    #[error("Network access is disabled in offline mode: {0}")]
+    Offline(String),
+
     #[error("Settings handling failed: {0}")]
     Settings(String),
 
+    #[error("The native runtime has been shut down")]
+    ShutDown,
+
+    #[error("The operation timed out after {0} ms")]
+    Timeout(u64),
+
     #[error("Reading failed: {0}")]
     Reading(String),
 
+    #[error("{0} assets can be read but not signed")]
+    ReadOnlyFormat(String),
+
+    #[error("A sidecar already exists at {0}")]
+    SidecarExists(String),
+
     #[error("Signing failed: {0}")]
     Signing(String),
 
+    #[error("Invalid signing key: {0}")]
+    SigningKey(String),
+
+    #[error("Soft binding plugin failed: {0}")]
+    SoftBinding(String),
+
     #[error(transparent)]
     TokioJoin(#[from] tokio::task::JoinError),
 
@@ -89,19 +138,330 @@ unsafe impl Sync for Error {}
 #[allow(dead_code)]
 pub type Result<T> = std::result::Result<T, Error>;
 
-pub fn as_js_error<'a>(cx: &mut TaskContext<'a>, err: Error) -> JsResult<'a, JsError> {
+fn panic_error(panic: Box<dyn Any + Send>) -> Error {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    Error::Internal(message)
+}
+
+/// Run a task on the runtime, turning a panic into `Error::Internal` so its
+/// promise is still rejected with an error instead of tearing down the task.
+pub async fn catch_panic<T>(task: impl Future<Output = Result<T>>) -> Result<T> {
+    AssertUnwindSafe(task)
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|panic| Err(panic_error(panic)))
+}
+
+/// Like `catch_panic`, for work run on the Node.js thread pool with `cx.task`.
+pub fn catch_panic_sync<T>(task: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(task)).unwrap_or_else(|panic| Err(panic_error(panic)))
+}
+
+impl Error {
+    /// A stable identifier for the kind of failure, so callers can branch on it
+    /// instead of matching messages.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Asset(_) => "ASSET_INVALID",
+            Error::AsyncSigning(_) | Error::Signing(_) => "SIGNING_FAILED",
+            Error::C2pa(err) => c2pa_code(err),
+            Error::Certificate(_) => "CERTIFICATE_INVALID",
+            Error::Disposed(_) => "DISPOSED",
+            Error::FileIO(_) => "FILE_IO",
+            Error::Image(image::ImageError::Unsupported(_)) => "UNSUPPORTED_FORMAT",
+            Error::Image(_) => "IMAGE_INVALID",
+            Error::Internal(_) | Error::TokioJoin(_) => "INTERNAL",
+            Error::InvalidArgument(_) => "INVALID_ARGUMENT",
+            Error::Json(_) => "JSON_INVALID",
+            Error::Lock(_) | Error::TokioLock(_) => "LOCK_FAILED",
+            Error::ManifestDataMismatch => "MANIFEST_DATA_MISMATCH",
+            Error::NeonSerde(_) => "CONVERSION_FAILED",
+            Error::ModelDownload(_) => "MODEL_DOWNLOAD_FAILED",
+            Error::RemoteManifestFetch(_) => "REMOTE_FETCH_FAILED",
+            Error::Network { source, .. } => source.code(),
+            Error::Offline(_) => "NETWORK_OFFLINE",
+            Error::Settings(_) => "SETTINGS_INVALID",
+            Error::ShutDown => "RUNTIME_SHUT_DOWN",
+            Error::Timeout(_) | Error::TokioTimeout(_) => "TIMEOUT",
+            Error::Reading(_) => "READ_FAILED",
+            Error::ReadOnlyFormat(_) => "UNSUPPORTED_FORMAT",
+            Error::SidecarExists(_) => "SIDECAR_EXISTS",
+            Error::SigningKey(_) => "SIGNING_KEY_INVALID",
+            Error::SoftBinding(_) => "SOFT_BINDING_FAILED",
+            Error::Watermark(_) => "WATERMARK_FAILED",
+            Error::WatermarkConfiguration(_) => "WATERMARK_CONFIG_INVALID",
+            Error::UTF8(_) => "UTF8_INVALID",
+        }
+    }
+
+    /// The name of the variant that caused the error, taken from the c2pa-rs
+    /// error when there is one.
+    pub fn variant(&self) -> String {
+        let debug = match self {
+            Error::C2pa(err) => format!("{err:?}"),
+            Error::Network { source, .. } => return source.variant(),
+            _ => format!("{self:?}"),
+        };
+        debug
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// Where in the manifest store an error happened, when the error says.
+#[derive(Debug, Default)]
+pub struct ErrorContext {
+    pub manifest_label: Option<String>,
+    pub assertion: Option<String>,
+    pub uri: Option<String>,
+}
+
+impl ErrorContext {
+    /// Fill in the manifest and assertion labels from a JUMBF URI.
+    fn from_uri(uri: &str) -> Self {
+        let (manifest_label, assertion) = jumbf_labels(uri);
+        ErrorContext {
+            manifest_label,
+            assertion,
+            uri: Some(uri.to_string()),
+        }
+    }
+}
+
+impl Error {
+    /// The manifest label, assertion label and URI an error refers to.
+    pub fn context(&self) -> ErrorContext {
+        use c2pa::Error::*;
+        if let Error::Network { source, .. } = self {
+            return source.context();
+        }
+        let Error::C2pa(err) = self else {
+            return ErrorContext::default();
+        };
+        match err {
+            ClaimMissing { label } => ErrorContext {
+                manifest_label: Some(label.clone()),
+                ..Default::default()
+            },
+            AssertionDecoding(err) => ErrorContext {
+                assertion: Some(err.label.clone()),
+                ..Default::default()
+            },
+            AssertionMissing { url } => ErrorContext::from_uri(url),
+            ResourceNotFound(uri) | RemoteManifestFetch(uri) | RemoteManifestUrl(uri) => {
+                ErrorContext::from_uri(uri)
+            }
+            _ => ErrorContext::default(),
+        }
+    }
+}
+
+fn c2pa_code(err: &c2pa::Error) -> &'static str {
+    use c2pa::Error::*;
+    match err {
+        JumbfNotFound => "C2PA_JUMBF_NOT_FOUND",
+        JumbfBoxNotFound => "C2PA_JUMBF_BOX_NOT_FOUND",
+        JumbfParseError(_) | JumbfCreationError | MissingDataBox => "C2PA_JUMBF_INVALID",
+        ProvenanceMissing => "C2PA_PROVENANCE_MISSING",
+        ClaimMissing { .. } => "C2PA_CLAIM_MISSING",
+        ClaimEncoding
+        | ClaimDecoding(_)
+        | ClaimAlreadySigned
+        | ClaimUnsigned
+        | ClaimMissingSignatureBox
+        | ClaimMissingIdentity
+        | ClaimVersion
+        | ClaimInvalidContent
+        | ClaimMissingHardBinding
+        | ClaimMultipleHardBinding
+        | ClaimSelfRedact
+        | ClaimDisallowedRedaction
+        | InvalidClaim(_)
+        | UpdateManifestInvalid
+        | TooManyManifestStores
+        | UnreferencedManifest
+        | CyclicIngredients { .. } => "C2PA_CLAIM_INVALID",
+        ClaimVerification(_) => "C2PA_CLAIM_VERIFICATION_FAILED",
+        C2PAValidation(_) => "C2PA_VALIDATION_FAILED",
+        HashMismatch(_) => "C2PA_HASH_MISMATCH",
+        AssertionMissing { .. } => "C2PA_ASSERTION_MISSING",
+        AssertionUnsupportedVersion
+        | AssertionEncoding(_)
+        | AssertionDecoding(_)
+        | AssertionInvalidRedaction
+        | AssertionRedactionNotFound
+        | AssertionSpecificError(_)
+        | VerifiableCredentialInvalid => "C2PA_ASSERTION_INVALID",
+        CoseCertExpiration => "SIGNING_CERT_EXPIRED",
+        CoseCertRevoked => "SIGNING_CERT_REVOKED",
+        CoseCertUntrusted | CertificateTrustError(_) => "SIGNING_CERT_UNTRUSTED",
+        CoseInvalidCert
+        | CoseX5ChainMissing
+        | CoseNoCerts
+        | CertificateProfileError(_)
+        | InvalidCertificateError(_) => "SIGNING_CERT_INVALID",
+        InvalidCoseSignature { .. }
+        | CoseSignatureAlgorithmNotSupported
+        | CoseMissingKey
+        | CoseSignature
+        | CoseVerifier
+        | InvalidEcdsaSignature
+        | RawSignatureValidationError(_) => "SIGNATURE_INVALID",
+        CoseInvalidTimeStamp
+        | CoseTimeStampValidity
+        | CoseTimeStampMismatch
+        | CoseTimeStampGeneration
+        | CoseTimeStampAuthority
+        | TimeStampError(_) => "TIME_STAMP_FAILED",
+        CoseSigboxTooSmall => "SIGNATURE_TOO_LARGE",
+        InvalidSigningKey => "SIGNING_KEY_INVALID",
+        RawSignerError(_) | FailedToRemoteSign | UnknownAlgorithm | MissingSignerSettings => {
+            "SIGNING_FAILED"
+        }
+        RemoteManifestFetch(_) | HttpError(_) | HttpResolverError(_) | FailedToFetchSettings => {
+            "REMOTE_FETCH_FAILED"
+        }
+        RemoteManifestUrl(_) => "REMOTE_MANIFEST_NOT_FETCHED",
+        UnsupportedType | UnsupportedThumbnailFormat(_) | XmpNotSupported => "UNSUPPORTED_FORMAT",
+        InvalidAsset(_) | EmbeddingError | XmpReadError(_) | XmpWriteError(_) | XmlWriteError
+        | PdfReadError | BmffError(_) | GifError(_) | JpegError(_) | Mp3Error(_) | PngError(_)
+        | RiffError(_) | SvgError(_) | TiffError(_) => "ASSET_INVALID",
+        #[cfg(feature = "pdf")]
+        PdfError(_) => "ASSET_INVALID",
+        NotFound | FileNotFound(_) | IngredientNotFound => "NOT_FOUND",
+        ResourceNotFound(_) => "RESOURCE_NOT_FOUND",
+        BadParam(_) | OutOfRange | MissingAutoCreatedActionSourceType => "INVALID_ARGUMENT",
+        NotImplemented(_) | MissingFeature(_) | VersionCompatibility(_) | PrereleaseError => {
+            "UNSUPPORTED"
+        }
+        IoError(_) => "FILE_IO",
+        JsonError(_) => "JSON_INVALID",
+        CborError(_) => "CBOR_INVALID",
+        Utf8Error(_) => "UTF8_INVALID",
+        InsufficientMemory => "INSUFFICIENT_MEMORY",
+        _ => "C2PA_ERROR",
+    }
+}
+
+/// The deepest cause kept in the `cause` chain.
+const MAX_CAUSES: usize = 8;
+
+/// Set the `code`, `operation` and `variant` properties that make up the
+/// error's structured form, the `context` it happened in, the request behind
+/// it for `network`, and its `cause` chain down to the low-level error.
+fn set_error_details<'a, C: Context<'a>>(
+    cx: &mut C,
+    js_err: Handle<'a, JsError>,
+    operation: &str,
+    err: &Error,
+) -> NeonResult<()> {
+    let js_err_name = cx.string(format!("{err:?}"));
+    js_err.set(cx, "name", js_err_name)?;
+    let code = cx.string(err.code());
+    js_err.set(cx, "code", code)?;
+    let js_operation = cx.string(operation);
+    js_err.set(cx, "operation", js_operation)?;
+    let variant = cx.string(err.variant());
+    js_err.set(cx, "variant", variant)?;
+
+    let context = err.context();
+    let js_context = cx.empty_object();
+    let js_operation = cx.string(operation);
+    js_context.set(cx, "operation", js_operation)?;
+    for (key, value) in [
+        ("manifestLabel", &context.manifest_label),
+        ("assertion", &context.assertion),
+        ("uri", &context.uri),
+    ] {
+        if let Some(value) = value {
+            let value = cx.string(value);
+            js_context.set(cx, key, value)?;
+        }
+    }
+    js_err.set(cx, "context", js_context)?;
+
+    if let Error::Network { failure, .. } = err {
+        let js_network = cx.empty_object();
+        let endpoint = cx.string(failure.endpoint.name());
+        js_network.set(cx, "endpoint", endpoint)?;
+        let url = cx.string(&failure.url);
+        js_network.set(cx, "url", url)?;
+        let reason = cx.string(failure.reason.name());
+        js_network.set(cx, "reason", reason)?;
+        if let Some(status) = failure.status {
+            let status = cx.number(status);
+            js_network.set(cx, "status", status)?;
+        }
+        let attempts = cx.number(failure.attempts);
+        js_network.set(cx, "attempts", attempts)?;
+        let message = cx.string(&failure.message);
+        js_network.set(cx, "message", message)?;
+        js_err.set(cx, "network", js_network)?;
+    }
+
+    // Transparent variants repeat their source's message, so skip causes that
+    // add nothing.
+    let mut messages = Vec::new();
+    let mut last = err.to_string();
+    let mut source = std::error::Error::source(err);
+    while let Some(cause) = source {
+        let message = cause.to_string();
+        if message != last && messages.len() < MAX_CAUSES {
+            messages.push(message.clone());
+        }
+        last = message;
+        source = cause.source();
+    }
+    let mut parent = js_err;
+    for message in messages {
+        let cause = cx.error(message)?;
+        parent.set(cx, "cause", cause)?;
+        parent = cause;
+    }
+    Ok(())
+}
+
+/// Convert `err`, raised by the JS-facing `operation` such as "Reader.fromAsset",
+/// into a JS error.
+pub fn as_js_error<'a>(
+    cx: &mut TaskContext<'a>,
+    operation: &str,
+    err: Error,
+) -> JsResult<'a, JsError> {
+    let err = classify_offline(err);
     cx.execute_scoped(|mut cx| {
         let js_err = cx.error(err.to_string())?;
-        let js_err_name = cx.string(format!("{err:?}"));
-        js_err.set(&mut cx, "name", js_err_name)?;
-
+        set_error_details(&mut cx, js_err, operation, &err)?;
         Ok(js_err)
     })
 }
 
-pub fn as_js_error_fn<'a>(cx: &mut FunctionContext<'a>, err: Error) -> JsResult<'a, JsError> {
+pub fn as_js_error_fn<'a>(
+    cx: &mut FunctionContext<'a>,
+    operation: &str,
+    err: Error,
+) -> JsResult<'a, JsError> {
+    let err = classify_offline(err);
     let js_err = cx.error(err.to_string())?;
-    let js_err_name = cx.string(format!("{err:?}"));
-    js_err.set(cx, "name", js_err_name)?;
+    set_error_details(cx, js_err, operation, &err)?;
     Ok(js_err)
 }
+
+/// Throw `err`, raised by the JS-facing `operation`, as a JS error with the
+/// same structured form as `as_js_error`.
+pub fn throw_js_error<'a, C: Context<'a>, T>(
+    cx: &mut C,
+    operation: &str,
+    err: impl Into<Error>,
+) -> NeonResult<T> {
+    let err = classify_offline(err.into());
+    let js_err = cx.error(err.to_string())?;
+    set_error_details(cx, js_err, operation, &err)?;
+    cx.throw(js_err)
+}
@@ -0,0 +1,279 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Reading fragmented MP4 content from an initialization segment and its
+//! fragments, each checked against the Merkle tree of the BMFF hash.
+
+use std::cmp::Ordering;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use c2pa::{Reader, ValidationState};
+use serde::Serialize;
+
+use crate::asset::Asset;
+use crate::bmff_merkle::{MerkleLeafReport, MerkleTreeReport, MerkleTrees};
+use crate::error::{Error, Result};
+use crate::validation::{validation_failures, ValidationMessage};
+use crate::validation_policy::validation_state;
+
+/// Read the manifest store of an initialization segment and validate a
+/// fragment against it, or read `fragment` on its own when there is no
+/// initialization segment.
+pub async fn read_fragment(
+    context: &Arc<c2pa::Context>,
+    format: &str,
+    init: Option<&[u8]>,
+    fragment: impl Read + std::io::Seek + Send,
+) -> Result<Reader> {
+    let reader = Reader::from_shared_context(context);
+    Ok(match init {
+        Some(init) => {
+            reader
+                .with_fragment_async(format, Cursor::new(init), fragment)
+                .await?
+        }
+        None => reader.with_stream_async(format, fragment).await?,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FragmentReport {
+    /// The position of the fragment in the list, or in the sorted glob matches.
+    index: usize,
+    /// The matched file, when fragments are given as a glob.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    validation_state: ValidationState,
+    failures: Vec<ValidationMessage>,
+    /// Where the fragment's leaves fit in the Merkle trees, when the
+    /// initialization segment has any.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    merkle: Vec<MerkleLeafReport>,
+    /// Why the fragment's leaves couldn't be found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merkle_error: Option<String>,
+    /// Why the fragment couldn't be read. The fragment is then invalid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl FragmentReport {
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || self.validation_state == ValidationState::Invalid
+    }
+}
+
+/// Fragments given as assets, or as a glob of files.
+pub enum Fragments {
+    Assets(Vec<Asset>),
+    Glob(String),
+}
+
+impl Fragments {
+    /// The fragment assets, with their paths when they came from a glob.
+    fn into_assets(self) -> Result<Vec<(Asset, Option<String>)>> {
+        match self {
+            Fragments::Assets(assets) => {
+                Ok(assets.into_iter().map(|asset| (asset, None)).collect())
+            }
+            Fragments::Glob(pattern) => Ok(glob_files(&pattern)?
+                .into_iter()
+                .map(|path| {
+                    let path = path.to_string_lossy().into_owned();
+                    (Asset::File(path.clone(), None), Some(path))
+                })
+                .collect()),
+        }
+    }
+}
+
+/// Read an initialization segment and validate each fragment against it.
+/// The Reader returned has the validation results of the first fragment that
+/// failed, or of the last fragment when none did, and is None when no
+/// fragment could be read at all. The Merkle trees of the initialization
+/// segment's BMFF hash are returned too, when it has any.
+pub async fn read_fragments(
+    context: Arc<c2pa::Context>,
+    init: Asset,
+    fragments: Fragments,
+) -> Result<(
+    Option<Reader>,
+    Vec<FragmentReport>,
+    Option<MerkleTreeReport>,
+)> {
+    let (format, mut stream) = init.into_typed_read_stream()?;
+    let format = format.ok_or_else(|| {
+        Error::Reading("Initialization segment must have a mime type".to_string())
+    })?;
+    let mut init = Vec::new();
+    stream.read_to_end(&mut init)?;
+    // A malformed manifest store is reported by each fragment's read instead.
+    let trees = MerkleTrees::from_init(&format, &init).unwrap_or(None);
+
+    let fragments = fragments.into_assets()?;
+    if fragments.is_empty() {
+        return Err(Error::Reading("No fragments were given".to_string()));
+    }
+    let mut reader = None;
+    let mut first_failure = None;
+    let mut reports = Vec::with_capacity(fragments.len());
+    for (index, (fragment, path)) in fragments.into_iter().enumerate() {
+        let mut data = Vec::new();
+        let result = match fragment.into_typed_read_stream() {
+            Ok((_, mut stream)) => match stream.read_to_end(&mut data) {
+                Ok(_) => read_fragment(&context, &format, Some(&init), Cursor::new(&data)).await,
+                Err(err) => Err(err.into()),
+            },
+            Err(err) => Err(err),
+        };
+        let mut report = match &result {
+            Ok(fragment_reader) => FragmentReport {
+                index,
+                path,
+                validation_state: validation_state(fragment_reader),
+                failures: validation_failures(fragment_reader),
+                merkle: Vec::new(),
+                merkle_error: None,
+                error: None,
+            },
+            Err(err) => FragmentReport {
+                index,
+                path,
+                validation_state: ValidationState::Invalid,
+                failures: Vec::new(),
+                merkle: Vec::new(),
+                merkle_error: None,
+                error: Some(err.to_string()),
+            },
+        };
+        if let (Some(trees), false) = (&trees, data.is_empty()) {
+            match trees.check(&data) {
+                Ok(leaves) => report.merkle = leaves,
+                Err(err) => report.merkle_error = Some(err.to_string()),
+            }
+        }
+        if let Ok(fragment_reader) = result {
+            if first_failure.is_none() {
+                if report.failed() {
+                    first_failure = Some(fragment_reader);
+                } else {
+                    reader = Some(fragment_reader);
+                }
+            }
+        }
+        reports.push(report);
+    }
+    let trees = trees.map(|trees| trees.into_report());
+    Ok((first_failure.or(reader), reports, trees))
+}
+
+/// The files matching a glob whose wildcards (`*` and `?`) are in the file
+/// name, such as `segments/seg-*.m4s`, in natural order so `seg-10` follows
+/// `seg-9`.
+fn glob_files(pattern: &str) -> Result<Vec<PathBuf>> {
+    let pattern_path = Path::new(pattern);
+    let name = pattern_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| Error::Reading(format!("Invalid fragment glob: {pattern}")))?;
+    let dir = match pattern_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    if dir.to_string_lossy().contains(['*', '?']) {
+        return Err(Error::Reading(format!(
+            "Only the file name of a fragment glob can have wildcards: {pattern}"
+        )));
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_file() && wildcard_match(&name, &file_name) {
+            files.push((file_name, entry.path()));
+        }
+    }
+    if files.is_empty() {
+        return Err(Error::Reading(format!("No fragments match {pattern}")));
+    }
+    files.sort_by(|(a, _), (b, _)| natural_cmp(a, b));
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // The positions after the last `*` and what it matched, to backtrack to.
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&ch) if ch == '?' || ch == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
+/// Compare names with runs of digits compared as numbers.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let a_len = a.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(a.len());
+                let b_len = b.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(b.len());
+                let (a_digits, b_digits) = (
+                    a[..a_len].trim_start_matches('0'),
+                    b[..b_len].trim_start_matches('0'),
+                );
+                let ordering = a_digits
+                    .len()
+                    .cmp(&b_digits.len())
+                    .then_with(|| a_digits.cmp(b_digits));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a = &a[a_len..];
+                b = &b[b_len..];
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a = &a[x.len_utf8()..];
+                b = &b[y.len_utf8()..];
+            }
+        }
+    }
+}
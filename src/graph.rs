@@ -0,0 +1,221 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt::Write;
+
+use c2pa::{Ingredient, Manifest, Reader, Relationship};
+use serde::Serialize;
+
+#[derive(Clone, Copy)]
+pub enum GraphFormat {
+    Json,
+    Dot,
+}
+
+impl GraphFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(GraphFormat::Json),
+            "dot" => Some(GraphFormat::Dot),
+            _ => None,
+        }
+    }
+}
+
+/// The manifests in a Reader's manifest store and the ingredients they were
+/// made from. Each edge points from an ingredient to the manifest that uses
+/// it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceGraph {
+    /// The active manifest first, then the manifests of its ingredients in the
+    /// order they were added, then ingredients without a manifest.
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum NodeKind {
+    Manifest,
+    /// An ingredient without a manifest in the store.
+    Ingredient,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Node {
+    /// The manifest label, or `<manifest label>/ingredients/<index>` for an
+    /// ingredient without a manifest.
+    id: String,
+    kind: NodeKind,
+    title: Option<String>,
+    format: Option<String>,
+    signer: Option<String>,
+    active: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Edge {
+    from: String,
+    to: String,
+    relationship: Relationship,
+    /// The ingredient's title as the manifest using it records it.
+    title: Option<String>,
+}
+
+impl ProvenanceGraph {
+    pub fn new(reader: &Reader) -> Self {
+        let mut labels: Vec<&str> = reader.active_label().into_iter().collect();
+        let mut edges = Vec::new();
+        let mut ingredient_nodes = Vec::new();
+        let mut next = 0;
+        while let Some(label) = labels.get(next).copied() {
+            next += 1;
+            let Some(manifest) = reader.get_manifest(label) else {
+                continue;
+            };
+            for (index, ingredient) in manifest.ingredients().iter().enumerate() {
+                let from = match ingredient.active_manifest() {
+                    Some(ingredient_label) if reader.get_manifest(ingredient_label).is_some() => {
+                        if !labels.contains(&ingredient_label) {
+                            labels.push(ingredient_label);
+                        }
+                        ingredient_label.to_string()
+                    }
+                    _ => {
+                        let id = format!("{label}/ingredients/{index}");
+                        ingredient_nodes.push(Node::ingredient(id.clone(), ingredient));
+                        id
+                    }
+                };
+                edges.push(Edge {
+                    from,
+                    to: label.to_string(),
+                    relationship: ingredient.relationship().clone(),
+                    title: ingredient.title().map(str::to_string),
+                });
+            }
+        }
+        // Manifests no ingredient refers to, such as those of redacted
+        // ingredients, are still part of the store.
+        let mut unreached: Vec<&str> = reader
+            .manifests()
+            .keys()
+            .map(String::as_str)
+            .filter(|label| !labels.contains(label))
+            .collect();
+        unreached.sort_unstable();
+        labels.extend(unreached);
+
+        let active = reader.active_label();
+        let nodes = labels
+            .iter()
+            .filter_map(|label| {
+                let manifest = reader.get_manifest(label)?;
+                Some(Node::manifest(label, manifest, active == Some(*label)))
+            })
+            .chain(ingredient_nodes)
+            .collect();
+        Self { nodes, edges }
+    }
+
+    pub fn render(&self, format: GraphFormat) -> serde_json::Result<String> {
+        match format {
+            GraphFormat::Json => serde_json::to_string(self),
+            GraphFormat::Dot => Ok(self.dot()),
+        }
+    }
+
+    fn dot(&self) -> String {
+        let mut out = String::from("digraph provenance {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let mut label = node.title.clone().unwrap_or_else(|| node.id.clone());
+            if let Some(signer) = &node.signer {
+                let _ = write!(label, "\nsigned by {signer}");
+            }
+            let shape = match node.kind {
+                NodeKind::Manifest => "box",
+                NodeKind::Ingredient => "ellipse",
+            };
+            let style = if node.active { ", style=bold" } else { "" };
+            let _ = writeln!(
+                out,
+                "  {} [label={}, shape={shape}{style}];",
+                quote(&node.id),
+                quote(&label)
+            );
+        }
+        for edge in &self.edges {
+            let relationship = match edge.relationship {
+                Relationship::ParentOf => "parentOf",
+                Relationship::ComponentOf => "componentOf",
+                Relationship::InputTo => "inputTo",
+            };
+            let _ = writeln!(
+                out,
+                "  {} -> {} [label={}];",
+                quote(&edge.from),
+                quote(&edge.to),
+                quote(relationship)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl Node {
+    fn manifest(label: &str, manifest: &Manifest, active: bool) -> Self {
+        let signer = manifest
+            .signature_info()
+            .and_then(|info| info.common_name.clone().or_else(|| info.issuer.clone()));
+        Self {
+            id: label.to_string(),
+            kind: NodeKind::Manifest,
+            title: manifest.title().map(str::to_string),
+            format: manifest.format().map(str::to_string),
+            signer,
+            active,
+        }
+    }
+
+    fn ingredient(id: String, ingredient: &Ingredient) -> Self {
+        Self {
+            id,
+            kind: NodeKind::Ingredient,
+            title: ingredient.title().map(str::to_string),
+            format: ingredient.format().map(str::to_string),
+            signer: None,
+            active: false,
+        }
+    }
+}
+
+/// A DOT string literal.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,188 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use c2pa::assertions::labels::DATA_HASH;
+use c2pa::jumbf_io::load_jumbf_from_stream;
+use c2pa::{hash_stream_by_alg, HashRange};
+use neon::prelude::*;
+use serde::Deserialize;
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::jumbf::{jumbf_boxes, superboxes};
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_timeout};
+
+/// Hash algorithms c2pa-rs can compute data hashes with.
+const HASH_ALGORITHMS: [&str; 3] = ["sha256", "sha384", "sha512"];
+
+/// The fields of a `c2pa.hash.data` assertion needed to recompute its hash.
+#[derive(Deserialize)]
+struct DataHash {
+    exclusions: Option<Vec<HashRange>>,
+    name: Option<String>,
+    alg: Option<String>,
+    #[serde(with = "serde_bytes")]
+    hash: Vec<u8>,
+}
+
+/// The data hash assertion of the active manifest in a manifest store, which
+/// is the last manifest in the store.
+fn active_data_hash(store: &[u8]) -> Result<Option<DataHash>> {
+    let Some((_, manifests)) = superboxes(store).next() else {
+        return Err(Error::Reading(
+            "The manifest store is malformed".to_string(),
+        ));
+    };
+    let Some((_, manifest)) = superboxes(manifests).last() else {
+        return Ok(None);
+    };
+    let assertions = superboxes(manifest)
+        .find(|(label, _)| label == "c2pa.assertions")
+        .map(|(_, assertions)| assertions);
+    // Hash assertions may carry an instance suffix, like `c2pa.hash.data__1`.
+    let cbor = assertions
+        .and_then(|assertions| {
+            superboxes(assertions).find(|(label, _)| label.starts_with(DATA_HASH))
+        })
+        .and_then(|(_, data_hash)| jumbf_boxes(data_hash).find(|b| b.kind == b"cbor"));
+    let Some(cbor) = cbor else {
+        return Ok(None);
+    };
+    ciborium::from_reader(cbor.payload)
+        .map(Some)
+        .map_err(|err| Error::Reading(format!("The data hash assertion is malformed: {err}")))
+}
+
+/// The hash algorithm argument at `arg_index`, sha256 when it's missing.
+pub fn parse_algorithm(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<String> {
+    let alg = match cx.argument_opt(arg_index) {
+        Some(alg) if alg.is_a::<JsString, _>(cx) => {
+            alg.downcast_or_throw::<JsString, _>(cx)?.value(cx)
+        }
+        _ => return Ok(HASH_ALGORITHMS[0].to_string()),
+    };
+    if !HASH_ALGORITHMS.contains(&alg.as_str()) {
+        return cx.throw_range_error(format!(
+            "Unknown hash algorithm \"{alg}\". Expected sha256, sha384, or sha512"
+        ));
+    }
+    Ok(alg)
+}
+
+/// Hash an asset the way a data hash assertion does, skipping the `exclusions`
+/// given as JSON `{ start, length }` ranges.
+pub fn hash_asset(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let alg = parse_algorithm(&mut cx, 1)?;
+    let exclusions: Vec<HashRange> = match cx.argument_opt(2) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => {
+            let json = json
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            serde_json::from_str(&json).or_else(|err| cx.throw_error(err.to_string()))?
+        }
+        _ => Vec::new(),
+    };
+    let timeout = parse_timeout(&mut cx, 3).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 4).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let mut stream = source.into_read_stream()?;
+            Ok(hash_stream_by_alg(
+                &alg,
+                &mut stream,
+                Some(exclusions),
+                true,
+            )?)
+        });
+        let result: Result<Vec<u8>> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(hash) => Ok(JsBuffer::from_slice(&mut cx, &hash)?),
+            Err(err) => as_js_error(&mut cx, "hashAsset", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
+
+/// The data hash assertion of the active manifest embedded in an asset, or
+/// null when the asset has no manifest or its manifest uses another kind of
+/// hard binding, such as a BMFF hash.
+pub fn read_data_hash(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let timeout = parse_timeout(&mut cx, 1).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let format = format
+                .ok_or_else(|| Error::Reading("Source asset must have a mime type".to_string()))?;
+            match load_jumbf_from_stream(&format, &mut stream) {
+                Ok(store) => active_data_hash(&store),
+                Err(c2pa::Error::JumbfNotFound) => Ok(None),
+                Err(err) => Err(err.into()),
+            }
+        });
+        let result: Result<Option<DataHash>> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(Some(data_hash)) => {
+                let obj = cx.empty_object();
+                if let Some(name) = data_hash.name {
+                    let name = cx.string(name);
+                    obj.set(&mut cx, "name", name)?;
+                }
+                // The claim's algorithm applies when the assertion names none.
+                let alg = data_hash
+                    .alg
+                    .unwrap_or_else(|| HASH_ALGORITHMS[0].to_string());
+                let alg = cx.string(alg);
+                obj.set(&mut cx, "alg", alg)?;
+                let hash = JsBuffer::from_slice(&mut cx, &data_hash.hash)?;
+                obj.set(&mut cx, "hash", hash)?;
+                let exclusions = cx.empty_array();
+                for (index, range) in data_hash.exclusions.iter().flatten().enumerate() {
+                    let exclusion = cx.empty_object();
+                    let start = cx.number(range.start() as f64);
+                    exclusion.set(&mut cx, "start", start)?;
+                    let length = cx.number(range.length() as f64);
+                    exclusion.set(&mut cx, "length", length)?;
+                    exclusions.set(&mut cx, index as u32, exclusion)?;
+                }
+                obj.set(&mut cx, "exclusions", exclusions)?;
+                Ok(obj.upcast::<JsValue>())
+            }
+            Ok(None) => Ok(cx.null().upcast::<JsValue>()),
+            Err(err) => as_js_error(&mut cx, "readDataHash", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
+
+use c2pa::{hash_stream_by_alg, Builder, Ingredient};
+use neon::prelude::*;
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::ingredient_cache;
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_settings, parse_timeout};
+
+/// An ingredient's JSON and the binary resources it refers to, such as its
+/// thumbnail and manifest data.
+struct PreparedIngredient {
+    json: String,
+    resources: Vec<(String, String, Vec<u8>)>,
+}
+
+impl PreparedIngredient {
+    fn new(ingredient: &Ingredient) -> Result<Self> {
+        let refs: Vec<_> = ingredient
+            .thumbnail_ref()
+            .into_iter()
+            .chain(ingredient.manifest_data_ref())
+            .chain(ingredient.data_ref())
+            .collect();
+        let resources = ingredient
+            .resources()
+            .resources()
+            .iter()
+            .map(|(identifier, data)| {
+                let format = refs
+                    .iter()
+                    .find(|r| &r.identifier == identifier)
+                    .map(|r| r.format.clone())
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                (identifier.clone(), format, data.clone())
+            })
+            .collect();
+        Ok(Self {
+            json: serde_json::to_string(ingredient)?,
+            resources,
+        })
+    }
+}
+
+/// Create an ingredient from an asset without a Builder. The ingredient JSON,
+/// when given, is merged with what is read from the asset and takes
+/// precedence, as with `Builder.addIngredient`. Unless the JSON gives one, the
+/// ingredient's hash is the hex SHA-256 of the asset.
+pub fn create_ingredient(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let ingredient_json = match cx.argument_opt(1) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => json
+            .downcast_or_throw::<JsString, _>(&mut cx)?
+            .value(&mut cx),
+        _ => "{}".to_string(),
+    };
+    let context_opt = parse_settings(&mut cx, 2, "createIngredient")
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    let timeout = parse_timeout(&mut cx, 3).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 4).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let format = format.ok_or_else(|| {
+                Error::Reading("Ingredient asset must have a mime type".to_string())
+            })?;
+            // The context's settings decide, for instance, whether to verify the
+            // ingredient's manifest and generate a thumbnail.
+            let context = match context_opt {
+                Some(context) => context,
+                None => Arc::clone(Builder::new().context()),
+            };
+            let mut ingredient = ingredient_cache::ingredient_from_stream(
+                &context,
+                &ingredient_json,
+                &format,
+                &mut stream,
+            )
+            .await?;
+            // A hash of the whole asset, so stored ingredients can be matched
+            // to their assets later.
+            if ingredient.hash().is_none() {
+                stream.seek(SeekFrom::Start(0))?;
+                let hash = hash_stream_by_alg("sha256", &mut stream, None, true)?;
+                let hash: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+                ingredient.set_hash(hash);
+            }
+            PreparedIngredient::new(&ingredient)
+        });
+        let result: Result<PreparedIngredient> =
+            with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(prepared) => {
+                let obj = cx.empty_object();
+                let json = cx.string(prepared.json);
+                obj.set(&mut cx, "json", json)?;
+                let resources = cx.empty_object();
+                for (identifier, format, data) in prepared.resources {
+                    let resource = cx.empty_object();
+                    let mime_type = cx.string(format);
+                    resource.set(&mut cx, "mimeType", mime_type)?;
+                    let buffer = JsBuffer::from_slice(&mut cx, &data)?;
+                    resource.set(&mut cx, "buffer", buffer)?;
+                    resources.set(&mut cx, identifier.as_str(), resource)?;
+                }
+                obj.set(&mut cx, "resources", resources)?;
+                Ok(obj)
+            }
+            Err(err) => as_js_error(&mut cx, "createIngredient", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
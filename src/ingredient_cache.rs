@@ -0,0 +1,248 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use c2pa::{hash_stream_by_alg, Builder, Ingredient};
+use neon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+const DEFAULT_MAX_MEMORY_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct IngredientCacheConfig {
+    /// Memory for cached ingredients and their resources. The least recently
+    /// used are dropped to make room.
+    max_memory_bytes: Option<u64>,
+}
+
+struct Entry {
+    ingredient: Ingredient,
+    size: u64,
+    used: u64,
+}
+
+/// Ingredients read from assets, set up by `configureIngredientCache`, so a
+/// batch of Builders adding the same ingredient validates its manifest and
+/// makes its thumbnail once.
+struct IngredientCache {
+    config: IngredientCacheConfig,
+    entries: HashMap<String, Entry>,
+    memory_bytes: u64,
+    /// Incremented on every lookup, to find the least recently used entry.
+    clock: u64,
+}
+
+static CACHE: Mutex<Option<IngredientCache>> = Mutex::new(None);
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+impl IngredientCache {
+    fn new(config: IngredientCacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            memory_bytes: 0,
+            clock: 0,
+        }
+    }
+
+    fn max_memory_bytes(&self) -> u64 {
+        self.config
+            .max_memory_bytes
+            .unwrap_or(DEFAULT_MAX_MEMORY_BYTES)
+    }
+
+    fn get(&mut self, key: &str) -> Option<Ingredient> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.used = self.clock;
+        Some(entry.ingredient.clone())
+    }
+
+    fn insert(&mut self, key: String, ingredient: Ingredient) {
+        let size = ingredient_size(&ingredient);
+        let max = self.max_memory_bytes();
+        if size > max {
+            return;
+        }
+        self.remove(&key);
+        while self.memory_bytes + size > max {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.clock += 1;
+        self.memory_bytes += size;
+        self.entries.insert(
+            key,
+            Entry {
+                ingredient,
+                size,
+                used: self.clock,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.memory_bytes -= entry.size;
+        }
+    }
+}
+
+/// The memory an ingredient holds, counting its JSON and resources.
+fn ingredient_size(ingredient: &Ingredient) -> u64 {
+    let json = serde_json::to_vec(ingredient).map_or(0, |json| json.len());
+    let resources: usize = ingredient
+        .resources()
+        .resources()
+        .values()
+        .map(Vec::len)
+        .sum();
+    (json + resources) as u64
+}
+
+fn hex_sha256<R: Read + Seek + Send + ?Sized>(stream: &mut R) -> Result<String> {
+    let hash = hash_stream_by_alg("sha256", stream, None, true)?;
+    Ok(hash.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// What an ingredient read from an asset depends on: the asset's content, its
+/// format, the ingredient JSON merged with it, and the settings it is read
+/// with.
+fn cache_key<R: Read + Seek + Send>(
+    context: &c2pa::Context,
+    ingredient_json: &str,
+    format: &str,
+    stream: &mut R,
+) -> Result<String> {
+    let content = hex_sha256(stream)?;
+    stream.rewind()?;
+    let settings = serde_json::to_string(context.settings())?;
+    let mut details = Cursor::new(format!("{format}\0{ingredient_json}\0{settings}"));
+    Ok(format!("{content}:{}", hex_sha256(&mut details)?))
+}
+
+/// Read an ingredient from an asset as `Builder.add_ingredient_from_stream`
+/// does, reusing the ingredient read from an identical asset with the same
+/// JSON and settings when the cache is on.
+pub async fn ingredient_from_stream<R: Read + Seek + Send>(
+    context: &Arc<c2pa::Context>,
+    ingredient_json: &str,
+    format: &str,
+    stream: &mut R,
+) -> Result<Ingredient> {
+    let enabled = CACHE.lock()?.is_some();
+    let key = if enabled {
+        let key = cache_key(context, ingredient_json, format, stream)?;
+        let cached = CACHE.lock()?.as_mut().and_then(|cache| cache.get(&key));
+        if let Some(ingredient) = cached {
+            HITS.fetch_add(1, Ordering::Relaxed);
+            return Ok(ingredient);
+        }
+        MISSES.fetch_add(1, Ordering::Relaxed);
+        Some(key)
+    } else {
+        None
+    };
+
+    // Read with a scratch Builder so the ingredient comes out exactly as the
+    // Builder would have added it.
+    let mut builder = Builder::from_shared_context(context);
+    let ingredient = builder
+        .add_ingredient_from_stream_async(ingredient_json, format, stream)
+        .await?
+        .clone();
+    if let Some(key) = key {
+        if let Some(cache) = CACHE.lock()?.as_mut() {
+            cache.insert(key, ingredient.clone());
+        }
+    }
+    Ok(ingredient)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IngredientCacheStats {
+    enabled: bool,
+    /// Ingredients held in memory, and their size in bytes.
+    entries: usize,
+    memory_bytes: u64,
+    /// Lookups since the process started.
+    hits: u64,
+    misses: u64,
+}
+
+/// Cache ingredients read from assets by `Builder.addIngredient` and
+/// `createIngredient`, replacing any previous cache. Passing null turns
+/// caching off.
+pub fn configure_ingredient_cache(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let config = match cx.argument_opt(0) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => {
+            let json = json
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            let config: IngredientCacheConfig = serde_json::from_str(&json).or_else(|err| {
+                cx.throw_error(format!("Invalid ingredient cache configuration: {err}"))
+            })?;
+            Some(IngredientCache::new(config))
+        }
+        _ => None,
+    };
+    let mut cache = CACHE
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    *cache = config;
+    Ok(cx.undefined())
+}
+
+/// Drop every cached ingredient, keeping the cache on.
+pub fn clear_ingredient_cache(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let mut cache = CACHE
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    if let Some(cache) = cache.as_mut() {
+        cache.entries.clear();
+        cache.memory_bytes = 0;
+    }
+    Ok(cx.undefined())
+}
+
+pub fn ingredient_cache_stats(mut cx: FunctionContext) -> JsResult<JsString> {
+    let stats = {
+        let cache = CACHE
+            .lock()
+            .or_else(|err| cx.throw_error(err.to_string()))?;
+        IngredientCacheStats {
+            enabled: cache.is_some(),
+            entries: cache.as_ref().map_or(0, |cache| cache.entries.len()),
+            memory_bytes: cache.as_ref().map_or(0, |cache| cache.memory_bytes),
+            hits: HITS.load(Ordering::Relaxed),
+            misses: MISSES.load(Ordering::Relaxed),
+        }
+    };
+    let json = serde_json::to_string(&stats).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
@@ -0,0 +1,66 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! The addon is loaded once per JavaScript thread: the main thread and each
+//! `worker_threads` Worker that requires it. The runtime, settings, and trust
+//! lists are shared by every instance in the process; anything that holds a
+//! JavaScript value is tied to the instance that created it.
+
+use neon::prelude::*;
+use neon::thread::LocalKey;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crate::logging::remove_instance_logger;
+use crate::settings::remove_instance_listeners;
+use crate::telemetry::remove_instance_span_listener;
+
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(1);
+static LOADED_INSTANCES: AtomicUsize = AtomicUsize::new(0);
+
+static INSTANCE: LocalKey<Instance> = LocalKey::new();
+
+/// Per-instance state, dropped when the instance's thread exits.
+struct Instance {
+    id: u64,
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        LOADED_INSTANCES.fetch_sub(1, Ordering::SeqCst);
+        remove_instance_listeners(self.id);
+        remove_instance_logger(self.id);
+        remove_instance_span_listener(self.id);
+    }
+}
+
+/// Record that the addon was loaded on this thread.
+pub fn register(cx: &mut ModuleContext) {
+    instance_id(cx);
+}
+
+/// Identifies the instance the current call is running in.
+pub fn instance_id<'a, C: Context<'a>>(cx: &mut C) -> u64 {
+    INSTANCE
+        .get_or_init(cx, || {
+            LOADED_INSTANCES.fetch_add(1, Ordering::SeqCst);
+            Instance {
+                id: NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed),
+            }
+        })
+        .id
+}
+
+/// Number of instances currently loaded in the process.
+pub fn loaded_instances() -> usize {
+    LOADED_INSTANCES.load(Ordering::SeqCst)
+}
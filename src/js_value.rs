@@ -0,0 +1,221 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Conversions between serde values and JS values that skip the JSON string.
+
+use neon::prelude::*;
+use neon::types::buffer::TypedArray;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// Convert a serializable value to the JS value `JSON.parse` would give for
+/// its JSON.
+pub fn to_js<'a, C, T>(cx: &mut C, value: &T) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+    T: Serialize + ?Sized,
+{
+    neon_serde4::to_value(cx, value).or_else(|err| cx.throw_error(err.to_string()))
+}
+
+/// Convert a JS value to the serde value `JSON.stringify` would give JSON
+/// for: `toJSON` is called, undefined and function properties are dropped,
+/// and non-finite numbers become null.
+pub fn from_js<'a, C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> NeonResult<Value> {
+    Converter::default().convert(cx, value)
+}
+
+/// Like [from_js], but Buffers and other `Uint8Array`s are copied out as they
+/// are. Each is replaced by its index in the returned list.
+pub fn from_js_with_buffers<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsValue>,
+) -> NeonResult<(Value, Vec<Vec<u8>>)> {
+    let mut converter = Converter {
+        buffers: Some(Vec::new()),
+        ..Default::default()
+    };
+    let value = converter.convert(cx, value)?;
+    Ok((value, converter.buffers.unwrap_or_default()))
+}
+
+/// Like [from_js], but for CBOR: Buffers and other `Uint8Array`s become byte
+/// strings rather than objects of numbered keys.
+pub fn cbor_from_js<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsValue>,
+) -> NeonResult<ciborium::Value> {
+    let mut converter = Converter {
+        buffers: Some(Vec::new()),
+        mark_buffers: true,
+        ..Default::default()
+    };
+    let value = converter.convert(cx, value)?;
+    let mut buffers = converter.buffers.unwrap_or_default();
+    Ok(cbor_value(value, &mut buffers))
+}
+
+/// Convert a JS value that may be a string of JSON or a plain object.
+pub fn json_argument<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsValue>,
+) -> NeonResult<Value> {
+    match value.downcast::<JsString, _>(cx) {
+        Ok(json) => {
+            let json = json.value(cx);
+            serde_json::from_str(&json).or_else(|err| cx.throw_error(err.to_string()))
+        }
+        Err(_) => from_js(cx, value),
+    }
+}
+
+/// Whether `value` would be left out of an object by `JSON.stringify`.
+fn is_skipped<'a, C: Context<'a>>(cx: &mut C, value: Handle<JsValue>) -> bool {
+    value.is_a::<JsUndefined, _>(cx) || value.is_a::<JsFunction, _>(cx)
+}
+
+#[derive(Default)]
+struct Converter<'a> {
+    /// The objects being converted, to catch cycles.
+    ancestors: Vec<Handle<'a, JsObject>>,
+    buffers: Option<Vec<Vec<u8>>>,
+    /// Leave each buffer's index in an object under [BUFFER_KEY], so it
+    /// can't be mistaken for a number.
+    mark_buffers: bool,
+}
+
+/// Marks where [cbor_from_js] found a buffer, under a key assertion data
+/// has no reason to use.
+const BUFFER_KEY: &str = "\0buffer";
+
+impl<'a> Converter<'a> {
+    fn convert<C: Context<'a>>(
+        &mut self,
+        cx: &mut C,
+        value: Handle<'a, JsValue>,
+    ) -> NeonResult<Value> {
+        if value.is_a::<JsNull, _>(cx) || is_skipped(cx, value) {
+            return Ok(Value::Null);
+        }
+        if let Ok(boolean) = value.downcast::<JsBoolean, _>(cx) {
+            return Ok(Value::Bool(boolean.value(cx)));
+        }
+        if let Ok(number) = value.downcast::<JsNumber, _>(cx) {
+            return Ok(number_value(number.value(cx)));
+        }
+        if let Ok(string) = value.downcast::<JsString, _>(cx) {
+            return Ok(Value::String(string.value(cx)));
+        }
+        let Ok(object) = value.downcast::<JsObject, _>(cx) else {
+            // Symbols and BigInts, which JSON can't represent.
+            return cx.throw_type_error("Value can't be converted to JSON");
+        };
+
+        if let Some(buffers) = self.buffers.as_mut() {
+            if let Ok(bytes) = object.downcast::<JsTypedArray<u8>, _>(cx) {
+                buffers.push(bytes.as_slice(cx).to_vec());
+                let index = Value::from(buffers.len() - 1);
+                if self.mark_buffers {
+                    return Ok(Value::Object(Map::from_iter([(
+                        BUFFER_KEY.to_string(),
+                        index,
+                    )])));
+                }
+                return Ok(index);
+            }
+        }
+
+        let to_json: Handle<JsValue> = object.get_value(cx, "toJSON")?;
+        if let Ok(to_json) = to_json.downcast::<JsFunction, _>(cx) {
+            let value: Handle<JsValue> = to_json
+                .call_with(cx)
+                .this(object)
+                .arg(cx.string(""))
+                .apply(cx)?;
+            return self.convert(cx, value);
+        }
+
+        for ancestor in self.ancestors.iter() {
+            if ancestor.strict_equals(cx, object) {
+                return cx.throw_type_error("Converting circular structure to JSON");
+            }
+        }
+        self.ancestors.push(object);
+        let converted = if let Ok(array) = object.downcast::<JsArray, _>(cx) {
+            let elements = array.to_vec(cx)?;
+            let mut values = Vec::with_capacity(elements.len());
+            for element in elements {
+                values.push(self.convert(cx, element)?);
+            }
+            Value::Array(values)
+        } else {
+            let keys = object.get_own_property_names(cx)?.to_vec(cx)?;
+            let mut map = Map::new();
+            for key in keys {
+                let key = key.downcast_or_throw::<JsString, _>(cx)?;
+                let property: Handle<JsValue> = object.get_value(cx, key)?;
+                if is_skipped(cx, property) {
+                    continue;
+                }
+                let property = self.convert(cx, property)?;
+                map.insert(key.value(cx), property);
+            }
+            Value::Object(map)
+        };
+        self.ancestors.pop();
+        Ok(converted)
+    }
+}
+
+/// Integers stay integers, so they deserialize into integer fields.
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < 9_007_199_254_740_992.0 {
+        Value::Number(Number::from(number as i64))
+    } else {
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// The CBOR for a value converted by [cbor_from_js], taking each marked
+/// buffer out of `buffers`.
+fn cbor_value(value: Value, buffers: &mut [Vec<u8>]) -> ciborium::Value {
+    match value {
+        Value::Null => ciborium::Value::Null,
+        Value::Bool(boolean) => ciborium::Value::Bool(boolean),
+        Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(integer), _) => ciborium::Value::Integer(integer.into()),
+            (None, Some(float)) => ciborium::Value::Float(float),
+            (None, None) => ciborium::Value::Null,
+        },
+        Value::String(string) => ciborium::Value::Text(string),
+        Value::Array(values) => ciborium::Value::Array(
+            values
+                .into_iter()
+                .map(|value| cbor_value(value, buffers))
+                .collect(),
+        ),
+        Value::Object(map) => {
+            if map.len() == 1 {
+                let index = map.get(BUFFER_KEY).and_then(Value::as_u64);
+                if let Some(bytes) = index.and_then(|index| buffers.get_mut(index as usize)) {
+                    return ciborium::Value::Bytes(std::mem::take(bytes));
+                }
+            }
+            ciborium::Value::Map(
+                map.into_iter()
+                    .map(|(key, value)| (ciborium::Value::Text(key), cbor_value(value, buffers)))
+                    .collect(),
+            )
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/// A JUMBF box: its four-character type and its contents.
+pub struct JumbfBox<'a> {
+    pub kind: &'a [u8],
+    pub payload: &'a [u8],
+    /// The size of the whole box, header included.
+    pub len: usize,
+}
+
+/// The boxes in `data`, one after another, stopping at the first malformed one.
+pub fn jumbf_boxes(mut data: &[u8]) -> impl Iterator<Item = JumbfBox<'_>> {
+    std::iter::from_fn(move || {
+        let size = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as u64;
+        let kind = data.get(4..8)?;
+        let (header_len, size) = match size {
+            0 => (8, data.len() as u64),
+            1 => (16, u64::from_be_bytes(data.get(8..16)?.try_into().ok()?)),
+            size => (8, size),
+        };
+        let size = usize::try_from(size).ok()?;
+        let payload = data.get(header_len..size)?;
+        data = &data[size..];
+        Some(JumbfBox {
+            kind,
+            payload,
+            len: size,
+        })
+    })
+}
+
+/// The label of a JUMBF superbox and its child boxes after the description box.
+pub fn superbox<'a>(jumbf_box: &JumbfBox<'a>) -> Option<(String, &'a [u8])> {
+    if jumbf_box.kind != b"jumb" {
+        return None;
+    }
+    let description = jumbf_boxes(jumbf_box.payload).next()?;
+    if description.kind != b"jumd" {
+        return None;
+    }
+    // A 16-byte type UUID and a toggles byte come before the label, which is
+    // only present when the second toggle bit is set.
+    let toggles = *description.payload.get(16)?;
+    let label = if toggles & 0x02 != 0 {
+        let label = description.payload.get(17..)?;
+        let end = label.iter().position(|&byte| byte == 0)?;
+        String::from_utf8_lossy(&label[..end]).into_owned()
+    } else {
+        String::new()
+    };
+    let children = &jumbf_box.payload[8 + description.payload.len()..];
+    Some((label, children))
+}
+
+/// The children of the superboxes in `data`, with their labels.
+pub fn superboxes(data: &[u8]) -> impl Iterator<Item = (String, &'_ [u8])> {
+    jumbf_boxes(data).filter_map(|jumbf_box| superbox(&jumbf_box))
+}
+
+/// The payload of the superbox at `path`, each step a label matched among the
+/// superboxes of the one before. This is what a hashed URI to the box hashes.
+pub fn superbox_payload<'a>(data: &'a [u8], path: &[&str]) -> Option<&'a [u8]> {
+    let (first, rest) = path.split_first()?;
+    let payload = jumbf_boxes(data)
+        .find(|jumbf_box| superbox(jumbf_box).is_some_and(|(label, _)| label == *first))?
+        .payload;
+    if rest.is_empty() {
+        return Some(payload);
+    }
+    let description = jumbf_boxes(payload).next()?;
+    superbox_payload(&payload[description.len..], rest)
+}
@@ -14,11 +14,46 @@
 use neon::prelude::*;
 
 mod asset;
+mod bmff_merkle;
+mod certificate;
+mod conformance;
+mod definition;
+mod env_config;
 mod error;
+mod fragments;
+mod graph;
+mod hashing;
+mod ingredient;
+mod ingredient_cache;
+mod instance;
+mod js_value;
+mod jumbf;
+mod logging;
+mod memory;
+mod metrics;
+mod network;
+mod network_cache;
+mod presentation;
+mod provenance_reference;
+mod remote_manifest;
+mod report;
 mod runtime;
+mod scan;
+mod settings;
+mod shared_reader;
+mod sidecar;
+mod sniff;
+mod soft_binding;
+mod store_stats;
+mod telemetry;
+mod thumbnail;
 mod utils;
+mod validation;
+mod validation_policy;
+mod version;
 
 pub mod neon_builder;
+pub mod neon_context;
 pub mod neon_credential_holder;
 pub mod neon_identity_assertion_builder;
 pub mod neon_identity_assertion_signer;
@@ -28,20 +63,29 @@ pub mod neon_trustmark;
 
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    instance::register(&mut cx);
+
     // Builder
     cx.export_function("builderNew", neon_builder::NeonBuilder::new)?;
     cx.export_function("builderWithJson", neon_builder::NeonBuilder::with_json)?;
     cx.export_function("builderSetIntent", neon_builder::NeonBuilder::set_intent)?;
+    cx.export_function("builderAddTimestamp", neon_builder::NeonBuilder::add_timestamp)?;
     cx.export_function("builderSetNoEmbed", neon_builder::NeonBuilder::set_no_embed)?;
     cx.export_function(
         "builderSetRemoteUrl",
         neon_builder::NeonBuilder::set_remote_url,
     )?;
+    cx.export_function("builderSetPlacement", neon_builder::NeonBuilder::set_placement)?;
     cx.export_function("builderAddAction", neon_builder::NeonBuilder::add_action)?;
     cx.export_function(
         "builderAddAssertion",
         neon_builder::NeonBuilder::add_assertion,
     )?;
+    cx.export_function("builderHashedUri", neon_builder::NeonBuilder::hashed_uri)?;
+    cx.export_function(
+        "builderAddSoftBindingPlugin",
+        neon_builder::NeonBuilder::add_soft_binding_plugin,
+    )?;
     cx.export_function(
         "builderAddResource",
         neon_builder::NeonBuilder::add_resource,
@@ -54,6 +98,10 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "builderAddIngredientFromAsset",
         neon_builder::NeonBuilder::add_ingredient_from_asset,
     )?;
+    cx.export_function(
+        "builderAddIngredientsFromAssets",
+        neon_builder::NeonBuilder::add_ingredients_from_assets,
+    )?;
     cx.export_function(
         "builderAddIngredientFromReader",
         neon_builder::NeonBuilder::add_ingredient_from_reader,
@@ -81,10 +129,24 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "builderUpdateManifestProperty",
         neon_builder::NeonBuilder::update_manifest_property,
     )?;
+    cx.export_function("builderDispose", neon_builder::NeonBuilder::dispose)?;
+    cx.export_function(
+        "builderSupportedMimeTypes",
+        neon_builder::NeonBuilder::supported_mime_types,
+    )?;
+    cx.export_function(
+        "builderMemoryUsage",
+        neon_builder::NeonBuilder::memory_usage,
+    )?;
 
     // Reader
     cx.export_function("readerNew", neon_reader::NeonReader::new)?;
     cx.export_function("readerFromAsset", neon_reader::NeonReader::from_stream)?;
+    cx.export_function("readerFromAssetSync", neon_reader::NeonReader::from_stream_sync)?;
+    cx.export_function("readerFromUrl", neon_reader::NeonReader::from_url)?;
+    cx.export_function("readerFromShared", neon_reader::NeonReader::from_shared)?;
+    cx.export_function("readerReleaseShared", neon_reader::NeonReader::release_shared)?;
+    cx.export_function("readerFromFragments", neon_reader::NeonReader::from_fragments)?;
     cx.export_function(
         "readerFromManifestDataAndAsset",
         neon_reader::NeonReader::from_manifest_data_and_asset,
@@ -96,10 +158,41 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("readerJson", neon_reader::NeonReader::json)?;
     cx.export_function("readerRemoteUrl", neon_reader::NeonReader::remote_url)?;
     cx.export_function("readerIsEmbedded", neon_reader::NeonReader::is_embedded)?;
+    cx.export_function(
+        "readerValidationMessages",
+        neon_reader::NeonReader::validation_messages,
+    )?;
+    cx.export_function("readerReport", neon_reader::NeonReader::report)?;
+    cx.export_function("readerSignatures", neon_reader::NeonReader::signatures)?;
+    cx.export_function(
+        "readerProvenanceGraph",
+        neon_reader::NeonReader::provenance_graph,
+    )?;
+    cx.export_function("readerConformance", neon_reader::NeonReader::conformance)?;
+    cx.export_function("readerJsonAsync", neon_reader::NeonReader::json_async)?;
+    cx.export_function(
+        "readerRemoteUrlAsync",
+        neon_reader::NeonReader::remote_url_async,
+    )?;
+    cx.export_function(
+        "readerIsEmbeddedAsync",
+        neon_reader::NeonReader::is_embedded_async,
+    )?;
     cx.export_function(
         "readerResourceToAsset",
         neon_reader::NeonReader::resource_to_asset,
     )?;
+    cx.export_function(
+        "readerExtractThumbnails",
+        neon_reader::NeonReader::extract_thumbnails,
+    )?;
+    cx.export_function("readerDispose", neon_reader::NeonReader::dispose)?;
+    cx.export_function("readerShare", neon_reader::NeonReader::share)?;
+    cx.export_function(
+        "readerSupportedMimeTypes",
+        neon_reader::NeonReader::supported_mime_types,
+    )?;
+    cx.export_function("readerMemoryUsage", neon_reader::NeonReader::memory_usage)?;
 
     // Signers
     cx.export_function("localSignerNew", neon_signer::NeonLocalSigner::new)?;
@@ -166,6 +259,14 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         "newCallbackCredentialHolder",
         neon_credential_holder::NeonCallbackCredentialHolder::from_js,
     )?;
+    cx.export_function(
+        "newVerifiableCredentialHolder",
+        neon_credential_holder::NeonVerifiableCredentialHolder::from_js,
+    )?;
+    cx.export_function(
+        "newX509CredentialHolder",
+        neon_credential_holder::NeonX509CredentialHolder::from_signer,
+    )?;
 
     // Trustmark
     cx.export_function(
@@ -175,5 +276,107 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("trustmarkEncode", neon_trustmark::NeonTrustmark::encode)?;
     cx.export_function("trustmarkDecode", neon_trustmark::NeonTrustmark::decode)?;
 
+    // Soft bindings
+    cx.export_function("softBindingNew", soft_binding::NeonSoftBinding::new)?;
+    cx.export_function("detectSoftBindings", soft_binding::detect_soft_bindings)?;
+
+    // Context
+    cx.export_function("contextNew", neon_context::NeonContext::new)?;
+    cx.export_function("contextSettings", neon_context::NeonContext::settings)?;
+
+    // Runtime
+    cx.export_function("configureRuntime", runtime::configure_runtime)?;
+    cx.export_function("shutdown", runtime::shutdown)?;
+    cx.export_function("reinitialize", runtime::reinitialize)?;
+    cx.export_function("getMetrics", metrics::get_metrics)?;
+    cx.export_function("getMemoryUsage", memory::get_memory_usage)?;
+    cx.export_function("setLogger", logging::set_logger)?;
+    cx.export_function("configureTelemetry", telemetry::configure_telemetry)?;
+    cx.export_function("setTraceParent", telemetry::set_trace_parent)?;
+    cx.export_function("flushTelemetry", telemetry::flush_telemetry)?;
+
+    cx.export_function("versionInfo", version::version_info)?;
+    cx.export_function("nativeFeatures", version::native_features)?;
+
+    // Settings
+    cx.export_function("getSettingsJson", settings::get_settings_json)?;
+    cx.export_function(
+        "configureTrustSources",
+        settings::configure_trust_sources,
+    )?;
+    cx.export_function("clearTrustSources", settings::clear_trust_sources)?;
+    cx.export_function("onSettingsChange", settings::on_settings_change)?;
+    cx.export_function("setOfflineMode", settings::set_offline_mode)?;
+    cx.export_function("isOfflineMode", settings::is_offline_mode)?;
+    cx.export_function("configureProxy", settings::configure_proxy)?;
+    cx.export_function("clearProxy", settings::clear_proxy)?;
+    cx.export_function("configureNetwork", network::configure_network)?;
+    cx.export_function(
+        "setRemoteManifestResolver",
+        remote_manifest::set_remote_manifest_resolver,
+    )?;
+    cx.export_function(
+        "configureNetworkCache",
+        network_cache::configure_network_cache,
+    )?;
+    cx.export_function("clearNetworkCache", network_cache::clear_network_cache)?;
+    cx.export_function("networkCacheStats", network_cache::network_cache_stats)?;
+    cx.export_function("offSettingsChange", settings::off_settings_change)?;
+
+    // Validation
+    cx.export_function(
+        "validationStatusCodes",
+        validation::validation_status_codes,
+    )?;
+    cx.export_function("validationStatusKind", validation::validation_status_kind)?;
+    cx.export_function(
+        "setValidationPolicy",
+        validation_policy::set_validation_policy,
+    )?;
+    cx.export_function(
+        "getValidationPolicy",
+        validation_policy::get_validation_policy,
+    )?;
+
+    // Thumbnails
+    cx.export_function("decodeThumbnail", thumbnail::decode_thumbnail)?;
+
+    // Certificates
+    cx.export_function("parseCertificates", certificate::parse_certificates)?;
+
+    // Hashing
+    cx.export_function("hashAsset", hashing::hash_asset)?;
+    cx.export_function("readDataHash", hashing::read_data_hash)?;
+    cx.export_function("manifestStoreStats", store_stats::manifest_store_stats)?;
+    cx.export_function("scanC2paRanges", scan::scan_c2pa_ranges)?;
+    cx.export_function(
+        "findProvenanceReference",
+        provenance_reference::find_provenance_reference,
+    )?;
+
+    // Fragmented presentations
+    cx.export_function("verifyPresentation", presentation::verify_presentation)?;
+
+    // Manifest definitions
+    cx.export_function(
+        "validateManifestDefinition",
+        definition::validate_manifest_definition,
+    )?;
+
+    // Ingredients
+    cx.export_function("createIngredient", ingredient::create_ingredient)?;
+    cx.export_function(
+        "configureIngredientCache",
+        ingredient_cache::configure_ingredient_cache,
+    )?;
+    cx.export_function(
+        "clearIngredientCache",
+        ingredient_cache::clear_ingredient_cache,
+    )?;
+    cx.export_function(
+        "ingredientCacheStats",
+        ingredient_cache::ingredient_cache_stats,
+    )?;
+
     Ok(())
 }
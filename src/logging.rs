@@ -0,0 +1,191 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, Once};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use neon::prelude::*;
+
+use crate::instance::instance_id;
+
+struct LogListener {
+    instance: u64,
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+}
+
+/// A log record copied out of the thread that emitted it.
+struct LogEvent {
+    level: &'static str,
+    target: String,
+    message: String,
+    module_path: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+/// Forwards records logged by c2pa-rs and its dependencies to the callback
+/// registered with `setLogger`.
+struct JsLogger {
+    listener: Mutex<Option<LogListener>>,
+}
+
+static LOGGER: JsLogger = JsLogger {
+    listener: Mutex::new(None),
+};
+static INSTALL_LOGGER: Once = Once::new();
+
+impl Log for JsLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let Ok(listener) = self.listener.lock() else {
+            return;
+        };
+        let Some(listener) = listener.as_ref() else {
+            return;
+        };
+        let event = LogEvent {
+            level: level_name(record.level()),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            module_path: record.module_path().map(str::to_string),
+            file: record.file().map(str::to_string),
+            line: record.line(),
+        };
+        let callback = Arc::clone(&listener.callback);
+        // Dropped if the listener's thread has exited.
+        let _ = listener.channel.try_send(move |mut cx| {
+            let event = event.to_js(&mut cx)?;
+            callback
+                .to_inner(&mut cx)
+                .call_with(&cx)
+                .arg(event)
+                .exec(&mut cx)
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+impl LogEvent {
+    fn to_js<'a>(&self, cx: &mut TaskContext<'a>) -> JsResult<'a, JsObject> {
+        let obj = cx.empty_object();
+        let level = cx.string(self.level);
+        obj.set(cx, "level", level)?;
+        let target = cx.string(&self.target);
+        obj.set(cx, "target", target)?;
+        let message = cx.string(&self.message);
+        obj.set(cx, "message", message)?;
+
+        let fields = cx.empty_object();
+        if let Some(module_path) = &self.module_path {
+            let module_path = cx.string(module_path);
+            fields.set(cx, "modulePath", module_path)?;
+        }
+        if let Some(file) = &self.file {
+            let file = cx.string(file);
+            fields.set(cx, "file", file)?;
+        }
+        if let Some(line) = self.line {
+            let line = cx.number(line);
+            fields.set(cx, "line", line)?;
+        }
+        obj.set(cx, "fields", fields)?;
+        Ok(obj)
+    }
+}
+
+fn level_name(level: log::Level) -> &'static str {
+    match level {
+        log::Level::Error => "error",
+        log::Level::Warn => "warn",
+        log::Level::Info => "info",
+        log::Level::Debug => "debug",
+        log::Level::Trace => "trace",
+    }
+}
+
+/// Stop forwarding records to a callback registered by an instance that is
+/// being unloaded.
+pub(crate) fn remove_instance_logger(instance: u64) {
+    if let Ok(mut listener) = LOGGER.listener.lock() {
+        if listener
+            .as_ref()
+            .is_some_and(|listener| listener.instance == instance)
+        {
+            *listener = None;
+            log::set_max_level(LevelFilter::Off);
+        }
+    }
+}
+
+/// Forward native log records at `level` (default "warn") or more severe to
+/// `callback`, replacing any previous callback. Passing null stops forwarding.
+pub fn set_logger(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsValue>(0)?;
+    let level = match cx.argument_opt(1) {
+        Some(level) if level.is_a::<JsString, _>(&mut cx) => {
+            let level = level
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            LevelFilter::from_str(&level).or_else(|_| {
+                cx.throw_range_error(format!(
+                    "Unknown log level \"{level}\". Expected error, warn, info, debug, or trace"
+                ))
+            })?
+        }
+        _ => LevelFilter::Warn,
+    };
+
+    let listener = if let Ok(callback) = callback.downcast::<JsFunction, _>(&mut cx) {
+        let mut channel = cx.channel();
+        // Logging should not keep the process alive.
+        channel.unref(&mut cx);
+        Some(LogListener {
+            instance: instance_id(&mut cx),
+            channel,
+            callback: Arc::new(callback.root(&mut cx)),
+        })
+    } else if callback.is_a::<JsNull, _>(&mut cx) || callback.is_a::<JsUndefined, _>(&mut cx) {
+        None
+    } else {
+        return cx.throw_type_error("The logger must be a function or null");
+    };
+
+    let mut install_error = None;
+    INSTALL_LOGGER.call_once(|| {
+        install_error = log::set_logger(&LOGGER).err();
+    });
+    if let Some(err) = install_error {
+        return cx.throw_error(format!("Failed to install the native logger: {err}"));
+    }
+
+    let mut current = LOGGER
+        .listener
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    log::set_max_level(if listener.is_some() {
+        level
+    } else {
+        LevelFilter::Off
+    });
+    *current = listener;
+    Ok(cx.undefined())
+}
@@ -0,0 +1,193 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use c2pa::{Builder, Ingredient, Reader};
+use neon::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Reader,
+    Builder,
+}
+
+struct Totals {
+    count: AtomicU64,
+    bytes: AtomicU64,
+}
+
+static TOTALS: [Totals; 2] = [const {
+    Totals {
+        count: AtomicU64::new(0),
+        bytes: AtomicU64::new(0),
+    }
+}; 2];
+
+/// The approximate native memory held by one Reader or Builder, counted in the
+/// process-wide totals until it is dropped.
+#[derive(Debug)]
+pub struct Footprint {
+    kind: Kind,
+    // Measured from the object's contents.
+    measured: AtomicU64,
+    // Resources added to a Builder, which c2pa-rs does not expose.
+    added: AtomicU64,
+    released: AtomicBool,
+}
+
+impl Footprint {
+    fn new(kind: Kind) -> Self {
+        kind.totals().count.fetch_add(1, Ordering::Relaxed);
+        Self {
+            kind,
+            measured: AtomicU64::new(0),
+            added: AtomicU64::new(0),
+            released: AtomicBool::new(false),
+        }
+    }
+
+    pub fn reader(reader: &Reader) -> Self {
+        let footprint = Self::new(Kind::Reader);
+        footprint.set_measured(reader_size(reader));
+        footprint
+    }
+
+    /// The footprint of a Reader already measured at `bytes`.
+    pub fn reader_of_size(bytes: u64) -> Self {
+        let footprint = Self::new(Kind::Reader);
+        footprint.set_measured(bytes);
+        footprint
+    }
+
+    pub fn builder(builder: &Builder) -> Self {
+        let footprint = Self::new(Kind::Builder);
+        footprint.set_measured(builder_size(builder));
+        footprint
+    }
+
+    pub fn bytes(&self) -> u64 {
+        if self.released.load(Ordering::Relaxed) {
+            return 0;
+        }
+        self.measured.load(Ordering::Relaxed) + self.added.load(Ordering::Relaxed)
+    }
+
+    pub fn set_measured(&self, bytes: u64) {
+        if self.released.load(Ordering::Relaxed) {
+            return;
+        }
+        let previous = self.measured.swap(bytes, Ordering::Relaxed);
+        let totals = &self.kind.totals().bytes;
+        totals.fetch_add(bytes, Ordering::Relaxed);
+        totals.fetch_sub(previous, Ordering::Relaxed);
+    }
+
+    pub fn add(&self, bytes: u64) {
+        if self.released.load(Ordering::Relaxed) {
+            return;
+        }
+        self.added.fetch_add(bytes, Ordering::Relaxed);
+        self.kind.totals().bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Stop counting the object, once it is disposed or dropped.
+    pub fn release(&self) {
+        if self.released.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let bytes = self.measured.load(Ordering::Relaxed) + self.added.load(Ordering::Relaxed);
+        let totals = self.kind.totals();
+        totals.bytes.fetch_sub(bytes, Ordering::Relaxed);
+        totals.count.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Footprint {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl Kind {
+    fn totals(self) -> &'static Totals {
+        &TOTALS[self as usize]
+    }
+}
+
+fn ingredient_size(ingredient: &Ingredient) -> u64 {
+    let resources: usize = ingredient
+        .resources()
+        .resources()
+        .values()
+        .map(Vec::len)
+        .sum();
+    let manifest_data = ingredient.manifest_data().map_or(0, |data| data.len());
+    (resources + manifest_data) as u64
+}
+
+/// Approximate size of a Reader: its manifest store as JSON plus the binary
+/// resources, such as thumbnails, held by each manifest.
+pub fn reader_size(reader: &Reader) -> u64 {
+    let resources: usize = reader
+        .iter_manifests()
+        .flat_map(|manifest| manifest.resources().resources().values())
+        .map(Vec::len)
+        .sum();
+    (reader.json().len() + resources) as u64
+}
+
+/// Approximate size of a Builder: its manifest definition as JSON plus the
+/// resources and manifest data held by its ingredients.
+pub fn builder_size(builder: &Builder) -> u64 {
+    let definition = serde_json::to_vec(&builder.definition).map_or(0, |json| json.len()) as u64;
+    let ingredients: u64 = builder
+        .definition
+        .ingredients
+        .iter()
+        .map(ingredient_size)
+        .sum();
+    definition + ingredients
+}
+
+#[derive(Serialize)]
+struct KindUsage {
+    count: u64,
+    bytes: u64,
+}
+
+#[derive(Serialize)]
+struct MemoryUsage {
+    readers: KindUsage,
+    builders: KindUsage,
+}
+
+fn usage(kind: Kind) -> KindUsage {
+    let totals = kind.totals();
+    KindUsage {
+        count: totals.count.load(Ordering::Relaxed),
+        bytes: totals.bytes.load(Ordering::Relaxed),
+    }
+}
+
+/// Approximate native memory held by the Readers and Builders that have not
+/// been disposed or garbage collected, as JSON.
+pub fn get_memory_usage(mut cx: FunctionContext) -> JsResult<JsString> {
+    let json = serde_json::to_string(&MemoryUsage {
+        readers: usage(Kind::Reader),
+        builders: usage(Kind::Builder),
+    })
+    .or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
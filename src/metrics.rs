@@ -0,0 +1,215 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use neon::prelude::*;
+use serde::Serialize;
+
+use crate::instance::loaded_instances;
+use crate::runtime::{batch_waiting, running_runtime};
+use crate::telemetry::{Span, SpanKind};
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Clone, Copy)]
+pub enum Operation {
+    Read,
+    Sign,
+    Resource,
+}
+
+impl Operation {
+    const ALL: [Operation; 3] = [Operation::Read, Operation::Sign, Operation::Resource];
+
+    fn name(self) -> &'static str {
+        match self {
+            Operation::Read => "read",
+            Operation::Sign => "sign",
+            Operation::Resource => "resource",
+        }
+    }
+
+    fn counters(self) -> &'static OperationCounters {
+        &OPERATIONS[self as usize]
+    }
+}
+
+struct OperationCounters {
+    started: AtomicU64,
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    // One count per bucket, plus one for slower operations.
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    latency_micros: AtomicU64,
+}
+
+impl OperationCounters {
+    const fn new() -> Self {
+        Self {
+            started: AtomicU64::new(0),
+            succeeded: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            buckets: [const { AtomicU64::new(0) }; LATENCY_BUCKETS.len() + 1],
+            latency_micros: AtomicU64::new(0),
+        }
+    }
+}
+
+static OPERATIONS: [OperationCounters; 3] = [const { OperationCounters::new() }; 3];
+
+/// Records one operation when dropped. It counts as failed unless `finish(true)`
+/// was called, so early returns are recorded as failures.
+pub struct OperationTimer {
+    operation: Operation,
+    start: Instant,
+    succeeded: bool,
+    span: Span,
+}
+
+impl OperationTimer {
+    pub fn start(operation: Operation) -> Self {
+        operation.counters().started.fetch_add(1, Ordering::Relaxed);
+        let mut span = Span::start(&format!("c2pa.{}", operation.name()), SpanKind::Internal);
+        span.set_attribute("c2pa.operation", operation.name());
+        Self {
+            operation,
+            start: Instant::now(),
+            succeeded: false,
+            span,
+        }
+    }
+
+    pub fn finish(mut self, succeeded: bool) {
+        self.succeeded = succeeded;
+    }
+}
+
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        let counters = self.operation.counters();
+        let elapsed = self.start.elapsed();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| elapsed.as_secs_f64() <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        counters.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        counters
+            .latency_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        let outcome = if self.succeeded {
+            &counters.succeeded
+        } else {
+            &counters.failed
+        };
+        outcome.fetch_add(1, Ordering::Relaxed);
+        self.span.end(self.succeeded);
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeSnapshot {
+    workers: usize,
+    alive_tasks: usize,
+    queued_tasks: usize,
+    /// Batch operations waiting for a slot.
+    batch_waiting: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LatencySnapshot {
+    /// Cumulative counts for each bucket bound, in seconds, ending with `+Inf`.
+    buckets: Vec<(String, u64)>,
+    count: u64,
+    sum_seconds: f64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationSnapshot {
+    started: u64,
+    succeeded: u64,
+    failed: u64,
+    active: u64,
+    latency: LatencySnapshot,
+}
+
+#[derive(Serialize)]
+struct MetricsSnapshot {
+    /// Threads that have loaded the addon.
+    instances: usize,
+    runtime: Option<RuntimeSnapshot>,
+    operations: BTreeMap<&'static str, OperationSnapshot>,
+}
+
+fn snapshot(operation: Operation) -> OperationSnapshot {
+    let counters = operation.counters();
+    let succeeded = counters.succeeded.load(Ordering::Relaxed);
+    let failed = counters.failed.load(Ordering::Relaxed);
+    let started = counters.started.load(Ordering::Relaxed);
+
+    let mut cumulative = 0;
+    let buckets = LATENCY_BUCKETS
+        .iter()
+        .map(|bound| bound.to_string())
+        .chain(std::iter::once("+Inf".to_string()))
+        .zip(&counters.buckets)
+        .map(|(bound, count)| {
+            cumulative += count.load(Ordering::Relaxed);
+            (bound, cumulative)
+        })
+        .collect();
+
+    OperationSnapshot {
+        started,
+        succeeded,
+        failed,
+        active: started.saturating_sub(succeeded + failed),
+        latency: LatencySnapshot {
+            buckets,
+            count: cumulative,
+            sum_seconds: counters.latency_micros.load(Ordering::Relaxed) as f64 / 1e6,
+        },
+    }
+}
+
+/// Counters for native operations and the runtime, as JSON.
+pub fn get_metrics(mut cx: FunctionContext) -> JsResult<JsString> {
+    let runtime = running_runtime().map(|rt| {
+        let metrics = rt.metrics();
+        RuntimeSnapshot {
+            workers: metrics.num_workers(),
+            alive_tasks: metrics.num_alive_tasks(),
+            queued_tasks: metrics.global_queue_depth(),
+            batch_waiting: batch_waiting(),
+        }
+    });
+    let operations = Operation::ALL
+        .into_iter()
+        .map(|operation| (operation.name(), snapshot(operation)))
+        .collect();
+    let json = serde_json::to_string(&MetricsSnapshot {
+        instances: loaded_instances(),
+        runtime,
+        operations,
+    })
+    .or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
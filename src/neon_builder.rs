@@ -12,76 +12,184 @@
 // each license.
 
 use crate::asset::parse_asset;
-use crate::error::{as_js_error, Error};
+use crate::definition::take_buffer_resources;
+use crate::error::{
+    as_js_error, as_js_error_fn, catch_panic, catch_panic_sync, throw_js_error, Error,
+};
+use crate::hashing::parse_algorithm;
+use crate::ingredient_cache;
+use crate::js_value::{cbor_from_js, from_js_with_buffers, json_argument};
+use crate::jumbf::superbox_payload;
+use crate::memory::{builder_size, Footprint};
+use crate::metrics::{Operation, OperationTimer};
 use crate::neon_identity_assertion_signer::NeonIdentityAssertionSigner;
 use crate::neon_reader::NeonReader;
 use crate::neon_signer::{CallbackSignerConfig, NeonCallbackSigner, NeonLocalSigner};
-use crate::runtime::runtime;
-use crate::utils::parse_settings;
-use c2pa::{Builder, BuilderIntent, Ingredient};
+use crate::runtime::{in_lane, running_runtime, runtime, with_timeout};
+use crate::sidecar::{embedding, Sidecar};
+use crate::soft_binding::{self, NeonSoftBinding};
+use crate::utils::{
+    mime_type_array, parse_network_options, parse_priority, parse_settings, parse_timeout,
+};
+use c2pa::{hash_stream_by_alg, Builder, BuilderIntent, Ingredient, ManifestDefinition, Reader};
+use futures::{StreamExt, TryStreamExt};
 use neon::context::Context as NeonContext;
 use neon::prelude::*;
-use neon_serde4;
 use serde_json;
+use std::io::{Cursor, SeekFrom};
 use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+/// Reject formats that c2pa-rs can read but not sign, such as PDF, before the
+/// output is opened, so an existing output file is left untouched.
+fn check_signable(format: &str) -> Result<(), Error> {
+    let format = format.to_lowercase();
+    if Builder::supported_mime_types().contains(&format) {
+        Ok(())
+    } else if Reader::supported_mime_types().contains(&format) {
+        Err(Error::ReadOnlyFormat(format))
+    } else {
+        Err(Error::C2pa(c2pa::Error::UnsupportedType))
+    }
+}
+
 pub struct NeonBuilder {
     builder: Arc<Mutex<Builder>>,
+    disposed: AtomicBool,
+    footprint: Arc<Footprint>,
+    soft_bindings: std::sync::Mutex<Vec<NeonSoftBinding>>,
 }
 
 impl NeonBuilder {
+    /// Handle to `this`, throwing if `dispose` has been called.
+    fn this_live<'a>(cx: &mut FunctionContext<'a>) -> JsResult<'a, JsBox<Self>> {
+        let this = cx.this::<JsBox<Self>>()?;
+        if this.disposed.load(Ordering::Acquire) {
+            let err = as_js_error_fn(cx, "Builder", Error::Disposed("Builder"))?;
+            return cx.throw(err);
+        }
+        Ok(this)
+    }
+
+    /// Release the Builder's native memory now instead of when it is garbage
+    /// collected. Later calls on it fail; disposing again does nothing.
+    pub fn dispose(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let this = cx.this::<JsBox<Self>>()?;
+        if this.disposed.swap(true, Ordering::AcqRel) {
+            return Ok(cx.undefined());
+        }
+        this.footprint.release();
+        if let Ok(mut plugins) = this.soft_bindings.lock() {
+            plugins.clear();
+        }
+        if let Ok(mut builder) = this.builder.try_lock() {
+            *builder = Builder::default();
+        } else if let Some(rt) = running_runtime() {
+            // A running task holds the Builder; clear it once that task is done.
+            let builder = Arc::clone(&this.builder);
+            rt.spawn(async move {
+                *builder.lock().await = Builder::default();
+            });
+        }
+        Ok(cx.undefined())
+    }
+
     pub fn new(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
         // Parse optional settings parameter (argument 0)
-        let context_opt =
-            parse_settings(&mut cx, 0, "Builder").or_else(|err| cx.throw_error(err.to_string()))?;
+        let context_opt = parse_settings(&mut cx, 0, "Builder")
+            .or_else(|err| throw_js_error(&mut cx, "Builder.new", err))?;
 
         let builder = if let Some(context) = context_opt {
-            Builder::from_context(context)
+            Builder::from_shared_context(&context)
         } else {
             Builder::default()
         };
 
         Ok(cx.boxed(Self {
+            disposed: AtomicBool::new(false),
+            footprint: Arc::new(Footprint::builder(&builder)),
             builder: Arc::new(Mutex::new(builder)),
+            soft_bindings: Default::default(),
         }))
     }
 
+    /// Create a Builder from a manifest definition, given as a JSON string or
+    /// as a plain object, which is converted without a JSON round trip. The
+    /// resource references of an object may hold their bytes in a `buffer`
+    /// field, which are added to the Builder's resources.
     pub fn with_json(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
-        let json = cx.argument::<JsString>(0)?.value(&mut cx);
+        let definition = cx.argument::<JsValue>(0)?;
+        let (definition, resources) = if definition.is_a::<JsString, _>(&mut cx) {
+            (json_argument(&mut cx, definition)?, Vec::new())
+        } else {
+            let (mut definition, buffers) = from_js_with_buffers(&mut cx, definition)?;
+            let resources = take_buffer_resources(&mut definition, buffers)
+                .or_else(|err| cx.throw_type_error(err))?;
+            (definition, resources)
+        };
+        let definition: ManifestDefinition = serde_json::from_value(definition)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.withJson", err))?;
 
         // Parse optional settings parameter (argument 1)
-        let context_opt =
-            parse_settings(&mut cx, 1, "Builder").or_else(|err| cx.throw_error(err.to_string()))?;
+        let context_opt = parse_settings(&mut cx, 1, "Builder")
+            .or_else(|err| throw_js_error(&mut cx, "Builder.withJson", err))?;
 
-        let builder = if let Some(context) = context_opt {
-            Builder::from_context(context)
-                .with_definition(json.as_str())
-                .or_else(|err| cx.throw_error(err.to_string()))?
+        let mut builder = if let Some(context) = context_opt {
+            Builder::from_shared_context(&context).with_definition(definition)
         } else {
-            Builder::from_json(&json).or_else(|err| cx.throw_error(err.to_string()))?
-        };
+            Builder::new().with_definition(definition)
+        }
+        .or_else(|err| throw_js_error(&mut cx, "Builder.withJson", err))?;
+
+        let footprint = Footprint::builder(&builder);
+        for (identifier, bytes) in resources {
+            footprint.add(bytes.len() as u64);
+            builder
+                .add_resource(&identifier, Cursor::new(bytes))
+                .or_else(|err| throw_js_error(&mut cx, "Builder.withJson", err))?;
+        }
 
         Ok(cx.boxed(Self {
+            disposed: AtomicBool::new(false),
+            footprint: Arc::new(footprint),
             builder: Arc::new(Mutex::new(builder)),
+            soft_bindings: Default::default(),
         }))
     }
 
     pub fn set_intent(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.setIntent", err))?;
+        let this = Self::this_live(&mut cx)?;
         let intent_str = cx.argument::<JsString>(0)?.value(&mut cx);
-        let intent: BuilderIntent = serde_json::from_str(&intent_str)
-            .or_else(|_| cx.throw_error(format!("Invalid intent: {}", intent_str)))?;
+        let intent: BuilderIntent = serde_json::from_str(&intent_str).or_else(|_| {
+            throw_js_error(
+                &mut cx,
+                "Builder.setIntent",
+                Error::InvalidArgument(format!("Invalid intent: {intent_str}")),
+            )
+        })?;
         let mut builder = rt.block_on(async { this.builder.lock().await });
         builder.set_intent(intent);
         Ok(cx.undefined())
     }
 
+    /// Fetch a fresh timestamp for the manifest with this label when signing,
+    /// recorded in a `c2pa.time-stamp` assertion. The manifest must be the
+    /// parent or another ingredient, and the signer must have a TSA URL.
+    pub fn add_timestamp(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addTimestamp", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let manifest_label = cx.argument::<JsString>(0)?.value(&mut cx);
+        let mut builder = rt.block_on(async { this.builder.lock().await });
+        builder.add_timestamp(manifest_label);
+        Ok(cx.undefined())
+    }
+
     pub fn set_no_embed(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.setNoEmbed", err))?;
+        let this = Self::this_live(&mut cx)?;
         let no_embed = cx.argument::<JsBoolean>(0)?.value(&mut cx);
         let mut builder = rt.block_on(async { this.builder.lock().await });
         builder.no_embed = no_embed;
@@ -89,30 +197,77 @@ impl NeonBuilder {
     }
 
     pub fn set_remote_url(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.setRemoteUrl", err))?;
+        let this = Self::this_live(&mut cx)?;
         let remote_url: String = cx.argument::<JsString>(0)?.value(&mut cx);
         let mut builder = rt.block_on(async { this.builder.lock().await });
         builder.set_remote_url(&remote_url);
         Ok(cx.undefined())
     }
 
+    /// Set where the manifest store goes when signing: embedded in the asset
+    /// or not, and referenced from the asset's XMP by a remote URL or not.
+    pub fn set_placement(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.setPlacement", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let embed = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+        let remote_url = cx
+            .argument_opt(1)
+            .and_then(|value| value.downcast::<JsString, _>(&mut cx).ok())
+            .map(|value| value.value(&mut cx));
+        if let Some(remote_url) = &remote_url {
+            if let Err(err) = url::Url::parse(remote_url) {
+                let err = format!("Invalid remote URL {remote_url}: {err}");
+                return throw_js_error(
+                    &mut cx,
+                    "Builder.setPlacement",
+                    Error::InvalidArgument(err),
+                );
+            }
+        }
+        let mut builder = rt.block_on(async { this.builder.lock().await });
+        builder.no_embed = !embed;
+        builder.remote_url = remote_url;
+        Ok(cx.undefined())
+    }
+
+    /// Run a soft binding plugin's embedder over the asset each time the
+    /// Builder is signed asynchronously, adding a `c2pa.soft-binding`
+    /// assertion for the watermark.
+    pub fn add_soft_binding_plugin(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let this = Self::this_live(&mut cx)?;
+        let plugin = cx.argument::<JsBox<NeonSoftBinding>>(0)?;
+        let plugin = NeonSoftBinding::clone(&plugin);
+        this.soft_bindings
+            .lock()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addSoftBindingPlugin", err))?
+            .push(plugin);
+        Ok(cx.undefined())
+    }
+
+    fn soft_bindings(&self) -> Vec<NeonSoftBinding> {
+        self.soft_bindings
+            .lock()
+            .map(|plugins| plugins.clone())
+            .unwrap_or_default()
+    }
+
     pub fn add_action(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addAction", err))?;
+        let this = Self::this_live(&mut cx)?;
         let action_json = cx.argument::<JsString>(0)?.value(&mut cx);
-        let action: c2pa::assertions::Action =
-            serde_json::from_str(&action_json).or_else(|err| cx.throw_error(err.to_string()))?;
+        let action: c2pa::assertions::Action = serde_json::from_str(&action_json)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addAction", err))?;
         let mut builder = rt.block_on(async { this.builder.lock().await });
         builder
             .add_action(action)
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addAction", err))?;
         Ok(cx.undefined())
     }
 
     pub fn add_assertion(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addAssertion", err))?;
+        let this = Self::this_live(&mut cx)?;
         let label = cx.argument::<JsString>(0)?.value(&mut cx);
         let assertion_kind = cx.argument_opt(2).and_then(|js_value| {
             js_value
@@ -127,99 +282,252 @@ impl NeonBuilder {
             // For Json, expect the assertion as a string (JSON) and parse it
             let assertion_str = cx.argument::<JsString>(1)?.value(&mut cx);
             let assertion: serde_json::Value = serde_json::from_str(&assertion_str)
-                .or_else(|err| cx.throw_error(format!("Invalid JSON: {}", err)))?;
+                .or_else(|err| throw_js_error(&mut cx, "Builder.addAssertion", err))?;
             builder
                 .add_assertion(&label, &assertion)
-                .or_else(|err| cx.throw_error(err.to_string()))?;
+                .or_else(|err| throw_js_error(&mut cx, "Builder.addAssertion", err))?;
         } else {
-            // For Cbor/Binary/Uri, expect the assertion as an object and serialize to CBOR
+            // For Cbor/Binary/Uri, expect the assertion as an object and serialize to CBOR.
+            // Buffers, such as the hash of a hashed URI, become byte strings.
             let assertion_obj = cx.argument::<JsValue>(1)?;
-            let assertion: serde_json::Value = neon_serde4::from_value(&mut cx, assertion_obj)
-                .or_else(|err| cx.throw_error(err.to_string()))?;
+            let assertion = cbor_from_js(&mut cx, assertion_obj)?;
             builder
                 .add_assertion(&label, &assertion)
-                .or_else(|err| cx.throw_error(err.to_string()))?;
+                .or_else(|err| throw_js_error(&mut cx, "Builder.addAssertion", err))?;
         };
 
         Ok(cx.undefined())
     }
 
+    /// A hashed URI to the manifest of an ingredient already added, or to one
+    /// of that manifest's assertions, for assertions that reference them.
+    /// The ingredient is found by its label, instance ID or active manifest.
+    pub fn hashed_uri(mut cx: FunctionContext) -> JsResult<JsObject> {
+        let rt =
+            runtime().or_else(|err| throw_js_error(&mut cx, "Builder.ingredientHashedUri", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let ingredient_id = cx.argument::<JsString>(0)?.value(&mut cx);
+        let assertion = cx
+            .argument_opt(1)
+            .and_then(|value| value.downcast::<JsString, _>(&mut cx).ok())
+            .map(|value| value.value(&mut cx));
+        let alg = parse_algorithm(&mut cx, 2)?;
+
+        let builder = rt.block_on(async { this.builder.lock().await });
+        let Some(ingredient) = builder.definition.ingredients.iter().find(|ingredient| {
+            ingredient.label() == Some(ingredient_id.as_str())
+                || ingredient.instance_id() == ingredient_id
+                || ingredient.active_manifest() == Some(ingredient_id.as_str())
+        }) else {
+            let err = Error::InvalidArgument(format!("No ingredient {ingredient_id}"));
+            return throw_js_error(&mut cx, "Builder.ingredientHashedUri", err);
+        };
+        let (Some(manifest_label), Some(manifest_data)) =
+            (ingredient.active_manifest(), ingredient.manifest_data())
+        else {
+            let err = Error::InvalidArgument(format!("Ingredient {ingredient_id} has no manifest"));
+            return throw_js_error(&mut cx, "Builder.ingredientHashedUri", err);
+        };
+
+        let mut path = vec!["c2pa", manifest_label];
+        if let Some(assertion) = assertion.as_deref() {
+            path.extend(["c2pa.assertions", assertion]);
+        }
+        let Some(payload) = superbox_payload(&manifest_data, &path) else {
+            let err = Error::InvalidArgument(format!("No box at /{}", path.join("/")));
+            return throw_js_error(&mut cx, "Builder.ingredientHashedUri", err);
+        };
+        let hash = hash_stream_by_alg(&alg, &mut Cursor::new(payload), None, true)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.ingredientHashedUri", err))?;
+
+        let result = cx.empty_object();
+        let url = cx.string(format!("self#jumbf=/{}", path.join("/")));
+        result.set(&mut cx, "url", url)?;
+        let alg = cx.string(alg);
+        result.set(&mut cx, "alg", alg)?;
+        let hash = JsBuffer::from_slice(&mut cx, &hash)?;
+        result.set(&mut cx, "hash", hash)?;
+        Ok(result)
+    }
+
     pub fn add_resource(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let this = cx.this::<JsBox<Self>>()?;
+        let this = Self::this_live(&mut cx)?;
         let uri = cx.argument::<JsString>(0)?.value(&mut cx);
         let resource = cx
             .argument::<JsObject>(1)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let builder = Arc::clone(&this.builder);
+        let footprint = Arc::clone(&this.footprint);
 
         let promise = cx
             .task(move || {
-                // Block on acquiring the async mutex lock
-                let rt = runtime();
-                let mut builder = rt.block_on(async { builder.lock().await });
-
-                resource.into_read_stream().and_then(|mut resource_stream| {
-                    builder.add_resource(&uri, &mut resource_stream)?;
-                    Ok(())
+                catch_panic_sync(|| {
+                    // Block on acquiring the async mutex lock
+                    let rt = runtime()?;
+                    let mut builder = rt.block_on(async { builder.lock().await });
+
+                    resource.into_read_stream().and_then(|mut resource_stream| {
+                        let size = resource_stream.seek(SeekFrom::End(0))?;
+                        resource_stream.rewind()?;
+                        builder.add_resource(&uri, &mut resource_stream)?;
+                        footprint.add(size);
+                        Ok(())
+                    })
                 })
             })
             .promise(move |mut cx, result: Result<(), Error>| match result {
                 Ok(_) => Ok(cx.undefined()),
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Err(err) => {
+                    as_js_error(&mut cx, "Builder.addResource", err).and_then(|err| cx.throw(err))
+                }
             });
 
         Ok(promise)
     }
     pub fn add_ingredient(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addIngredient", err))?;
+        let this = Self::this_live(&mut cx)?;
         let ingredient_json = cx.argument::<JsString>(0)?.value(&mut cx);
         let ingredient = Ingredient::from_json(&ingredient_json)
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addIngredient", err))?;
 
         let mut builder = rt.block_on(async { this.builder.lock().await });
         builder.add_ingredient(ingredient);
+        this.footprint.set_measured(builder_size(&builder));
         Ok(cx.undefined())
     }
 
     pub fn add_ingredient_from_asset(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addIngredient", err))?;
+        let this = Self::this_live(&mut cx)?;
         let ingredient_json = cx.argument::<JsString>(0)?.value(&mut cx);
         let ingredient = cx
             .argument::<JsObject>(1)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
 
         let builder = Arc::clone(&this.builder);
+        let footprint = Arc::clone(&this.footprint);
 
         let channel = cx.channel();
         let (deferred, promise) = cx.promise();
 
         rt.spawn(async move {
-            let mut builder = builder.lock().await;
-
-            let result = async {
-                let format = ingredient
-                    .mime_type()
-                    .ok_or_else(|| {
-                        Error::Signing("Ingredient asset must have a mime type".to_string())
-                    })?
-                    .to_owned();
-                let mut ingredient_stream = ingredient.into_read_stream()?;
-                builder
-                    .add_ingredient_from_stream_async(
-                        &ingredient_json,
-                        &format,
-                        &mut ingredient_stream,
-                    )
-                    .await?;
+            let result = catch_panic(async {
+                let mut builder = builder.lock().await;
+                let (format, mut ingredient_stream) = ingredient.into_typed_read_stream()?;
+                let format = format.ok_or_else(|| {
+                    Error::Signing("Ingredient asset must have a mime type".to_string())
+                })?;
+                let context = Arc::clone(builder.context());
+                let ingredient = ingredient_cache::ingredient_from_stream(
+                    &context,
+                    &ingredient_json,
+                    &format,
+                    &mut ingredient_stream,
+                )
+                .await?;
+                builder.add_ingredient(ingredient);
+                footprint.set_measured(builder_size(&builder));
                 Ok(())
+            })
+            .await;
+
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok(_) => Ok(cx.undefined()),
+                Err(err) => {
+                    as_js_error(&mut cx, "Builder.addIngredient", err).and_then(|err| cx.throw(err))
+                }
+            });
+        });
+
+        Ok(promise)
+    }
+
+    /// Add ingredients read from assets, reading up to `concurrency` of them
+    /// at once so validating their manifests runs in parallel. They are added
+    /// in the order given, and none are added if any fails.
+    pub fn add_ingredients_from_assets(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.addIngredients", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let jsons = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+        let jsons = jsons
+            .into_iter()
+            .map(|json| {
+                Ok(json
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx))
+            })
+            .collect::<NeonResult<Vec<_>>>()?;
+        let assets = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+        let assets = assets
+            .into_iter()
+            .map(|asset| {
+                let asset = asset.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                parse_asset(&mut cx, asset)
+            })
+            .collect::<NeonResult<Vec<_>>>()?;
+        if jsons.len() != assets.len() {
+            return cx.throw_type_error("Expected an asset for each ingredient");
+        }
+        let concurrency = match cx.argument_opt(2) {
+            Some(value) if value.is_a::<JsNumber, _>(&mut cx) => {
+                let value = value
+                    .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                    .value(&mut cx);
+                if value.is_nan() || value < 1.0 {
+                    return cx.throw_range_error("concurrency must be at least 1");
+                }
+                value as usize
             }
+            _ => std::thread::available_parallelism().map_or(1, |count| count.get()),
+        };
+
+        let builder = Arc::clone(&this.builder);
+        let footprint = Arc::clone(&this.footprint);
+
+        let channel = cx.channel();
+        let (deferred, promise) = cx.promise();
+
+        rt.spawn(async move {
+            let result = catch_panic(async {
+                let context = Arc::clone(builder.lock().await.context());
+                let reads = jsons.into_iter().zip(assets).map(|(json, asset)| {
+                    let context = Arc::clone(&context);
+                    // Each read is its own task, so they run on separate
+                    // worker threads.
+                    tokio::spawn(catch_panic(async move {
+                        let (format, mut stream) = asset.into_typed_read_stream()?;
+                        let format = format.ok_or_else(|| {
+                            Error::Signing("Ingredient asset must have a mime type".to_string())
+                        })?;
+                        ingredient_cache::ingredient_from_stream(
+                            &context,
+                            &json,
+                            &format,
+                            &mut stream,
+                        )
+                        .await
+                    }))
+                });
+                let ingredients: Vec<Ingredient> = futures::stream::iter(reads)
+                    .buffered(concurrency)
+                    .map(|read| read?)
+                    .try_collect()
+                    .await?;
+
+                let mut builder = builder.lock().await;
+                for ingredient in ingredients {
+                    builder.add_ingredient(ingredient);
+                }
+                footprint.set_measured(builder_size(&builder));
+                Ok(())
+            })
             .await;
 
             deferred.settle_with(&channel, move |mut cx| match result {
                 Ok(_) => Ok(cx.undefined()),
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Err(err) => as_js_error(&mut cx, "Builder.addIngredients", err)
+                    .and_then(|err| cx.throw(err)),
             });
         });
 
@@ -227,22 +535,27 @@ impl NeonBuilder {
     }
 
     pub fn add_ingredient_from_reader(mut cx: FunctionContext) -> JsResult<JsString> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
-        let reader = cx.argument::<JsBox<NeonReader>>(0)?.reader();
+        let rt = runtime()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addIngredientFromReader", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let reader = cx
+            .argument::<JsBox<NeonReader>>(0)?
+            .reader()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addIngredientFromReader", err))?;
 
         let mut builder = rt.block_on(async { this.builder.lock().await });
         let reader = rt.block_on(async { reader.lock().await });
         let ingredient = builder
             .add_ingredient_from_reader(&reader)
-            .or_else(|err| cx.throw_error(err.to_string()))?;
-        let json =
-            serde_json::to_string(&ingredient).or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addIngredientFromReader", err))?;
+        let json = serde_json::to_string(&ingredient)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.addIngredientFromReader", err))?;
+        this.footprint.set_measured(builder_size(&builder));
         Ok(cx.string(json))
     }
 
     pub fn to_archive(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let this = cx.this::<JsBox<Self>>()?;
+        let this = Self::this_live(&mut cx)?;
         let dest_obj = cx.argument::<JsObject>(0)?;
         let dest = parse_asset(&mut cx, dest_obj)?;
         let is_buffer = dest.name() == "destination_buffer";
@@ -251,25 +564,28 @@ impl NeonBuilder {
 
         let promise = cx
             .task(move || {
-                // Block on acquiring the async mutex lock
-                // Settings are automatically applied when runtime() is called
-                let rt = runtime();
-                let mut builder = rt.block_on(async { builder.lock().await });
-
-                dest.write_stream().and_then(|mut dest_stream| {
-                    builder.to_archive(&mut dest_stream)?;
-                    if is_buffer {
-                        let mut archive_data = Vec::new();
-                        dest_stream
-                            .rewind()
-                            .map_err(|e| Error::Asset(format!("Failed to rewind stream: {e}")))?;
-                        dest_stream
-                            .read_to_end(&mut archive_data)
-                            .map_err(|e| Error::Asset(format!("Failed to read stream: {e}")))?;
-                        Ok(Some(archive_data))
-                    } else {
-                        Ok(None)
-                    }
+                catch_panic_sync(|| {
+                    // Block on acquiring the async mutex lock
+                    // Settings are automatically applied when runtime() is called
+                    let rt = runtime()?;
+                    let mut builder = rt.block_on(async { builder.lock().await });
+
+                    dest.write_stream().and_then(|mut dest_stream| {
+                        builder.to_archive(&mut dest_stream)?;
+                        dest_stream.finish()?;
+                        if is_buffer {
+                            let mut archive_data = Vec::new();
+                            dest_stream.rewind().map_err(|e| {
+                                Error::Asset(format!("Failed to rewind stream: {e}"))
+                            })?;
+                            dest_stream
+                                .read_to_end(&mut archive_data)
+                                .map_err(|e| Error::Asset(format!("Failed to read stream: {e}")))?;
+                            Ok(Some(archive_data))
+                        } else {
+                            Ok(None)
+                        }
+                    })
                 })
             })
             .promise(move |mut cx, result: Result<Option<Vec<u8>>, Error>| {
@@ -282,7 +598,9 @@ impl NeonBuilder {
                         Ok(cx.undefined())
                     }
                     Ok(None) => Ok(cx.undefined()),
-                    Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                    Err(err) => {
+                        as_js_error(&mut cx, "Builder.toArchive", err).and_then(|err| cx.throw(err))
+                    }
                 }
             });
         Ok(promise)
@@ -293,54 +611,91 @@ impl NeonBuilder {
             .argument::<JsObject>(0)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
 
-        let context_opt =
-            parse_settings(&mut cx, 1, "Builder").or_else(|err| cx.throw_error(err.to_string()))?;
+        let context_opt = parse_settings(&mut cx, 1, "Builder")
+            .or_else(|err| throw_js_error(&mut cx, "Builder.fromArchive", err))?;
 
         let promise = cx
             .task(move || {
-                let source_stream = source.into_read_stream()?;
-                let builder = if let Some(context) = context_opt {
-                    Builder::from_context(context).with_archive(source_stream)?
-                } else {
-                    Builder::from_archive(source_stream)?
-                };
-                Ok(builder)
+                catch_panic_sync(|| {
+                    let source_stream = source.into_read_stream()?;
+                    let builder = if let Some(context) = context_opt {
+                        Builder::from_shared_context(&context).with_archive(source_stream)?
+                    } else {
+                        Builder::from_archive(source_stream)?
+                    };
+                    let footprint = Footprint::builder(&builder);
+                    Ok((builder, footprint))
+                })
             })
             .promise(
-                move |mut cx, result: crate::error::Result<Builder>| match result {
-                    Ok(builder) => Ok(cx.boxed(Self {
+                move |mut cx, result: crate::error::Result<(Builder, Footprint)>| match result {
+                    Ok((builder, footprint)) => Ok(cx.boxed(Self {
+                        disposed: AtomicBool::new(false),
+                        footprint: Arc::new(footprint),
                         builder: Arc::new(Mutex::new(builder)),
+                        soft_bindings: Default::default(),
                     })),
-                    Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                    Err(err) => as_js_error(&mut cx, "Builder.fromArchive", err)
+                        .and_then(|err| cx.throw(err)),
                 },
             );
         Ok(promise)
     }
 
     pub fn sign(mut cx: FunctionContext) -> JsResult<JsBuffer> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
+        let this = Self::this_live(&mut cx)?;
         let signer = cx.argument::<JsBox<NeonLocalSigner>>(0)?;
         let input = cx
             .argument::<JsObject>(1)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let output_obj = cx.argument::<JsObject>(2)?;
         let output = parse_asset(&mut cx, output_obj)?;
-        let mut builder = rt.block_on(async { this.builder.lock().await });
+        // Plugins are JS callbacks, which can't be awaited on the JS thread.
+        if !this.soft_bindings().is_empty() {
+            let err = Error::InvalidArgument(
+                "Soft binding plugins need an async signer; use signAsync".to_string(),
+            );
+            return throw_js_error(&mut cx, "Builder.sign", err);
+        }
+        let timer = OperationTimer::start(Operation::Sign);
+        let builder = rt.block_on(async { this.builder.lock().await });
         let signer = signer.signer();
-        let format = match input.mime_type() {
-            Some(mime_type) => mime_type.to_owned(),
-            None => return cx.throw_error("Input asset must have a mime type"),
+        let (format, mut input_stream) = input
+            .into_typed_read_stream()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
+        let format = match format {
+            Some(mime_type) => mime_type,
+            None => {
+                let err = Error::Asset("Input asset must have a mime type".to_string());
+                return throw_js_error(&mut cx, "Builder.sign", err);
+            }
         };
-        let mut input_stream = input
-            .into_read_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+        if let Err(err) = check_signable(&format) {
+            let err = as_js_error_fn(&mut cx, "Builder.sign", err)?;
+            return cx.throw(err);
+        }
+        let sidecar = Sidecar::from_destination(&mut cx, output_obj, &output, "Builder.sign")?;
         let mut output_stream = output
             .write_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
-        let bytes = builder
-            .sign(&**signer, &format, &mut input_stream, &mut output_stream)
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
+        let mut builder = embedding(sidecar.as_ref(), builder);
+        let bytes = match builder.sign(&**signer, &format, &mut input_stream, &mut output_stream) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                let err = as_js_error_fn(&mut cx, "Builder.sign", err.into())?;
+                return cx.throw(err);
+            }
+        };
+        output_stream
+            .finish()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
+        if let Some(sidecar) = &sidecar {
+            if let Err(err) = sidecar.write(&bytes) {
+                let err = as_js_error_fn(&mut cx, "Builder.sign", err)?;
+                return cx.throw(err);
+            }
+        }
 
         // If the output is a buffer, write the signed asset to it
         // Create a new JsBuffer with the contents of output_stream
@@ -348,15 +703,16 @@ impl NeonBuilder {
             let mut signed_asset = Vec::new();
             output_stream
                 .rewind()
-                .or_else(|e| cx.throw_error(format!("Failed to rewind stream: {e}")))?;
+                .or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
             output_stream
                 .read_to_end(&mut signed_asset)
-                .or_else(|e| cx.throw_error(format!("Failed to read stream: {e}")))?;
+                .or_else(|err| throw_js_error(&mut cx, "Builder.sign", err))?;
             let buffer = JsBuffer::from_slice(&mut cx, &signed_asset)?;
             // Set the new JsBuffer on the output JsObject
             output_obj.set(&mut cx, "buffer", buffer)?;
         }
 
+        timer.finish(true);
         let buffer = JsBuffer::from_slice(&mut cx, bytes.as_slice())?;
         Ok(buffer)
     }
@@ -364,10 +720,11 @@ impl NeonBuilder {
     // TODO: This mimics the previous c2pa-node iteration's arguments.
     // It is probably redundant with sign_async.
     pub fn sign_config_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt =
+            runtime().or_else(|err| throw_js_error(&mut cx, "Builder.signConfigAsync", err))?;
         let channel = cx.channel();
 
-        let this = cx.this::<JsBox<Self>>()?;
+        let this = Self::this_live(&mut cx)?;
         let callback = cx.argument::<JsFunction>(0)?;
         let rooted_callback: Arc<Root<JsFunction>> = Arc::new(Root::new(&mut cx, &callback));
         let js_config = cx.argument::<JsObject>(1)?;
@@ -379,26 +736,54 @@ impl NeonBuilder {
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let output_obj = cx.argument::<JsObject>(3)?;
         let output = parse_asset(&mut cx, output_obj)?;
-        let format = match input.mime_type() {
-            Some(mime_type) => mime_type.to_owned(),
-            None => return cx.throw_error("Input asset must have a mime type"),
+        let timeout = parse_timeout(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signConfigAsync", err))?;
+        let priority = parse_priority(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signConfigAsync", err))?;
+        let (format, input_stream) = input
+            .into_typed_read_stream()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signConfigAsync", err))?;
+        let format = match format {
+            Some(mime_type) => mime_type,
+            None => {
+                let err = Error::Asset("Input asset must have a mime type".to_string());
+                return throw_js_error(&mut cx, "Builder.signConfigAsync", err);
+            }
         };
-        let mut input_stream = input
-            .into_read_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+        if let Err(err) = check_signable(&format) {
+            let err = as_js_error_fn(&mut cx, "Builder.signConfigAsync", err)?;
+            return cx.throw(err);
+        }
+        let sidecar =
+            Sidecar::from_destination(&mut cx, output_obj, &output, "Builder.signConfigAsync")?;
         let mut output_stream = output
             .write_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signConfigAsync", err))?;
 
         let builder = Arc::clone(&this.builder);
+        let soft_bindings = this.soft_bindings();
         let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Sign);
         rt.spawn(async move {
-            let result = builder
-                .lock()
-                .await
-                .sign_async(&signer, &format, &mut input_stream, &mut output_stream)
-                .await
-                .map(|sign_result| (sign_result, output_stream));
+            let task = catch_panic(async move {
+                let mut builder = embedding(sidecar.as_ref(), builder.lock().await);
+                let sign_result = soft_binding::sign_async(
+                    &mut builder,
+                    &soft_bindings,
+                    &signer,
+                    &format,
+                    input_stream,
+                    &mut output_stream,
+                )
+                .await?;
+                output_stream.finish()?;
+                if let Some(sidecar) = &sidecar {
+                    sidecar.write(&sign_result)?;
+                }
+                Ok((sign_result, output_stream))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
 
             deferred.settle_with(&channel, move |mut cx| match result {
                 Ok((signed_bytes, mut output_stream)) => {
@@ -406,13 +791,11 @@ impl NeonBuilder {
                         let mut buffer = Vec::new();
                         match output_stream.rewind() {
                             Ok(_) => (),
-                            Err(e) => {
-                                return cx.throw_error(format!("Failed to rewind stream: {e}"))
-                            }
+                            Err(e) => return throw_js_error(&mut cx, "Builder.signConfigAsync", e),
                         }
                         match output_stream.read_to_end(&mut buffer) {
                             Ok(_) => (),
-                            Err(e) => return cx.throw_error(format!("Failed to read stream: {e}")),
+                            Err(e) => return throw_js_error(&mut cx, "Builder.signConfigAsync", e),
                         }
                         Some(buffer)
                     } else {
@@ -431,45 +814,75 @@ impl NeonBuilder {
                         Ok(result_buffer.upcast::<JsValue>())
                     }
                 }
-                Err(err) => cx.throw_error(err.to_string()),
+                Err(err) => as_js_error(&mut cx, "Builder.signConfigAsync", err)
+                    .and_then(|err| cx.throw(err)),
             });
         });
         Ok(promise)
     }
 
     pub fn sign_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
         let channel = cx.channel();
 
-        let this = cx.this::<JsBox<Self>>()?;
+        let this = Self::this_live(&mut cx)?;
         let signer = cx.argument::<JsBox<NeonCallbackSigner>>(0)?;
         let signer_ref: &NeonCallbackSigner = signer.deref();
-        let signer = signer_ref.clone();
         let input = cx
             .argument::<JsObject>(1)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let output_obj = cx.argument::<JsObject>(2)?;
         let output = parse_asset(&mut cx, output_obj)?;
-        let format = match input.mime_type() {
-            Some(mime_type) => mime_type.to_owned(),
-            None => return cx.throw_error("Input asset must have a mime type"),
+        let timeout = parse_timeout(&mut cx, 3)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
+        let priority = parse_priority(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
+        let network = parse_network_options(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
+        let signer = signer_ref.clone().with_network(network);
+        let (format, input_stream) = input
+            .into_typed_read_stream()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
+        let format = match format {
+            Some(mime_type) => mime_type,
+            None => {
+                let err = Error::Asset("Input asset must have a mime type".to_string());
+                return throw_js_error(&mut cx, "Builder.signAsync", err);
+            }
         };
-        let mut input_stream = input
-            .into_read_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+        if let Err(err) = check_signable(&format) {
+            let err = as_js_error_fn(&mut cx, "Builder.signAsync", err)?;
+            return cx.throw(err);
+        }
+        let sidecar = Sidecar::from_destination(&mut cx, output_obj, &output, "Builder.signAsync")?;
         let mut output_stream = output
             .write_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.signAsync", err))?;
 
         let builder = Arc::clone(&this.builder);
+        let soft_bindings = this.soft_bindings();
         let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Sign);
         rt.spawn(async move {
-            let result = builder
-                .lock()
-                .await
-                .sign_async(&signer, &format, &mut input_stream, &mut output_stream)
-                .await
-                .map(|sign_result| (sign_result, output_stream));
+            let task = catch_panic(async move {
+                let mut builder = embedding(sidecar.as_ref(), builder.lock().await);
+                let sign_result = soft_binding::sign_async(
+                    &mut builder,
+                    &soft_bindings,
+                    &signer,
+                    &format,
+                    input_stream,
+                    &mut output_stream,
+                )
+                .await?;
+                output_stream.finish()?;
+                if let Some(sidecar) = &sidecar {
+                    sidecar.write(&sign_result)?;
+                }
+                Ok((sign_result, output_stream))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
 
             deferred.settle_with(&channel, move |mut cx| match result {
                 Ok((signed_bytes, mut output_stream)) => {
@@ -477,13 +890,11 @@ impl NeonBuilder {
                         let mut buffer = Vec::new();
                         match output_stream.rewind() {
                             Ok(_) => (),
-                            Err(e) => {
-                                return cx.throw_error(format!("Failed to rewind stream: {e}"))
-                            }
+                            Err(e) => return throw_js_error(&mut cx, "Builder.signAsync", e),
                         }
                         match output_stream.read_to_end(&mut buffer) {
                             Ok(_) => (),
-                            Err(e) => return cx.throw_error(format!("Failed to read stream: {e}")),
+                            Err(e) => return throw_js_error(&mut cx, "Builder.signAsync", e),
                         }
                         Some(buffer)
                     } else {
@@ -502,46 +913,79 @@ impl NeonBuilder {
                         Ok(result_buffer.upcast::<JsValue>())
                     }
                 }
-                Err(err) => cx.throw_error(err.to_string()),
+                Err(err) => {
+                    as_js_error(&mut cx, "Builder.signAsync", err).and_then(|err| cx.throw(err))
+                }
             });
         });
         Ok(promise)
     }
 
     pub fn identity_sign_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt =
+            runtime().or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
         let channel = cx.channel();
 
-        let this = cx.this::<JsBox<Self>>()?;
+        let this = Self::this_live(&mut cx)?;
         let signer = cx.argument::<JsBox<NeonIdentityAssertionSigner>>(0)?;
         let signer_ref: &NeonIdentityAssertionSigner = signer.deref();
-        let signer = signer_ref.clone();
         let input = cx
             .argument::<JsObject>(1)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let output_obj = cx.argument::<JsObject>(2)?;
         let output = parse_asset(&mut cx, output_obj)?;
-        let format = match input.mime_type() {
-            Some(mime_type) => mime_type.to_owned(),
-            None => return cx.throw_error("Input asset must have a mime type"),
+        let timeout = parse_timeout(&mut cx, 3)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
+        let priority = parse_priority(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
+        let network = parse_network_options(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
+        let signer = signer_ref.clone().with_network(network);
+        let (format, input_stream) = input
+            .into_typed_read_stream()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
+        let format = match format {
+            Some(mime_type) => mime_type,
+            None => {
+                let err = Error::Asset("Input asset must have a mime type".to_string());
+                return throw_js_error(&mut cx, "Builder.identitySignAsync", err);
+            }
         };
-        let mut input_stream = input
-            .into_read_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+        if let Err(err) = check_signable(&format) {
+            let err = as_js_error_fn(&mut cx, "Builder.identitySignAsync", err)?;
+            return cx.throw(err);
+        }
+        let sidecar =
+            Sidecar::from_destination(&mut cx, output_obj, &output, "Builder.identitySignAsync")?;
         let mut output_stream = output
             .write_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.identitySignAsync", err))?;
 
         let builder = Arc::clone(&this.builder);
+        let soft_bindings = this.soft_bindings();
         let (deferred, promise) = cx.promise();
 
+        let timer = OperationTimer::start(Operation::Sign);
         rt.spawn(async move {
-            let result = builder
-                .lock()
-                .await
-                .sign_async(&signer, &format, &mut input_stream, &mut output_stream)
-                .await
-                .map(|sign_result| (sign_result, output_stream));
+            let task = catch_panic(async move {
+                let mut builder = embedding(sidecar.as_ref(), builder.lock().await);
+                let sign_result = soft_binding::sign_async(
+                    &mut builder,
+                    &soft_bindings,
+                    &signer,
+                    &format,
+                    input_stream,
+                    &mut output_stream,
+                )
+                .await?;
+                output_stream.finish()?;
+                if let Some(sidecar) = &sidecar {
+                    sidecar.write(&sign_result)?;
+                }
+                Ok((sign_result, output_stream))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
 
             deferred.settle_with(&channel, move |mut cx| match result {
                 Ok((signed_bytes, mut output_stream)) => {
@@ -550,12 +994,14 @@ impl NeonBuilder {
                         match output_stream.rewind() {
                             Ok(_) => (),
                             Err(e) => {
-                                return cx.throw_error(format!("Failed to rewind stream: {e}"))
+                                return throw_js_error(&mut cx, "Builder.identitySignAsync", e)
                             }
                         }
                         match output_stream.read_to_end(&mut buffer) {
                             Ok(_) => (),
-                            Err(e) => return cx.throw_error(format!("Failed to read stream: {e}")),
+                            Err(e) => {
+                                return throw_js_error(&mut cx, "Builder.identitySignAsync", e)
+                            }
                         }
                         Some(buffer)
                     } else {
@@ -574,26 +1020,38 @@ impl NeonBuilder {
                         Ok(result_buffer.upcast::<JsValue>())
                     }
                 }
-                Err(err) => cx.throw_error(err.to_string()),
+                Err(err) => as_js_error(&mut cx, "Builder.identitySignAsync", err)
+                    .and_then(|err| cx.throw(err)),
             });
         });
         Ok(promise)
     }
 
-    pub fn manifest_definition(mut cx: FunctionContext) -> JsResult<JsValue> {
-        let rt = runtime();
+    pub fn supported_mime_types(mut cx: FunctionContext) -> JsResult<JsArray> {
+        mime_type_array(&mut cx, Builder::supported_mime_types())
+    }
+
+    pub fn memory_usage(mut cx: FunctionContext) -> JsResult<JsNumber> {
         let this = cx.this::<JsBox<Self>>()?;
+        Ok(cx.number(this.footprint.bytes() as f64))
+    }
+
+    pub fn manifest_definition(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.getManifestDefinition", err))?;
+        let this = Self::this_live(&mut cx)?;
         let builder = rt.block_on(async { this.builder.lock().await });
         let json = serde_json::to_string(&builder.definition)
-            .or_else(|err| cx.throw_error(err.to_string()))?;
+            .or_else(|err| throw_js_error(&mut cx, "Builder.getManifestDefinition", err))?;
         Ok(cx.string(json).upcast())
     }
 
     /// Update a manifest property. Available properties are limited to strings and numbers.
     /// There are other methods for thumbnails, ingredients and assertions, etc.
     pub fn update_manifest_property(mut cx: FunctionContext) -> JsResult<JsUndefined> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime()
+            .or_else(|err| throw_js_error(&mut cx, "Builder.updateManifestProperty", err))?;
+        let this = Self::this_live(&mut cx)?;
         let property = cx.argument::<JsString>(0)?.value(&mut cx);
         let value = cx.argument::<JsValue>(1)?;
 
@@ -630,6 +1088,38 @@ impl NeonBuilder {
                     .value(&mut cx);
                 builder.definition.label = Some(value);
             }
+            "hash_alg" => {
+                let value = parse_algorithm(&mut cx, 1)?;
+                builder.definition.hash_alg = Some(value);
+            }
+            "bmff_hash_version" => {
+                // c2pa-rs writes the v3 BMFF hash in 2.x claims and falls back
+                // to v2 for 1.x claims, which older validators read. A claim
+                // version chosen already is kept rather than overridden.
+                let version = value
+                    .downcast_or_throw::<JsNumber, _>(&mut cx)?
+                    .value(&mut cx);
+                let claim_version = if version == 2.0 {
+                    1
+                } else if version == 3.0 {
+                    2
+                } else {
+                    return cx.throw_range_error(format!(
+                        "Unsupported BMFF hash version {version}. Expected 2 or 3"
+                    ));
+                };
+                match builder.definition.claim_version {
+                    None => builder.definition.claim_version = Some(claim_version),
+                    Some(current) if current == claim_version => {}
+                    Some(current) => {
+                        let err = Error::InvalidArgument(format!(
+                            "BMFF hash version {version} needs claim version {claim_version}, \
+                             but the claim version is {current}"
+                        ));
+                        return throw_js_error(&mut cx, "Builder.updateManifestProperty", err);
+                    }
+                }
+            }
             "claim_version" => {
                 let value = value
                     .downcast_or_throw::<JsNumber, _>(&mut cx)?
@@ -637,9 +1127,10 @@ impl NeonBuilder {
                 builder.definition.claim_version = Some(value);
             }
             _ => {
-                return cx.throw_error(format!(
+                let err = Error::InvalidArgument(format!(
                     "Property '{property}' not found or not a valid type"
-                ))
+                ));
+                return throw_js_error(&mut cx, "Builder.updateManifestProperty", err);
             }
         }
 
@@ -0,0 +1,75 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::error::as_js_error_fn;
+use crate::js_value::from_js;
+use crate::network::build_context;
+use crate::settings::{resolve_settings, settings_to_json};
+use c2pa::Context as C2paContext;
+use neon::prelude::*;
+use std::sync::Arc;
+
+/// A c2pa Context built once from settings and shared by every Reader and
+/// Builder it is passed to, so trust lists are only parsed a single time.
+#[derive(Debug)]
+pub struct NeonContext {
+    context: Arc<C2paContext>,
+}
+
+impl Finalize for NeonContext {}
+
+impl NeonContext {
+    pub fn new(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
+        let settings = match cx.argument_opt(0) {
+            Some(value) if value.is_a::<JsString, _>(&mut cx) => Some(
+                value
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx),
+            ),
+            Some(value)
+                if value.is_a::<JsNull, _>(&mut cx) || value.is_a::<JsUndefined, _>(&mut cx) =>
+            {
+                None
+            }
+            Some(value) if value.is_a::<JsObject, _>(&mut cx) => {
+                Some(from_js(&mut cx, value)?.to_string())
+            }
+            Some(_) => {
+                return cx.throw_error("Settings must be a string, object, null, or undefined")
+            }
+            None => None,
+        };
+
+        let context = resolve_settings(settings.as_deref()).and_then(build_context);
+        match context {
+            Ok(context) => Ok(cx.boxed(Self {
+                context: context.into_shared(),
+            })),
+            Err(err) => as_js_error_fn(&mut cx, "Context.new", err).and_then(|err| cx.throw(err)),
+        }
+    }
+
+    pub(crate) fn context(&self) -> Arc<C2paContext> {
+        Arc::clone(&self.context)
+    }
+
+    pub fn settings(mut cx: FunctionContext) -> JsResult<JsString> {
+        let this = cx.this::<JsBox<Self>>()?;
+        match settings_to_json(this.context.settings()) {
+            Ok(json) => Ok(cx.string(json)),
+            Err(err) => {
+                as_js_error_fn(&mut cx, "Context.settings", err).and_then(|err| cx.throw(err))
+            }
+        }
+    }
+}
@@ -11,12 +11,18 @@
 // specific language governing permissions and limitations under
 // each license.
 
+use crate::neon_signer::NeonCallbackSigner;
 use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use c2pa::identity::builder::{AsyncCredentialHolder, IdentityBuilderError};
+use c2pa::identity::x509::AsyncX509CredentialHolder;
 use c2pa::identity::SignerPayload;
+use coset::{iana, CoseSign1Builder, HeaderBuilder, TaggedCborSerializable};
 use neon::prelude::*;
 use neon::types::buffer::TypedArray;
 use neon_serde4;
+use serde_json::{json, Map};
+use std::ops::Deref;
 use std::sync::Arc;
 use tokio::sync::oneshot;
 
@@ -147,3 +153,193 @@ impl AsyncCredentialHolder for NeonCallbackCredentialHolder {
 }
 
 impl Finalize for NeonCallbackCredentialHolder {}
+
+/// `sig_type` of an identity claims aggregation credential.
+const ICA_SIG_TYPE: &str = "cawg.identity_claims_aggregation";
+
+/// NeonVerifiableCredentialHolder binds a caller-supplied W3C verifiable
+/// credential to the claim and signs it as an identity claims aggregation
+/// credential. The signer payload is written to the credential subject's
+/// `c2paAsset` and the credential is wrapped in a COSE_Sign1 whose EdDSA
+/// signature comes from JS.
+#[derive(Clone)]
+pub struct NeonVerifiableCredentialHolder {
+    channel: Channel,
+    credential: Map<String, serde_json::Value>,
+    // JS function: (data: Buffer) => Promise<Buffer>
+    callback: Arc<Root<JsFunction>>,
+    reserve_size: usize,
+}
+
+impl NeonVerifiableCredentialHolder {
+    pub fn from_js(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
+        let credential_json = cx.argument::<JsString>(0)?.value(&mut cx);
+        let reserve_size = cx.argument::<JsNumber>(1)?.value(&mut cx) as usize;
+        let callback = cx.argument::<JsFunction>(2)?.root(&mut cx);
+        let credential = match serde_json::from_str::<serde_json::Value>(&credential_json) {
+            Ok(serde_json::Value::Object(credential)) => credential,
+            Ok(_) => return cx.throw_type_error("The credential must be a JSON object"),
+            Err(err) => return cx.throw_type_error(format!("Invalid credential: {err}")),
+        };
+        match credential.get("credentialSubject") {
+            Some(serde_json::Value::Object(_)) => {}
+            Some(serde_json::Value::Array(subjects))
+                if !subjects.is_empty() && subjects.iter().all(serde_json::Value::is_object) => {}
+            _ => {
+                return cx.throw_type_error(
+                    "The credential must have a credentialSubject object or array of objects",
+                )
+            }
+        }
+        let channel = cx.channel();
+        Ok(cx.boxed(Self {
+            channel,
+            credential,
+            callback: Arc::new(callback),
+            reserve_size,
+        }))
+    }
+
+    /// The credential with the signer payload bound to each credential subject.
+    fn bind(&self, signer_payload: &SignerPayload) -> Map<String, serde_json::Value> {
+        let c2pa_asset = c2pa_asset(signer_payload);
+        let mut credential = self.credential.clone();
+        match credential.get_mut("credentialSubject") {
+            Some(serde_json::Value::Object(subject)) => {
+                subject.insert("c2paAsset".to_string(), c2pa_asset);
+            }
+            Some(serde_json::Value::Array(subjects)) => {
+                for subject in subjects
+                    .iter_mut()
+                    .filter_map(serde_json::Value::as_object_mut)
+                {
+                    subject.insert("c2paAsset".to_string(), c2pa_asset.clone());
+                }
+            }
+            _ => {}
+        }
+        credential
+    }
+
+    async fn sign_bytes(&self, data: Vec<u8>) -> Result<Vec<u8>, IdentityBuilderError> {
+        let (tx, rx) = oneshot::channel();
+        let callback = self.callback.clone();
+
+        self.channel
+            .try_send(move |mut cx| {
+                let js_data = JsBuffer::from_slice(&mut cx, &data)?;
+                let js_fn = callback.to_inner(&mut cx);
+                let promise = js_fn
+                    .call_with(&cx)
+                    .arg(js_data)
+                    .apply::<JsPromise, _>(&mut cx)?
+                    .to_future(&mut cx, |mut cx, result| match result {
+                        Ok(value) => Ok(Ok(value
+                            .downcast_or_throw::<JsBuffer, _>(&mut cx)?
+                            .as_slice(&cx)
+                            .to_vec())),
+                        Err(err) => {
+                            let err_string = err.to_string(&mut cx)?.value(&mut cx);
+                            Ok(Err(IdentityBuilderError::SignerError(err_string)))
+                        }
+                    })?;
+                let _ = tx.send(promise);
+                Ok(())
+            })
+            .map_err(|err| IdentityBuilderError::SignerError(err.to_string()))?;
+
+        let fut = rx
+            .await
+            .map_err(|err| IdentityBuilderError::SignerError(err.to_string()))?;
+
+        fut.await
+            .map_err(|err| IdentityBuilderError::SignerError(err.to_string()))?
+    }
+}
+
+/// The signer payload as the JSON `c2paAsset` of a credential subject, with
+/// each referenced assertion's hash base64 encoded.
+fn c2pa_asset(signer_payload: &SignerPayload) -> serde_json::Value {
+    let referenced_assertions: Vec<serde_json::Value> = signer_payload
+        .referenced_assertions
+        .iter()
+        .map(|assertion| {
+            let mut reference = json!({
+                "url": assertion.url(),
+                "hash": BASE64.encode(assertion.hash()),
+            });
+            if let Some(alg) = assertion.alg() {
+                reference["alg"] = serde_json::Value::String(alg);
+            }
+            reference
+        })
+        .collect();
+    let mut c2pa_asset = json!({
+        "referenced_assertions": referenced_assertions,
+        "sig_type": signer_payload.sig_type,
+    });
+    if !signer_payload.roles.is_empty() {
+        c2pa_asset["role"] = json!(signer_payload.roles);
+    }
+    c2pa_asset
+}
+
+// See the `Send` and `Sync` notes on `NeonCallbackCredentialHolder`.
+unsafe impl Send for NeonVerifiableCredentialHolder {}
+unsafe impl Sync for NeonVerifiableCredentialHolder {}
+
+#[async_trait]
+impl AsyncCredentialHolder for NeonVerifiableCredentialHolder {
+    fn sig_type(&self) -> &'static str {
+        ICA_SIG_TYPE
+    }
+
+    fn reserve_size(&self) -> usize {
+        self.reserve_size
+    }
+
+    async fn sign(&self, signer_payload: &SignerPayload) -> Result<Vec<u8>, IdentityBuilderError> {
+        let credential = self.bind(signer_payload);
+        let payload = serde_json::to_vec(&credential)
+            .map_err(|err| IdentityBuilderError::CredentialError(err.to_string()))?;
+
+        let protected = HeaderBuilder::new()
+            .algorithm(iana::Algorithm::EdDSA)
+            .content_type("application/vc".to_string())
+            .build();
+        let mut sign1 = CoseSign1Builder::new()
+            .protected(protected)
+            .payload(payload)
+            .build();
+        sign1.signature = self.sign_bytes(sign1.tbs_data(b"")).await?;
+
+        sign1
+            .to_tagged_vec()
+            .map_err(|err| IdentityBuilderError::CborGenerationError(err.to_string()))
+    }
+}
+
+impl Finalize for NeonVerifiableCredentialHolder {}
+
+/// NeonX509CredentialHolder signs identity assertions with the certificate
+/// chain and JS callback of a `NeonCallbackSigner`. The signer payload is
+/// bound to the claim and wrapped in a COSE_Sign1 natively; the callback only
+/// signs the bytes it is given.
+#[derive(Clone)]
+pub struct NeonX509CredentialHolder(Arc<AsyncX509CredentialHolder>);
+
+impl NeonX509CredentialHolder {
+    pub fn from_signer(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
+        let signer_handle = cx.argument::<JsBox<NeonCallbackSigner>>(0)?;
+        let signer: &NeonCallbackSigner = signer_handle.deref();
+        let credential_holder =
+            AsyncX509CredentialHolder::from_async_raw_signer(Box::new(signer.clone()));
+        Ok(cx.boxed(Self(Arc::new(credential_holder))))
+    }
+
+    pub(crate) fn credential_holder(&self) -> Arc<dyn AsyncCredentialHolder> {
+        self.0.clone()
+    }
+}
+
+impl Finalize for NeonX509CredentialHolder {}
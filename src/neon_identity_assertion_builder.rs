@@ -11,7 +11,9 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use crate::neon_credential_holder::NeonCallbackCredentialHolder;
+use crate::neon_credential_holder::{
+    NeonCallbackCredentialHolder, NeonVerifiableCredentialHolder, NeonX509CredentialHolder,
+};
 use c2pa::{
     dynamic_assertion::{AsyncDynamicAssertion, DynamicAssertionContent},
     identity::{builder::AsyncCredentialHolder, SignerPayload},
@@ -20,12 +22,12 @@ use neon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
 use std::ops::Deref;
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 
 /// A `NeonIdentityAssertionBuilder` gathers the necessary components
 /// for an identity assertion using a Neon-based credential holder.
 pub struct NeonIdentityAssertionBuilder {
-    credential_holder: RwLock<NeonCallbackCredentialHolder>,
+    credential_holder: RwLock<Arc<dyn AsyncCredentialHolder>>,
     referenced_assertions: RwLock<Vec<String>>,
     roles: RwLock<Vec<String>>,
 }
@@ -64,12 +66,25 @@ impl Clone for NeonIdentityAssertionBuilder {
 }
 
 impl NeonIdentityAssertionBuilder {
-    /// Create a `NeonIdentityAssertionBuilder` for the given JS credential holder.
+    /// Create a `NeonIdentityAssertionBuilder` for the given JS credential holder:
+    /// a callback, verifiable credential, or X.509 credential holder.
     pub fn for_credential_holder(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
-        let credential_holder_handle = cx.argument::<JsBox<NeonCallbackCredentialHolder>>(0)?;
-        let credential_holder_ref: &NeonCallbackCredentialHolder = credential_holder_handle.deref();
+        let handle = cx.argument::<JsValue>(0)?;
+        let credential_holder: Arc<dyn AsyncCredentialHolder> = if let Ok(holder) =
+            handle.downcast::<JsBox<NeonCallbackCredentialHolder>, _>(&mut cx)
+        {
+            Arc::new(NeonCallbackCredentialHolder::clone(holder.deref()))
+        } else if let Ok(holder) =
+            handle.downcast::<JsBox<NeonVerifiableCredentialHolder>, _>(&mut cx)
+        {
+            Arc::new(NeonVerifiableCredentialHolder::clone(holder.deref()))
+        } else {
+            handle
+                .downcast_or_throw::<JsBox<NeonX509CredentialHolder>, _>(&mut cx)?
+                .credential_holder()
+        };
         Ok(cx.boxed(Self {
-            credential_holder: RwLock::new(credential_holder_ref.clone()),
+            credential_holder: RwLock::new(credential_holder),
             referenced_assertions: RwLock::new(vec![]),
             roles: RwLock::new(vec![]),
         }))
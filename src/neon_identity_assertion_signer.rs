@@ -13,12 +13,13 @@
 
 use crate::{
     neon_identity_assertion_builder::NeonIdentityAssertionBuilder, neon_signer::NeonCallbackSigner,
+    network::NetworkOptions,
 };
 use async_trait::async_trait;
 use c2pa::{
     crypto::{
         raw_signature::{AsyncRawSigner, RawSignerError},
-        time_stamp::AsyncTimeStampProvider,
+        time_stamp::{AsyncTimeStampProvider, TimeStampError},
     },
     dynamic_assertion::AsyncDynamicAssertion,
     AsyncSigner, SigningAlg,
@@ -55,6 +56,15 @@ impl NeonIdentityAssertionSigner {
             .push(iab_ref.clone());
         Ok(cx.undefined())
     }
+
+    /// This signer with `network` for its time-stamp requests.
+    pub fn with_network(self, network: Option<NetworkOptions>) -> Self {
+        let signer = self.signer.into_inner().unwrap().with_network(network);
+        Self {
+            signer: RwLock::new(signer),
+            ..self
+        }
+    }
 }
 
 impl Clone for NeonIdentityAssertionSigner {
@@ -116,6 +126,7 @@ impl AsyncRawSigner for NeonIdentityAssertionSigner {
     }
 }
 
+#[async_trait]
 impl AsyncTimeStampProvider for NeonIdentityAssertionSigner {
     fn time_stamp_service_url(&self) -> Option<String> {
         self.signer.read().unwrap().time_stamp_service_url()
@@ -124,6 +135,14 @@ impl AsyncTimeStampProvider for NeonIdentityAssertionSigner {
     fn time_stamp_request_headers(&self) -> Option<Vec<(String, String)>> {
         self.signer.read().unwrap().time_stamp_request_headers()
     }
+
+    async fn send_time_stamp_request(
+        &self,
+        message: &[u8],
+    ) -> Option<Result<Vec<u8>, TimeStampError>> {
+        let signer = self.signer.read().unwrap().clone();
+        AsyncTimeStampProvider::send_time_stamp_request(&signer, message).await
+    }
 }
 
 #[async_trait]
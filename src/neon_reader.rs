@@ -11,88 +11,205 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use crate::asset::parse_asset;
-use crate::error::{as_js_error, Error, Result};
-use crate::runtime::runtime;
-use crate::utils::parse_settings;
+use crate::asset::{parse_asset, Asset, JsBytes};
+use crate::conformance::ConformanceReport;
+use crate::error::{as_js_error, as_js_error_fn, catch_panic, throw_js_error, Error, Result};
+use crate::fragments::{read_fragments, Fragments};
+use crate::graph::{GraphFormat, ProvenanceGraph};
+use crate::js_value::to_js;
+use crate::memory::Footprint;
+use crate::metrics::{Operation, OperationTimer};
+use crate::network::RangeClient;
+use crate::provenance_reference::ProvenanceReference;
+use crate::remote_manifest::fetch_remote_manifest;
+use crate::report::{signatures, Report, ReportFormat};
+use crate::runtime::{blocking, in_lane, running_runtime, runtime, with_timeout};
+use crate::shared_reader;
+use crate::utils::{
+    mime_type_array, parse_priority, parse_settings, parse_timeout, parse_verify_options,
+};
+use crate::validation::{validation_messages, MessageFilter};
+use crate::validation_policy::manifest_store_value;
+use c2pa::jumbf_io::load_jumbf_from_stream;
 use c2pa::Reader;
 use neon::context::Context as NeonContext;
 use neon::prelude::*;
-use neon::types::buffer::TypedArray;
+use std::io::Seek;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 #[derive(Debug)]
 pub struct NeonReader {
     reader: Arc<Mutex<Reader>>,
+    disposed: AtomicBool,
+    // Set once the Reader is shared, so disposing one handle doesn't clear it
+    // for the others.
+    shared: AtomicBool,
+    footprint: Footprint,
 }
 
 impl Finalize for NeonReader {}
 
+/// Which manifest store `from_manifest_data_and_asset` reads when the asset
+/// has one embedded that differs from the provided manifest data.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Precedence {
+    #[default]
+    Provided,
+    Embedded,
+    /// Fail instead of choosing.
+    Error,
+}
+
+impl Precedence {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "provided" => Some(Precedence::Provided),
+            "embedded" => Some(Precedence::Embedded),
+            "error" => Some(Precedence::Error),
+            _ => None,
+        }
+    }
+}
+
+/// How the manifest store embedded in an asset compares with the manifest
+/// data provided for it.
+#[derive(Clone, Copy, PartialEq)]
+enum EmbeddedMatch {
+    Absent,
+    Same,
+    /// The bytes differ, or the embedded store can't be loaded.
+    Different,
+}
+
+impl EmbeddedMatch {
+    fn as_str(self) -> &'static str {
+        match self {
+            EmbeddedMatch::Absent => "absent",
+            EmbeddedMatch::Same => "same",
+            EmbeddedMatch::Different => "different",
+        }
+    }
+}
+
 impl NeonReader {
     pub fn new(mut cx: FunctionContext) -> JsResult<JsBox<NeonReader>> {
+        let reader = Reader::default();
         Ok(cx.boxed(Self {
-            reader: Arc::new(Mutex::new(Reader::default())),
+            disposed: AtomicBool::new(false),
+            shared: AtomicBool::new(false),
+            footprint: Footprint::reader(&reader),
+            reader: Arc::new(Mutex::new(reader)),
         }))
     }
 
     #[allow(clippy::borrowed_box)]
-    pub(crate) fn reader(&self) -> Arc<Mutex<Reader>> {
-        Arc::clone(&self.reader)
+    pub(crate) fn reader(&self) -> Result<Arc<Mutex<Reader>>> {
+        if self.disposed.load(Ordering::Acquire) {
+            return Err(Error::Disposed("Reader"));
+        }
+        Ok(Arc::clone(&self.reader))
+    }
+
+    /// Handle to `this`, throwing if `dispose` has been called.
+    fn this_live<'a>(cx: &mut FunctionContext<'a>) -> JsResult<'a, JsBox<Self>> {
+        let this = cx.this::<JsBox<Self>>()?;
+        if this.disposed.load(Ordering::Acquire) {
+            let err = as_js_error_fn(cx, "Reader", Error::Disposed("Reader"))?;
+            return cx.throw(err);
+        }
+        Ok(this)
+    }
+
+    /// Release the Reader's native memory now instead of when it is garbage
+    /// collected. Later calls on it fail; disposing again does nothing.
+    pub fn dispose(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+        let this = cx.this::<JsBox<Self>>()?;
+        if this.disposed.swap(true, Ordering::AcqRel) {
+            return Ok(cx.undefined());
+        }
+        this.footprint.release();
+        if this.shared.load(Ordering::Acquire) {
+            // Other handles may still use the Reader; it is freed with the last one.
+        } else if let Ok(mut reader) = this.reader.try_lock() {
+            *reader = Reader::default();
+        } else if let Some(rt) = running_runtime() {
+            // A running task holds the Reader; clear it once that task is done.
+            let reader = Arc::clone(&this.reader);
+            rt.spawn(async move {
+                *reader.lock().await = Reader::default();
+            });
+        }
+        Ok(cx.undefined())
+    }
+
+    /// Register the Reader in a process-wide registry and return its key,
+    /// which `from_shared` opens from any worker thread without re-reading.
+    pub fn share(mut cx: FunctionContext) -> JsResult<JsString> {
+        let this = Self::this_live(&mut cx)?;
+        this.shared.store(true, Ordering::Release);
+        let key = shared_reader::share(Arc::clone(&this.reader), this.footprint.bytes());
+        Ok(cx.string(key))
+    }
+
+    /// Open a new handle on the Reader shared under `key`.
+    pub fn from_shared(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
+        let key = cx.argument::<JsString>(0)?.value(&mut cx);
+        let Some((reader, bytes)) = shared_reader::get(&key) else {
+            let err = Error::InvalidArgument(format!("No Reader is shared under the key {key}"));
+            return throw_js_error(&mut cx, "Reader.fromShared", err);
+        };
+        // Taking the Reader's lock here would block the JS thread while any
+        // task holds it.
+        let footprint = Footprint::reader_of_size(bytes);
+        Ok(cx.boxed(Self {
+            disposed: AtomicBool::new(false),
+            shared: AtomicBool::new(true),
+            footprint,
+            reader,
+        }))
+    }
+
+    /// Remove `key` from the registry, returning whether it was registered.
+    pub fn release_shared(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+        let key = cx.argument::<JsString>(0)?.value(&mut cx);
+        Ok(cx.boolean(shared_reader::release(&key)))
     }
 
     pub fn from_stream(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.fromAsset", err))?;
         let channel = cx.channel();
         let source = cx
             .argument::<JsObject>(0)
             .and_then(|obj| parse_asset(&mut cx, obj))?;
 
-        // Parse optional settings parameter (argument 1)
-        let context_opt =
-            parse_settings(&mut cx, 1, "Reader").or_else(|err| cx.throw_error(err.to_string()))?;
+        // Parse optional settings (argument 1) and verify options (argument 2)
+        let context_opt = parse_settings(&mut cx, 1, "Reader")
+            .and_then(|context| parse_verify_options(&mut cx, 2, context, "Reader"))
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAsset", err))?;
+        let timeout = parse_timeout(&mut cx, 3)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAsset", err))?;
+        let priority = parse_priority(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAsset", err))?;
 
         let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Read);
         rt.spawn(async move {
-            let result: Result<Reader> = async {
-                let format = source
-                    .mime_type()
-                    .ok_or_else(|| {
-                        Error::Reading("Source asset must have a mime type".to_string())
-                    })?
-                    .to_owned();
+            let task = catch_panic(Self::read_stream(source, context_opt));
+            let result: Result<(Reader, Footprint)> =
+                with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(matches!(
+                result,
+                Ok(_) | Err(Error::C2pa(c2pa::Error::JumbfNotFound))
+            ));
 
-                let stream = source.into_read_stream()?;
-
-                // Create reader with or without context
-                let reader = if let Some(context) = context_opt {
-                    Reader::from_context(context)
-                        .with_stream_async(&format, stream)
-                        .await?
-                } else {
-                    Reader::from_stream_async(&format, stream).await?
-                };
-
-                Ok(reader)
-            }
-            .await;
-
-            deferred.settle_with(&channel, move |mut cx| match result {
-                Ok(reader) => {
-                    let boxed_reader = cx.boxed(Self {
-                        reader: Arc::new(Mutex::new(reader)),
-                    });
-                    Ok(boxed_reader.upcast::<JsValue>())
-                }
-                Err(err) => {
-                    // Check if the error is due to missing C2PA data
-                    // Return null instead of throwing for these specific cases
-                    match &err {
-                        Error::C2pa(c2pa_err) => match c2pa_err {
-                            c2pa::Error::JumbfNotFound => Ok(cx.null().upcast::<JsValue>()),
-                            _ => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
-                        },
-                        _ => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+            deferred.settle_with(&channel, move |mut cx| {
+                match Self::read_result(&mut cx, result) {
+                    Ok(value) => Ok(value),
+                    Err(err) => {
+                        as_js_error(&mut cx, "Reader.fromAsset", err).and_then(|err| cx.throw(err))
                     }
                 }
             });
@@ -100,8 +217,112 @@ impl NeonReader {
         Ok(promise)
     }
 
+    /// Read an asset like `from_stream`, on the runtime's worker threads, with
+    /// the JS thread waiting for the result instead of receiving a promise.
+    pub fn from_stream_sync(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.fromAssetSync", err))?;
+        let source = cx
+            .argument::<JsObject>(0)
+            .and_then(|obj| parse_asset(&mut cx, obj))?;
+        if source.is_js_backed() {
+            let err = Error::Asset(
+                "Stream and IO assets can only be used with asynchronous methods".to_string(),
+            );
+            return as_js_error_fn(&mut cx, "Reader.fromAssetSync", err)
+                .and_then(|err| cx.throw(err));
+        }
+        let context_opt = parse_settings(&mut cx, 1, "Reader")
+            .and_then(|context| parse_verify_options(&mut cx, 2, context, "Reader"))
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAssetSync", err))?;
+        let timeout = parse_timeout(&mut cx, 3)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAssetSync", err))?;
+        let priority = parse_priority(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromAssetSync", err))?;
+
+        let timer = OperationTimer::start(Operation::Read);
+        let task = blocking(catch_panic(Self::read_stream(source, context_opt)));
+        let handle = rt.spawn(with_timeout(timeout, in_lane(priority, task)));
+        let result = rt
+            .block_on(handle)
+            .unwrap_or_else(|err| Err(Error::TokioJoin(err)));
+        timer.finish(matches!(
+            result,
+            Ok(_) | Err(Error::C2pa(c2pa::Error::JumbfNotFound))
+        ));
+        match Self::read_result(&mut cx, result) {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                as_js_error_fn(&mut cx, "Reader.fromAssetSync", err).and_then(|err| cx.throw(err))
+            }
+        }
+    }
+
+    async fn read_stream(
+        source: Asset,
+        context_opt: Option<Arc<c2pa::Context>>,
+    ) -> Result<(Reader, Footprint)> {
+        let asset_path = source.path().map(Path::to_path_buf);
+        let (format, mut stream) = source.into_typed_read_stream()?;
+        let format = format
+            .ok_or_else(|| Error::Reading("Source asset must have a mime type".to_string()))?;
+
+        // Create reader with or without context
+        let new_reader = || match &context_opt {
+            Some(context) => Reader::from_shared_context(context),
+            None => Reader::default(),
+        };
+        let reader = match new_reader().with_stream_async(&format, &mut stream).await {
+            // Without a manifest store of its own, the asset may refer to one
+            // only from its XMP.
+            Err(c2pa::Error::JumbfNotFound) => {
+                let Some(reference) = ProvenanceReference::find(
+                    &mut stream,
+                    Some(format.clone()),
+                    asset_path.as_deref(),
+                )?
+                else {
+                    return Err(Error::C2pa(c2pa::Error::JumbfNotFound));
+                };
+                let manifest_data = reference
+                    .resolve(asset_path.as_deref(), context_opt.as_deref())
+                    .await?;
+                stream.rewind()?;
+                new_reader()
+                    .with_manifest_data_and_stream_async(&manifest_data, &format, stream)
+                    .await?
+            }
+            result => result?,
+        };
+
+        // Measured here rather than on the JS thread.
+        let footprint = Footprint::reader(&reader);
+        Ok((reader, footprint))
+    }
+
+    /// Box a newly read reader, or null when the asset has no manifest.
+    fn read_result<'a, C: NeonContext<'a>>(
+        cx: &mut C,
+        result: Result<(Reader, Footprint)>,
+    ) -> Result<Handle<'a, JsValue>> {
+        match result {
+            Ok((reader, footprint)) => {
+                let boxed_reader = cx.boxed(Self {
+                    disposed: AtomicBool::new(false),
+                    shared: AtomicBool::new(false),
+                    footprint,
+                    reader: Arc::new(Mutex::new(reader)),
+                });
+                Ok(boxed_reader.upcast::<JsValue>())
+            }
+            // Missing C2PA data returns null instead of throwing
+            Err(Error::C2pa(c2pa::Error::JumbfNotFound)) => Ok(cx.null().upcast::<JsValue>()),
+            Err(err) => Err(err),
+        }
+    }
+
     pub fn from_manifest_data_and_asset(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt = runtime()
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromManifestDataAndAsset", err))?;
         let channel = cx.channel();
         let manifest_data = cx.argument::<JsBuffer>(0)?;
         let asset = cx
@@ -110,72 +331,441 @@ impl NeonReader {
 
         // Parse optional settings parameter (argument 2) - note: settings are not currently used
         // for from_manifest_data_and_asset as the c2pa-rs API doesn't support context for this method yet
-        let context_opt =
-            parse_settings(&mut cx, 2, "Reader").or_else(|err| cx.throw_error(err.to_string()))?;
+        let context_opt = parse_settings(&mut cx, 2, "Reader")
+            .and_then(|context| parse_verify_options(&mut cx, 3, context, "Reader"))
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromManifestDataAndAsset", err))?;
+        let timeout = parse_timeout(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromManifestDataAndAsset", err))?;
+        let priority = parse_priority(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromManifestDataAndAsset", err))?;
+        let precedence = match cx.argument_opt(6) {
+            Some(name) if name.is_a::<JsString, _>(&mut cx) => {
+                let name = name
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx);
+                Precedence::parse(&name).map_or_else(
+                    || {
+                        cx.throw_range_error(format!(
+                            "Unknown precedence \"{name}\". Expected provided, embedded, or error"
+                        ))
+                    },
+                    Ok,
+                )?
+            }
+            _ => Precedence::default(),
+        };
+
+        let c2pa_data = JsBytes::new(&mut cx, manifest_data);
+        let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Read);
+        rt.spawn(async move {
+            let task = catch_panic(async move {
+                let (format, mut stream) = asset.into_typed_read_stream()?;
+                let format = format.ok_or_else(|| {
+                    Error::Reading("Source asset must have a mime type".to_string())
+                })?;
+
+                let embedded = match load_jumbf_from_stream(&format, &mut stream) {
+                    Ok(store) if store == c2pa_data.as_ref() => EmbeddedMatch::Same,
+                    Err(c2pa::Error::JumbfNotFound) => EmbeddedMatch::Absent,
+                    _ => EmbeddedMatch::Different,
+                };
+                stream.rewind()?;
+
+                let new_reader = || match &context_opt {
+                    Some(context) => Reader::from_shared_context(context),
+                    None => Reader::default(),
+                };
+                let reader = match (precedence, embedded) {
+                    (Precedence::Error, EmbeddedMatch::Different) => {
+                        return Err(Error::ManifestDataMismatch)
+                    }
+                    (Precedence::Embedded, EmbeddedMatch::Same | EmbeddedMatch::Different) => {
+                        new_reader().with_stream_async(&format, stream).await?
+                    }
+                    _ => {
+                        new_reader()
+                            .with_manifest_data_and_stream_async(
+                                c2pa_data.as_ref(),
+                                &format,
+                                stream,
+                            )
+                            .await?
+                    }
+                };
+
+                // Measured here rather than on the JS thread.
+                let footprint = Footprint::reader(&reader);
+                Ok((reader, footprint, embedded))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
+
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok((reader, footprint, embedded)) => {
+                    let obj = cx.empty_object();
+                    let boxed_reader = cx.boxed(Self {
+                        disposed: AtomicBool::new(false),
+                        shared: AtomicBool::new(false),
+                        footprint,
+                        reader: Arc::new(Mutex::new(reader)),
+                    });
+                    obj.set(&mut cx, "reader", boxed_reader)?;
+                    let embedded = cx.string(embedded.as_str());
+                    obj.set(&mut cx, "embedded", embedded)?;
+                    Ok(obj)
+                }
+                Err(err) => as_js_error(&mut cx, "Reader.fromManifestDataAndAsset", err)
+                    .and_then(|err| cx.throw(err)),
+            });
+        });
+        Ok(promise)
+    }
+
+    /// Read an asset from an HTTP(S) URL with range requests. When the asset
+    /// has no manifest of its own, one the server links to with a
+    /// `rel="c2pa-manifest"` Link header is fetched and verified against it,
+    /// unless remote manifest fetching is turned off.
+    pub fn from_url(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.fromUrl", err))?;
+        let channel = cx.channel();
+        let url = cx.argument::<JsString>(0)?.value(&mut cx);
+        let mime_type = match cx.argument_opt(1) {
+            Some(mime_type) if mime_type.is_a::<JsString, _>(&mut cx) => Some(
+                mime_type
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx),
+            ),
+            _ => None,
+        };
+        let context_opt = parse_settings(&mut cx, 2, "Reader")
+            .and_then(|context| parse_verify_options(&mut cx, 3, context, "Reader"))
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromUrl", err))?;
+        let timeout = parse_timeout(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromUrl", err))?;
+        let priority = parse_priority(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromUrl", err))?;
 
-        let c2pa_data = manifest_data.as_slice(&cx).to_vec();
         let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Read);
         rt.spawn(async move {
-            let result = async {
-                let format = asset
-                    .mime_type()
-                    .ok_or_else(|| {
+            let task = catch_panic(async move {
+                let open = || {
+                    let (format, stream) =
+                        Asset::Url(url.clone(), mime_type.clone()).into_typed_read_stream()?;
+                    let format = format.ok_or_else(|| {
                         Error::Reading("Source asset must have a mime type".to_string())
-                    })?
-                    .to_owned();
-                let stream = asset.into_read_stream()?;
-
-                let reader = if let Some(context) = context_opt {
-                    Reader::from_context(context)
-                        .with_manifest_data_and_stream_async(&c2pa_data, &format, stream)
-                        .await?
-                } else {
-                    Reader::from_manifest_data_and_stream_async(&c2pa_data, &format, stream).await?
+                    })?;
+                    Ok::<_, Error>((format, stream))
+                };
+                let fetch_remote = cfg!(feature = "fetch_remote_manifests")
+                    && context_opt
+                        .as_ref()
+                        .is_none_or(|context| context.settings().verify.remote_manifest_fetch);
+                let new_reader = || match &context_opt {
+                    Some(context) => Reader::from_shared_context(context),
+                    None => Reader::default(),
                 };
 
-                Ok(reader)
-            }
-            .await;
+                let (format, stream) = open()?;
+                let reader = match new_reader().with_stream_async(&format, stream).await {
+                    Err(c2pa::Error::JumbfNotFound) if fetch_remote => {
+                        let Some(link) = RangeClient::new(&url)?.manifest_link()? else {
+                            return Ok(None);
+                        };
+                        let manifest_data = fetch_remote_manifest(&link).await?;
+                        let (format, stream) = open()?;
+                        new_reader()
+                            .with_manifest_data_and_stream_async(&manifest_data, &format, stream)
+                            .await?
+                    }
+                    Err(c2pa::Error::JumbfNotFound) => return Ok(None),
+                    result => result?,
+                };
+
+                let footprint = Footprint::reader(&reader);
+                Ok(Some((reader, footprint)))
+            });
+            let result: Result<Option<(Reader, Footprint)>> =
+                with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
 
             deferred.settle_with(&channel, move |mut cx| match result {
-                Ok(reader) => {
+                Ok(Some((reader, footprint))) => {
                     let boxed_reader = cx.boxed(Self {
+                        disposed: AtomicBool::new(false),
+                        shared: AtomicBool::new(false),
+                        footprint,
                         reader: Arc::new(Mutex::new(reader)),
                     });
                     Ok(boxed_reader.upcast::<JsValue>())
                 }
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Ok(None) => Ok(cx.null().upcast::<JsValue>()),
+                Err(err) => {
+                    as_js_error(&mut cx, "Reader.fromUrl", err).and_then(|err| cx.throw(err))
+                }
+            });
+        });
+        Ok(promise)
+    }
+
+    /// Read fragmented MP4 content from an initialization segment and a list
+    /// of fragment assets, or a glob of fragment files, validating each
+    /// fragment against the Merkle tree in the manifest. Resolves to the
+    /// Reader, or null when no fragment could be read, and a report for each
+    /// fragment.
+    pub fn from_fragments(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.fromFragments", err))?;
+        let channel = cx.channel();
+        let init = cx
+            .argument::<JsObject>(0)
+            .and_then(|obj| parse_asset(&mut cx, obj))?;
+        let fragments = cx.argument::<JsValue>(1)?;
+        let fragments = if let Ok(pattern) = fragments.downcast::<JsString, _>(&mut cx) {
+            Fragments::Glob(pattern.value(&mut cx))
+        } else {
+            let array = fragments.downcast_or_throw::<JsArray, _>(&mut cx)?;
+            let mut assets = Vec::new();
+            for fragment in array.to_vec(&mut cx)? {
+                let fragment = fragment.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                assets.push(parse_asset(&mut cx, fragment)?);
+            }
+            Fragments::Assets(assets)
+        };
+        let context = parse_settings(&mut cx, 2, "Reader")
+            .and_then(|context| parse_verify_options(&mut cx, 3, context, "Reader"))
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromFragments", err))?
+            .unwrap_or_else(|| Arc::new(c2pa::Context::new()));
+        let timeout = parse_timeout(&mut cx, 4)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromFragments", err))?;
+        let priority = parse_priority(&mut cx, 5)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.fromFragments", err))?;
+
+        let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Read);
+        rt.spawn(async move {
+            let task = catch_panic(async move {
+                let (reader, reports, trees) = read_fragments(context, init, fragments).await?;
+                let reader = reader.map(|reader| {
+                    let footprint = Footprint::reader(&reader);
+                    (reader, footprint)
+                });
+                Ok((reader, reports, trees))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
+
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok((reader, reports, trees)) => {
+                    let obj = cx.empty_object();
+                    let reader = match reader {
+                        Some((reader, footprint)) => cx
+                            .boxed(Self {
+                                disposed: AtomicBool::new(false),
+                                shared: AtomicBool::new(false),
+                                footprint,
+                                reader: Arc::new(Mutex::new(reader)),
+                            })
+                            .upcast::<JsValue>(),
+                        None => cx.null().upcast::<JsValue>(),
+                    };
+                    obj.set(&mut cx, "reader", reader)?;
+                    let reports = to_js(&mut cx, &reports)?;
+                    obj.set(&mut cx, "fragments", reports)?;
+                    let trees = to_js(&mut cx, &trees)?;
+                    obj.set(&mut cx, "merkleTree", trees)?;
+                    Ok(obj)
+                }
+                Err(err) => {
+                    as_js_error(&mut cx, "Reader.fromFragments", err).and_then(|err| cx.throw(err))
+                }
             });
         });
         Ok(promise)
     }
 
     pub fn json(mut cx: FunctionContext) -> JsResult<JsValue> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.json", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let reader = rt.block_on(async { this.reader.lock().await });
+        let store = manifest_store_value(&reader)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.json", err))?;
+        to_js(&mut cx, &store)
+    }
+
+    /// Validation findings, optionally only those at least as severe as the
+    /// filter ("errors", "warnings", or "all"), so the rest are not converted
+    /// to JS.
+    pub fn validation_messages(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt =
+            runtime().or_else(|err| throw_js_error(&mut cx, "Reader.validationMessages", err))?;
+        let filter = match cx.argument_opt(0) {
+            Some(name) if name.is_a::<JsString, _>(&mut cx) => {
+                let name = name
+                    .downcast_or_throw::<JsString, _>(&mut cx)?
+                    .value(&mut cx);
+                MessageFilter::parse(&name).map_or_else(
+                    || {
+                        cx.throw_range_error(format!(
+                            "Unknown filter \"{name}\". Expected errors, warnings, or all"
+                        ))
+                    },
+                    Ok,
+                )?
+            }
+            _ => MessageFilter::All,
+        };
+        let this = Self::this_live(&mut cx)?;
+        let reader = rt.block_on(async { this.reader.lock().await });
+        let mut messages = validation_messages(&reader);
+        messages.retain(|message| filter.includes(message.kind));
+        to_js(&mut cx, &messages)
+    }
+
+    /// A summary of who signed the asset, when, with what tool, what was done
+    /// to it, and what failed, as an object, or rendered as Markdown or HTML.
+    pub fn report(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.report", err))?;
+        let name = cx.argument::<JsString>(0)?.value(&mut cx);
+        let format = ReportFormat::parse(&name).map_or_else(
+            || {
+                cx.throw_range_error(format!(
+                    "Unknown report format \"{name}\". Expected json, markdown, or html"
+                ))
+            },
+            Ok,
+        )?;
+        let this = Self::this_live(&mut cx)?;
+        let reader = rt.block_on(async { this.reader.lock().await });
+        let report = Report::new(&reader);
+        if let ReportFormat::Json = format {
+            return to_js(&mut cx, &report);
+        }
+        let report = report
+            .render(format)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.report", err))?;
+        Ok(cx.string(report).upcast())
+    }
+
+    /// Every claim and identity signature in the manifest store, with the
+    /// validation state of each.
+    pub fn signatures(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.signatures", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let reader = rt.block_on(async { this.reader.lock().await });
+        to_js(&mut cx, &signatures(&reader))
+    }
+
+    pub fn provenance_graph(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.provenanceGraph", err))?;
+        let name = cx.argument::<JsString>(0)?.value(&mut cx);
+        let format = GraphFormat::parse(&name).map_or_else(
+            || {
+                cx.throw_range_error(format!(
+                    "Unknown graph format \"{name}\". Expected json or dot"
+                ))
+            },
+            Ok,
+        )?;
+        let this = Self::this_live(&mut cx)?;
+        let reader = rt.block_on(async { this.reader.lock().await });
+        let graph = ProvenanceGraph::new(&reader);
+        if let GraphFormat::Json = format {
+            return to_js(&mut cx, &graph);
+        }
+        let graph = graph
+            .render(format)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.provenanceGraph", err))?;
+        Ok(cx.string(graph).upcast())
+    }
+
+    /// How the manifest store measures up to the C2PA 2.x requirements, as
+    /// pass, warn, and fail checks.
+    pub fn conformance(mut cx: FunctionContext) -> JsResult<JsValue> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.conformance", err))?;
+        let this = Self::this_live(&mut cx)?;
         let reader = rt.block_on(async { this.reader.lock().await });
-        let json = reader.json();
-        Ok(cx.string(json).upcast())
+        to_js(&mut cx, &ConformanceReport::new(&reader))
     }
 
     pub fn remote_url(mut cx: FunctionContext) -> JsResult<JsValue> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.remoteUrl", err))?;
+        let this = Self::this_live(&mut cx)?;
         let reader = rt.block_on(async { this.reader.lock().await });
         let remote_url = reader.remote_url().unwrap_or("");
         Ok(cx.string(remote_url).upcast())
     }
 
     pub fn is_embedded(mut cx: FunctionContext) -> JsResult<JsValue> {
-        let rt = runtime();
-        let this = cx.this::<JsBox<Self>>()?;
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.isEmbedded", err))?;
+        let this = Self::this_live(&mut cx)?;
         let reader = rt.block_on(async { this.reader.lock().await });
         let is_embedded = reader.is_embedded();
         Ok(cx.boolean(is_embedded).upcast())
     }
 
+    pub fn supported_mime_types(mut cx: FunctionContext) -> JsResult<JsArray> {
+        mime_type_array(&mut cx, Reader::supported_mime_types())
+    }
+
+    pub fn memory_usage(mut cx: FunctionContext) -> JsResult<JsNumber> {
+        let this = cx.this::<JsBox<Self>>()?;
+        Ok(cx.number(this.footprint.bytes() as f64))
+    }
+
+    pub fn json_async(cx: FunctionContext) -> JsResult<JsPromise> {
+        Self::with_reader_async(cx, "Reader.jsonAsync", manifest_store_value, |cx, store| {
+            match store {
+                Ok(store) => to_js(cx, &store),
+                Err(err) => throw_js_error(cx, "Reader.jsonAsync", err),
+            }
+        })
+    }
+
+    pub fn remote_url_async(cx: FunctionContext) -> JsResult<JsPromise> {
+        Self::with_reader_async(
+            cx,
+            "Reader.remoteUrlAsync",
+            |reader| reader.remote_url().unwrap_or("").to_string(),
+            |cx, remote_url| Ok(cx.string(remote_url).upcast()),
+        )
+    }
+
+    pub fn is_embedded_async(cx: FunctionContext) -> JsResult<JsPromise> {
+        Self::with_reader_async(
+            cx,
+            "Reader.isEmbeddedAsync",
+            |reader| reader.is_embedded(),
+            |cx, is_embedded| Ok(cx.boolean(is_embedded).upcast()),
+        )
+    }
+
+    /// Run `read` against the reader on the runtime, so the JS thread never
+    /// waits for the lock while another task holds it.
+    fn with_reader_async<'a, T: Send + 'static>(
+        mut cx: FunctionContext<'a>,
+        operation: &'static str,
+        read: fn(&Reader) -> T,
+        to_js: for<'b> fn(&mut TaskContext<'b>, T) -> JsResult<'b, JsValue>,
+    ) -> JsResult<'a, JsPromise> {
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, operation, err))?;
+        let channel = cx.channel();
+        let this = Self::this_live(&mut cx)?;
+        let reader = Arc::clone(&this.reader);
+
+        let (deferred, promise) = cx.promise();
+        rt.spawn(async move {
+            let result = catch_panic(async { Ok(read(&*reader.lock().await)) }).await;
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok(value) => to_js(&mut cx, value),
+                Err(err) => as_js_error(&mut cx, operation, err).and_then(|err| cx.throw(err)),
+            });
+        });
+        Ok(promise)
+    }
+
     pub fn resource_to_asset(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt = runtime().or_else(|err| throw_js_error(&mut cx, "Reader.resourceToAsset", err))?;
         let channel = cx.channel();
         let uri = cx.argument::<JsString>(0)?.value(&mut cx);
         let output = cx
@@ -183,31 +773,39 @@ impl NeonReader {
             .and_then(|obj| parse_asset(&mut cx, obj))?;
         let mut output_stream = output
             .write_stream()
-            .or_else(|err| cx.throw_error(err.to_string()))?;
-        let this = cx.this::<JsBox<Self>>()?;
+            .or_else(|err| throw_js_error(&mut cx, "Reader.resourceToAsset", err))?;
+        let this = Self::this_live(&mut cx)?;
+        let timeout = parse_timeout(&mut cx, 2)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.resourceToAsset", err))?;
+        let priority = parse_priority(&mut cx, 3)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.resourceToAsset", err))?;
 
         let reader = Arc::clone(&this.reader);
 
         let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Resource);
         rt.spawn(async move {
-            let result = reader
-                .lock()
-                .await
-                .resource_to_stream(&uri, &mut output_stream)
-                .map(|bytes_written| (bytes_written, output_stream))
-                .map_err(Error::from);
+            let task = catch_panic(async move {
+                let bytes_written = reader
+                    .lock()
+                    .await
+                    .resource_to_stream(&uri, &mut output_stream)?;
+                output_stream.finish()?;
+                let buffer = if output.name() == "destination_buffer" {
+                    let mut buffer = Vec::new();
+                    output_stream.rewind()?;
+                    output_stream.read_to_end(&mut buffer)?;
+                    Some(buffer)
+                } else {
+                    None
+                };
+                Ok((bytes_written, buffer))
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
 
             deferred.settle_with(&channel, move |mut cx| match result {
-                Ok((bytes_written, mut output_stream)) => {
-                    let buffer = if output.name() == "destination_buffer" {
-                        let mut buffer = Vec::new();
-                        output_stream.rewind().unwrap();
-                        output_stream.read_to_end(&mut buffer).unwrap();
-                        Some(buffer)
-                    } else {
-                        None
-                    };
-
+                Ok((bytes_written, buffer)) => {
                     let result = cx.empty_object();
                     let js_bytes_written = cx.number(bytes_written as f64);
                     if let Some(buffer) = buffer {
@@ -220,7 +818,85 @@ impl NeonReader {
                     result.set(&mut cx, "bytes_written", js_bytes_written)?;
                     Ok(result.upcast::<JsValue>())
                 }
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Err(err) => as_js_error(&mut cx, "Reader.resourceToAsset", err)
+                    .and_then(|err| cx.throw(err)),
+            });
+        });
+        Ok(promise)
+    }
+
+    /// Every manifest and ingredient thumbnail in the store, read under one
+    /// lock. Claim thumbnails are keyed by their manifest's label and
+    /// ingredient thumbnails by "<manifest label>/<ingredient label>".
+    pub fn extract_thumbnails(mut cx: FunctionContext) -> JsResult<JsPromise> {
+        let rt =
+            runtime().or_else(|err| throw_js_error(&mut cx, "Reader.extractThumbnails", err))?;
+        let channel = cx.channel();
+        let this = Self::this_live(&mut cx)?;
+        let timeout = parse_timeout(&mut cx, 0)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.extractThumbnails", err))?;
+        let priority = parse_priority(&mut cx, 1)
+            .or_else(|err| throw_js_error(&mut cx, "Reader.extractThumbnails", err))?;
+
+        let reader = Arc::clone(&this.reader);
+
+        let (deferred, promise) = cx.promise();
+        let timer = OperationTimer::start(Operation::Resource);
+        rt.spawn(async move {
+            let task = catch_panic(async move {
+                let reader = reader.lock().await;
+                let mut thumbnails = Vec::new();
+                for (label, manifest) in reader.manifests() {
+                    if let Some(thumbnail) = manifest.thumbnail_ref() {
+                        let data = manifest.resources().get(&thumbnail.identifier)?;
+                        thumbnails.push((
+                            label.clone(),
+                            thumbnail.format.clone(),
+                            data.into_owned(),
+                        ));
+                    }
+                    for (index, ingredient) in manifest.ingredients().iter().enumerate() {
+                        let Some(thumbnail) = ingredient.thumbnail_ref() else {
+                            continue;
+                        };
+                        // Ingredient thumbnails may be kept with the ingredient
+                        // or with the manifest that holds it.
+                        let resources = if ingredient.resources().exists(&thumbnail.identifier) {
+                            ingredient.resources()
+                        } else {
+                            manifest.resources()
+                        };
+                        let data = resources.get(&thumbnail.identifier)?;
+                        let ingredient_label = ingredient
+                            .label()
+                            .map_or_else(|| format!("ingredients[{index}]"), str::to_string);
+                        thumbnails.push((
+                            format!("{label}/{ingredient_label}"),
+                            thumbnail.format.clone(),
+                            data.into_owned(),
+                        ));
+                    }
+                }
+                Ok(thumbnails)
+            });
+            let result = with_timeout(timeout, in_lane(priority, task)).await;
+            timer.finish(result.is_ok());
+
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok(thumbnails) => {
+                    let obj = cx.empty_object();
+                    for (key, format, data) in thumbnails {
+                        let thumbnail = cx.empty_object();
+                        let mime_type = cx.string(format);
+                        thumbnail.set(&mut cx, "mimeType", mime_type)?;
+                        let buffer = JsBuffer::from_slice(&mut cx, &data)?;
+                        thumbnail.set(&mut cx, "buffer", buffer)?;
+                        obj.set(&mut cx, key.as_str(), thumbnail)?;
+                    }
+                    Ok(obj)
+                }
+                Err(err) => as_js_error(&mut cx, "Reader.extractThumbnails", err)
+                    .and_then(|err| cx.throw(err)),
             });
         });
         Ok(promise)
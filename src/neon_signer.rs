@@ -17,19 +17,23 @@ use c2pa::{
     create_signer,
     crypto::{
         raw_signature::{AsyncRawSigner, RawSigner, RawSignerError},
-        time_stamp::{AsyncTimeStampProvider, TimeStampProvider},
+        time_stamp::{AsyncTimeStampProvider, TimeStampError, TimeStampProvider},
     },
-    AsyncSigner,
+    hash_stream_by_alg, AsyncSigner,
     Error::OtherError,
     Signer, SigningAlg,
 };
 use neon::prelude::*;
 use neon::types::buffer::TypedArray;
+use std::io::Cursor;
 use std::ops::Deref;
 use std::{boxed::Box, str::FromStr, sync::Arc};
 use tokio::sync::oneshot;
 
-use crate::error::Error;
+use crate::certificate::check_signing_alg;
+use crate::env_config;
+use crate::error::{as_js_error_fn, catch_panic, Error};
+use crate::network::{self, NetworkOptions};
 
 #[derive(Debug, Clone)]
 pub struct CallbackSignerConfig {
@@ -40,6 +44,7 @@ pub struct CallbackSignerConfig {
     pub tsa_headers: Option<Vec<(String, String)>>,
     pub tsa_body: Option<Vec<u8>>,
     pub direct_cose_handling: bool,
+    pub sign_digest: bool,
 }
 
 impl CallbackSignerConfig {
@@ -60,6 +65,31 @@ impl CallbackSignerConfig {
             tsa_headers,
             tsa_body,
             direct_cose_handling,
+            sign_digest: false,
+        }
+    }
+
+    /// The hash a digest signing callback is given for `alg`. Ed25519 signs
+    /// the message itself, so it has none.
+    fn digest_alg(alg: SigningAlg) -> Option<&'static str> {
+        match alg {
+            SigningAlg::Es256 | SigningAlg::Ps256 => Some("sha256"),
+            SigningAlg::Es384 | SigningAlg::Ps384 => Some("sha384"),
+            SigningAlg::Es512 | SigningAlg::Ps512 => Some("sha512"),
+            SigningAlg::Ed25519 => None,
+        }
+    }
+
+    /// What the callback signs: `data` itself, or in digest mode its hash along
+    /// with the name of the hash algorithm.
+    fn to_be_signed(&self, data: Vec<u8>) -> std::io::Result<(Vec<u8>, Option<&'static str>)> {
+        match Self::digest_alg(self.alg).filter(|_| self.sign_digest) {
+            Some(hash) => {
+                let digest = hash_stream_by_alg(hash, &mut Cursor::new(data), None, true)
+                    .map_err(std::io::Error::other)?;
+                Ok((digest, Some(hash)))
+            }
+            None => Ok((data, None)),
         }
     }
 
@@ -86,17 +116,33 @@ impl CallbackSignerConfig {
                 .downcast_or_throw::<JsBuffer, _>(cx)?;
             certs.extend_from_slice(cert_buffer.as_slice(cx));
         }
+        if let Err(err) = check_signing_alg(&certs, alg) {
+            let err = as_js_error_fn(cx, "CallbackSigner.newSigner", err)?;
+            return cx.throw(err);
+        }
 
         let reserve_size = js_config
             .get::<JsNumber, _, _>(cx, "reserveSize")?
             .value(cx) as usize;
         let tsa_url = js_config
             .get_opt::<JsString, _, _>(cx, "tsaUrl")?
-            .map(|js_string| js_string.value(cx));
+            .map(|js_string| js_string.value(cx))
+            .or_else(env_config::tsa_url);
         let direct_cose_handling = js_config
             .get::<JsBoolean, _, _>(cx, "directCoseHandling")?
             .downcast_or_throw::<JsBoolean, _>(cx)?
             .value(cx);
+        let sign_digest = js_config
+            .get_opt::<JsBoolean, _, _>(cx, "signDigest")?
+            .is_some_and(|js_bool| js_bool.value(cx));
+        if sign_digest && direct_cose_handling {
+            return cx.throw_error("signDigest can't be used with directCoseHandling");
+        }
+        if sign_digest && Self::digest_alg(alg).is_none() {
+            return cx.throw_error(format!(
+                "{alg} signs the message itself, so it can't be used with signDigest"
+            ));
+        }
         let tsa_headers =
             if let Some(js_array) = js_config.get_opt::<JsArray, _, _>(cx, "tsaHeaders")? {
                 let len = js_array.len(cx);
@@ -121,15 +167,18 @@ impl CallbackSignerConfig {
             .get_opt::<JsBuffer, _, _>(cx, "tsaBody")?
             .map(|js_buffer| js_buffer.as_slice(cx).to_vec());
 
-        Ok(cx.boxed(Self::new(
-            alg,
-            certs,
-            reserve_size,
-            tsa_url,
-            tsa_headers,
-            tsa_body,
-            direct_cose_handling,
-        )))
+        Ok(cx.boxed(Self {
+            sign_digest,
+            ..Self::new(
+                alg,
+                certs,
+                reserve_size,
+                tsa_url,
+                tsa_headers,
+                tsa_body,
+                direct_cose_handling,
+            )
+        }))
     }
 }
 
@@ -144,9 +193,12 @@ pub fn callback_signer_config_from_js(
 #[derive(Clone)]
 pub struct NeonCallbackSigner {
     channel: Channel,
-    // JsFunction must be of the form (data: Buffer) => Promise<Buffer>
+    // JsFunction must be of the form (data: Buffer) => Promise<Buffer>, or in
+    // digest mode (digest: Buffer, hashAlgorithm: string) => Promise<Buffer>
     callback: Arc<Root<JsFunction>>,
     config: CallbackSignerConfig,
+    // Timeouts and retries for time-stamp requests, set for a single call.
+    network: Option<NetworkOptions>,
 }
 
 impl NeonCallbackSigner {
@@ -159,9 +211,15 @@ impl NeonCallbackSigner {
             channel,
             callback,
             config,
+            network: None,
         }
     }
 
+    /// This signer with `network` for its time-stamp requests.
+    pub fn with_network(self, network: Option<NetworkOptions>) -> Self {
+        Self { network, ..self }
+    }
+
     pub fn from_config(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
         let config_handle = cx.argument::<JsBox<CallbackSignerConfig>>(0)?;
         let config_ref: &CallbackSignerConfig = config_handle.deref();
@@ -207,7 +265,7 @@ impl NeonCallbackSigner {
     }
 
     pub fn sign(mut cx: FunctionContext) -> JsResult<JsPromise> {
-        let rt = runtime();
+        let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
         let this = cx.this::<JsBox<Self>>()?;
         let data = cx.argument::<JsBuffer>(0)?.as_slice(&cx).to_vec();
         let (deferred, promise) = cx.promise();
@@ -217,9 +275,10 @@ impl NeonCallbackSigner {
 
         rt.spawn(async move {
             let signer = NeonCallbackSigner::new(channel.clone(), callback, config);
-            let result = <Self as AsyncSigner>::sign(&signer, data)
-                .await
-                .map_err(|e| e.to_string());
+            let result =
+                catch_panic(async { Ok(<Self as AsyncSigner>::sign(&signer, data).await?) })
+                    .await
+                    .map_err(|e| e.to_string());
 
             deferred.settle_with(&channel, move |mut cx| match result {
                 Ok(signature) => {
@@ -268,33 +327,39 @@ impl AsyncSigner for NeonCallbackSigner {
     async fn sign(&self, data: Vec<u8>) -> Result<Vec<u8>, c2pa::Error> {
         let (tx, rx) = oneshot::channel();
         let sign_fn = self.callback.clone();
-        let data = data.to_vec();
+        let (data, hash) = self.config.to_be_signed(data)?;
 
         self.channel
             .try_send(move |mut cx| {
                 let to_be_signed = JsBuffer::from_slice(&mut cx, &data)?;
+                let hash = hash.map(|hash| cx.string(hash));
                 let sign_fn = sign_fn.to_inner(&mut cx);
 
-                let sign_fut = sign_fn
-                    .call_with(&cx)
-                    .arg(to_be_signed)
-                    .apply::<JsPromise, _>(&mut cx)?
-                    .to_future(&mut cx, |mut cx, result| match result {
-                        Ok(value) => Ok(Ok(value
-                            .downcast_or_throw::<JsBuffer, _>(&mut cx)?
-                            .as_slice(&cx)
-                            .to_vec())),
-                        Err(err) => {
-                            let js_string_result = err.to_string(&mut cx);
-                            match js_string_result {
-                                Ok(js_string) => {
-                                    let err_string: String = js_string.value(&mut cx);
-                                    Ok(Err(OtherError(Box::new(Error::AsyncSigning(err_string)))))
+                let mut call = sign_fn.call_with(&cx);
+                call.arg(to_be_signed);
+                if let Some(hash) = hash {
+                    call.arg(hash);
+                }
+                let sign_fut =
+                    call.apply::<JsPromise, _>(&mut cx)?
+                        .to_future(&mut cx, |mut cx, result| match result {
+                            Ok(value) => Ok(Ok(value
+                                .downcast_or_throw::<JsBuffer, _>(&mut cx)?
+                                .as_slice(&cx)
+                                .to_vec())),
+                            Err(err) => {
+                                let js_string_result = err.to_string(&mut cx);
+                                match js_string_result {
+                                    Ok(js_string) => {
+                                        let err_string: String = js_string.value(&mut cx);
+                                        Ok(Err(OtherError(Box::new(Error::AsyncSigning(
+                                            err_string,
+                                        )))))
+                                    }
+                                    Err(throw) => Err(throw),
                                 }
-                                Err(throw) => Err(throw),
                             }
-                        }
-                    })?;
+                        })?;
 
                 let _ = tx.send(sign_fut);
 
@@ -343,6 +408,7 @@ impl TimeStampProvider for NeonCallbackSigner {
     }
 }
 
+#[async_trait]
 impl AsyncTimeStampProvider for NeonCallbackSigner {
     fn time_stamp_service_url(&self) -> Option<String> {
         self.config.tsa_url.clone()
@@ -350,6 +416,18 @@ impl AsyncTimeStampProvider for NeonCallbackSigner {
     fn time_stamp_request_headers(&self) -> Option<Vec<(String, String)>> {
         self.config.tsa_headers.clone()
     }
+    // Sent through this crate's resolver so the proxy, offline mode, timeouts
+    // and retries apply.
+    async fn send_time_stamp_request(
+        &self,
+        message: &[u8],
+    ) -> Option<Result<Vec<u8>, TimeStampError>> {
+        let url = self.config.tsa_url.as_ref()?;
+        let body = AsyncTimeStampProvider::time_stamp_request_body(self, message).ok()?;
+        let headers = self.config.tsa_headers.clone();
+        let network = self.network.clone();
+        Some(network::time_stamp_request(url, headers, &body, message, network).await)
+    }
 }
 
 #[async_trait]
@@ -357,31 +435,35 @@ impl AsyncRawSigner for NeonCallbackSigner {
     async fn sign(&self, data: Vec<u8>) -> Result<Vec<u8>, RawSignerError> {
         let (tx, rx) = oneshot::channel();
         let sign_fn = self.callback.clone();
-        let data = data.to_vec();
+        let (data, hash) = self.config.to_be_signed(data)?;
 
         // Send the signing request to the JavaScript side
         self.channel
             .try_send(move |mut cx| {
                 let to_be_signed = JsBuffer::from_slice(&mut cx, &data)?;
+                let hash = hash.map(|hash| cx.string(hash));
                 let sign_fn = sign_fn.to_inner(&mut cx);
 
-                let sign_fut = sign_fn
-                    .call_with(&cx)
-                    .arg(to_be_signed)
-                    .apply::<JsPromise, _>(&mut cx)?
-                    .to_future(&mut cx, |mut cx, result| match result {
-                        Ok(value) => {
-                            let buffer = value.downcast_or_throw::<JsBuffer, _>(&mut cx)?;
-                            Ok(Ok(buffer.as_slice(&cx).to_vec()))
-                        }
-                        Err(err) => {
-                            let err_string = match err.to_string(&mut cx) {
-                                Ok(js_string) => js_string.value(&mut cx),
-                                Err(e) => return Err(e),
-                            };
-                            Ok(Err(RawSignerError::CryptoLibraryError(err_string)))
-                        }
-                    })?;
+                let mut call = sign_fn.call_with(&cx);
+                call.arg(to_be_signed);
+                if let Some(hash) = hash {
+                    call.arg(hash);
+                }
+                let sign_fut =
+                    call.apply::<JsPromise, _>(&mut cx)?
+                        .to_future(&mut cx, |mut cx, result| match result {
+                            Ok(value) => {
+                                let buffer = value.downcast_or_throw::<JsBuffer, _>(&mut cx)?;
+                                Ok(Ok(buffer.as_slice(&cx).to_vec()))
+                            }
+                            Err(err) => {
+                                let err_string = match err.to_string(&mut cx) {
+                                    Ok(js_string) => js_string.value(&mut cx),
+                                    Err(e) => return Err(e),
+                                };
+                                Ok(Err(RawSignerError::CryptoLibraryError(err_string)))
+                            }
+                        })?;
 
                 let _ = tx.send(sign_fut);
                 Ok(())
@@ -449,12 +531,19 @@ impl NeonLocalSigner {
         let pkey = cx.argument::<JsBuffer>(1)?.as_slice(&cx).to_vec();
         let alg_str = cx.argument::<JsString>(2)?.value(&mut cx);
         let alg = SigningAlg::from_str(&alg_str).or_else(|err| cx.throw_error(err.to_string()))?;
-        let tsa_url = cx.argument_opt(3).and_then(|js_value| {
-            js_value
-                .downcast::<JsString, _>(&mut cx)
-                .ok()
-                .map(|js_string| js_string.value(&mut cx))
-        });
+        if let Err(err) = check_signing_alg(&signcert, alg) {
+            let err = as_js_error_fn(&mut cx, "LocalSigner.newSigner", err)?;
+            return cx.throw(err);
+        }
+        let tsa_url = cx
+            .argument_opt(3)
+            .and_then(|js_value| {
+                js_value
+                    .downcast::<JsString, _>(&mut cx)
+                    .ok()
+                    .map(|js_string| js_string.value(&mut cx))
+            })
+            .or_else(env_config::tsa_url);
         let signer = create_signer::from_keys(&signcert, &pkey, alg, tsa_url)
             .or_else(|err| cx.throw_error(format!("Failed to create signer from keys: {err}")))?;
         Ok(cx.boxed(Self { signer }))
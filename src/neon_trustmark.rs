@@ -11,7 +11,8 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use crate::error::{as_js_error, as_js_error_fn, Error, Result};
+use crate::error::{as_js_error, as_js_error_fn, catch_panic_sync, Error, Result};
+use crate::network::ensure_online;
 use crate::runtime::runtime;
 use neon::prelude::*;
 use neon::result::{JsResult, NeonResult};
@@ -101,11 +102,17 @@ impl NeonTrustmark {
             .and_then(|obj| parse_watermark_config(&mut cx, obj))?;
         let model_path = match fetch_model(config.variant, &config.model_path) {
             Ok(path) => path,
-            Err(err) => return as_js_error_fn(&mut cx, err).and_then(|err| cx.throw(err)),
+            Err(err) => {
+                return as_js_error_fn(&mut cx, "Trustmark.newTrustmark", err)
+                    .and_then(|err| cx.throw(err))
+            }
         };
         let trustmark = match Trustmark::new(model_path, config.variant, config.version) {
             Ok(trustmark) => trustmark,
-            Err(err) => return as_js_error_fn(&mut cx, err.into()).and_then(|err| cx.throw(err)),
+            Err(err) => {
+                return as_js_error_fn(&mut cx, "Trustmark.newTrustmark", err.into())
+                    .and_then(|err| cx.throw(err))
+            }
         };
 
         Ok(cx.boxed(Self {
@@ -123,7 +130,9 @@ impl NeonTrustmark {
         let strength = cx.argument::<JsNumber>(1)?.value(&mut cx) as f32;
         match check_strength(strength) {
             Ok(_) => Ok(()),
-            Err(err) => as_js_error_fn(&mut cx, err).and_then(|err| cx.throw(err)),
+            Err(err) => {
+                as_js_error_fn(&mut cx, "Trustmark.encode", err).and_then(|err| cx.throw(err))
+            }
         }?;
 
         let watermark = cx.argument_opt(2).and_then(|js_value| {
@@ -135,21 +144,25 @@ impl NeonTrustmark {
 
         let promise = cx
             .task(move || {
-                let trustmark = trustmark.lock()?;
-                let image = image::load_from_memory(&image_bytes)?;
-                let watermark = watermark.unwrap_or_else(|| gen_watermark(version));
-                let watermarked_image = trustmark
-                    .encode(watermark, image, strength)
-                    .map_err(Error::Watermark)?;
-
-                // Convert to RGB8 format and return raw pixel data
-                let rgb_image = watermarked_image.to_rgb8();
-                let raw_pixel_data = rgb_image.into_raw();
-                Ok(raw_pixel_data)
+                catch_panic_sync(|| {
+                    let trustmark = trustmark.lock()?;
+                    let image = image::load_from_memory(&image_bytes)?;
+                    let watermark = watermark.unwrap_or_else(|| gen_watermark(version));
+                    let watermarked_image = trustmark
+                        .encode(watermark, image, strength)
+                        .map_err(Error::Watermark)?;
+
+                    // Convert to RGB8 format and return raw pixel data
+                    let rgb_image = watermarked_image.to_rgb8();
+                    let raw_pixel_data = rgb_image.into_raw();
+                    Ok(raw_pixel_data)
+                })
             })
             .promise(move |mut cx, result: Result<Vec<u8>>| match result {
                 Ok(raw_pixel_data) => JsBuffer::from_slice(&mut cx, &raw_pixel_data),
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Err(err) => {
+                    as_js_error(&mut cx, "Trustmark.encode", err).and_then(|err| cx.throw(err))
+                }
             });
         Ok(promise)
     }
@@ -162,14 +175,18 @@ impl NeonTrustmark {
 
         let promise = cx
             .task(move || {
-                let trustmark = trustmark.lock()?;
-                let image = image::load_from_memory(&image_bytes)?;
-                let watermark = trustmark.decode(image)?;
-                Ok(watermark)
+                catch_panic_sync(|| {
+                    let trustmark = trustmark.lock()?;
+                    let image = image::load_from_memory(&image_bytes)?;
+                    let watermark = trustmark.decode(image)?;
+                    Ok(watermark)
+                })
             })
             .promise(move |mut cx, result: Result<String>| match result {
                 Ok(watermark) => Ok(cx.string(watermark)),
-                Err(err) => as_js_error(&mut cx, err).and_then(|err| cx.throw(err)),
+                Err(err) => {
+                    as_js_error(&mut cx, "Trustmark.decode", err).and_then(|err| cx.throw(err))
+                }
             });
         Ok(promise)
     }
@@ -193,7 +210,7 @@ const MAX_RETRIES: u32 = 3;
 // Taken from Trustmark xtask.
 // Will not overwrite models if they already exist.
 pub fn fetch_model(variant: Variant, dir_path: &std::path::Path) -> Result<PathBuf> {
-    let rt = runtime();
+    let rt = runtime()?;
     let client = Client::builder()
         .timeout(Duration::from_secs(120))
         .connect_timeout(Duration::from_secs(10))
@@ -216,6 +233,7 @@ pub fn fetch_model(variant: Variant, dir_path: &std::path::Path) -> Result<PathB
             continue;
         }
         let model_url = format!("{root}/{filename}");
+        ensure_online(&model_url)?;
 
         let mut last_err = None;
         for attempt in 1..=MAX_RETRIES {
@@ -0,0 +1,1029 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::{self, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use c2pa::crypto::time_stamp::{default_rfc3161_request_async, TimeStampError};
+use c2pa::http::http::{header, Method, Request, Response, StatusCode, Uri};
+use c2pa::http::restricted::RestrictedResolver;
+use c2pa::http::{AsyncHttpResolver, HttpResolverError, SyncHttpResolver};
+use c2pa::{Context, Settings};
+use neon::prelude::{Context as _, *};
+use serde::{Deserialize, Serialize};
+
+use crate::asset::size_limit_error;
+use crate::error::{Error, Result};
+use crate::network_cache::{self, ok_response, store_response, Resource};
+use crate::remote_manifest::resolve_request;
+use crate::telemetry::{Span, SpanKind};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+// Proxy set by `configureProxy`. When unset, the standard environment variables apply.
+static PROXY: RwLock<Option<ProxyConfig>> = RwLock::new(None);
+
+// Timeouts and retries set by `configureNetwork`, under those given per call.
+static NETWORK: RwLock<Option<NetworkOptions>> = RwLock::new(None);
+
+/// Delay before the first retry of a request, doubled for each one after it.
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+tokio::task_local! {
+    // The last request of the running operation that failed, set by `tracked`.
+    static FAILURE: Arc<Mutex<Option<NetworkFailure>>>;
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ProxyConfig {
+    /// Proxy for `http://` requests.
+    pub http_proxy: Option<String>,
+    /// Proxy for `https://` requests.
+    pub https_proxy: Option<String>,
+    /// Hosts reached directly. An entry matches the host and its subdomains; `*` matches all.
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+impl ProxyConfig {
+    /// Read `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY`, and `NO_PROXY` (or their
+    /// lowercase forms).
+    fn from_env() -> Self {
+        let all_proxy = env_var(&["ALL_PROXY", "all_proxy"]);
+        Self {
+            http_proxy: env_var(&["HTTP_PROXY", "http_proxy"]).or_else(|| all_proxy.clone()),
+            https_proxy: env_var(&["HTTPS_PROXY", "https_proxy"]).or(all_proxy),
+            no_proxy: env_var(&["NO_PROXY", "no_proxy"])
+                .map(|list| list.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The proxy set with `configureProxy`, or else the one from the environment.
+    pub fn current() -> Result<Self> {
+        Ok(PROXY.read()?.clone().unwrap_or_else(Self::from_env))
+    }
+
+    fn bypass(&self, host: &str) -> bool {
+        self.no_proxy.iter().any(|entry| {
+            let entry = entry.trim().trim_start_matches('.');
+            entry == "*"
+                || host.eq_ignore_ascii_case(entry)
+                || host
+                    .to_ascii_lowercase()
+                    .ends_with(&format!(".{}", entry.to_ascii_lowercase()))
+        })
+    }
+
+    fn proxy_for(&self, uri: &Uri) -> Option<&str> {
+        if uri.host().is_some_and(|host| self.bypass(host)) {
+            return None;
+        }
+        match uri.scheme_str() {
+            Some("http") => self.http_proxy.as_deref(),
+            Some("https") => self.https_proxy.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// A kind of request c2pa-rs makes, which can have its own timeouts and retries.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Endpoint {
+    RemoteManifest,
+    Ocsp,
+    Timestamp,
+}
+
+impl Endpoint {
+    pub fn name(self) -> &'static str {
+        match self {
+            Endpoint::RemoteManifest => "remoteManifest",
+            Endpoint::Ocsp => "ocsp",
+            Endpoint::Timestamp => "timestamp",
+        }
+    }
+
+    /// What `request` is for. Time-stamps are POSTed as `timestamp-query`.
+    fn of_request(request: &Request<Vec<u8>>) -> Option<Self> {
+        match Resource::of_request(request) {
+            Some(Resource::RemoteManifest) => Some(Endpoint::RemoteManifest),
+            Some(Resource::Ocsp) => Some(Endpoint::Ocsp),
+            _ => (request.method() == Method::POST
+                && request
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .is_some_and(|value| value == "application/timestamp-query"))
+            .then_some(Endpoint::Timestamp),
+        }
+    }
+}
+
+/// Timeouts and retries for requests. Unset values fall back to the next level:
+/// per-call options, then `configureNetwork`, then the HTTP client defaults.
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub struct NetworkPolicy {
+    /// How long to wait for a connection to the server.
+    pub connect_timeout_ms: Option<u64>,
+    /// How long to wait for each read of the response.
+    pub read_timeout_ms: Option<u64>,
+    /// How many more times to send a request that failed to connect, timed out,
+    /// or got a 408, 429, or 5xx response.
+    pub retries: Option<u32>,
+}
+
+impl NetworkPolicy {
+    fn or(self, fallback: Self) -> Self {
+        Self {
+            connect_timeout_ms: self.connect_timeout_ms.or(fallback.connect_timeout_ms),
+            read_timeout_ms: self.read_timeout_ms.or(fallback.read_timeout_ms),
+            retries: self.retries.or(fallback.retries),
+        }
+    }
+
+    fn timeouts(&self) -> Timeouts {
+        Timeouts {
+            connect_ms: self.connect_timeout_ms,
+            read_ms: self.read_timeout_ms,
+        }
+    }
+}
+
+/// A `NetworkPolicy` for every endpoint, with overrides for each.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct NetworkOptions {
+    #[serde(flatten)]
+    pub defaults: NetworkPolicy,
+    pub remote_manifest: Option<NetworkPolicy>,
+    pub ocsp: Option<NetworkPolicy>,
+    pub timestamp: Option<NetworkPolicy>,
+}
+
+impl NetworkOptions {
+    fn policy(&self, endpoint: Endpoint) -> NetworkPolicy {
+        let policy = match endpoint {
+            Endpoint::RemoteManifest => self.remote_manifest,
+            Endpoint::Ocsp => self.ocsp,
+            Endpoint::Timestamp => self.timestamp,
+        };
+        policy.unwrap_or_default().or(self.defaults)
+    }
+}
+
+/// The policy for `endpoint`: `call`'s, over the one set with `configureNetwork`.
+fn policy(call: Option<&NetworkOptions>, endpoint: Endpoint) -> NetworkPolicy {
+    let configured = NETWORK
+        .read()
+        .ok()
+        .and_then(|options| options.as_ref().map(|options| options.policy(endpoint)))
+        .unwrap_or_default();
+    call.map(|options| options.policy(endpoint))
+        .unwrap_or_default()
+        .or(configured)
+}
+
+/// Why a request failed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FailureReason {
+    /// No connection could be made to the server.
+    Connect,
+    /// The server didn't respond in time.
+    Timeout,
+    /// The server responded with an error status.
+    Status,
+    /// The host isn't in `core.allowed_network_hosts`.
+    Disallowed,
+    /// The connection failed while the request was under way.
+    Io,
+}
+
+impl FailureReason {
+    pub fn name(self) -> &'static str {
+        match self {
+            FailureReason::Connect => "connect",
+            FailureReason::Timeout => "timeout",
+            FailureReason::Status => "status",
+            FailureReason::Disallowed => "disallowed",
+            FailureReason::Io => "io",
+        }
+    }
+}
+
+/// The request that made an operation fail.
+#[derive(Clone, Debug)]
+pub struct NetworkFailure {
+    pub endpoint: Endpoint,
+    pub url: String,
+    pub reason: FailureReason,
+    /// The response status, for `FailureReason::Status`.
+    pub status: Option<u16>,
+    /// How many times the request was sent.
+    pub attempts: u32,
+    pub message: String,
+}
+
+/// Why `result` is a failure, if it is, with the response status and a message.
+fn failure_of(
+    result: &std::result::Result<Response<Box<dyn Read>>, HttpResolverError>,
+) -> Option<(FailureReason, Option<u16>, String)> {
+    let err = match result {
+        Ok(response) if response.status().is_success() => return None,
+        Ok(response) => {
+            let status = response.status();
+            return Some((
+                FailureReason::Status,
+                Some(status.as_u16()),
+                format!("The server responded with {status}"),
+            ));
+        }
+        Err(err) => err,
+    };
+    let io_reason = |err: &io::Error| match err.kind() {
+        io::ErrorKind::TimedOut => FailureReason::Timeout,
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::NotConnected => FailureReason::Connect,
+        _ => FailureReason::Io,
+    };
+    let (reason, status) = match err {
+        HttpResolverError::UriDisallowed { .. } => (FailureReason::Disallowed, None),
+        HttpResolverError::Io(err) => (io_reason(err), None),
+        HttpResolverError::Other(err) => {
+            if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+                let status = err.status().map(|status| status.as_u16());
+                let reason = if err.is_timeout() {
+                    FailureReason::Timeout
+                } else if err.is_connect() {
+                    FailureReason::Connect
+                } else if status.is_some() {
+                    FailureReason::Status
+                } else {
+                    FailureReason::Io
+                };
+                (reason, status)
+            } else if let Some(err) = err.downcast_ref::<ureq::Error>() {
+                match err {
+                    ureq::Error::StatusCode(status) => (FailureReason::Status, Some(*status)),
+                    ureq::Error::Timeout(_) => (FailureReason::Timeout, None),
+                    ureq::Error::ConnectionFailed | ureq::Error::HostNotFound => {
+                        (FailureReason::Connect, None)
+                    }
+                    ureq::Error::Io(err) => (io_reason(err), None),
+                    _ => (FailureReason::Io, None),
+                }
+            } else {
+                (FailureReason::Io, None)
+            }
+        }
+        _ => (FailureReason::Io, None),
+    };
+    // `Other` only says the client failed, so describe the client's error.
+    let message = match err {
+        HttpResolverError::Other(err) => err.to_string(),
+        err => err.to_string(),
+    };
+    Some((reason, status, message))
+}
+
+/// Whether a request that failed for `reason` may succeed if sent again.
+fn is_transient(reason: FailureReason, status: Option<u16>) -> bool {
+    match reason {
+        FailureReason::Connect | FailureReason::Timeout | FailureReason::Io => true,
+        FailureReason::Status => status.is_some_and(|status| {
+            status == 408
+                || status == 429
+                || StatusCode::from_u16(status).is_ok_and(|s| s.is_server_error())
+        }),
+        FailureReason::Disallowed => false,
+    }
+}
+
+/// Remember the failed request of the running operation, so its error can say
+/// which endpoint failed.
+fn record_failure(failure: NetworkFailure) {
+    let _ = FAILURE.try_with(|last| {
+        if let Ok(mut last) = last.lock() {
+            *last = Some(failure);
+        }
+    });
+}
+
+/// Run an operation, adding the request that failed to its error when the
+/// operation failed because of it.
+pub async fn tracked<T>(task: impl Future<Output = Result<T>>) -> Result<T> {
+    let failure = Arc::new(Mutex::new(None));
+    let result = FAILURE.scope(Arc::clone(&failure), task).await;
+    result.map_err(|err| {
+        let failure = failure.lock().ok().and_then(|mut failure| failure.take());
+        match failure {
+            Some(failure) if is_network_error(&err) => Error::Network {
+                source: Box::new(err),
+                failure,
+            },
+            _ => err,
+        }
+    })
+}
+
+/// Whether `err` is how c2pa-rs reports a failed request.
+fn is_network_error(err: &Error) -> bool {
+    matches!(
+        err.code(),
+        "REMOTE_FETCH_FAILED" | "REMOTE_MANIFEST_NOT_FETCHED" | "TIME_STAMP_FAILED"
+    )
+}
+
+/// A copy of a request, to send it again.
+fn copy_request(
+    request: &Request<Vec<u8>>,
+) -> std::result::Result<Request<Vec<u8>>, HttpResolverError> {
+    let mut copy = Request::builder()
+        .method(request.method().clone())
+        .uri(request.uri().clone())
+        .version(request.version());
+    if let Some(headers) = copy.headers_mut() {
+        *headers = request.headers().clone();
+    }
+    Ok(copy.body(request.body().clone())?)
+}
+
+/// Tracks the attempts at sending a request under its endpoint's policy.
+struct Attempts {
+    endpoint: Option<Endpoint>,
+    policy: NetworkPolicy,
+    count: u32,
+}
+
+impl Attempts {
+    fn new(request: &Request<Vec<u8>>, options: Option<&NetworkOptions>) -> Self {
+        let endpoint = Endpoint::of_request(request);
+        Self {
+            endpoint,
+            policy: endpoint
+                .map(|endpoint| policy(options, endpoint))
+                .unwrap_or_default(),
+            count: 0,
+        }
+    }
+
+    /// How long to wait before sending `request` again after `result`, or
+    /// `None` if it succeeded or won't be retried. Records the final failure.
+    fn retry_after(
+        &mut self,
+        request: &Request<Vec<u8>>,
+        result: &std::result::Result<Response<Box<dyn Read>>, HttpResolverError>,
+    ) -> Option<Duration> {
+        self.count += 1;
+        let (reason, status, message) = failure_of(result)?;
+        if self.count <= self.policy.retries.unwrap_or(0) && is_transient(reason, status) {
+            return Some(RETRY_DELAY * 2u32.saturating_pow(self.count - 1));
+        }
+        if let Some(endpoint) = self.endpoint {
+            record_failure(NetworkFailure {
+                endpoint,
+                url: request.uri().to_string(),
+                reason,
+                status,
+                attempts: self.count,
+                message,
+            });
+        }
+        None
+    }
+}
+
+/// Record a request refused by `core.allowed_network_hosts`, which is never sent.
+fn record_disallowed(
+    endpoint: Option<Endpoint>,
+    url: &Uri,
+    result: &std::result::Result<Response<Box<dyn Read>>, HttpResolverError>,
+) {
+    if let (Some(endpoint), Err(err @ HttpResolverError::UriDisallowed { .. })) = (endpoint, result)
+    {
+        record_failure(NetworkFailure {
+            endpoint,
+            url: url.to_string(),
+            reason: FailureReason::Disallowed,
+            status: None,
+            attempts: 0,
+            message: err.to_string(),
+        });
+    }
+}
+
+/// Set or clear the proxy used for requests made by the native layer, returning
+/// whether it changed. Fails without changing anything if a proxy URL is invalid.
+pub fn set_proxy(config: Option<ProxyConfig>) -> Result<bool> {
+    if let Some(config) = &config {
+        Clients::<ureq::Agent>::new(config, None)?;
+        Clients::<reqwest::Client>::new(config, None)?;
+    }
+    let mut current = PROXY.write()?;
+    let changed = *current != config;
+    *current = config;
+    Ok(changed)
+}
+
+/// Timeouts an HTTP client is built with. Unset ones are the client's defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+struct Timeouts {
+    connect_ms: Option<u64>,
+    read_ms: Option<u64>,
+}
+
+trait ProxyClient: Sized + Clone {
+    fn with_proxy(proxy: Option<&str>, timeouts: Timeouts) -> Result<Self>;
+}
+
+impl ProxyClient for ureq::Agent {
+    fn with_proxy(proxy: Option<&str>, timeouts: Timeouts) -> Result<Self> {
+        let proxy = proxy
+            .map(ureq::Proxy::new)
+            .transpose()
+            .map_err(|e| Error::Settings(format!("Invalid proxy: {e}")))?;
+        let read = timeouts.read_ms.map(Duration::from_millis);
+        Ok(ureq::Agent::config_builder()
+            .proxy(proxy)
+            .timeout_connect(timeouts.connect_ms.map(Duration::from_millis))
+            .timeout_recv_response(read)
+            .timeout_recv_body(read)
+            .build()
+            .into())
+    }
+}
+
+impl ProxyClient for reqwest::Client {
+    fn with_proxy(proxy: Option<&str>, timeouts: Timeouts) -> Result<Self> {
+        // Environment variables are handled by `ProxyConfig`, not reqwest.
+        let mut builder = reqwest::Client::builder().no_proxy();
+        if let Some(ms) = timeouts.connect_ms {
+            builder = builder.connect_timeout(Duration::from_millis(ms));
+        }
+        if let Some(ms) = timeouts.read_ms {
+            builder = builder.read_timeout(Duration::from_millis(ms));
+        }
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(
+                reqwest::Proxy::all(proxy)
+                    .map_err(|e| Error::Settings(format!("Invalid proxy: {e}")))?,
+            );
+        }
+        builder
+            .build()
+            .map_err(|e| Error::Settings(format!("Invalid proxy: {e}")))
+    }
+}
+
+/// One HTTP client per route, so each request can go through the right proxy,
+/// and per set of timeouts, built when a request first needs them.
+struct Clients<T> {
+    config: ProxyConfig,
+    /// Timeouts and retries given for the operation, over `configureNetwork`.
+    options: Option<Arc<NetworkOptions>>,
+    direct: T,
+    http: Option<T>,
+    https: Option<T>,
+    with_timeouts: Mutex<HashMap<(Option<String>, Timeouts), T>>,
+}
+
+impl<T: ProxyClient> Clients<T> {
+    fn new(config: &ProxyConfig, options: Option<Arc<NetworkOptions>>) -> Result<Self> {
+        let client = |proxy: &Option<String>| {
+            proxy
+                .as_deref()
+                .map(|proxy| T::with_proxy(Some(proxy), Timeouts::default()))
+                .transpose()
+        };
+        Ok(Self {
+            config: config.clone(),
+            options,
+            direct: T::with_proxy(None, Timeouts::default())?,
+            http: client(&config.http_proxy)?,
+            https: client(&config.https_proxy)?,
+            with_timeouts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn client_for(
+        &self,
+        uri: &Uri,
+        timeouts: Timeouts,
+    ) -> std::result::Result<T, HttpResolverError> {
+        let proxy = self.config.proxy_for(uri);
+        if timeouts == Timeouts::default() {
+            let proxied = match proxy {
+                Some(_) if uri.scheme_str() == Some("http") => self.http.as_ref(),
+                Some(_) => self.https.as_ref(),
+                None => None,
+            };
+            return Ok(proxied.unwrap_or(&self.direct).clone());
+        }
+        let mut clients = self
+            .with_timeouts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let key = (proxy.map(str::to_string), timeouts);
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+        let client = T::with_proxy(proxy, timeouts)
+            .map_err(|e| HttpResolverError::Io(io::Error::other(e.to_string())))?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+}
+
+impl<T: ProxyClient + SyncHttpResolver> Clients<T> {
+    fn send(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        let mut attempts = Attempts::new(&request, self.options.as_deref());
+        let client = self.client_for(request.uri(), attempts.policy.timeouts())?;
+        loop {
+            let result = client.http_resolve(copy_request(&request)?);
+            match attempts.retry_after(&request, &result) {
+                Some(delay) => std::thread::sleep(delay),
+                None => return result,
+            }
+        }
+    }
+}
+
+impl<T: ProxyClient + AsyncHttpResolver> Clients<T> {
+    async fn send_async(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        let mut attempts = Attempts::new(&request, self.options.as_deref());
+        let client = self.client_for(request.uri(), attempts.policy.timeouts())?;
+        loop {
+            // The response isn't `Send`, so it mustn't live across the sleep.
+            let delay = {
+                let result = client.http_resolve_async(copy_request(&request)?).await;
+                match attempts.retry_after(&request, &result) {
+                    Some(delay) => delay,
+                    None => return result,
+                }
+            };
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+impl<T: ProxyClient + SyncHttpResolver> SyncHttpResolver for Clients<T> {
+    fn http_resolve(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        let Some(resource) = Resource::of_request(&request) else {
+            return self.send(request);
+        };
+        let url = request.uri().to_string();
+        if let Some(bytes) = network_cache::get(resource, &url) {
+            return ok_response(bytes);
+        }
+        let response = self.send(request)?;
+        store_response(resource, &url, response)
+    }
+}
+
+#[async_trait]
+impl<T: ProxyClient + AsyncHttpResolver> AsyncHttpResolver for Clients<T> {
+    async fn http_resolve_async(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        let Some(resource) = Resource::of_request(&request) else {
+            return self.send_async(request).await;
+        };
+        let url = request.uri().to_string();
+        if let Some(bytes) = network_cache::get(resource, &url) {
+            return ok_response(bytes);
+        }
+        if let Some(response) = resolve_request(&request).await {
+            return store_response(resource, &url, response?);
+        }
+        let response = self.send_async(request).await?;
+        store_response(resource, &url, response)
+    }
+}
+
+/// A span for an HTTP request, ended by the caller.
+fn http_span(method: &str, url: &impl ToString) -> Span {
+    let mut span = Span::start("c2pa.http", SpanKind::Client);
+    span.set_attribute("http.request.method", method);
+    span.set_attribute("url.full", url.to_string());
+    span
+}
+
+fn end_http_span(
+    mut span: Span,
+    result: &std::result::Result<Response<Box<dyn Read>>, HttpResolverError>,
+) {
+    if let Ok(response) = result {
+        span.set_attribute("http.response.status_code", response.status().as_u16());
+    }
+    span.end(
+        result
+            .as_ref()
+            .is_ok_and(|response| response.status().is_success()),
+    );
+}
+
+/// A reqwest client for `url` with the configured proxy.
+fn client_for(url: &str) -> Result<reqwest::Client> {
+    let config = ProxyConfig::current()?;
+    let proxy = url
+        .parse::<Uri>()
+        .ok()
+        .and_then(|uri| config.proxy_for(&uri).map(str::to_string));
+    reqwest::Client::with_proxy(proxy.as_deref(), Timeouts::default())
+}
+
+/// GET a URL with the configured proxy. Used for downloads made by this crate
+/// itself rather than by c2pa-rs.
+async fn get(url: &str) -> Result<reqwest::Response> {
+    ensure_online(url)?;
+    let mut span = http_span("GET", &url);
+    let response = client_for(url)?.get(url).send().await?;
+    span.set_attribute("http.response.status_code", response.status().as_u16());
+    let response = response.error_for_status()?;
+    span.end(true);
+    Ok(response)
+}
+
+/// POST a JSON body to a URL with the configured proxy, such as spans to an
+/// OTLP collector. Not traced, so exporting spans makes no more of them.
+pub async fn post_json(
+    url: &str,
+    headers: &HashMap<String, String>,
+    body: &serde_json::Value,
+) -> Result<()> {
+    ensure_online(url)?;
+    let mut request = client_for(url)?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(body)?);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    request.send().await?.error_for_status()?;
+    Ok(())
+}
+
+/// Fetch a URL as text with the configured proxy.
+pub async fn fetch_text(url: &str) -> Result<String> {
+    Ok(get(url).await?.text().await?)
+}
+
+/// Fetch a URL as bytes with the configured proxy.
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>> {
+    Ok(get(url).await?.bytes().await?.to_vec())
+}
+
+/// Part of a resource fetched with `RangeClient::fetch`.
+pub struct RangeResponse {
+    /// Offset of `data` in the resource.
+    pub start: u64,
+    pub data: Vec<u8>,
+    /// Size of the whole resource, if the server reported it.
+    pub total: Option<u64>,
+}
+
+/// Reads parts of an HTTP(S) resource with range requests, through the configured proxy.
+pub struct RangeClient {
+    url: String,
+    agent: ureq::Agent,
+    max_len: Option<u64>,
+}
+
+impl RangeClient {
+    pub fn new(url: &str) -> Result<Self> {
+        let uri = url
+            .parse::<Uri>()
+            .map_err(|e| Error::Asset(format!("Invalid asset URL {url}: {e}")))?;
+        if !matches!(uri.scheme_str(), Some("http" | "https")) {
+            return Err(Error::Asset(format!(
+                "Asset URLs must use http or https: {url}"
+            )));
+        }
+        let config = ProxyConfig::current()?;
+        Ok(Self {
+            url: url.to_string(),
+            agent: ureq::Agent::with_proxy(config.proxy_for(&uri), Timeouts::default())?,
+            max_len: None,
+        })
+    }
+
+    /// Fail when a server that ignores ranges sends more than `max_len` bytes,
+    /// rather than receiving all of them.
+    pub fn with_limit(mut self, max_len: u64) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// The manifest store the server links the resource to with a
+    /// `Link: <url>; rel="c2pa-manifest"` header, for assets whose manifest
+    /// is neither embedded nor referenced from their metadata.
+    pub fn manifest_link(&self) -> Result<Option<String>> {
+        ensure_online(&self.url)?;
+        let url = &self.url;
+        let response = self
+            .agent
+            .head(url)
+            .call()
+            .map_err(|e| Error::Asset(format!("Failed to fetch {url}: {e}")))?;
+        Ok(response
+            .headers()
+            .get_all("link")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(c2pa_manifest_link)
+            .map(|target| resolve_link(url, target)))
+    }
+
+    /// Fetch `len` bytes starting at `start`. Servers that do not support range
+    /// requests return the whole resource instead.
+    pub fn fetch(&self, start: u64, len: u64) -> Result<RangeResponse> {
+        ensure_online(&self.url)?;
+        let mut span = http_span("GET", &self.url);
+        let response = self.fetch_range(start, len);
+        span.end(response.is_ok());
+        response
+    }
+
+    fn fetch_range(&self, start: u64, len: u64) -> Result<RangeResponse> {
+        let url = &self.url;
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Range", format!("bytes={}-{}", start, start + len - 1))
+            .call()
+            .map_err(|e| Error::Asset(format!("Failed to fetch {url}: {e}")))?;
+        let read_err = |e: ureq::Error| Error::Asset(format!("Failed to read {url}: {e}"));
+
+        if response.status() == 206 {
+            let (start, total) = response
+                .headers()
+                .get("content-range")
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_content_range)
+                .ok_or_else(|| Error::Asset(format!("Invalid Content-Range from {url}")))?;
+            let data = response
+                .body_mut()
+                .with_config()
+                .limit(len)
+                .read_to_vec()
+                .map_err(read_err)?;
+            Ok(RangeResponse { start, data, total })
+        } else {
+            // The server ignored the range and sent the whole resource. One byte
+            // past the limit is enough to tell that it is too large.
+            let limit = self
+                .max_len
+                .map_or(u64::MAX, |max_len| max_len.saturating_add(1));
+            let data = response.body_mut().with_config().limit(limit).read_to_vec();
+            let data = match (data, self.max_len) {
+                (Ok(data), Some(max_len)) if data.len() as u64 > max_len => {
+                    return Err(Error::FileIO(size_limit_error(max_len)))
+                }
+                (Err(ureq::Error::BodyExceedsLimit(_)), Some(max_len)) => {
+                    return Err(Error::FileIO(size_limit_error(max_len)))
+                }
+                (data, _) => data.map_err(read_err)?,
+            };
+            let total = Some(data.len() as u64);
+            Ok(RangeResponse {
+                start: 0,
+                data,
+                total,
+            })
+        }
+    }
+}
+
+/// The target of the `rel="c2pa-manifest"` link in a `Link` header, which can
+/// hold several comma-separated links.
+fn c2pa_manifest_link(value: &str) -> Option<&str> {
+    value.split(',').find_map(|link| {
+        let (target, params) = link.trim().strip_prefix('<')?.split_once('>')?;
+        let is_manifest = params.split(';').any(|param| {
+            param.split_once('=').is_some_and(|(name, rels)| {
+                name.trim().eq_ignore_ascii_case("rel")
+                    && rels
+                        .trim()
+                        .trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("c2pa-manifest"))
+            })
+        });
+        is_manifest.then_some(target.trim())
+    })
+}
+
+/// Resolve a link target against the URL of the resource that linked to it.
+fn resolve_link(base: &str, target: &str) -> String {
+    let Ok(uri) = base.parse::<Uri>() else {
+        return target.to_string();
+    };
+    let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) else {
+        return target.to_string();
+    };
+    if target.contains("://") {
+        target.to_string()
+    } else if let Some(target) = target.strip_prefix("//") {
+        format!("{scheme}://{target}")
+    } else if target.starts_with('/') {
+        format!("{scheme}://{authority}{target}")
+    } else {
+        let path = uri.path();
+        let dir = &path[..path.rfind('/').map_or(0, |index| index + 1)];
+        format!("{scheme}://{authority}{dir}{target}")
+    }
+}
+
+/// Parse a `Content-Range` header such as `bytes 0-1023/4096` into the start
+/// offset and, unless it is `*`, the total size.
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let start = range.split_once('-')?.0.trim().parse().ok()?;
+    let total = match total.trim() {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    Some((start, total))
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+pub fn set_offline(offline: bool) -> bool {
+    OFFLINE.swap(offline, Ordering::SeqCst) != offline
+}
+
+/// Fail with `Error::Offline` if network access is disabled.
+pub fn ensure_online(target: &str) -> Result<()> {
+    if is_offline() {
+        Err(Error::Offline(target.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// While offline, report failures caused by refused network access as `Error::Offline`
+/// instead of the c2pa-rs error they surface as.
+pub fn classify_offline(err: Error) -> Error {
+    if !is_offline() {
+        return err;
+    }
+    match err {
+        Error::C2pa(c2pa::Error::HttpResolverError(_))
+        | Error::C2pa(c2pa::Error::RemoteManifestFetch(_))
+        | Error::C2pa(c2pa::Error::RemoteManifestUrl(_))
+        | Error::RemoteManifestFetch(_) => Error::Offline(err.to_string()),
+        err => err,
+    }
+}
+
+fn refuse(uri: &impl ToString) -> HttpResolverError {
+    HttpResolverError::Io(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        Error::Offline(uri.to_string()).to_string(),
+    ))
+}
+
+/// HTTP resolver installed on every Context built by this crate. Applies
+/// `core.allowed_network_hosts` like the c2pa-rs default resolver, routes
+/// requests through the configured proxy with the configured timeouts and
+/// retries, and refuses every request while offline mode is on, including for
+/// Contexts built before it was turned on.
+struct NetworkResolver<T> {
+    inner: RestrictedResolver<Clients<T>>,
+}
+
+impl<T: ProxyClient> NetworkResolver<T> {
+    fn new(
+        proxy: &ProxyConfig,
+        settings: &Settings,
+        options: Option<Arc<NetworkOptions>>,
+    ) -> Result<Self> {
+        let mut inner = RestrictedResolver::new(Clients::new(proxy, options)?);
+        inner.set_allowed_hosts(settings.core.allowed_network_hosts.clone());
+        Ok(Self { inner })
+    }
+}
+
+impl SyncHttpResolver for NetworkResolver<ureq::Agent> {
+    fn http_resolve(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        if is_offline() {
+            return Err(refuse(request.uri()));
+        }
+        let (endpoint, uri) = (Endpoint::of_request(&request), request.uri().clone());
+        let span = http_span(request.method().as_str(), &uri);
+        let result = self.inner.http_resolve(request);
+        record_disallowed(endpoint, &uri, &result);
+        end_http_span(span, &result);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncHttpResolver for NetworkResolver<reqwest::Client> {
+    async fn http_resolve_async(
+        &self,
+        request: Request<Vec<u8>>,
+    ) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+        if is_offline() {
+            return Err(refuse(request.uri()));
+        }
+        let (endpoint, uri) = (Endpoint::of_request(&request), request.uri().clone());
+        let span = http_span(request.method().as_str(), &uri);
+        let result = self.inner.http_resolve_async(request).await;
+        record_disallowed(endpoint, &uri, &result);
+        end_http_span(span, &result);
+        result
+    }
+}
+
+/// Build a c2pa Context for the given settings with this crate's HTTP resolvers.
+pub fn build_context(settings: Settings) -> Result<Context> {
+    build_context_with(settings, None)
+}
+
+/// Build a c2pa Context whose requests use `options` over the timeouts and
+/// retries set with `configureNetwork`.
+pub fn build_context_with(settings: Settings, options: Option<NetworkOptions>) -> Result<Context> {
+    let proxy = ProxyConfig::current()?;
+    let options = options.map(Arc::new);
+    let sync_resolver = NetworkResolver::<ureq::Agent>::new(&proxy, &settings, options.clone())?;
+    let async_resolver = NetworkResolver::<reqwest::Client>::new(&proxy, &settings, options)?;
+    Ok(Context::new()
+        .with_settings(settings)?
+        .with_resolver(sync_resolver)
+        .with_resolver_async(async_resolver))
+}
+
+/// Send a time-stamp request for a signer, with `options` over the timeouts and
+/// retries set with `configureNetwork`. Used in place of the c2pa-rs default,
+/// which doesn't go through this crate's resolver.
+pub async fn time_stamp_request(
+    url: &str,
+    headers: Option<Vec<(String, String)>>,
+    data: &[u8],
+    message: &[u8],
+    options: Option<NetworkOptions>,
+) -> std::result::Result<Vec<u8>, TimeStampError> {
+    let settings = Settings::default();
+    let resolver = ProxyConfig::current()
+        .and_then(|proxy| {
+            NetworkResolver::<reqwest::Client>::new(&proxy, &settings, options.map(Arc::new))
+        })
+        .map_err(|e| TimeStampError::InternalError(e.to_string()))?;
+    default_rfc3161_request_async(url, headers, data, message, &resolver).await
+}
+
+/// Set the timeouts and retries for requests made by c2pa-rs, replacing any
+/// previous ones. Passing null goes back to the HTTP client defaults.
+pub fn configure_network(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let options = match cx.argument_opt(0) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => {
+            let json = json
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            let options: NetworkOptions = serde_json::from_str(&json)
+                .or_else(|err| cx.throw_error(format!("Invalid network configuration: {err}")))?;
+            Some(options)
+        }
+        _ => None,
+    };
+    let mut network = NETWORK
+        .write()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    *network = options;
+    Ok(cx.undefined())
+}
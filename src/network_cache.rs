@@ -0,0 +1,380 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Cursor, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use c2pa::hash_stream_by_alg;
+use c2pa::http::http::{header, Method, Request, Response, StatusCode};
+use c2pa::http::HttpResolverError;
+use neon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::error::{as_js_error_fn, Result};
+
+const DEFAULT_MAX_MEMORY_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resource {
+    RemoteManifest,
+    Ocsp,
+    Trust,
+}
+
+impl Resource {
+    const ALL: [Resource; 3] = [Resource::RemoteManifest, Resource::Ocsp, Resource::Trust];
+
+    fn name(self) -> &'static str {
+        match self {
+            Resource::RemoteManifest => "remoteManifest",
+            Resource::Ocsp => "ocsp",
+            Resource::Trust => "trust",
+        }
+    }
+
+    fn default_ttl_secs(self) -> u64 {
+        match self {
+            Resource::RemoteManifest => 24 * 60 * 60,
+            // OCSP responses are usually valid for days, but a revocation
+            // should be noticed within the hour.
+            Resource::Ocsp => 60 * 60,
+            Resource::Trust => 24 * 60 * 60,
+        }
+    }
+
+    fn counters(self) -> &'static Counters {
+        &COUNTERS[self as usize]
+    }
+
+    /// What a request made by c2pa-rs fetches, if it is something cached.
+    /// Remote manifests are fetched with a bare GET and OCSP responses with a
+    /// GET that only sets the Host header; time-stamps are POSTed.
+    pub fn of_request(request: &Request<Vec<u8>>) -> Option<Self> {
+        if request.method() != Method::GET || !request.body().is_empty() {
+            return None;
+        }
+        let headers = request.headers();
+        if headers.is_empty() {
+            Some(Resource::RemoteManifest)
+        } else if headers.len() == 1 && headers.contains_key(header::HOST) {
+            Some(Resource::Ocsp)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct NetworkCacheConfig {
+    /// Memory for cached responses. The oldest are dropped to make room.
+    pub max_memory_bytes: Option<u64>,
+    /// Also keep responses in this directory, so they outlive the process and
+    /// can be shared with other processes.
+    pub dir: Option<PathBuf>,
+    /// How long responses are used for. Zero turns caching off for that kind.
+    pub remote_manifest_ttl_secs: Option<u64>,
+    pub ocsp_ttl_secs: Option<u64>,
+    pub trust_ttl_secs: Option<u64>,
+}
+
+impl NetworkCacheConfig {
+    fn ttl(&self, resource: Resource) -> Duration {
+        let secs = match resource {
+            Resource::RemoteManifest => self.remote_manifest_ttl_secs,
+            Resource::Ocsp => self.ocsp_ttl_secs,
+            Resource::Trust => self.trust_ttl_secs,
+        };
+        Duration::from_secs(secs.unwrap_or_else(|| resource.default_ttl_secs()))
+    }
+}
+
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+static COUNTERS: [Counters; 3] = [const {
+    Counters {
+        hits: AtomicU64::new(0),
+        misses: AtomicU64::new(0),
+    }
+}; 3];
+
+struct Entry {
+    bytes: Arc<[u8]>,
+    stored: SystemTime,
+}
+
+/// Responses fetched by the native layer, set up by `configureNetworkCache`.
+struct NetworkCache {
+    config: NetworkCacheConfig,
+    entries: HashMap<(Resource, String), Entry>,
+    memory_bytes: u64,
+}
+
+static CACHE: Mutex<Option<NetworkCache>> = Mutex::new(None);
+
+fn age(stored: SystemTime) -> Duration {
+    SystemTime::now()
+        .duration_since(stored)
+        .unwrap_or(Duration::ZERO)
+}
+
+impl NetworkCache {
+    fn new(config: NetworkCacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            memory_bytes: 0,
+        }
+    }
+
+    fn max_memory_bytes(&self) -> u64 {
+        self.config
+            .max_memory_bytes
+            .unwrap_or(DEFAULT_MAX_MEMORY_BYTES)
+    }
+
+    /// The file a response is kept in, named by the SHA-256 of its URL since
+    /// OCSP URLs are too long for file names.
+    fn path(&self, resource: Resource, url: &str) -> Option<PathBuf> {
+        let dir = self.config.dir.as_ref()?;
+        let hash =
+            hash_stream_by_alg("sha256", &mut Cursor::new(url.as_bytes()), None, true).ok()?;
+        let name: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+        Some(dir.join(resource.name()).join(name))
+    }
+
+    fn get(&mut self, resource: Resource, url: &str) -> Option<Arc<[u8]>> {
+        let ttl = self.config.ttl(resource);
+        if ttl.is_zero() {
+            return None;
+        }
+        let key = (resource, url.to_string());
+        match self.entries.get(&key) {
+            Some(entry) if age(entry.stored) < ttl => return Some(Arc::clone(&entry.bytes)),
+            Some(_) => self.remove(&key),
+            None => {}
+        }
+        let path = self.path(resource, url)?;
+        let stored = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()?;
+        if age(stored) >= ttl {
+            return None;
+        }
+        let bytes: Arc<[u8]> = std::fs::read(path).ok()?.into();
+        self.insert(key, Arc::clone(&bytes), stored);
+        Some(bytes)
+    }
+
+    fn put(&mut self, resource: Resource, url: &str, bytes: &[u8]) {
+        if self.config.ttl(resource).is_zero() {
+            return;
+        }
+        if let Some(path) = self.path(resource, url) {
+            // A cache that cannot be written only costs a download next time.
+            if let Err(err) = store_file(&path, bytes) {
+                log::warn!("Failed to cache {url}: {err}");
+            }
+        }
+        self.insert((resource, url.to_string()), bytes.into(), SystemTime::now());
+    }
+
+    fn insert(&mut self, key: (Resource, String), bytes: Arc<[u8]>, stored: SystemTime) {
+        self.remove(&key);
+        let size = bytes.len() as u64;
+        let max = self.max_memory_bytes();
+        if size > max {
+            return;
+        }
+        while self.memory_bytes + size > max {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.memory_bytes += size;
+        self.entries.insert(key, Entry { bytes, stored });
+    }
+
+    fn remove(&mut self, key: &(Resource, String)) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.memory_bytes -= entry.bytes.len() as u64;
+        }
+    }
+
+    /// Drop every response, including those on disk.
+    fn clear(&mut self) -> Result<()> {
+        self.entries.clear();
+        self.memory_bytes = 0;
+        if let Some(dir) = &self.config.dir {
+            for resource in Resource::ALL {
+                match std::fs::remove_dir_all(dir.join(resource.name())) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replace the file at `path` in whole, so other processes sharing the
+/// directory never read a partial response.
+fn store_file(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or(path);
+    std::fs::create_dir_all(dir)?;
+    let mut file = NamedTempFile::new_in(dir)?;
+    file.write_all(bytes)?;
+    file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// The cached response for `url`, if caching is on and it is fresh.
+pub fn get(resource: Resource, url: &str) -> Option<Vec<u8>> {
+    let mut cache = CACHE.lock().ok()?;
+    let bytes = cache.as_mut()?.get(resource, url);
+    let counters = resource.counters();
+    match &bytes {
+        Some(_) => counters.hits.fetch_add(1, Ordering::Relaxed),
+        None => counters.misses.fetch_add(1, Ordering::Relaxed),
+    };
+    bytes.map(|bytes| bytes.to_vec())
+}
+
+/// Keep a response for `url`, if caching is on.
+pub fn put(resource: Resource, url: &str, bytes: &[u8]) {
+    if let Ok(mut cache) = CACHE.lock() {
+        if let Some(cache) = cache.as_mut() {
+            cache.put(resource, url, bytes);
+        }
+    }
+}
+
+/// A 200 response with `bytes` as the body, as c2pa-rs expects from a
+/// resolver.
+pub fn ok_response(
+    bytes: Vec<u8>,
+) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_LENGTH, bytes.len())
+        .body(Box::new(Cursor::new(bytes)) as Box<dyn Read>)
+        .map_err(HttpResolverError::Http)
+}
+
+/// Keep the body of a successful response, returning the response with the
+/// body read back in.
+pub fn store_response(
+    resource: Resource,
+    url: &str,
+    response: Response<Box<dyn Read>>,
+) -> std::result::Result<Response<Box<dyn Read>>, HttpResolverError> {
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+    let (parts, mut body) = response.into_parts();
+    let mut bytes = Vec::new();
+    body.read_to_end(&mut bytes)?;
+    put(resource, url, &bytes);
+    Ok(Response::from_parts(parts, Box::new(Cursor::new(bytes))))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceStats {
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NetworkCacheStats {
+    enabled: bool,
+    /// Responses held in memory, and their size in bytes.
+    entries: usize,
+    memory_bytes: u64,
+    resources: BTreeMap<&'static str, ResourceStats>,
+}
+
+/// Cache remote manifests, OCSP responses, and trust lists fetched by the
+/// native layer, in memory and optionally on disk, replacing any previous
+/// cache. Passing null turns caching off.
+pub fn configure_network_cache(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let config = match cx.argument_opt(0) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => {
+            let json = json
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            let config: NetworkCacheConfig = serde_json::from_str(&json).or_else(|err| {
+                cx.throw_error(format!("Invalid network cache configuration: {err}"))
+            })?;
+            Some(NetworkCache::new(config))
+        }
+        _ => None,
+    };
+    let mut cache = CACHE
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    *cache = config;
+    Ok(cx.undefined())
+}
+
+/// Drop every cached response, in memory and on disk.
+pub fn clear_network_cache(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let result = CACHE
+        .lock()
+        .map_err(Into::into)
+        .and_then(|mut cache| cache.as_mut().map_or(Ok(()), NetworkCache::clear));
+    match result {
+        Ok(()) => Ok(cx.undefined()),
+        Err(err) => as_js_error_fn(&mut cx, "clearNetworkCache", err).and_then(|err| cx.throw(err)),
+    }
+}
+
+pub fn network_cache_stats(mut cx: FunctionContext) -> JsResult<JsString> {
+    let stats = {
+        let cache = CACHE
+            .lock()
+            .or_else(|err| cx.throw_error(err.to_string()))?;
+        NetworkCacheStats {
+            enabled: cache.is_some(),
+            entries: cache.as_ref().map_or(0, |cache| cache.entries.len()),
+            memory_bytes: cache.as_ref().map_or(0, |cache| cache.memory_bytes),
+            resources: Resource::ALL
+                .into_iter()
+                .map(|resource| {
+                    let counters = resource.counters();
+                    let stats = ResourceStats {
+                        hits: counters.hits.load(Ordering::Relaxed),
+                        misses: counters.misses.load(Ordering::Relaxed),
+                    };
+                    (resource.name(), stats)
+                })
+                .collect(),
+        }
+    };
+    let json = serde_json::to_string(&stats).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
@@ -0,0 +1,765 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Verification of fragmented MP4 presentations from their DASH MPD or HLS
+//! playlist.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use c2pa::ValidationState;
+use neon::prelude::*;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, Event};
+use serde::Serialize;
+use url::Url;
+
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::fragments::read_fragment;
+use crate::js_value::to_js;
+use crate::metrics::{Operation, OperationTimer};
+use crate::network::fetch_bytes;
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_settings, parse_timeout};
+use crate::validation::{validation_failures, ValidationMessage};
+use crate::validation_policy::validation_state;
+
+/// More segments than this in one representation is taken to be a mistake in
+/// the MPD, such as a segment duration in the wrong timescale.
+const MAX_SEGMENTS: u64 = 100_000;
+
+/// The mime type of segments when the MPD or playlist doesn't give one.
+const DEFAULT_MIME_TYPE: &str = "video/mp4";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentationReport {
+    /// "dash" or "hls".
+    kind: &'static str,
+    /// The lowest state of any segment.
+    validation_state: ValidationState,
+    representations: Vec<RepresentationReport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RepresentationReport {
+    id: String,
+    mime_type: String,
+    /// The initialization segment, or None when each segment is verified on
+    /// its own.
+    init: Option<String>,
+    /// The label of the active manifest of the first segment that has one.
+    active_manifest: Option<String>,
+    /// The lowest state of any of its segments.
+    validation_state: ValidationState,
+    segments: Vec<SegmentReport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentReport {
+    uri: String,
+    validation_state: ValidationState,
+    failures: Vec<ValidationMessage>,
+    /// Why the segment couldn't be read. The segment is then invalid.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+struct Representation {
+    id: String,
+    mime_type: String,
+    segments: Vec<Segment>,
+}
+
+struct Segment {
+    url: Url,
+    init: Option<Url>,
+}
+
+fn rank(state: ValidationState) -> u8 {
+    match state {
+        ValidationState::Invalid => 0,
+        ValidationState::Valid => 1,
+        ValidationState::Trusted => 2,
+    }
+}
+
+fn lowest(states: impl Iterator<Item = ValidationState>) -> ValidationState {
+    states
+        .min_by_key(|state| rank(*state))
+        .unwrap_or(ValidationState::Invalid)
+}
+
+/// The URL of a presentation given as a URL or a file path.
+fn source_url(source: &str) -> Result<Url> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        return Url::parse(source).map_err(|err| Error::Reading(format!("Invalid URL: {err}")));
+    }
+    let path = std::path::absolute(source)?;
+    Url::from_file_path(&path).map_err(|_| Error::Reading(format!("Invalid path: {source}")))
+}
+
+/// Resolve a reference in an MPD or playlist against its base URL. Remote
+/// presentations may only refer to remote segments.
+fn resolve(base: &Url, reference: &str) -> Result<Url> {
+    let url = base
+        .join(reference.trim())
+        .map_err(|err| Error::Reading(format!("Invalid segment URL {reference}: {err}")))?;
+    let remote = |url: &Url| matches!(url.scheme(), "http" | "https");
+    if remote(base) && !remote(&url) {
+        return Err(Error::Reading(format!(
+            "A remote presentation can't refer to {url}"
+        )));
+    }
+    Ok(url)
+}
+
+async fn load(url: &Url) -> Result<Vec<u8>> {
+    match url.scheme() {
+        "file" => {
+            let path = url
+                .to_file_path()
+                .map_err(|_| Error::Reading(format!("Invalid file URL: {url}")))?;
+            Ok(std::fs::read(path)?)
+        }
+        "http" | "https" => fetch_bytes(url.as_str()).await,
+        scheme => Err(Error::Reading(format!("Unsupported URL scheme: {scheme}"))),
+    }
+}
+
+async fn load_text(url: &Url) -> Result<String> {
+    String::from_utf8(load(url).await?)
+        .map_err(|_| Error::Reading(format!("{url} is not UTF-8 text")))
+}
+
+/// Just enough of an XML element tree to walk an MPD.
+#[derive(Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(start: &BytesStart) -> Result<Self> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(xml_error)?;
+            let name = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute.unescape_value().map_err(xml_error)?;
+            attributes.push((name, value.into_owned()));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+fn xml_error(err: impl std::fmt::Display) -> Error {
+    Error::Reading(format!("Invalid MPD: {err}"))
+}
+
+fn parse_xml(xml: &str) -> Result<Element> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    // The document is the only child of the element at the bottom.
+    let mut stack = vec![Element::default()];
+    loop {
+        match reader.read_event().map_err(xml_error)? {
+            Event::Start(start) => stack.push(Element::new(&start)?),
+            Event::Empty(start) => {
+                let element = Element::new(&start)?;
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(element);
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().filter(|_| !stack.is_empty());
+                match (element, stack.last_mut()) {
+                    (Some(element), Some(parent)) => parent.children.push(element),
+                    _ => return Err(xml_error("unbalanced end tag")),
+                }
+            }
+            Event::Text(text) => {
+                let text = text.xml_content().map_err(xml_error)?;
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text);
+                }
+            }
+            Event::GeneralRef(reference) => {
+                let name = reference.decode().map_err(xml_error)?;
+                let text = match reference.resolve_char_ref().map_err(xml_error)? {
+                    Some(ch) => ch.to_string(),
+                    None => resolve_predefined_entity(&name)
+                        .ok_or_else(|| xml_error(format!("unknown entity &{name};")))?
+                        .to_string(),
+                };
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&text);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    match stack.pop() {
+        Some(root) if stack.is_empty() => root
+            .children
+            .into_iter()
+            .next()
+            .ok_or_else(|| xml_error("no root element")),
+        _ => Err(xml_error("unclosed element")),
+    }
+}
+
+/// Seconds in an ISO 8601 duration such as `PT1H2M3.5S`, as used in MPDs.
+fn parse_duration(duration: &str) -> Option<f64> {
+    let rest = duration.trim().strip_prefix('P')?;
+    let mut seconds = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    for ch in rest.chars() {
+        match ch {
+            'T' => in_time = true,
+            '0'..='9' | '.' => number.push(ch),
+            unit => {
+                let value: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += value
+                    * match (unit, in_time) {
+                        ('Y', false) => 365.0 * 86400.0,
+                        ('M', false) => 30.0 * 86400.0,
+                        ('W', false) => 7.0 * 86400.0,
+                        ('D', false) => 86400.0,
+                        ('H', true) => 3600.0,
+                        ('M', true) => 60.0,
+                        ('S', true) => 1.0,
+                        _ => return None,
+                    };
+            }
+        }
+    }
+    number.is_empty().then_some(seconds)
+}
+
+/// Fill in the `$Identifier$`s of a SegmentTemplate, with an optional
+/// `%0<width>d` format for the numbers.
+fn fill_template(
+    template: &str,
+    representation: &Element,
+    number: u64,
+    time: u64,
+) -> Result<String> {
+    let mut filled = String::new();
+    let mut parts = template.split('$');
+    filled.push_str(parts.next().unwrap_or_default());
+    while let Some(identifier) = parts.next() {
+        let literal = parts
+            .next()
+            .ok_or_else(|| Error::Reading(format!("Unterminated identifier in {template}")))?;
+        let (name, format) = identifier.split_once('%').unwrap_or((identifier, ""));
+        let value = match name {
+            "" => "$".to_string(),
+            "RepresentationID" => representation.attr("id").unwrap_or_default().to_string(),
+            "Number" => number.to_string(),
+            "Time" => time.to_string(),
+            "Bandwidth" => representation
+                .attr("bandwidth")
+                .unwrap_or_default()
+                .to_string(),
+            _ => {
+                return Err(Error::Reading(format!(
+                    "Unknown identifier ${identifier}$ in {template}"
+                )))
+            }
+        };
+        let width: usize = format
+            .strip_suffix('d')
+            .map(|width| width.trim_start_matches('0'))
+            .filter(|width| !width.is_empty())
+            .and_then(|width| width.parse().ok())
+            .unwrap_or(0);
+        filled.push_str(&format!("{value:0>width$}"));
+        filled.push_str(literal);
+    }
+    Ok(filled)
+}
+
+/// A SegmentTemplate or SegmentList with what it inherits from the levels
+/// above it filled in.
+#[derive(Default)]
+struct Segmentation<'a> {
+    attributes: HashMap<&'a str, &'a str>,
+    timeline: Option<&'a Element>,
+    initialization: Option<&'a Element>,
+    urls: Vec<&'a Element>,
+}
+
+impl<'a> Segmentation<'a> {
+    /// Merge the `name` elements of `levels`, from the outermost.
+    fn merge(levels: &[&'a Element], name: &str) -> Option<Self> {
+        let mut merged: Option<Self> = None;
+        for element in levels.iter().filter_map(|level| level.child(name)) {
+            let segmentation = merged.get_or_insert_with(Self::default);
+            for (key, value) in &element.attributes {
+                segmentation.attributes.insert(key, value);
+            }
+            segmentation.timeline = element.child("SegmentTimeline").or(segmentation.timeline);
+            segmentation.initialization = element
+                .child("Initialization")
+                .or(segmentation.initialization);
+            let urls: Vec<&Element> = element.children("SegmentURL").collect();
+            if !urls.is_empty() {
+                segmentation.urls = urls;
+            }
+        }
+        merged
+    }
+
+    fn number(&self, name: &str, default: u64) -> Result<u64> {
+        match self.attributes.get(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::Reading(format!("Invalid {name} in MPD: {value}"))),
+            None => Ok(default),
+        }
+    }
+}
+
+fn segment_count(count: u64) -> Result<u64> {
+    if count > MAX_SEGMENTS {
+        return Err(Error::Reading(format!(
+            "A representation has more than {MAX_SEGMENTS} segments"
+        )));
+    }
+    Ok(count)
+}
+
+/// The start times and numbers of the segments of a SegmentTemplate.
+fn template_segments(template: &Segmentation, duration: Option<f64>) -> Result<Vec<(u64, u64)>> {
+    let start_number = template.number("startNumber", 1)?;
+    let timescale = template.number("timescale", 1)?.max(1);
+    let end = duration.map(|duration| (duration * timescale as f64).ceil() as u64);
+    let mut segments = Vec::new();
+
+    if let Some(timeline) = template.timeline {
+        let entries: Vec<&Element> = timeline.children("S").collect();
+        let mut time = 0;
+        for (index, entry) in entries.iter().enumerate() {
+            let number = |name: &str| -> Result<Option<i64>> {
+                entry
+                    .attr(name)
+                    .map(|value| {
+                        value.parse().map_err(|_| {
+                            Error::Reading(format!("Invalid S@{name} in MPD: {value}"))
+                        })
+                    })
+                    .transpose()
+            };
+            if let Some(start) = number("t")? {
+                time = start.max(0) as u64;
+            }
+            let length = number("d")?
+                .filter(|length| *length > 0)
+                .ok_or_else(|| Error::Reading("An S element in the MPD has no d".to_string()))?
+                as u64;
+            let repeat = match number("r")?.unwrap_or(0) {
+                // Repeat until the next S element or the end of the period.
+                repeat if repeat < 0 => {
+                    let until = entries
+                        .get(index + 1)
+                        .and_then(|next| next.attr("t"))
+                        .and_then(|next| next.parse().ok())
+                        .or(end)
+                        .ok_or_else(|| {
+                            Error::Reading(
+                                "Can't count the segments of a presentation without a duration"
+                                    .to_string(),
+                            )
+                        })?;
+                    until
+                        .saturating_sub(time)
+                        .div_ceil(length)
+                        .saturating_sub(1)
+                }
+                repeat => repeat as u64,
+            };
+            for _ in 0..=repeat {
+                segments.push((start_number + segments.len() as u64, time));
+                segment_count(segments.len() as u64)?;
+                time += length;
+            }
+        }
+        return Ok(segments);
+    }
+
+    let length = template
+        .number("duration", 0)
+        .ok()
+        .filter(|length| *length > 0)
+        .ok_or_else(|| {
+            Error::Reading("A SegmentTemplate has neither a duration nor a timeline".to_string())
+        })?;
+    let end = end.ok_or_else(|| {
+        Error::Reading("Can't count the segments of a presentation without a duration".to_string())
+    })?;
+    let count = segment_count(end.div_ceil(length))?;
+    for index in 0..count {
+        segments.push((start_number + index, index * length));
+    }
+    Ok(segments)
+}
+
+/// The segments of a representation, given it and the levels that contain
+/// it. The most specific level with a SegmentTemplate or SegmentList decides
+/// which is used.
+fn representation_segments(
+    levels: &[&Element],
+    representation: &Element,
+    base: &Url,
+    duration: Option<f64>,
+) -> Result<Vec<Segment>> {
+    let kind = levels.iter().rev().find_map(|level| {
+        ["SegmentTemplate", "SegmentList"]
+            .into_iter()
+            .find(|name| level.child(name).is_some())
+    });
+    let byte_ranges =
+        || Error::Reading("Byte ranges of a single file aren't supported".to_string());
+    match kind.and_then(|kind| Some((kind, Segmentation::merge(levels, kind)?))) {
+        Some(("SegmentTemplate", template)) => {
+            let init = template
+                .attributes
+                .get("initialization")
+                .map(|init| fill_template(init, representation, 0, 0))
+                .transpose()?
+                .map(|init| resolve(base, &init))
+                .transpose()?;
+            let media = template.attributes.get("media").ok_or_else(|| {
+                Error::Reading("A SegmentTemplate has no media attribute".to_string())
+            })?;
+            template_segments(&template, duration)?
+                .into_iter()
+                .map(|(number, time)| {
+                    let uri = fill_template(media, representation, number, time)?;
+                    Ok(Segment {
+                        url: resolve(base, &uri)?,
+                        init: init.clone(),
+                    })
+                })
+                .collect()
+        }
+        Some((_, list)) => {
+            let init = list
+                .initialization
+                .map(|init| init.attr("sourceURL").ok_or_else(byte_ranges))
+                .transpose()?
+                .map(|init| resolve(base, init))
+                .transpose()?;
+            list.urls
+                .iter()
+                .map(|segment_url| {
+                    let media = segment_url.attr("media").ok_or_else(byte_ranges)?;
+                    Ok(Segment {
+                        url: resolve(base, media)?,
+                        init: init.clone(),
+                    })
+                })
+                .collect()
+        }
+        // A single file, which is verified as a whole.
+        None => Ok(vec![Segment {
+            url: base.clone(),
+            init: None,
+        }]),
+    }
+}
+
+fn dash_representations(mpd: &Element, source: &Url) -> Result<Vec<Representation>> {
+    if mpd.name != "MPD" {
+        return Err(xml_error(format!(
+            "expected an MPD element, found {}",
+            mpd.name
+        )));
+    }
+    let with_base = |base: &Url, element: &Element| -> Result<Url> {
+        match element.child("BaseURL") {
+            Some(base_url) if !base_url.text.trim().is_empty() => resolve(base, &base_url.text),
+            _ => Ok(base.clone()),
+        }
+    };
+    let mpd_base = with_base(source, mpd)?;
+    let total = mpd
+        .attr("mediaPresentationDuration")
+        .and_then(parse_duration);
+    let periods: Vec<&Element> = mpd.children("Period").collect();
+    let start = |period: &Element| period.attr("start").and_then(parse_duration);
+
+    let mut representations = Vec::new();
+    for (index, period) in periods.iter().enumerate() {
+        let duration = period
+            .attr("duration")
+            .and_then(parse_duration)
+            .or_else(|| {
+                let period_start = start(period).unwrap_or(0.0);
+                let next_start = periods.get(index + 1).and_then(|next| start(next));
+                next_start.or(total).map(|end| end - period_start)
+            });
+        let period_base = with_base(&mpd_base, period)?;
+        for adaptation in period.children("AdaptationSet") {
+            let adaptation_base = with_base(&period_base, adaptation)?;
+            for representation in adaptation.children("Representation") {
+                let base = with_base(&adaptation_base, representation)?;
+                let mut id = representation.attr("id").unwrap_or_default().to_string();
+                if periods.len() > 1 {
+                    let period_id = period
+                        .attr("id")
+                        .map(str::to_string)
+                        .unwrap_or_else(|| index.to_string());
+                    id = format!("{period_id}/{id}");
+                }
+                let mime_type = representation
+                    .attr("mimeType")
+                    .or(adaptation.attr("mimeType"))
+                    .unwrap_or(DEFAULT_MIME_TYPE)
+                    .to_string();
+                let levels = [*period, adaptation, representation];
+                let segments = representation_segments(&levels, representation, &base, duration)?;
+                representations.push(Representation {
+                    id,
+                    mime_type,
+                    segments,
+                });
+            }
+        }
+    }
+    Ok(representations)
+}
+
+/// The value of an attribute in an HLS tag such as
+/// `#EXT-X-MAP:URI="init.mp4",BYTERANGE="720@0"`.
+fn hls_attribute(line: &str, name: &str) -> Option<String> {
+    let (_, mut rest) = line.split_once(':')?;
+    while !rest.is_empty() {
+        let (key, value) = rest.split_once('=')?;
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let (value, next) = quoted.split_once('"')?;
+                (value, next.trim_start_matches(','))
+            }
+            None => value.split_once(',').unwrap_or((value, "")),
+        };
+        if key.trim() == name {
+            return Some(value.to_string());
+        }
+        rest = next;
+    }
+    None
+}
+
+fn hls_media_playlist(id: String, playlist: &str, base: &Url) -> Result<Representation> {
+    let mut init = None;
+    let mut segments = Vec::new();
+    for line in playlist.lines().map(str::trim) {
+        if line.starts_with("#EXT-X-BYTERANGE")
+            || (line.starts_with("#EXT-X-MAP") && hls_attribute(line, "BYTERANGE").is_some())
+        {
+            return Err(Error::Reading(
+                "Byte ranges of a single file aren't supported".to_string(),
+            ));
+        }
+        if line.starts_with("#EXT-X-MAP") {
+            let uri = hls_attribute(line, "URI")
+                .ok_or_else(|| Error::Reading("An EXT-X-MAP tag has no URI".to_string()))?;
+            init = Some(resolve(base, &uri)?);
+        } else if !line.is_empty() && !line.starts_with('#') {
+            segments.push(Segment {
+                url: resolve(base, line)?,
+                init: init.clone(),
+            });
+        }
+    }
+    Ok(Representation {
+        id,
+        mime_type: DEFAULT_MIME_TYPE.to_string(),
+        segments,
+    })
+}
+
+async fn hls_representations(playlist: &str, source: &Url) -> Result<Vec<Representation>> {
+    // A multivariant playlist lists the media playlists of its variants and
+    // of their alternative renditions.
+    let mut playlists = Vec::new();
+    let mut lines = playlist.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if line.starts_with("#EXT-X-STREAM-INF") {
+            if let Some(uri) = lines.find(|line| !line.is_empty() && !line.starts_with('#')) {
+                playlists.push(uri.to_string());
+            }
+        } else if line.starts_with("#EXT-X-MEDIA:") {
+            playlists.extend(hls_attribute(line, "URI"));
+        }
+    }
+    if playlists.is_empty() {
+        let id = source
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default()
+            .to_string();
+        return Ok(vec![hls_media_playlist(id, playlist, source)?]);
+    }
+
+    let mut representations = Vec::new();
+    for uri in playlists {
+        let url = resolve(source, &uri)?;
+        let media_playlist = load_text(&url).await?;
+        representations.push(hls_media_playlist(uri, &media_playlist, &url)?);
+    }
+    Ok(representations)
+}
+
+async fn verify_representation(
+    context: &Arc<c2pa::Context>,
+    representation: Representation,
+) -> RepresentationReport {
+    let mut inits: HashMap<Url, std::result::Result<Vec<u8>, String>> = HashMap::new();
+    let mut active_manifest = None;
+    let mut segments = Vec::new();
+    for segment in &representation.segments {
+        let init = match &segment.init {
+            Some(url) => {
+                if !inits.contains_key(url) {
+                    let init = load(url).await.map_err(|err| err.to_string());
+                    inits.insert(url.clone(), init);
+                }
+                inits.get(url).map(|init| init.as_deref())
+            }
+            None => None,
+        };
+        let result = match (init, load(&segment.url).await) {
+            (Some(Err(err)), _) => Err(format!("Can't load the initialization segment: {err}")),
+            (_, Err(err)) => Err(err.to_string()),
+            (init, Ok(data)) => {
+                let init = init.and_then(|init| init.ok());
+                read_fragment(context, &representation.mime_type, init, Cursor::new(data))
+                    .await
+                    .map_err(|err| err.to_string())
+            }
+        };
+        segments.push(match result {
+            Ok(reader) => {
+                if active_manifest.is_none() {
+                    active_manifest = reader.active_label().map(str::to_string);
+                }
+                SegmentReport {
+                    uri: segment.url.to_string(),
+                    validation_state: validation_state(&reader),
+                    failures: validation_failures(&reader),
+                    error: None,
+                }
+            }
+            Err(err) => SegmentReport {
+                uri: segment.url.to_string(),
+                validation_state: ValidationState::Invalid,
+                failures: Vec::new(),
+                error: Some(err),
+            },
+        });
+    }
+    RepresentationReport {
+        id: representation.id,
+        mime_type: representation.mime_type,
+        init: representation
+            .segments
+            .first()
+            .and_then(|segment| segment.init.as_ref())
+            .map(Url::to_string),
+        active_manifest,
+        validation_state: lowest(segments.iter().map(|segment| segment.validation_state)),
+        segments,
+    }
+}
+
+async fn verify(context: Arc<c2pa::Context>, source: &str) -> Result<PresentationReport> {
+    let url = source_url(source)?;
+    let manifest = load_text(&url).await?;
+    let (kind, representations) = if manifest.trim_start().starts_with("#EXTM3U") {
+        ("hls", hls_representations(&manifest, &url).await?)
+    } else {
+        ("dash", dash_representations(&parse_xml(&manifest)?, &url)?)
+    };
+    let mut reports = Vec::new();
+    for representation in representations {
+        reports.push(verify_representation(&context, representation).await);
+    }
+    Ok(PresentationReport {
+        kind,
+        validation_state: lowest(reports.iter().map(|report| report.validation_state)),
+        representations: reports,
+    })
+}
+
+/// Verify the segments of a fragmented MP4 presentation listed by a DASH MPD
+/// or an HLS playlist, given as a path or an http(s) URL. Each media segment
+/// is verified with its representation's initialization segment, and the
+/// results are reported per representation and per segment. A segment that
+/// can't be loaded or read is reported as invalid rather than failing the
+/// whole presentation.
+pub fn verify_presentation(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx.argument::<JsString>(0)?.value(&mut cx);
+    let context = parse_settings(&mut cx, 1, "verifyPresentation")
+        .or_else(|err| cx.throw_error(err.to_string()))?
+        .unwrap_or_else(|| Arc::new(c2pa::Context::new()));
+    let timeout = parse_timeout(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 3).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move { verify(context, &source).await });
+        let result: Result<PresentationReport> =
+            with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(report) => to_js(&mut cx, &report),
+            Err(err) => {
+                as_js_error(&mut cx, "verifyPresentation", err).and_then(|err| cx.throw(err))
+            }
+        });
+    });
+    Ok(promise)
+}
@@ -0,0 +1,149 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs;
+use std::io::{self, Read, Seek};
+use std::path::Path;
+
+use neon::prelude::*;
+use serde::Serialize;
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::metrics::{Operation, OperationTimer};
+use crate::remote_manifest::fetch_remote_manifest;
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::scan::{provenance, ScanResult};
+use crate::utils::{parse_priority, parse_timeout};
+
+/// Where an XMP `dcterms:provenance` reference was found.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ReferenceSource {
+    /// XMP embedded in the asset.
+    Embedded,
+    /// An `.xmp` sidecar file next to the asset.
+    Sidecar,
+}
+
+/// A manifest store outside the asset, referred to from its XMP.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceReference {
+    url: String,
+    source: ReferenceSource,
+}
+
+impl ProvenanceReference {
+    /// The reference in the XMP embedded in `stream`, or else in the `.xmp`
+    /// sidecar of the file at `asset_path`. References to a manifest store in
+    /// the asset itself (`self#jumbf=`) are not returned.
+    pub fn find<R: Read + Seek + ?Sized>(
+        stream: &mut R,
+        format: Option<String>,
+        asset_path: Option<&Path>,
+    ) -> Result<Option<Self>> {
+        // Formats the scan doesn't know have no XMP to follow.
+        let embedded = ScanResult::scan(stream, format)
+            .ok()
+            .and_then(|scan| scan.provenance().map(str::to_string));
+        let (url, source) = match (embedded, asset_path) {
+            (Some(url), _) => (url, ReferenceSource::Embedded),
+            (None, Some(path)) => match fs::read(path.with_extension("xmp")) {
+                Ok(xmp) => match provenance(&xmp) {
+                    Some(url) => (url, ReferenceSource::Sidecar),
+                    None => return Ok(None),
+                },
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            },
+            (None, None) => return Ok(None),
+        };
+        if url.is_empty() || url.starts_with("self#") {
+            return Ok(None);
+        }
+        Ok(Some(Self { url, source }))
+    }
+
+    /// The manifest store bytes the reference points to. HTTP(S) URLs are
+    /// fetched only when remote manifest fetching is on; other references are
+    /// paths or `file:` URLs, relative ones to the directory of the asset.
+    pub async fn resolve(
+        &self,
+        asset_path: Option<&Path>,
+        context: Option<&c2pa::Context>,
+    ) -> Result<Vec<u8>> {
+        let url = self.url.as_str();
+        if url.starts_with("http://") || url.starts_with("https://") {
+            let fetch_remote = cfg!(feature = "fetch_remote_manifests")
+                && context.is_none_or(|context| context.settings().verify.remote_manifest_fetch);
+            if !fetch_remote {
+                return Err(Error::C2pa(c2pa::Error::RemoteManifestUrl(url.to_string())));
+            }
+            return fetch_remote_manifest(url).await;
+        }
+        let path = Path::new(url.strip_prefix("file://").unwrap_or(url));
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            let directory = asset_path.and_then(Path::parent).ok_or_else(|| {
+                Error::Reading(format!(
+                    "The manifest store reference {url} is relative, so it can only be resolved for file assets"
+                ))
+            })?;
+            directory.join(path)
+        };
+        fs::read(&path).map_err(|err| {
+            Error::Reading(format!(
+                "Can't read the manifest store {} referenced by the asset's XMP: {err}",
+                path.display()
+            ))
+        })
+    }
+}
+
+/// The manifest store reference in an asset's XMP, or its `.xmp` sidecar, as
+/// JSON, or null. The reference is not resolved.
+pub fn find_provenance_reference(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let timeout = parse_timeout(&mut cx, 1).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let asset_path = source.path().map(Path::to_path_buf);
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let reference = ProvenanceReference::find(&mut stream, format, asset_path.as_deref())?;
+            Ok(reference
+                .map(|reference| serde_json::to_string(&reference))
+                .transpose()?)
+        });
+        let result: Result<Option<String>> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(Some(json)) => Ok(cx.string(json).upcast::<JsValue>()),
+            Ok(None) => Ok(cx.null().upcast::<JsValue>()),
+            Err(err) => {
+                as_js_error(&mut cx, "findProvenanceReference", err).and_then(|err| cx.throw(err))
+            }
+        });
+    });
+    Ok(promise)
+}
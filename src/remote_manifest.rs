@@ -0,0 +1,171 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use c2pa::http::http::{Request, Response};
+use c2pa::http::HttpResolverError;
+use neon::prelude::*;
+use neon::types::buffer::TypedArray;
+use tokio::sync::oneshot;
+
+use crate::error::{Error, Result};
+use crate::network::{ensure_online, fetch_bytes};
+use crate::network_cache::{self, ok_response, Resource};
+use crate::runtime::blocks_thread;
+
+/// The callback registered with `setRemoteManifestResolver`.
+#[derive(Clone)]
+struct JsResolver {
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+    js_thread: ThreadId,
+}
+
+static RESOLVER: Mutex<Option<JsResolver>> = Mutex::new(None);
+
+fn resolver() -> Option<JsResolver> {
+    RESOLVER.lock().ok()?.clone()
+}
+
+/// Why the callback failed, starting with the URL.
+type ResolveError = String;
+
+impl JsResolver {
+    /// Ask the callback for the manifest store at `url`. None means the
+    /// callback left it to the built-in HTTP client.
+    async fn resolve(&self, url: &str) -> std::result::Result<Option<Vec<u8>>, ResolveError> {
+        if blocks_thread(self.js_thread) {
+            return Err(format!(
+                "{url}: The remote manifest resolver can only be used with asynchronous methods"
+            ));
+        }
+        let (tx, rx) = oneshot::channel();
+        let callback = Arc::clone(&self.callback);
+        let owned_url = url.to_string();
+        self.channel
+            .try_send(move |mut cx| {
+                let url = cx.string(&owned_url);
+                let future = callback
+                    .to_inner(&mut cx)
+                    .call_with(&cx)
+                    .arg(url)
+                    .apply::<JsPromise, _>(&mut cx)?
+                    .to_future(&mut cx, move |mut cx, result| match result {
+                        Ok(value) => manifest_bytes(&mut cx, value, &owned_url),
+                        Err(err) => {
+                            let message = err.to_string(&mut cx)?.value(&mut cx);
+                            Ok(Err(format!("{owned_url}: {message}")))
+                        }
+                    })?;
+                let _ = tx.send(future);
+                Ok(())
+            })
+            .map_err(|err| format!("{url}: {err}"))?;
+        let future = rx.await.map_err(|err| format!("{url}: {err}"))?;
+        future.await.map_err(|err| format!("{url}: {err}"))?
+    }
+}
+
+/// The manifest store bytes the callback resolved to.
+fn manifest_bytes(
+    cx: &mut TaskContext,
+    value: Handle<JsValue>,
+    url: &str,
+) -> NeonResult<std::result::Result<Option<Vec<u8>>, ResolveError>> {
+    if value.is_a::<JsNull, _>(cx) || value.is_a::<JsUndefined, _>(cx) {
+        return Ok(Ok(None));
+    }
+    if let Ok(buffer) = value.downcast::<JsBuffer, _>(cx) {
+        return Ok(Ok(Some(buffer.as_slice(cx).to_vec())));
+    }
+    if let Ok(array) = value.downcast::<JsUint8Array, _>(cx) {
+        return Ok(Ok(Some(array.as_slice(cx).to_vec())));
+    }
+    Ok(Err(format!(
+        "{url}: The remote manifest resolver must return a Buffer, Uint8Array, or null"
+    )))
+}
+
+/// Answer a remote manifest request from c2pa-rs with the registered
+/// callback. None when there is no callback, the request is not for a remote
+/// manifest, or the callback left it to the built-in HTTP client.
+pub async fn resolve_request(
+    request: &Request<Vec<u8>>,
+) -> Option<std::result::Result<Response<Box<dyn Read>>, HttpResolverError>> {
+    if Resource::of_request(request) != Some(Resource::RemoteManifest) {
+        return None;
+    }
+    let resolver = resolver()?;
+    let url = request.uri().to_string();
+    let bytes = match resolver.resolve(&url).await {
+        Ok(bytes) => bytes?,
+        Err(message) => return Some(Err(HttpResolverError::Io(io::Error::other(message)))),
+    };
+    Some(ok_response(bytes))
+}
+
+/// Fetch the manifest store at `url` from the network cache, with the
+/// registered callback if there is one and it resolves the URL, or else with
+/// the built-in HTTP client.
+pub async fn fetch_remote_manifest(url: &str) -> Result<Vec<u8>> {
+    ensure_online(url)?;
+    if let Some(bytes) = network_cache::get(Resource::RemoteManifest, url) {
+        return Ok(bytes);
+    }
+    let bytes = fetch_uncached(url).await?;
+    network_cache::put(Resource::RemoteManifest, url, &bytes);
+    Ok(bytes)
+}
+
+async fn fetch_uncached(url: &str) -> Result<Vec<u8>> {
+    if let Some(resolver) = resolver() {
+        let resolved = resolver
+            .resolve(url)
+            .await
+            .map_err(|message| Error::C2pa(c2pa::Error::RemoteManifestFetch(message)))?;
+        if let Some(bytes) = resolved {
+            return Ok(bytes);
+        }
+    }
+    fetch_bytes(url).await
+}
+
+/// Resolve remote manifest URLs with `callback` instead of the built-in HTTP
+/// client, replacing any previous callback. The callback is given the URL and
+/// returns a promise of the manifest store bytes, or of null to let the
+/// built-in client fetch it. Passing null removes the callback.
+pub fn set_remote_manifest_resolver(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsValue>(0)?;
+    let resolver = if let Ok(callback) = callback.downcast::<JsFunction, _>(&mut cx) {
+        let mut channel = cx.channel();
+        // A registered resolver should not keep the process alive.
+        channel.unref(&mut cx);
+        Some(JsResolver {
+            channel,
+            callback: Arc::new(callback.root(&mut cx)),
+            js_thread: thread::current().id(),
+        })
+    } else if callback.is_a::<JsNull, _>(&mut cx) || callback.is_a::<JsUndefined, _>(&mut cx) {
+        None
+    } else {
+        return cx.throw_type_error("The remote manifest resolver must be a function or null");
+    };
+    let mut current = RESOLVER
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    *current = resolver;
+    Ok(cx.undefined())
+}
@@ -0,0 +1,716 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt::Write;
+
+use c2pa::assertions::{Actions, SoftwareAgent};
+use c2pa::{Manifest, Reader, Relationship, ValidationState};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::validation::{validation_failures, validation_messages, ValidationMessage};
+use crate::validation_policy;
+
+#[derive(Clone, Copy)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(ReportFormat::Json),
+            "markdown" => Some(ReportFormat::Markdown),
+            "html" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+/// A summary of a Reader's manifest store: who signed each manifest, when,
+/// with what tool, what was done to the asset, and which checks failed.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    validation_state: ValidationState,
+    active_manifest: Option<String>,
+    /// The active manifest first, then the manifests of its ingredients in the
+    /// order they were added, recursively.
+    manifests: Vec<ManifestSummary>,
+    failures: Vec<ValidationMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestSummary {
+    label: Option<String>,
+    title: Option<String>,
+    signer: Option<SignerSummary>,
+    generators: Vec<GeneratorSummary>,
+    actions: Vec<ActionSummary>,
+    ingredients: Vec<IngredientSummary>,
+    identities: Vec<IdentitySummary>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignerSummary {
+    common_name: Option<String>,
+    issuer: Option<String>,
+    alg: Option<String>,
+    time: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeneratorSummary {
+    name: String,
+    version: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionSummary {
+    action: String,
+    when: Option<String>,
+    software_agent: Option<String>,
+    digital_source_type: Option<String>,
+    description: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IngredientSummary {
+    title: Option<String>,
+    relationship: Relationship,
+    active_manifest: Option<String>,
+}
+
+/// A CAWG identity assertion: the identities an identity claims aggregator
+/// verified, or the X.509 certificate that signed it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IdentitySummary {
+    label: String,
+    /// `cawg.identity_claims_aggregation` or `cawg.x509.cose`.
+    sig_type: Option<String>,
+    /// Whether c2pa-rs validated the identity signature without failures.
+    valid: bool,
+    /// The aggregator's DID, for an identity claims aggregation credential.
+    issuer: Option<String>,
+    valid_from: Option<String>,
+    valid_until: Option<String>,
+    verified_identities: Vec<VerifiedIdentity>,
+    /// The certificate's subject, for an X.509 identity.
+    signer: Option<SignerSummary>,
+    failures: Vec<ValidationMessage>,
+}
+
+/// A signature in a manifest store: a manifest's claim signature, or the
+/// signature of one of its CAWG identity assertions.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureSummary {
+    manifest_label: Option<String>,
+    /// `claim` or `identity`.
+    kind: &'static str,
+    /// The identity assertion's label, for an identity signature.
+    label: Option<String>,
+    /// The identity assertion's signature type, for an identity signature.
+    sig_type: Option<String>,
+    /// Whether the manifest is an update manifest, which adds a signature,
+    /// such as an approver's endorsement, without rebinding the asset.
+    update_manifest: bool,
+    signer: Option<SignerSummary>,
+    validation_state: ValidationState,
+    failures: Vec<ValidationMessage>,
+}
+
+/// An identity an aggregator verified, such as a social media account or an
+/// identity document.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VerifiedIdentity {
+    #[serde(rename = "type")]
+    kind: String,
+    name: Option<String>,
+    username: Option<String>,
+    address: Option<String>,
+    uri: Option<String>,
+    verified_at: Option<String>,
+    provider: Option<IdentityProvider>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct IdentityProvider {
+    id: Option<String>,
+    name: Option<String>,
+}
+
+/// The active manifest first, then the manifests of its ingredients in the
+/// order they were added, recursively. Ingredients can share a manifest, so
+/// each is listed only once.
+fn manifest_chain(reader: &Reader) -> Vec<&Manifest> {
+    let mut labels: Vec<&str> = reader.active_label().into_iter().collect();
+    let mut manifests = Vec::new();
+    let mut next = 0;
+    while let Some(manifest) = labels
+        .get(next)
+        .and_then(|label| reader.get_manifest(label))
+    {
+        for label in manifest
+            .ingredients()
+            .iter()
+            .filter_map(|ingredient| ingredient.active_manifest())
+        {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        manifests.push(manifest);
+        next += 1;
+    }
+    manifests
+}
+
+const ICA_SIG_TYPE: &str = "cawg.identity_claims_aggregation";
+
+/// Success codes c2pa-rs gives an identity signature it validated.
+const IDENTITY_VALID_CODES: &[&str] = &[
+    "cawg.ica.credential_valid",
+    "cawg.identity.well-formed",
+    "cawg.identity.trusted",
+];
+
+impl Report {
+    pub fn new(reader: &Reader) -> Self {
+        let messages = validation_messages(reader);
+        let manifests = manifest_chain(reader)
+            .into_iter()
+            .map(|manifest| ManifestSummary::new(manifest, &messages))
+            .collect();
+        Self {
+            validation_state: validation_policy::validation_state(reader),
+            active_manifest: reader.active_label().map(str::to_string),
+            manifests,
+            failures: validation_failures(reader),
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> serde_json::Result<String> {
+        match format {
+            ReportFormat::Json => serde_json::to_string(self),
+            ReportFormat::Markdown => Ok(markdown(&self.blocks())),
+            ReportFormat::Html => Ok(html(&self.blocks())),
+        }
+    }
+
+    fn blocks(&self) -> Vec<Block> {
+        let state = match self.validation_state {
+            ValidationState::Invalid => "Invalid",
+            ValidationState::Valid => "Valid",
+            ValidationState::Trusted => "Trusted",
+        };
+        let mut blocks = vec![
+            Block::Heading(1, "Verification report".to_string()),
+            Block::Fields(vec![("Validation state", state.to_string())]),
+        ];
+        for manifest in &self.manifests {
+            let title = manifest.title.as_deref().unwrap_or("Untitled");
+            let heading = if manifest.label.is_some() && manifest.label == self.active_manifest {
+                format!("{title} (active manifest)")
+            } else {
+                title.to_string()
+            };
+            blocks.push(Block::Heading(2, heading));
+            blocks.push(Block::Fields(manifest.fields()));
+            if !manifest.actions.is_empty() {
+                blocks.push(Block::Heading(3, "Actions".to_string()));
+                blocks.push(Block::List(
+                    manifest.actions.iter().map(ActionSummary::line).collect(),
+                ));
+            }
+            if !manifest.identities.is_empty() {
+                blocks.push(Block::Heading(3, "Identities".to_string()));
+                blocks.push(Block::List(
+                    manifest
+                        .identities
+                        .iter()
+                        .flat_map(IdentitySummary::lines)
+                        .collect(),
+                ));
+            }
+            if !manifest.ingredients.is_empty() {
+                blocks.push(Block::Heading(3, "Ingredients".to_string()));
+                blocks.push(Block::List(
+                    manifest
+                        .ingredients
+                        .iter()
+                        .map(IngredientSummary::line)
+                        .collect(),
+                ));
+            }
+        }
+        blocks.push(Block::Heading(2, "Failures".to_string()));
+        if self.failures.is_empty() {
+            blocks.push(Block::Text("No validation checks failed.".to_string()));
+        } else {
+            blocks.push(Block::List(
+                self.failures
+                    .iter()
+                    .map(|failure| match &failure.message {
+                        Some(message) => format!("{}: {message}", failure.code),
+                        None => failure.code.clone(),
+                    })
+                    .collect(),
+            ));
+        }
+        blocks
+    }
+}
+
+/// Success codes for a signing credential on a trust list.
+const TRUSTED_CODES: &[&str] = &["signingCredential.trusted", "cawg.identity.trusted"];
+
+/// Every signature in the Reader's manifest store, in manifest order: each
+/// manifest's claim signature followed by its identity signatures.
+pub fn signatures(reader: &Reader) -> Vec<SignatureSummary> {
+    let messages = validation_messages(reader);
+    let mut signatures = Vec::new();
+    for manifest in manifest_chain(reader) {
+        let summary = ManifestSummary::new(manifest, &messages);
+        let update_manifest = !manifest
+            .assertion_references()
+            .any(|reference| reference.url().contains("c2pa.hash."));
+        let findings: Vec<&ValidationMessage> = messages
+            .iter()
+            .filter(|message| {
+                message.params.get("manifestLabel").map(String::as_str) == manifest.label()
+                    && !summary
+                        .identities
+                        .iter()
+                        .any(|identity| message.params.get("assertion") == Some(&identity.label))
+            })
+            .collect();
+        signatures.push(SignatureSummary {
+            manifest_label: summary.label.clone(),
+            kind: "claim",
+            label: None,
+            sig_type: None,
+            update_manifest,
+            signer: summary.signer.clone(),
+            validation_state: signature_state(&findings),
+            failures: findings
+                .iter()
+                .filter(|message| message.kind == "failure")
+                .map(|message| (*message).clone())
+                .collect(),
+        });
+        for identity in summary.identities {
+            let findings: Vec<&ValidationMessage> = messages
+                .iter()
+                .filter(|message| {
+                    message.params.get("manifestLabel").map(String::as_str) == manifest.label()
+                        && message.params.get("assertion") == Some(&identity.label)
+                })
+                .collect();
+            signatures.push(SignatureSummary {
+                manifest_label: summary.label.clone(),
+                kind: "identity",
+                validation_state: signature_state(&findings),
+                label: Some(identity.label),
+                sig_type: identity.sig_type,
+                update_manifest,
+                signer: identity.signer,
+                failures: identity.failures,
+            });
+        }
+    }
+    signatures
+}
+
+/// A signature is invalid if any check about it failed, other than its
+/// credential not being on a trust list, and trusted if its credential is.
+fn signature_state(findings: &[&ValidationMessage]) -> ValidationState {
+    let invalid = findings
+        .iter()
+        .any(|message| message.kind == "failure" && message.code != "signingCredential.untrusted");
+    if invalid {
+        ValidationState::Invalid
+    } else if findings
+        .iter()
+        .any(|message| TRUSTED_CODES.contains(&message.code.as_str()))
+    {
+        ValidationState::Trusted
+    } else {
+        ValidationState::Valid
+    }
+}
+
+impl ManifestSummary {
+    fn new(manifest: &Manifest, messages: &[ValidationMessage]) -> Self {
+        let signer = manifest.signature_info().map(|info| SignerSummary {
+            common_name: info.common_name.clone(),
+            issuer: info.issuer.clone(),
+            alg: info.alg.map(|alg| alg.to_string()),
+            time: info.time.clone(),
+        });
+        let generators = manifest
+            .claim_generator_info
+            .iter()
+            .flatten()
+            .map(|info| GeneratorSummary {
+                name: info.name.clone(),
+                version: info.version.clone(),
+            })
+            .collect();
+        // Matches both `c2pa.actions` and `c2pa.actions.v2`.
+        let actions = manifest
+            .find_assertion::<Actions>(Actions::LABEL)
+            .map(|actions| actions.actions.iter().map(ActionSummary::new).collect())
+            .unwrap_or_default();
+        let ingredients = manifest
+            .ingredients()
+            .iter()
+            .map(|ingredient| IngredientSummary {
+                title: ingredient.title().map(str::to_string),
+                relationship: ingredient.relationship().clone(),
+                active_manifest: ingredient.active_manifest().map(str::to_string),
+            })
+            .collect();
+        let identities = manifest
+            .assertions()
+            .iter()
+            .filter(|assertion| {
+                assertion.label() == "cawg.identity"
+                    || assertion.label().starts_with("cawg.identity__")
+            })
+            .filter_map(|assertion| {
+                let data = assertion.value().ok()?;
+                Some(IdentitySummary::new(
+                    manifest.label(),
+                    assertion.label(),
+                    data,
+                    messages,
+                ))
+            })
+            .collect();
+        Self {
+            label: manifest.label().map(str::to_string),
+            title: manifest.title().map(str::to_string),
+            signer,
+            generators,
+            actions,
+            ingredients,
+            identities,
+        }
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(label) = &self.label {
+            fields.push(("Manifest", label.clone()));
+        }
+        if let Some(signer) = &self.signer {
+            let name = signer.common_name.as_ref().or(signer.issuer.as_ref());
+            if let Some(name) = name {
+                fields.push(("Signed by", name.clone()));
+            }
+            if let Some(issuer) = &signer.issuer {
+                fields.push(("Issuer", issuer.clone()));
+            }
+            if let Some(time) = &signer.time {
+                fields.push(("Signed at", time.clone()));
+            }
+            if let Some(alg) = &signer.alg {
+                fields.push(("Algorithm", alg.clone()));
+            }
+        } else {
+            fields.push(("Signed by", "Unknown".to_string()));
+        }
+        if !self.generators.is_empty() {
+            let generators = self
+                .generators
+                .iter()
+                .map(|generator| match &generator.version {
+                    Some(version) => format!("{} {version}", generator.name),
+                    None => generator.name.clone(),
+                })
+                .collect::<Vec<_>>();
+            fields.push(("Created with", generators.join(", ")));
+        }
+        fields
+    }
+}
+
+impl ActionSummary {
+    fn new(action: &c2pa::assertions::Action) -> Self {
+        let software_agent = action.software_agent().map(|agent| match agent {
+            SoftwareAgent::String(name) => name.clone(),
+            SoftwareAgent::ClaimGeneratorInfo(info) => match &info.version {
+                Some(version) => format!("{} {version}", info.name),
+                None => info.name.clone(),
+            },
+        });
+        Self {
+            action: action.action().to_string(),
+            when: action.when().map(str::to_string),
+            software_agent,
+            digital_source_type: action.source_type().map(|source| source.to_string()),
+            description: action.description().map(str::to_string),
+        }
+    }
+
+    fn line(&self) -> String {
+        let mut line = self.action.clone();
+        if let Some(when) = &self.when {
+            let _ = write!(line, " at {when}");
+        }
+        if let Some(software_agent) = &self.software_agent {
+            let _ = write!(line, " with {software_agent}");
+        }
+        if let Some(digital_source_type) = &self.digital_source_type {
+            let _ = write!(line, " ({digital_source_type})");
+        }
+        if let Some(description) = &self.description {
+            let _ = write!(line, ": {description}");
+        }
+        line
+    }
+}
+
+impl IdentitySummary {
+    /// Summarize an identity assertion from its data in the manifest, which
+    /// c2pa-rs replaces with the credential it validated, and the findings
+    /// about it.
+    fn new(
+        manifest_label: Option<&str>,
+        label: &str,
+        data: &Value,
+        messages: &[ValidationMessage],
+    ) -> Self {
+        let findings: Vec<&ValidationMessage> = messages
+            .iter()
+            .filter(|message| {
+                message.params.get("manifestLabel").map(String::as_str) == manifest_label
+                    && message.params.get("assertion").map(String::as_str) == Some(label)
+            })
+            .collect();
+        let failures: Vec<ValidationMessage> = findings
+            .iter()
+            .filter(|message| message.kind == "failure")
+            .map(|message| (*message).clone())
+            .collect();
+        let validated = findings
+            .iter()
+            .any(|message| IDENTITY_VALID_CODES.contains(&message.code.as_str()));
+        let text = |key: &str| data.get(key).and_then(Value::as_str).map(str::to_string);
+        let verified_identities: Vec<VerifiedIdentity> = data
+            .get("verifiedIdentities")
+            .and_then(Value::as_array)
+            .map(|identities| {
+                identities
+                    .iter()
+                    .filter_map(|identity| serde_json::from_value(identity.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let sig_type = data
+            .pointer("/signer_payload/sig_type")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| {
+                data.get("verifiedIdentities")
+                    .map(|_| ICA_SIG_TYPE.to_string())
+            });
+        let signer = data.get("signature_info").map(|info| {
+            let text = |key: &str| info.get(key).and_then(Value::as_str).map(str::to_string);
+            SignerSummary {
+                common_name: text("common_name"),
+                issuer: text("issuer"),
+                alg: text("alg"),
+                time: text("time"),
+            }
+        });
+        Self {
+            label: label.to_string(),
+            sig_type,
+            valid: validated && failures.is_empty(),
+            issuer: text("issuer"),
+            valid_from: text("validFrom"),
+            valid_until: text("validUntil"),
+            verified_identities,
+            signer,
+            failures,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let validity = if self.valid { "valid" } else { "not valid" };
+        let mut lines: Vec<String> = self
+            .verified_identities
+            .iter()
+            .map(|identity| {
+                let who = identity
+                    .username
+                    .as_ref()
+                    .or(identity.name.as_ref())
+                    .or(identity.address.as_ref())
+                    .or(identity.uri.as_ref());
+                let provider = identity
+                    .provider
+                    .as_ref()
+                    .and_then(|provider| provider.name.as_ref().or(provider.id.as_ref()));
+                let mut line = match (who, provider) {
+                    (Some(who), Some(provider)) => format!("{who} on {provider}"),
+                    (Some(who), None) => who.clone(),
+                    (None, Some(provider)) => provider.clone(),
+                    (None, None) => "Unnamed identity".to_string(),
+                };
+                let _ = write!(line, " ({}", identity.kind);
+                if let Some(verified_at) = &identity.verified_at {
+                    let _ = write!(line, ", verified {verified_at}");
+                }
+                let _ = write!(line, ", {validity})");
+                line
+            })
+            .collect();
+        if let Some(signer) = &self.signer {
+            let name = signer.common_name.as_ref().or(signer.issuer.as_ref());
+            let name = name.map(String::as_str).unwrap_or("Unknown signer");
+            lines.push(format!("{name} (X.509 certificate, {validity})"));
+        }
+        if lines.is_empty() {
+            let sig_type = self.sig_type.as_deref().unwrap_or("unknown signature");
+            lines.push(format!("{} ({sig_type}, {validity})", self.label));
+        }
+        lines
+    }
+}
+
+impl IngredientSummary {
+    fn line(&self) -> String {
+        let relationship = match self.relationship {
+            Relationship::ParentOf => "parent",
+            Relationship::ComponentOf => "component",
+            Relationship::InputTo => "input",
+        };
+        let title = self.title.as_deref().unwrap_or("Untitled");
+        match &self.active_manifest {
+            Some(label) => format!("{title} ({relationship}, manifest {label})"),
+            None => format!("{title} ({relationship}, no manifest)"),
+        }
+    }
+}
+
+/// The parts of a rendered report, so Markdown and HTML list the same things.
+enum Block {
+    Heading(usize, String),
+    Fields(Vec<(&'static str, String)>),
+    List(Vec<String>),
+    Text(String),
+}
+
+fn markdown(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                let _ = writeln!(out, "{} {}\n", "#".repeat(*level), escape_markdown(text));
+            }
+            Block::Fields(fields) => {
+                for (name, value) in fields {
+                    let _ = writeln!(out, "- **{name}:** {}", escape_markdown(value));
+                }
+                out.push('\n');
+            }
+            Block::List(items) => {
+                for item in items {
+                    let _ = writeln!(out, "- {}", escape_markdown(item));
+                }
+                out.push('\n');
+            }
+            Block::Text(text) => {
+                let _ = writeln!(out, "{}\n", escape_markdown(text));
+            }
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn html(blocks: &[Block]) -> String {
+    let mut out = String::from("<section class=\"c2pa-report\">\n");
+    for block in blocks {
+        match block {
+            Block::Heading(level, text) => {
+                let _ = writeln!(out, "<h{level}>{}</h{level}>", escape_html(text));
+            }
+            Block::Fields(fields) => {
+                out.push_str("<dl>\n");
+                for (name, value) in fields {
+                    let _ = writeln!(out, "<dt>{name}</dt><dd>{}</dd>", escape_html(value));
+                }
+                out.push_str("</dl>\n");
+            }
+            Block::List(items) => {
+                out.push_str("<ul>\n");
+                for item in items {
+                    let _ = writeln!(out, "<li>{}</li>", escape_html(item));
+                }
+                out.push_str("</ul>\n");
+            }
+            Block::Text(text) => {
+                let _ = writeln!(out, "<p>{}</p>", escape_html(text));
+            }
+        }
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// Manifest values come from the asset, so they must not be able to add
+/// markup or formatting of their own.
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '!' => {
+                out.push('\\');
+                out.push(c);
+            }
+            // Keep each value on its own line.
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -5,20 +5,404 @@
 // accordance with the terms of the Adobe license agreement accompanying
 // it.
 
-use std::sync::{Arc, OnceLock};
+use crate::env_config;
+use crate::error::{throw_js_error, Error, Result};
+use crate::instance::loaded_instances;
+use crate::network;
+use crate::settings::stop_trust_refresh;
+use neon::prelude::*;
+use serde::Deserialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle, ThreadId};
+use std::time::{Duration, Instant};
 use tokio::runtime::{Builder, Runtime};
+use tokio::sync::{oneshot, Semaphore};
 
-// Runtime singleton - no longer needs reload functionality since settings are per-instance
-static RUNTIME: OnceLock<Arc<Runtime>> = OnceLock::new();
+enum RuntimeState {
+    NotStarted,
+    Running(Arc<Runtime>),
+    ShutDown,
+}
+
+// Locked before `RUNTIME_CONFIG` wherever both are held.
+static RUNTIME: Mutex<RuntimeState> = Mutex::new(RuntimeState::NotStarted);
+
+// Set by `configureRuntime`, read once when the runtime is built. Replaces the
+// configuration from the environment.
+static RUNTIME_CONFIG: Mutex<Option<RuntimeConfig>> = Mutex::new(None);
+
+/// How the runtime schedules work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RuntimeFlavor {
+    /// A pool of worker threads that share the work.
+    #[default]
+    MultiThread,
+    /// A single thread that runs every task, for environments such as AWS
+    /// Lambda where more threads only add start-up cost and memory.
+    CurrentThread,
+}
+
+impl RuntimeFlavor {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "multiThread" => Some(RuntimeFlavor::MultiThread),
+            "currentThread" => Some(RuntimeFlavor::CurrentThread),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeConfig {
+    /// How work is scheduled. Defaults to a pool of worker threads.
+    #[serde(default)]
+    pub(crate) flavor: RuntimeFlavor,
+    /// Number of worker threads. Defaults to the number of CPU cores.
+    pub(crate) worker_threads: Option<usize>,
+    /// Maximum number of threads for blocking work.
+    pub(crate) max_blocking_threads: Option<usize>,
+    /// Name given to the runtime's threads.
+    pub(crate) thread_name: Option<String>,
+    /// Stack size of the runtime's threads, in bytes.
+    pub(crate) thread_stack_size: Option<usize>,
+    /// Number of batch operations that may run at once. Defaults to half the
+    /// worker threads.
+    pub(crate) batch_concurrency: Option<usize>,
+}
+
+/// The configuration from `configureRuntime`, or else from the environment.
+fn runtime_config() -> Result<RuntimeConfig> {
+    match RUNTIME_CONFIG.lock()?.clone() {
+        Some(config) => Ok(config),
+        None => env_config::runtime_config(),
+    }
+}
+
+// Signals the thread that drives a current-thread runtime to stop, and waits
+// for it. Unset for a multi-threaded runtime, whose workers drive it.
+type Driver = (oneshot::Sender<()>, JoinHandle<()>);
+static DRIVER: Mutex<Option<Driver>> = Mutex::new(None);
 
-fn build_runtime() -> Arc<Runtime> {
-    let rt = Builder::new_multi_thread()
-        .enable_all()
+/// Run a current-thread runtime on a thread of its own, so tasks spawned from
+/// the JS thread make progress, until `stop_driver` is called.
+fn start_driver(rt: &Arc<Runtime>, config: &RuntimeConfig) -> Result<()> {
+    let (stop, stopped) = oneshot::channel::<()>();
+    let mut thread = thread::Builder::new().name(
+        config
+            .thread_name
+            .clone()
+            .unwrap_or_else(|| "c2pa-runtime".to_string()),
+    );
+    if let Some(thread_stack_size) = config.thread_stack_size {
+        thread = thread.stack_size(thread_stack_size);
+    }
+    let driven = Arc::clone(rt);
+    let handle = thread
+        .spawn(move || {
+            let _ = driven.block_on(stopped);
+        })
+        .map_err(|err| Error::Internal(format!("Failed to start the runtime thread: {err}")))?;
+    if let Ok(mut driver) = DRIVER.lock() {
+        *driver = Some((stop, handle));
+    }
+    Ok(())
+}
+
+/// The thread driving the current runtime, if it has one. Taken while the
+/// runtime state is locked, so a runtime started later keeps its own.
+fn take_driver() -> Option<Driver> {
+    DRIVER.lock().ok().and_then(|mut driver| driver.take())
+}
+
+/// Stop a runtime that is no longer in use, along with the thread driving it.
+/// Blocks until that thread has finished.
+fn retire(rt: Arc<Runtime>, driver: Option<Driver>) {
+    if let Some((stop, handle)) = driver {
+        let _ = stop.send(());
+        let _ = handle.join();
+    }
+    if let Ok(rt) = Arc::try_unwrap(rt) {
+        rt.shutdown_background();
+    }
+}
+
+fn build_runtime() -> Result<Arc<Runtime>> {
+    let config = runtime_config()?;
+    let mut builder = match config.flavor {
+        RuntimeFlavor::MultiThread => Builder::new_multi_thread(),
+        RuntimeFlavor::CurrentThread => Builder::new_current_thread(),
+    };
+    builder.enable_all();
+    if let Some(worker_threads) = config.worker_threads {
+        builder.worker_threads(worker_threads);
+    }
+    if let Some(max_blocking_threads) = config.max_blocking_threads {
+        builder.max_blocking_threads(max_blocking_threads);
+    }
+    if let Some(thread_name) = &config.thread_name {
+        builder.thread_name(thread_name);
+    }
+    if let Some(thread_stack_size) = config.thread_stack_size {
+        builder.thread_stack_size(thread_stack_size);
+    }
+    let rt = builder
         .build()
-        .expect("Failed to build runtime");
-    Arc::new(rt)
+        .map_err(|err| Error::Internal(format!("Failed to build the runtime: {err}")))?;
+    let rt = Arc::new(rt);
+    if config.flavor == RuntimeFlavor::CurrentThread {
+        start_driver(&rt, &config)?;
+    }
+    Ok(rt)
+}
+
+/// The runtime for asynchronous work, started on first use. Fails once `shutdown`
+/// has been called.
+pub fn runtime() -> Result<Arc<Runtime>> {
+    let mut state = RUNTIME.lock()?;
+    match &*state {
+        RuntimeState::NotStarted => {
+            let rt = build_runtime()?;
+            *state = RuntimeState::Running(Arc::clone(&rt));
+            Ok(rt)
+        }
+        RuntimeState::Running(rt) => Ok(Arc::clone(rt)),
+        RuntimeState::ShutDown => Err(Error::ShutDown),
+    }
+}
+
+/// The runtime if it is running, without starting it.
+pub fn running_runtime() -> Option<Arc<Runtime>> {
+    match &*RUNTIME.lock().ok()? {
+        RuntimeState::Running(rt) => Some(Arc::clone(rt)),
+        _ => None,
+    }
+}
+
+/// Configure the thread pool used for asynchronous work. Must be called before
+/// any operation that uses the runtime.
+pub fn configure_runtime(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let config_json = cx.argument::<JsString>(0)?.value(&mut cx);
+    let config: RuntimeConfig = serde_json::from_str(&config_json).or_else(|err| {
+        let err = Error::Settings(format!("Invalid runtime configuration: {err}"));
+        throw_js_error(&mut cx, "configureRuntime", err)
+    })?;
+    if config.worker_threads == Some(0)
+        || config.max_blocking_threads == Some(0)
+        || config.batch_concurrency == Some(0)
+    {
+        let err = Error::Settings("Thread counts must be at least 1".to_string());
+        return throw_js_error(&mut cx, "configureRuntime", err);
+    }
+    if config.flavor == RuntimeFlavor::CurrentThread && config.worker_threads.is_some() {
+        let err =
+            Error::Settings("workerThreads can't be set for a currentThread runtime".to_string());
+        return throw_js_error(&mut cx, "configureRuntime", err);
+    }
+
+    // Held until the configuration is stored, so the runtime can't start
+    // in between.
+    let state = RUNTIME
+        .lock()
+        .or_else(|err| throw_js_error(&mut cx, "configureRuntime", err))?;
+    if !matches!(*state, RuntimeState::NotStarted) {
+        let err = Error::Settings(
+            "The runtime has already started. Call configureRuntime before any other operation."
+                .to_string(),
+        );
+        return throw_js_error(&mut cx, "configureRuntime", err);
+    }
+    let mut current = RUNTIME_CONFIG
+        .lock()
+        .or_else(|err| throw_js_error(&mut cx, "configureRuntime", err))?;
+    *current = Some(config);
+    Ok(cx.undefined())
+}
+
+/// Stop accepting new work, wait up to `timeoutMs` (default 30 seconds) for running
+/// tasks to finish, and stop the runtime. Resolves to whether every task finished
+/// in time; unfinished tasks are abandoned. Only allowed from the last thread that
+/// has the addon loaded.
+pub fn shutdown(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let timeout_ms = cx
+        .argument_opt(0)
+        .and_then(|arg| arg.downcast::<JsNumber, _>(&mut cx).ok())
+        .map(|ms| ms.value(&mut cx).max(0.0) as u64)
+        .unwrap_or(30_000);
+    // The runtime is shared with every Worker that loaded the addon.
+    let instances = loaded_instances();
+    if instances > 1 {
+        return cx.throw_error(format!(
+            "The runtime is shared by {instances} threads. Terminate the other Workers before calling shutdown."
+        ));
+    }
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    let (previous, driver) = match RUNTIME.lock() {
+        Ok(mut state) => (
+            std::mem::replace(&mut *state, RuntimeState::ShutDown),
+            take_driver(),
+        ),
+        Err(err) => return cx.throw_error(err.to_string()),
+    };
+    let rt = match previous {
+        RuntimeState::Running(rt) => rt,
+        _ => {
+            deferred.settle_with(&channel, |mut cx| Ok(cx.boolean(true)));
+            return Ok(promise);
+        }
+    };
+    // The refresh task never finishes on its own.
+    let _ = stop_trust_refresh();
+
+    // Wait off the JS thread, since tasks may need it to finish.
+    thread::spawn(move || {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        let mut drained = rt.metrics().num_alive_tasks() == 0;
+        while !drained && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+            drained = rt.metrics().num_alive_tasks() == 0;
+        }
+        retire(rt, driver);
+        deferred.settle_with(&channel, move |mut cx| Ok(cx.boolean(drained)));
+    });
+    Ok(promise)
+}
+
+/// Return to the state before first use, for processes restored from a
+/// snapshot such as Lambda SnapStart: the runtime is stopped and started again
+/// on next use, and the `C2PA_*` environment variables are read again, so no
+/// threads or configuration from before the snapshot are used. Operations still
+/// running are abandoned and trust sources stop refreshing. Also allowed after
+/// `shutdown`. Only allowed from the last thread that has the addon loaded.
+pub fn reinitialize(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let instances = loaded_instances();
+    if instances > 1 {
+        return cx.throw_error(format!(
+            "The runtime is shared by {instances} threads. Terminate the other Workers before calling reinitialize."
+        ));
+    }
+    let (previous, driver) = match RUNTIME.lock() {
+        Ok(mut state) => (
+            std::mem::replace(&mut *state, RuntimeState::NotStarted),
+            take_driver(),
+        ),
+        Err(err) => return cx.throw_error(err.to_string()),
+    };
+    *BATCH_PERMITS.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    let reset = stop_trust_refresh().and_then(|_| env_config::reset());
+    if let RuntimeState::Running(rt) = previous {
+        // Stopping waits for the runtime's thread, which may be busy.
+        thread::spawn(move || retire(rt, driver));
+    }
+    match reset {
+        Ok(()) => Ok(cx.undefined()),
+        Err(err) => cx.throw_error(err.to_string()),
+    }
+}
+
+/// Run `task` on the runtime, failing with `Error::Timeout` if it has not finished
+/// after `timeout`. The task runs on its own, so the timeout fires even while it is
+/// blocked in synchronous work, such as a remote manifest fetch; that work is
+/// abandoned rather than interrupted.
+pub async fn with_timeout<T: Send + 'static>(
+    timeout: Option<Duration>,
+    task: impl Future<Output = Result<T>> + Send + 'static,
+) -> Result<T> {
+    let task = network::tracked(task);
+    let Some(timeout) = timeout else {
+        return task.await;
+    };
+    let handle = tokio::spawn(task);
+    let abort = handle.abort_handle();
+    match tokio::time::timeout(timeout, handle).await {
+        Ok(result) => result?,
+        Err(_) => {
+            abort.abort();
+            Err(Error::Timeout(timeout.as_millis() as u64))
+        }
+    }
+}
+
+tokio::task_local! {
+    static BLOCKED_THREAD: ThreadId;
+}
+
+/// Run `task` for a synchronous method, which blocks the calling JS thread until
+/// it finishes. Callbacks into that thread check `blocks_thread` first, since
+/// they could never run.
+pub fn blocking<F: Future>(task: F) -> impl Future<Output = F::Output> {
+    BLOCKED_THREAD.scope(thread::current().id(), task)
+}
+
+/// Whether the current task is run by a synchronous method on `thread`.
+pub fn blocks_thread(thread: ThreadId) -> bool {
+    BLOCKED_THREAD
+        .try_with(|blocked| *blocked == thread)
+        .unwrap_or(false)
+}
+
+/// Scheduling lane of an operation. Interactive operations start right away,
+/// while batch operations share a limited number of slots so they never occupy
+/// every worker thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Priority {
+    #[default]
+    Interactive,
+    Batch,
+}
+
+impl Priority {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "interactive" => Ok(Priority::Interactive),
+            "batch" => Ok(Priority::Batch),
+            _ => Err(Error::Settings(format!(
+                "Unknown priority \"{name}\". Expected interactive or batch"
+            ))),
+        }
+    }
+}
+
+// Sized from the runtime configuration on first use.
+static BATCH_PERMITS: Mutex<Option<Arc<Semaphore>>> = Mutex::new(None);
+static BATCH_WAITING: AtomicUsize = AtomicUsize::new(0);
+
+fn batch_permits() -> Arc<Semaphore> {
+    let mut permits = BATCH_PERMITS.lock().unwrap_or_else(PoisonError::into_inner);
+    let permits = permits.get_or_insert_with(|| {
+        let config = runtime_config().unwrap_or_default();
+        let workers = match config.flavor {
+            RuntimeFlavor::MultiThread => config
+                .worker_threads
+                .unwrap_or_else(|| thread::available_parallelism().map_or(1, |count| count.get())),
+            RuntimeFlavor::CurrentThread => 1,
+        };
+        let permits = config.batch_concurrency.unwrap_or(workers / 2).max(1);
+        Arc::new(Semaphore::new(permits))
+    });
+    Arc::clone(permits)
+}
+
+/// Run `task` in its priority lane, waiting for a batch slot first if needed.
+pub async fn in_lane<T>(priority: Priority, task: impl Future<Output = T>) -> T {
+    let _permit = match priority {
+        Priority::Interactive => None,
+        Priority::Batch => {
+            BATCH_WAITING.fetch_add(1, Ordering::Relaxed);
+            let permit = batch_permits().acquire_owned().await.ok();
+            BATCH_WAITING.fetch_sub(1, Ordering::Relaxed);
+            permit
+        }
+    };
+    task.await
 }
 
-pub fn runtime() -> Arc<Runtime> {
-    RUNTIME.get_or_init(build_runtime).clone()
+/// Batch operations waiting for a slot.
+pub fn batch_waiting() -> usize {
+    BATCH_WAITING.load(Ordering::Relaxed)
 }
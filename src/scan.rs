@@ -0,0 +1,414 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::HashSet;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use c2pa::jumbf_io::load_jumbf_from_memory;
+use neon::prelude::*;
+use serde::Serialize;
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_timeout};
+
+/// The BMFF `uuid` box type for C2PA manifest stores and Merkle trees.
+pub(crate) const C2PA_UUID: [u8; 16] = [
+    0xd8, 0xfe, 0xc3, 0xd6, 0x1b, 0x0e, 0x48, 0x3c, 0x92, 0x97, 0x58, 0x28, 0x87, 0x7e, 0xc4, 0x81,
+];
+
+/// The BMFF `uuid` box type for XMP.
+const XMP_UUID: [u8; 16] = [
+    0xbe, 0x7a, 0xcf, 0xcb, 0x97, 0xa9, 0x42, 0xe8, 0x9c, 0x71, 0x99, 0x94, 0x91, 0xe3, 0xaf, 0xac,
+];
+
+/// The namespace that starts XMP in a JPEG APP1 segment.
+const JPEG_XMP_NAMESPACE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// The keyword of XMP in a PNG iTXt chunk.
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum RangeKind {
+    /// Manifest store data, such as JUMBF.
+    C2pa,
+    /// An XMP packet that refers to a manifest store.
+    Xmp,
+}
+
+/// Bytes of an asset that hold C2PA data.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ByteRange {
+    kind: RangeKind,
+    start: u64,
+    length: u64,
+    /// The structure of the format holding the data, such as a JPEG `APP11`
+    /// segment or a PNG `caBX` chunk.
+    container: &'static str,
+    /// For XMP, the manifest store URL in its `dcterms:provenance`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResult {
+    format: Option<String>,
+    ranges: Vec<ByteRange>,
+}
+
+fn read_array<const N: usize, R: Read + ?Sized>(stream: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    stream.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Up to `len` bytes from the stream.
+fn read_up_to<R: Read + ?Sized>(stream: &mut R, len: u64) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    stream.take(len).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The manifest store URL an XMP packet refers to in `dcterms:provenance`,
+/// written as an attribute or as an element.
+pub fn provenance(xmp: &[u8]) -> Option<String> {
+    let xmp = String::from_utf8_lossy(xmp);
+    let (_, rest) = xmp.split_once("dcterms:provenance")?;
+    let url = if let Some(rest) = rest.trim_start().strip_prefix('=') {
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        rest[1..].split(quote).next()?
+    } else {
+        rest.strip_prefix('>')?.split('<').next()?
+    };
+    Some(url.trim().to_string())
+}
+
+/// The label of a JUMBF superbox from the start of its bytes.
+fn jumbf_label(header: &[u8]) -> Option<&[u8]> {
+    if header.get(4..8)? != b"jumb" || header.get(12..16)? != b"jumd" {
+        return None;
+    }
+    // The label follows a 16-byte type UUID and a toggles byte.
+    let label = header.get(33..)?;
+    let end = label.iter().position(|&byte| byte == 0)?;
+    Some(&label[..end])
+}
+
+/// JUMBF in APP11 segments whose first box is labeled `c2pa`, and XMP in APP1
+/// segments, up to the start of the image data.
+fn scan_jpeg<R: Read + Seek + ?Sized>(stream: &mut R) -> Result<Vec<ByteRange>> {
+    let mut ranges = Vec::new();
+    // Segments continuing a C2PA box, by their box instance number.
+    let mut c2pa_instances = HashSet::new();
+    let mut start = 2;
+    loop {
+        stream.seek(SeekFrom::Start(start))?;
+        let Ok([0xff, marker]) = read_array::<2, _>(stream) else {
+            break;
+        };
+        match marker {
+            // Start of scan and end of image.
+            0xda | 0xd9 => break,
+            // Markers without a length.
+            0x01 | 0xd0..=0xd8 | 0xff => {
+                start += if marker == 0xff { 1 } else { 2 };
+                continue;
+            }
+            _ => {}
+        }
+        let Ok(len) = read_array::<2, _>(stream).map(u16::from_be_bytes) else {
+            break;
+        };
+        let segment_len = 2 + len as u64;
+        let data_len = (len as u64).saturating_sub(2);
+        match marker {
+            0xeb => {
+                let header = read_up_to(stream, data_len.min(64))?;
+                if header.starts_with(b"JP") && header.len() >= 8 {
+                    let instance = u16::from_be_bytes([header[2], header[3]]);
+                    let sequence = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+                    if sequence == 1 {
+                        if jumbf_label(&header[8..]) == Some(b"c2pa") {
+                            c2pa_instances.insert(instance);
+                        } else {
+                            c2pa_instances.remove(&instance);
+                        }
+                    }
+                    if c2pa_instances.contains(&instance) {
+                        ranges.push(ByteRange {
+                            kind: RangeKind::C2pa,
+                            start,
+                            length: segment_len,
+                            container: "APP11",
+                            provenance: None,
+                        });
+                    }
+                }
+            }
+            0xe1 => {
+                let data = read_up_to(stream, data_len)?;
+                if let Some(xmp) = data.strip_prefix(JPEG_XMP_NAMESPACE) {
+                    if let Some(url) = provenance(xmp) {
+                        ranges.push(ByteRange {
+                            kind: RangeKind::Xmp,
+                            start,
+                            length: segment_len,
+                            container: "APP1",
+                            provenance: Some(url),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        start += segment_len;
+    }
+    Ok(ranges)
+}
+
+/// `caBX` chunks, and XMP in `iTXt` chunks.
+fn scan_png<R: Read + Seek + ?Sized>(stream: &mut R) -> Result<Vec<ByteRange>> {
+    let mut ranges = Vec::new();
+    let mut start = 8;
+    loop {
+        stream.seek(SeekFrom::Start(start))?;
+        let Ok(header) = read_array::<8, _>(stream) else {
+            break;
+        };
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as u64;
+        let chunk_len = 12 + len;
+        match &header[4..] {
+            b"caBX" => ranges.push(ByteRange {
+                kind: RangeKind::C2pa,
+                start,
+                length: chunk_len,
+                container: "caBX",
+                provenance: None,
+            }),
+            b"iTXt" => {
+                let data = read_up_to(stream, len)?;
+                if data.starts_with(PNG_XMP_KEYWORD) {
+                    if let Some(url) = provenance(&data) {
+                        ranges.push(ByteRange {
+                            kind: RangeKind::Xmp,
+                            start,
+                            length: chunk_len,
+                            container: "iTXt",
+                            provenance: Some(url),
+                        });
+                    }
+                }
+            }
+            b"IEND" => break,
+            _ => {}
+        }
+        start += chunk_len;
+    }
+    Ok(ranges)
+}
+
+/// Top-level C2PA and XMP `uuid` boxes.
+fn scan_bmff<R: Read + Seek + ?Sized>(stream: &mut R) -> Result<Vec<ByteRange>> {
+    let end = stream.seek(SeekFrom::End(0))?;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < end {
+        stream.seek(SeekFrom::Start(start))?;
+        let Ok(header) = read_array::<8, _>(stream) else {
+            break;
+        };
+        let (header_len, box_len) =
+            match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+                0 => (8, end - start),
+                1 => match read_array::<8, _>(stream) {
+                    Ok(size) => (16, u64::from_be_bytes(size)),
+                    Err(_) => break,
+                },
+                size => (8, size as u64),
+            };
+        if box_len < header_len {
+            break;
+        }
+        if &header[4..] == b"uuid" {
+            let Ok(uuid) = read_array::<16, _>(stream) else {
+                break;
+            };
+            if uuid == C2PA_UUID {
+                ranges.push(ByteRange {
+                    kind: RangeKind::C2pa,
+                    start,
+                    length: box_len,
+                    container: "uuid",
+                    provenance: None,
+                });
+            } else if uuid == XMP_UUID {
+                let xmp = read_up_to(stream, box_len.saturating_sub(header_len + 16))?;
+                if let Some(url) = provenance(&xmp) {
+                    ranges.push(ByteRange {
+                        kind: RangeKind::Xmp,
+                        start,
+                        length: box_len,
+                        container: "uuid",
+                        provenance: Some(url),
+                    });
+                }
+            }
+        }
+        start += box_len;
+    }
+    Ok(ranges)
+}
+
+/// Top-level `C2PA` chunks, and XMP in `XMP ` or `_PMX` chunks.
+fn scan_riff<R: Read + Seek + ?Sized>(stream: &mut R) -> Result<Vec<ByteRange>> {
+    let end = stream.seek(SeekFrom::End(0))?;
+    let mut ranges = Vec::new();
+    let mut start = 12;
+    while start < end {
+        stream.seek(SeekFrom::Start(start))?;
+        let Ok(header) = read_array::<8, _>(stream) else {
+            break;
+        };
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+        // Chunks are padded to an even length.
+        let chunk_len = 8 + len + (len & 1);
+        match &header[..4] {
+            b"C2PA" => ranges.push(ByteRange {
+                kind: RangeKind::C2pa,
+                start,
+                length: chunk_len,
+                container: "C2PA",
+                provenance: None,
+            }),
+            b"XMP " | b"_PMX" => {
+                let xmp = read_up_to(stream, len)?;
+                if let Some(url) = provenance(&xmp) {
+                    ranges.push(ByteRange {
+                        kind: RangeKind::Xmp,
+                        start,
+                        length: chunk_len,
+                        container: if &header[..4] == b"XMP " {
+                            "XMP "
+                        } else {
+                            "_PMX"
+                        },
+                        provenance: Some(url),
+                    });
+                }
+            }
+            _ => {}
+        }
+        start += chunk_len;
+    }
+    Ok(ranges)
+}
+
+/// For other formats, the manifest store as c2pa-rs reads it, found in the
+/// asset's bytes. Formats that split it up or encode it, such as GIF and SVG,
+/// can't be scanned this way.
+fn scan_other<R: Read + Seek + ?Sized>(stream: &mut R, format: &str) -> Result<Vec<ByteRange>> {
+    let mut data = Vec::new();
+    stream.rewind()?;
+    stream.read_to_end(&mut data)?;
+    let store = match load_jumbf_from_memory(format, &data) {
+        Ok(store) => store,
+        Err(c2pa::Error::JumbfNotFound) => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    if store.is_empty() {
+        return Ok(Vec::new());
+    }
+    let start = data
+        .windows(store.len())
+        .position(|window| window == store.as_slice())
+        .ok_or_else(|| {
+            Error::Reading(format!(
+                "The manifest store in this {format} asset is not stored as-is, so its bytes can't be located"
+            ))
+        })?;
+    Ok(vec![ByteRange {
+        kind: RangeKind::C2pa,
+        start: start as u64,
+        length: store.len() as u64,
+        container: "jumbf",
+        provenance: None,
+    }])
+}
+
+impl ScanResult {
+    /// Scan a stream for C2PA data, telling formats apart by their leading
+    /// bytes rather than by `format`.
+    pub fn scan<R: Read + Seek + ?Sized>(stream: &mut R, format: Option<String>) -> Result<Self> {
+        stream.rewind()?;
+        let header = read_up_to(stream, 12)?;
+        let ranges = if header.starts_with(&[0xff, 0xd8]) {
+            scan_jpeg(stream)?
+        } else if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+            scan_png(stream)?
+        } else if header.starts_with(b"RIFF") {
+            scan_riff(stream)?
+        } else if header.get(4..8) == Some(b"ftyp") {
+            scan_bmff(stream)?
+        } else {
+            let format = format
+                .as_deref()
+                .ok_or_else(|| Error::Reading("Source asset must have a mime type".to_string()))?;
+            scan_other(stream, format)?
+        };
+        Ok(Self { format, ranges })
+    }
+
+    /// The manifest store URL of the first XMP packet that refers to one.
+    pub fn provenance(&self) -> Option<&str> {
+        self.ranges
+            .iter()
+            .find(|range| range.kind == RangeKind::Xmp)
+            .and_then(|range| range.provenance.as_deref())
+    }
+}
+
+/// The byte ranges of an asset that hold manifest store data or XMP referring
+/// to a manifest store, as JSON. Nothing is validated.
+pub fn scan_c2pa_ranges(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let timeout = parse_timeout(&mut cx, 1).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let result = ScanResult::scan(&mut stream, format)?;
+            Ok(serde_json::to_string(&result)?)
+        });
+        let result: Result<String> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(json) => Ok(cx.string(json)),
+            Err(err) => as_js_error(&mut cx, "scanC2paRanges", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
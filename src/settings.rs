@@ -0,0 +1,590 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use c2pa::settings::Trust;
+use c2pa::Settings;
+use neon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use tokio::task::JoinHandle;
+
+use crate::env_config;
+use crate::error::{as_js_error, as_js_error_fn, catch_panic, Error, Result};
+use crate::instance::instance_id;
+use crate::js_value::from_js;
+use crate::network::{fetch_text, is_offline, set_offline, set_proxy, NetworkOptions, ProxyConfig};
+use crate::network_cache::{self, Resource};
+use crate::runtime::runtime;
+
+/// Trust list values. Depending on context these are either the locations to
+/// load from (file paths or HTTPS URLs) or the loaded contents.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct TrustLists {
+    pub trust_anchors: Option<String>,
+    pub user_anchors: Option<String>,
+    pub allowed_list: Option<String>,
+    pub trust_config: Option<String>,
+}
+
+impl TrustLists {
+    pub(crate) fn apply(&self, trust: &mut Trust) {
+        if let Some(trust_anchors) = &self.trust_anchors {
+            trust.trust_anchors = Some(trust_anchors.clone());
+        }
+        if let Some(user_anchors) = &self.user_anchors {
+            trust.user_anchors = Some(user_anchors.clone());
+        }
+        if let Some(allowed_list) = &self.allowed_list {
+            trust.allowed_list = Some(allowed_list.clone());
+        }
+        if let Some(trust_config) = &self.trust_config {
+            trust.trust_config = Some(trust_config.clone());
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct TrustSourceConfig {
+    #[serde(default)]
+    pub trust: TrustLists,
+    #[serde(default)]
+    pub cawg_trust: TrustLists,
+    /// Reload the sources on this interval. No refresh when unset.
+    pub refresh_interval_secs: Option<u64>,
+    /// Keep verified downloads in this directory. No cache when unset.
+    pub cache_dir: Option<PathBuf>,
+    /// Use cached downloads younger than this instead of downloading again.
+    /// Without it, the cache is only used when a download fails.
+    pub cache_ttl_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LoadedTrust {
+    trust: TrustLists,
+    cawg_trust: TrustLists,
+}
+
+// Trust lists loaded by `configureTrustSources`, applied to every Context built afterwards.
+static LOADED_TRUST: RwLock<Option<LoadedTrust>> = RwLock::new(None);
+static TRUST_REFRESH: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+struct SettingsListener {
+    id: u64,
+    instance: u64,
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+}
+
+// Callbacks registered with `onSettingsChange`.
+static SETTINGS_LISTENERS: Mutex<Vec<SettingsListener>> = Mutex::new(Vec::new());
+static NEXT_LISTENER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Drop the listeners registered by an instance that is being unloaded.
+pub(crate) fn remove_instance_listeners(instance: u64) {
+    if let Ok(mut listeners) = SETTINGS_LISTENERS.lock() {
+        listeners.retain(|listener| listener.instance != instance);
+    }
+}
+
+/// Tell every registered listener that the process-wide settings changed.
+fn notify_settings_change(reason: &'static str) {
+    let Ok(listeners) = SETTINGS_LISTENERS.lock() else {
+        return;
+    };
+    for listener in listeners.iter() {
+        let callback = Arc::clone(&listener.callback);
+        // The listener's thread may have exited; its listeners are removed then.
+        let _ = listener.channel.try_send(move |mut cx| {
+            let reason = cx.string(reason);
+            callback
+                .to_inner(&mut cx)
+                .call_with(&cx)
+                .arg(reason)
+                .exec(&mut cx)
+        });
+    }
+}
+
+/// Trust lists downloaded over HTTPS, cached on disk once they have been
+/// verified, so a restart or a failed download can use the last good copy.
+struct TrustCache<'a> {
+    dir: &'a Path,
+    ttl: Option<Duration>,
+}
+
+impl TrustCache<'_> {
+    fn path(&self, url: &str) -> PathBuf {
+        let name: String = url
+            .trim_start_matches("https://")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(name)
+    }
+
+    /// The cached copy of `url`, if it is younger than the TTL.
+    fn fresh(&self, url: &str) -> Option<String> {
+        let path = self.path(url);
+        let age = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+        if age < self.ttl? {
+            std::fs::read_to_string(path).ok()
+        } else {
+            None
+        }
+    }
+
+    /// The cached copy of `url`, however old.
+    fn any(&self, url: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(url)).ok()
+    }
+
+    /// Replace the cached copy of `url`. The file is swapped in whole, so other
+    /// processes sharing the directory never read a partial list.
+    fn store(&self, url: &str, contents: &str) -> Result<()> {
+        std::fs::create_dir_all(self.dir)?;
+        let mut file = NamedTempFile::new_in(self.dir)?;
+        file.write_all(contents.as_bytes())?;
+        file.persist(self.path(url)).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+/// Where a configuration loads its trust lists from, and the downloads made
+/// while loading them.
+/// Whether a trust list source is the list itself rather than where to load it
+/// from: PEM, or anything spanning several lines, like an EKU list.
+pub(crate) fn is_inline_trust_list(source: &str) -> bool {
+    source.contains("-----BEGIN") || source.contains('\n')
+}
+
+struct TrustLoader<'a> {
+    cache: Option<TrustCache<'a>>,
+    downloads: Vec<(String, String)>,
+}
+
+impl<'a> TrustLoader<'a> {
+    fn new(config: &'a TrustSourceConfig) -> Self {
+        let cache = config.cache_dir.as_deref().map(|dir| TrustCache {
+            dir,
+            ttl: config.cache_ttl_secs.map(Duration::from_secs),
+        });
+        TrustLoader {
+            cache,
+            downloads: Vec::new(),
+        }
+    }
+
+    async fn load_source(&mut self, source: &str) -> Result<String> {
+        if is_inline_trust_list(source) {
+            Ok(source.to_string())
+        } else if source.starts_with("https://") {
+            if let Some(contents) = self.cache.as_ref().and_then(|cache| cache.fresh(source)) {
+                return Ok(contents);
+            }
+            if let Some(contents) = network_cache::get(Resource::Trust, source)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+            {
+                return Ok(contents);
+            }
+            match fetch_text(source).await {
+                Ok(contents) => {
+                    self.downloads.push((source.to_string(), contents.clone()));
+                    Ok(contents)
+                }
+                Err(e) => match self.cache.as_ref().and_then(|cache| cache.any(source)) {
+                    Some(contents) => {
+                        log::warn!("Using the cached trust list for {source}: {e}");
+                        Ok(contents)
+                    }
+                    None => Err(match e {
+                        Error::Offline(_) => e,
+                        e => Error::Settings(format!(
+                            "Failed to fetch trust list from {source}: {e}"
+                        )),
+                    }),
+                },
+            }
+        } else if source.starts_with("http://") {
+            Err(Error::Settings(format!(
+                "Trust lists must be fetched over HTTPS: {source}"
+            )))
+        } else {
+            std::fs::read_to_string(source)
+                .map_err(|e| Error::Settings(format!("Failed to read trust list {source}: {e}")))
+        }
+    }
+
+    async fn load_optional(&mut self, source: &Option<String>) -> Result<Option<String>> {
+        match source {
+            Some(source) => self.load_source(source).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn load_lists(&mut self, sources: &TrustLists) -> Result<TrustLists> {
+        Ok(TrustLists {
+            trust_anchors: self.load_optional(&sources.trust_anchors).await?,
+            user_anchors: self.load_optional(&sources.user_anchors).await?,
+            allowed_list: self.load_optional(&sources.allowed_list).await?,
+            trust_config: self.load_optional(&sources.trust_config).await?,
+        })
+    }
+}
+
+/// Load every configured source and check that the result is accepted by c2pa-rs,
+/// so a bad download never replaces a working trust list. Downloads are cached
+/// only after they pass that check.
+async fn load_trust(config: &TrustSourceConfig) -> Result<LoadedTrust> {
+    let mut loader = TrustLoader::new(config);
+    let loaded = LoadedTrust {
+        trust: loader.load_lists(&config.trust).await?,
+        cawg_trust: loader.load_lists(&config.cawg_trust).await?,
+    };
+    let check = serde_json::json!({
+        "trust": &loaded.trust,
+        "cawg_trust": &loaded.cawg_trust,
+    });
+    Settings::default()
+        .update_from_str(&check.to_string(), "json")
+        .map_err(|e| Error::Settings(format!("Invalid trust list: {e}")))?;
+    for (url, contents) in &loader.downloads {
+        network_cache::put(Resource::Trust, url, contents.as_bytes());
+    }
+    if let Some(cache) = &loader.cache {
+        for (url, contents) in &loader.downloads {
+            // A cache that cannot be written only costs a download next time.
+            if let Err(e) = cache.store(url, contents) {
+                log::warn!("Failed to cache the trust list for {url}: {e}");
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+/// Replace the loaded trust lists, returning whether they changed.
+fn install_trust(loaded: Option<LoadedTrust>) -> Result<bool> {
+    let mut current = LOADED_TRUST.write()?;
+    let changed = *current != loaded;
+    *current = loaded;
+    Ok(changed)
+}
+
+fn replace_refresh_task(task: Option<JoinHandle<()>>) -> Result<()> {
+    let mut current = TRUST_REFRESH.lock()?;
+    if let Some(previous) = current.take() {
+        previous.abort();
+    }
+    *current = task;
+    Ok(())
+}
+
+/// Stop refreshing trust lists, keeping the ones loaded last.
+pub(crate) fn stop_trust_refresh() -> Result<()> {
+    replace_refresh_task(None)
+}
+
+fn spawn_refresh(config: TrustSourceConfig, interval_secs: u64) -> Result<JoinHandle<()>> {
+    Ok(runtime()?.spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs.max(1)));
+        // The first tick completes immediately and the lists were just loaded.
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            // Keep serving the previous lists if a refresh fails.
+            if let Ok(loaded) = load_trust(&config).await {
+                if let Ok(true) = install_trust(Some(loaded)) {
+                    notify_settings_change("trustSourcesRefreshed");
+                }
+            }
+        }
+    }))
+}
+
+/// Common verification toggles for a single read, layered over the settings
+/// of that call. Unset fields keep their configured values.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct VerifyOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ocsp_fetch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_manifest_fetch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_trust: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_timestamp_trust: Option<bool>,
+    /// End-entity certificates, as PEM or SHA-256 hashes, that are the only
+    /// ones trusted for this call. Not part of the `verify` section.
+    #[serde(default, skip_serializing)]
+    pub allowed_certificates: Option<Vec<String>>,
+    /// Timeouts and retries for the requests of this call, over those set with
+    /// `configureNetwork`. Not part of the `verify` section.
+    #[serde(default, skip_serializing)]
+    pub network: Option<NetworkOptions>,
+}
+
+impl VerifyOptions {
+    // Some of these fields are not public on `Verify`, so go through the settings parser.
+    pub fn apply(&self, settings: &mut Settings) -> Result<()> {
+        let mut verify = self.clone();
+        if let Some(certificates) = &self.allowed_certificates {
+            // Only the listed certificates are trusted, whatever the anchors.
+            settings.trust.trust_anchors = None;
+            settings.trust.user_anchors = None;
+            settings.trust.allowed_list = allowed_list(certificates)?;
+            verify.verify_trust.get_or_insert(true);
+        }
+        let overlay = serde_json::json!({ "verify": verify });
+        settings
+            .update_from_str(&overlay.to_string(), "json")
+            .map_err(|e| Error::Settings(e.to_string()))
+    }
+}
+
+/// The c2pa-rs allowed list for `certificates`: PEM blocks as they are, and
+/// SHA-256 hashes of the DER as base64, one per line. Hex hashes are
+/// converted to base64. None when there are no certificates, which c2pa-rs
+/// doesn't accept as a list.
+fn allowed_list(certificates: &[String]) -> Result<Option<String>> {
+    let entries = certificates
+        .iter()
+        .map(|certificate| {
+            let certificate = certificate.trim();
+            if certificate.contains("-----BEGIN") {
+                return Ok(certificate.to_string());
+            }
+            let hash = if certificate.len() == 64 {
+                (0..64)
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&certificate[i..i + 2], 16))
+                    .collect::<std::result::Result<Vec<u8>, _>>()
+                    .ok()
+            } else {
+                BASE64_STANDARD.decode(certificate).ok()
+            };
+            match hash {
+                Some(hash) if hash.len() == 32 => Ok(BASE64_STANDARD.encode(hash)),
+                _ => Err(Error::Settings(format!(
+                    "Allowed certificates must be PEM or SHA-256 hashes, got {certificate}"
+                ))),
+            }
+        })
+        .collect::<Result<Vec<String>>>()?;
+    Ok((!entries.is_empty()).then(|| entries.join("\n")))
+}
+
+/// Resolve the settings a Reader or Builder would run with: the c2pa-rs
+/// defaults, then the `C2PA_*` environment variables, then any trust lists
+/// from `configureTrustSources`, then the optional JSON or TOML overrides.
+pub fn resolve_settings(overrides: Option<&str>) -> Result<Settings> {
+    let mut settings = Settings::default();
+    env_config::apply(&mut settings)?;
+    if let Some(loaded) = LOADED_TRUST.read()?.as_ref() {
+        loaded.trust.apply(&mut settings.trust);
+        loaded.cawg_trust.apply(&mut settings.cawg_trust);
+    }
+    if let Some(overrides) = overrides {
+        settings
+            .update_from_str(overrides, "json")
+            .or_else(|_| settings.update_from_str(overrides, "toml"))
+            .map_err(|e| Error::Settings(e.to_string()))?;
+    }
+    Ok(settings)
+}
+
+/// Serialize resolved settings for display. Signer sections are dropped since
+/// they can carry private keys.
+pub fn settings_to_json(settings: &Settings) -> Result<String> {
+    let mut settings = settings.clone();
+    settings.signer = None;
+    settings.cawg_x509_signer = None;
+    Ok(serde_json::to_string(&settings)?)
+}
+
+/// Return the effective settings as a JSON string. Takes the same optional
+/// settings argument accepted by the Reader and Builder constructors.
+pub fn get_settings_json(mut cx: FunctionContext) -> JsResult<JsString> {
+    let overrides = match cx.argument_opt(0) {
+        Some(value) if value.is_a::<JsString, _>(&mut cx) => Some(
+            value
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx),
+        ),
+        Some(value)
+            if value.is_a::<JsNull, _>(&mut cx) || value.is_a::<JsUndefined, _>(&mut cx) =>
+        {
+            None
+        }
+        Some(value) if value.is_a::<JsObject, _>(&mut cx) => {
+            Some(from_js(&mut cx, value)?.to_string())
+        }
+        Some(_) => return cx.throw_error("Settings must be a string, object, null, or undefined"),
+        None => None,
+    };
+
+    match resolve_settings(overrides.as_deref()).and_then(|s| settings_to_json(&s)) {
+        Ok(json) => Ok(cx.string(json)),
+        Err(err) => {
+            as_js_error_fn(&mut cx, "getEffectiveSettings", err).and_then(|err| cx.throw(err))
+        }
+    }
+}
+
+/// Load trust lists from file paths, HTTPS URLs, or the lists themselves and
+/// apply them to every Reader and Builder created afterwards, optionally
+/// refreshing on an interval.
+pub fn configure_trust_sources(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let config_json = cx.argument::<JsString>(0)?.value(&mut cx);
+    let config: TrustSourceConfig = serde_json::from_str(&config_json)
+        .or_else(|err| cx.throw_error(format!("Invalid trust source configuration: {err}")))?;
+
+    let (deferred, promise) = cx.promise();
+    rt.spawn(async move {
+        let result = catch_panic(async {
+            let loaded = load_trust(&config).await?;
+            if install_trust(Some(loaded))? {
+                notify_settings_change("trustSourcesConfigured");
+            }
+            let task = config
+                .refresh_interval_secs
+                .map(|secs| spawn_refresh(config.clone(), secs))
+                .transpose()?;
+            replace_refresh_task(task)
+        })
+        .await;
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(_) => Ok(cx.undefined()),
+            Err(err) => {
+                as_js_error(&mut cx, "configureTrustSources", err).and_then(|err| cx.throw(err))
+            }
+        });
+    });
+    Ok(promise)
+}
+
+/// Stop refreshing and drop any trust lists loaded by `configureTrustSources`.
+pub fn clear_trust_sources(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    match replace_refresh_task(None).and_then(|_| install_trust(None)) {
+        Ok(changed) => {
+            if changed {
+                notify_settings_change("trustSourcesCleared");
+            }
+            Ok(cx.undefined())
+        }
+        Err(err) => as_js_error_fn(&mut cx, "clearTrustSources", err).and_then(|err| cx.throw(err)),
+    }
+}
+
+/// Register a callback that is called with the reason whenever the process-wide
+/// settings change. Returns an id for `offSettingsChange`.
+pub fn on_settings_change(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let mut channel = cx.channel();
+    // Listeners should not keep the process alive.
+    channel.unref(&mut cx);
+
+    let id = NEXT_LISTENER_ID.fetch_add(1, Ordering::Relaxed);
+    let listener = SettingsListener {
+        id,
+        instance: instance_id(&mut cx),
+        channel,
+        callback: Arc::new(callback),
+    };
+    match SETTINGS_LISTENERS.lock() {
+        Ok(mut listeners) => listeners.push(listener),
+        Err(err) => {
+            return as_js_error_fn(&mut cx, "onSettingsChange", err.into())
+                .and_then(|err| cx.throw(err));
+        }
+    }
+    Ok(cx.number(id as f64))
+}
+
+/// Remove a callback registered with `onSettingsChange` on this thread.
+pub fn off_settings_change(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let id = cx.argument::<JsNumber>(0)?.value(&mut cx) as u64;
+    let instance = instance_id(&mut cx);
+    match SETTINGS_LISTENERS.lock() {
+        Ok(mut listeners) => {
+            listeners.retain(|listener| listener.id != id || listener.instance != instance);
+            Ok(cx.undefined())
+        }
+        Err(err) => {
+            as_js_error_fn(&mut cx, "onSettingsChange", err.into()).and_then(|err| cx.throw(err))
+        }
+    }
+}
+
+/// Turn offline mode on or off. While on, no network requests are made: remote
+/// manifests, OCSP, timestamping, trust list and model downloads all fail with
+/// an `Offline` error instead.
+pub fn set_offline_mode(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let offline = cx.argument::<JsBoolean>(0)?.value(&mut cx);
+    if set_offline(offline) {
+        notify_settings_change("offlineModeChanged");
+    }
+    Ok(cx.undefined())
+}
+
+pub fn is_offline_mode(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    Ok(cx.boolean(is_offline()))
+}
+
+/// Route requests made by the native layer (remote manifests, OCSP, timestamps,
+/// trust lists) through an HTTP proxy. Without one, the standard `HTTP_PROXY`,
+/// `HTTPS_PROXY`, `ALL_PROXY`, and `NO_PROXY` environment variables apply.
+pub fn configure_proxy(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let config_json = cx.argument::<JsString>(0)?.value(&mut cx);
+    let config: ProxyConfig = serde_json::from_str(&config_json)
+        .or_else(|err| cx.throw_error(format!("Invalid proxy configuration: {err}")))?;
+    match set_proxy(Some(config)) {
+        Ok(changed) => {
+            if changed {
+                notify_settings_change("proxyChanged");
+            }
+            Ok(cx.undefined())
+        }
+        Err(err) => as_js_error_fn(&mut cx, "configureProxy", err).and_then(|err| cx.throw(err)),
+    }
+}
+
+/// Remove the proxy set by `configureProxy`, falling back to the environment variables.
+pub fn clear_proxy(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    match set_proxy(None) {
+        Ok(changed) => {
+            if changed {
+                notify_settings_change("proxyChanged");
+            }
+            Ok(cx.undefined())
+        }
+        Err(err) => as_js_error_fn(&mut cx, "clearProxy", err).and_then(|err| cx.throw(err)),
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use c2pa::Reader;
+
+/// A shared Reader, with its approximate native memory in bytes as measured
+/// when it was shared, so handles can count it without taking the lock.
+type Shared = (Arc<tokio::sync::Mutex<Reader>>, u64);
+
+/// Readers shared with `Reader.share`, by key. The native library is loaded
+/// once per process, so every worker thread sees the same registry.
+static SHARED: Mutex<Option<HashMap<String, Shared>>> = Mutex::new(None);
+
+/// Register `reader`, which holds about `bytes` of native memory, and return
+/// the key other threads open it with.
+pub fn share(reader: Arc<tokio::sync::Mutex<Reader>>, bytes: u64) -> String {
+    let key = format!("{:032x}", rand::random::<u128>());
+    if let Ok(mut shared) = SHARED.lock() {
+        shared
+            .get_or_insert_with(HashMap::new)
+            .insert(key.clone(), (reader, bytes));
+    }
+    key
+}
+
+/// The Reader registered under `key` and its size, if it has not been released.
+pub fn get(key: &str) -> Option<Shared> {
+    SHARED.lock().ok()?.as_ref()?.get(key).cloned()
+}
+
+/// Remove `key` from the registry. Handles already opened from it keep the
+/// Reader alive until they are disposed or garbage collected.
+pub fn release(key: &str) -> bool {
+    SHARED
+        .lock()
+        .ok()
+        .and_then(|mut shared| shared.as_mut()?.remove(key))
+        .is_some()
+}
@@ -0,0 +1,167 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+use c2pa::Builder;
+use neon::prelude::*;
+
+use crate::asset::Asset;
+use crate::error::{as_js_error_fn, Error};
+
+/// Extension of sidecar files when none is given.
+const DEFAULT_EXTENSION: &str = "c2pa";
+
+/// Where to write the manifest store of a sign call as a sidecar file, from the
+/// `sidecar` option of its destination asset.
+pub struct Sidecar {
+    path: PathBuf,
+    overwrite: bool,
+    embed: bool,
+}
+
+impl Sidecar {
+    /// Parse the `sidecar` option of the destination `obj`. The sidecar is named
+    /// after the destination file unless a `fileName` is given, and an existing
+    /// sidecar is rejected before signing unless `overwrite` is set.
+    pub fn from_destination<'a>(
+        cx: &mut FunctionContext<'a>,
+        obj: Handle<'a, JsObject>,
+        output: &Asset,
+        operation: &str,
+    ) -> NeonResult<Option<Self>> {
+        let Some(options) = obj.get_opt::<JsObject, _, _>(cx, "sidecar")? else {
+            return Ok(None);
+        };
+        let directory = options
+            .get_opt::<JsString, _, _>(cx, "directory")?
+            .map(|val| val.value(cx));
+        let file_name = options
+            .get_opt::<JsString, _, _>(cx, "fileName")?
+            .map(|val| val.value(cx));
+        let extension = options
+            .get_opt::<JsString, _, _>(cx, "extension")?
+            .map(|val| val.value(cx));
+        let overwrite = options
+            .get_opt::<JsBoolean, _, _>(cx, "overwrite")?
+            .is_some_and(|val| val.value(cx));
+        let embed = options
+            .get_opt::<JsBoolean, _, _>(cx, "embed")?
+            .is_some_and(|val| val.value(cx));
+
+        let output_path = match output {
+            Asset::File(path, _) => Some(Path::new(path)),
+            _ => None,
+        };
+        let file_name = match (file_name, output_path.and_then(Path::file_stem)) {
+            (Some(file_name), _) => file_name,
+            (None, Some(stem)) => {
+                let extension = extension.as_deref().unwrap_or(DEFAULT_EXTENSION);
+                format!(
+                    "{}.{}",
+                    stem.to_string_lossy(),
+                    extension.trim_start_matches('.')
+                )
+            }
+            (None, None) => {
+                return cx
+                    .throw_error("A sidecar fileName is required unless the destination is a file")
+            }
+        };
+        if Path::new(&file_name).file_name() != Some(file_name.as_ref()) {
+            return cx.throw_error("The sidecar fileName must not include a directory");
+        }
+        let directory = directory
+            .map(PathBuf::from)
+            .or_else(|| output_path.and_then(Path::parent).map(Path::to_path_buf))
+            .unwrap_or_default();
+        let path = directory.join(file_name);
+
+        if output_path == Some(path.as_path()) {
+            return cx.throw_error("The sidecar must not replace the destination file");
+        }
+        if !overwrite && path.exists() {
+            let err = Error::SidecarExists(path.to_string_lossy().into_owned());
+            let err = as_js_error_fn(cx, operation, err)?;
+            return cx.throw(err);
+        }
+        let path_value = cx.string(path.to_string_lossy());
+        options.set(cx, "path", path_value)?;
+        Ok(Some(Self {
+            path,
+            overwrite,
+            embed,
+        }))
+    }
+
+    /// Write the manifest store returned by signing.
+    pub fn write(&self, manifest: &[u8]) -> Result<(), Error> {
+        let mut options = OpenOptions::new();
+        if self.overwrite {
+            options.write(true).create(true).truncate(true);
+        } else {
+            // Another call may have written it since the check before signing.
+            options.write(true).create_new(true);
+        }
+        let mut file = options.open(&self.path).map_err(|err| match err.kind() {
+            io::ErrorKind::AlreadyExists => {
+                Error::SidecarExists(self.path.to_string_lossy().into_owned())
+            }
+            _ => Error::from(err),
+        })?;
+        file.write_all(manifest)?;
+        Ok(())
+    }
+}
+
+/// A locked Builder that embeds the manifest store only if the sidecar asks for
+/// it. The Builder's own `no_embed` setting is restored when this is dropped,
+/// including when signing times out.
+pub struct Embedding<B: DerefMut<Target = Builder>> {
+    builder: B,
+    no_embed: bool,
+}
+
+pub fn embedding<B: DerefMut<Target = Builder>>(
+    sidecar: Option<&Sidecar>,
+    mut builder: B,
+) -> Embedding<B> {
+    let no_embed = builder.no_embed;
+    if let Some(sidecar) = sidecar {
+        builder.no_embed = !sidecar.embed;
+    }
+    Embedding { builder, no_embed }
+}
+
+impl<B: DerefMut<Target = Builder>> Deref for Embedding<B> {
+    type Target = Builder;
+
+    fn deref(&self) -> &Builder {
+        &self.builder
+    }
+}
+
+impl<B: DerefMut<Target = Builder>> DerefMut for Embedding<B> {
+    fn deref_mut(&mut self) -> &mut Builder {
+        &mut self.builder
+    }
+}
+
+impl<B: DerefMut<Target = Builder>> Drop for Embedding<B> {
+    fn drop(&mut self) {
+        self.builder.no_embed = self.no_embed;
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Number of leading bytes inspected when detecting a format.
+const SNIFF_LEN: usize = 512;
+
+/// Detect the MIME type of a stream from its leading bytes, for the formats
+/// c2pa-rs supports. The stream is rewound afterwards.
+pub fn sniff_mime_type<R: Read + Seek + ?Sized>(
+    stream: &mut R,
+) -> io::Result<Option<&'static str>> {
+    let mut header = Vec::with_capacity(SNIFF_LEN);
+    (&mut *stream)
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut header)?;
+    stream.seek(SeekFrom::Start(0))?;
+    Ok(mime_type_from_header(&header))
+}
+
+fn mime_type_from_header(header: &[u8]) -> Option<&'static str> {
+    match header {
+        [0xff, 0xd8, 0xff, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some("image/gif"),
+        [b'I', b'I', 0x2a | 0x2b, 0x00, ..] | [b'M', b'M', 0x00, 0x2a | 0x2b, ..] => {
+            Some(tiff_mime_type(header))
+        }
+        [b'R', b'I', b'F', b'F', _, _, _, _, form @ ..] => match form.get(..4)? {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            b"AVI " => Some("video/avi"),
+            _ => None,
+        },
+        [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..] => bmff_mime_type(brand.get(..4)?),
+        [b'%', b'P', b'D', b'F', b'-', ..] => Some("application/pdf"),
+        // Fonts are detected so they are reported as unsupported rather than
+        // as having no MIME type; c2pa-rs has no font handler yet.
+        [0x00, 0x01, 0x00, 0x00, ..] | [b't', b'r', b'u', b'e', ..] => Some("font/ttf"),
+        [b'O', b'T', b'T', b'O', ..] => Some("font/otf"),
+        [b'w', b'O', b'F', b'F', ..] => Some("font/woff"),
+        [b'w', b'O', b'F', b'2', ..] => Some("font/woff2"),
+        // Likewise for ZIP containers such as Office documents.
+        [b'P', b'K', 0x03, 0x04, ..] => Some(zip_mime_type(header)),
+        // And for MPEG transport streams, with or without the 4-byte timestamps
+        // that Blu-ray and AVCHD add to each packet.
+        _ if has_sync_bytes(header, 0, 188) || has_sync_bytes(header, 4, 192) => Some("video/mp2t"),
+        [b'I', b'D', b'3', ..] => Some("audio/mpeg"),
+        // MPEG audio frame sync for files without an ID3 tag.
+        [0xff, second, ..] if second & 0xe0 == 0xe0 => Some("audio/mpeg"),
+        _ => svg_mime_type(header),
+    }
+}
+
+/// Office Open XML documents, told apart by the parts named in their first local
+/// file headers. Other ZIP files are reported as plain ZIP.
+fn zip_mime_type(header: &[u8]) -> &'static str {
+    let has_part = |prefix: &[u8]| header.windows(prefix.len()).any(|name| name == prefix);
+    if has_part(b"word/") {
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    } else if has_part(b"ppt/") {
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+    } else if has_part(b"xl/") {
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    } else {
+        "application/zip"
+    }
+}
+
+/// Whether the header holds three transport stream packets of `packet_len` bytes,
+/// each starting with the 0x47 sync byte at `offset`.
+fn has_sync_bytes(header: &[u8], offset: usize, packet_len: usize) -> bool {
+    (0..3).all(|packet| header.get(offset + packet * packet_len) == Some(&0x47))
+}
+
+/// DNG files are TIFF files with a DNGVersion tag in their first IFD.
+const DNG_VERSION_TAG: u16 = 0xc612;
+
+/// Tell DNG files apart from other TIFF files, when the first IFD falls within
+/// the header. Camera raw formats c2pa-rs handles the same way, like ARW and
+/// NEF, are only recognized by their file extension.
+fn tiff_mime_type(header: &[u8]) -> &'static str {
+    let big_endian = header[0] == b'M';
+    let read = |offset: usize, len: usize| -> Option<u64> {
+        let bytes = header.get(offset..offset.checked_add(len)?)?;
+        let fold = |value, byte: &u8| value << 8 | u64::from(*byte);
+        Some(if big_endian {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        })
+    };
+    // BigTIFF uses 8-byte offsets and counts, and 20-byte entries. The offset
+    // of the first IFD directly follows the header either way.
+    let (offset_len, count_len, entry_len) = if read(2, 2) == Some(0x2b) {
+        (8, 8, 20)
+    } else {
+        (4, 2, 12)
+    };
+    let is_dng = (|| {
+        let ifd = usize::try_from(read(offset_len, offset_len)?).ok()?;
+        let count = usize::try_from(read(ifd, count_len)?).ok()?;
+        let entries = ifd + count_len;
+        Some(
+            (0..count)
+                .map_while(|index| read(entries + index * entry_len, 2))
+                .any(|tag| tag == u64::from(DNG_VERSION_TAG)),
+        )
+    })();
+    if is_dng == Some(true) {
+        "image/x-adobe-dng"
+    } else {
+        "image/tiff"
+    }
+}
+
+fn bmff_mime_type(brand: &[u8]) -> Option<&'static str> {
+    match brand {
+        b"avif" | b"avis" => Some("image/avif"),
+        b"heic" | b"heix" | b"heim" | b"heis" => Some("image/heic"),
+        // HEVC image sequences, such as bursts.
+        b"hevc" | b"hevx" | b"hevm" | b"hevs" => Some("image/heic"),
+        b"mif1" | b"msf1" => Some("image/heif"),
+        b"qt  " => Some("video/quicktime"),
+        b"M4A " | b"M4B " => Some("audio/mp4"),
+        b"M4V " | b"M4VH" | b"M4VP" => Some("video/x-m4v"),
+        _ => Some("video/mp4"),
+    }
+}
+
+/// Detect SVG by its root element, after any XML declaration, comments, processing
+/// instructions, and DOCTYPE. Other XML documents are not detected, since
+/// c2pa-rs would embed a manifest in them as if they were SVG.
+fn svg_mime_type(header: &[u8]) -> Option<&'static str> {
+    let text = String::from_utf8_lossy(header);
+    let mut rest = text.trim_start_matches('\u{feff}').trim_start();
+    if !rest.starts_with('<') {
+        return None;
+    }
+    loop {
+        let end = if rest.starts_with("<?") {
+            rest.find("?>").map(|end| end + 2)
+        } else if rest.starts_with("<!--") {
+            rest.find("-->").map(|end| end + 3)
+        } else if rest.starts_with("<!DOCTYPE") {
+            doctype_end(rest)
+        } else if rest.is_empty() {
+            None
+        } else {
+            break;
+        };
+        match end {
+            Some(end) => rest = rest[end..].trim_start(),
+            // The prolog runs past the header, so the root element is unknown.
+            None => return None,
+        }
+    }
+    let name = rest
+        .strip_prefix('<')?
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()?;
+    // The root element may be namespace-prefixed, like `<svg:svg>`.
+    let local_name = name.rsplit(':').next()?;
+    (local_name == "svg").then_some("image/svg+xml")
+}
+
+/// The end of a DOCTYPE declaration, past any internal subset in brackets.
+fn doctype_end(doctype: &str) -> Option<usize> {
+    let open = doctype.find(['[', '>'])?;
+    if doctype[open..].starts_with('>') {
+        return Some(open + 1);
+    }
+    let close = open + doctype[open..].find(']')?;
+    Some(close + doctype[close..].find('>')? + 1)
+}
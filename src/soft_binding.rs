@@ -0,0 +1,328 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, Read, Seek};
+use std::sync::Arc;
+
+use c2pa::assertions::{SoftBinding, SoftBindingBlock, SoftBindingScope};
+use c2pa::{AsyncSigner, Builder, Reader};
+use neon::prelude::*;
+use neon::types::buffer::TypedArray;
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+use crate::asset::{parse_asset, NeonReadStreamTrait, NeonWriteStreamTrait};
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_settings, parse_timeout};
+
+/// A soft binding algorithm implemented in JS: an embedder that watermarks an
+/// asset before it is signed, and a detector that reads the watermark back.
+#[derive(Clone)]
+pub struct NeonSoftBinding {
+    /// The algorithm's name from the C2PA soft binding algorithm list, e.g.
+    /// `com.adobe.trustmark.Q`.
+    alg: String,
+    channel: Channel,
+    // (asset: Buffer, mimeType: string) => Promise<{ asset: Buffer, value: string }>
+    embed: Option<Arc<Root<JsFunction>>>,
+    // (asset: Buffer, mimeType: string) => Promise<string | null>
+    detect: Option<Arc<Root<JsFunction>>>,
+}
+
+impl Finalize for NeonSoftBinding {}
+
+/// What a detector found in an asset, and whether the asset's manifest agrees.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SoftBindingHint {
+    alg: String,
+    /// The detected value, or None when the detector found no watermark.
+    value: Option<String>,
+    /// Whether a soft binding assertion of the active manifest has the value,
+    /// or None when the asset has no manifest to compare with. A value without
+    /// a manifest can be used to look the manifest up in a repository.
+    matches_manifest: Option<bool>,
+}
+
+fn optional_callback(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+) -> NeonResult<Option<Arc<Root<JsFunction>>>> {
+    match cx.argument_opt(arg_index) {
+        Some(value) if value.is_a::<JsFunction, _>(cx) => {
+            let callback = value.downcast_or_throw::<JsFunction, _>(cx)?;
+            Ok(Some(Arc::new(callback.root(cx))))
+        }
+        _ => Ok(None),
+    }
+}
+
+impl NeonSoftBinding {
+    pub fn new(mut cx: FunctionContext) -> JsResult<JsBox<Self>> {
+        let alg = cx.argument::<JsString>(0)?.value(&mut cx);
+        let embed = optional_callback(&mut cx, 1)?;
+        let detect = optional_callback(&mut cx, 2)?;
+        if embed.is_none() && detect.is_none() {
+            return cx.throw_type_error("A soft binding plugin needs an embed or detect callback");
+        }
+        let channel = cx.channel();
+        Ok(cx.boxed(Self {
+            alg,
+            channel,
+            embed,
+            detect,
+        }))
+    }
+
+    /// Call a plugin callback with an asset and await the promise it returns,
+    /// converting the result on the JS thread.
+    async fn call<T: Send + 'static>(
+        &self,
+        callback: &Arc<Root<JsFunction>>,
+        format: &str,
+        asset: Vec<u8>,
+        convert: fn(&mut TaskContext, Handle<JsValue>) -> NeonResult<Result<T>>,
+    ) -> Result<T> {
+        let (tx, rx) = oneshot::channel();
+        let callback = Arc::clone(callback);
+        let format = format.to_string();
+        self.channel
+            .try_send(move |mut cx| {
+                let asset = JsBuffer::from_slice(&mut cx, &asset)?;
+                let format = cx.string(format);
+                let future = callback
+                    .to_inner(&mut cx)
+                    .call_with(&cx)
+                    .arg(asset)
+                    .arg(format)
+                    .apply::<JsPromise, _>(&mut cx)?
+                    .to_future(&mut cx, move |mut cx, result| match result {
+                        Ok(value) => convert(&mut cx, value),
+                        Err(err) => {
+                            let message = err.to_string(&mut cx)?.value(&mut cx);
+                            Ok(Err(Error::SoftBinding(message)))
+                        }
+                    })?;
+                let _ = tx.send(future);
+                Ok(())
+            })
+            .map_err(|err| Error::SoftBinding(err.to_string()))?;
+        let future = rx
+            .await
+            .map_err(|err| Error::SoftBinding(err.to_string()))?;
+        future
+            .await
+            .map_err(|err| Error::SoftBinding(err.to_string()))?
+    }
+
+    /// Watermark an asset, returning the watermarked asset and the soft
+    /// binding assertion that describes it.
+    async fn embed(
+        &self,
+        embed: &Arc<Root<JsFunction>>,
+        format: &str,
+        asset: Vec<u8>,
+    ) -> Result<(Vec<u8>, SoftBinding)> {
+        let (asset, value) = self.call(embed, format, asset, embedded).await?;
+        let mut assertion = SoftBinding::default();
+        assertion.alg = Some(self.alg.clone());
+        assertion.blocks = vec![SoftBindingBlock {
+            scope: SoftBindingScope::default(),
+            value,
+        }];
+        Ok((asset, assertion))
+    }
+}
+
+fn embedded(cx: &mut TaskContext, value: Handle<JsValue>) -> NeonResult<Result<(Vec<u8>, String)>> {
+    let invalid =
+        || Error::SoftBinding("embed must resolve to { asset: Buffer, value: string }".to_string());
+    let Ok(obj) = value.downcast::<JsObject, _>(cx) else {
+        return Ok(Err(invalid()));
+    };
+    let asset = obj.get_value(cx, "asset")?;
+    let value = obj.get_value(cx, "value")?;
+    match (
+        asset.downcast::<JsBuffer, _>(cx),
+        value.downcast::<JsString, _>(cx),
+    ) {
+        (Ok(asset), Ok(value)) => Ok(Ok((asset.as_slice(cx).to_vec(), value.value(cx)))),
+        _ => Ok(Err(invalid())),
+    }
+}
+
+fn detected(cx: &mut TaskContext, value: Handle<JsValue>) -> NeonResult<Result<Option<String>>> {
+    if value.is_a::<JsNull, _>(cx) || value.is_a::<JsUndefined, _>(cx) {
+        return Ok(Ok(None));
+    }
+    match value.downcast::<JsString, _>(cx) {
+        Ok(value) => Ok(Ok(Some(value.value(cx)))),
+        Err(_) => Ok(Err(Error::SoftBinding(
+            "detect must resolve to a string or null".to_string(),
+        ))),
+    }
+}
+
+/// Sign with a Builder, first running each plugin's embedder over the source
+/// and adding a soft binding assertion for what it embedded. The assertions
+/// are removed again afterwards, so signing again embeds fresh watermarks.
+pub async fn sign_async(
+    builder: &mut Builder,
+    plugins: &[NeonSoftBinding],
+    signer: &dyn AsyncSigner,
+    format: &str,
+    source: Box<dyn NeonReadStreamTrait>,
+    dest: &mut Box<dyn NeonWriteStreamTrait>,
+) -> Result<Vec<u8>> {
+    let assertions = builder.definition.assertions.len();
+    let result = async {
+        let mut source = embed_all(plugins, builder, format, source).await?;
+        Ok(builder
+            .sign_async(signer, format, &mut source, dest)
+            .await?)
+    }
+    .await;
+    builder.definition.assertions.truncate(assertions);
+    result
+}
+
+async fn embed_all(
+    plugins: &[NeonSoftBinding],
+    builder: &mut Builder,
+    format: &str,
+    mut source: Box<dyn NeonReadStreamTrait>,
+) -> Result<Box<dyn NeonReadStreamTrait>> {
+    if plugins.iter().all(|plugin| plugin.embed.is_none()) {
+        return Ok(source);
+    }
+    let mut asset = Vec::new();
+    source.rewind()?;
+    source.read_to_end(&mut asset)?;
+    for plugin in plugins {
+        let Some(embed) = &plugin.embed else {
+            continue;
+        };
+        let (watermarked, assertion) = plugin.embed(embed, format, asset).await?;
+        asset = watermarked;
+        builder.add_assertion(SoftBinding::LABEL, &assertion)?;
+    }
+    Ok(Box::new(Cursor::new(asset)))
+}
+
+/// The soft binding values of the active manifest's soft binding assertions,
+/// by algorithm, or None when the asset has no manifest.
+async fn manifest_soft_bindings(
+    context_opt: Option<Arc<c2pa::Context>>,
+    format: &str,
+    asset: &[u8],
+) -> Result<Option<Vec<(Option<String>, String)>>> {
+    let stream = Cursor::new(asset);
+    let reader = match context_opt {
+        Some(context) => {
+            Reader::from_shared_context(&context)
+                .with_stream_async(format, stream)
+                .await
+        }
+        None => Reader::from_stream_async(format, stream).await,
+    };
+    let reader = match reader {
+        Ok(reader) => reader,
+        Err(c2pa::Error::JumbfNotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let Some(manifest) = reader.active_manifest() else {
+        return Ok(None);
+    };
+    let values = manifest
+        .assertions()
+        .iter()
+        .filter(|assertion| assertion.label() == SoftBinding::LABEL)
+        .filter_map(|assertion| assertion.to_assertion::<SoftBinding>().ok())
+        .flat_map(|binding| {
+            let alg = binding.alg;
+            binding
+                .blocks
+                .into_iter()
+                .map(move |block| (alg.clone(), block.value))
+        })
+        .collect();
+    Ok(Some(values))
+}
+
+/// Run each plugin's detector over an asset, returning JSON hints comparing
+/// what it found with the soft binding assertions of the asset's manifest.
+pub fn detect_soft_bindings(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let plugins = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+    let plugins = plugins
+        .into_iter()
+        .map(|plugin| {
+            let plugin = plugin.downcast_or_throw::<JsBox<NeonSoftBinding>, _>(&mut cx)?;
+            Ok(NeonSoftBinding::clone(&plugin))
+        })
+        .collect::<NeonResult<Vec<_>>>()?;
+    let context_opt = parse_settings(&mut cx, 2, "detectSoftBindings")
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    let timeout = parse_timeout(&mut cx, 3).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 4).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let format = format
+                .ok_or_else(|| Error::Reading("Source asset must have a mime type".to_string()))?;
+            let mut asset = Vec::new();
+            stream.read_to_end(&mut asset)?;
+            let bindings = manifest_soft_bindings(context_opt, &format, &asset).await?;
+            let mut hints = Vec::new();
+            for plugin in &plugins {
+                let Some(detect) = &plugin.detect else {
+                    continue;
+                };
+                let value = plugin
+                    .call(detect, &format, asset.clone(), detected)
+                    .await?;
+                let matches_manifest = bindings.as_ref().map(|bindings| {
+                    bindings.iter().any(|(alg, bound)| {
+                        alg.as_deref().is_none_or(|alg| alg == plugin.alg)
+                            && Some(bound) == value.as_ref()
+                    })
+                });
+                hints.push(SoftBindingHint {
+                    alg: plugin.alg.clone(),
+                    value,
+                    matches_manifest,
+                });
+            }
+            Ok(serde_json::to_string(&hints)?)
+        });
+        let result: Result<String> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(json) => Ok(cx.string(json)),
+            Err(err) => {
+                as_js_error(&mut cx, "detectSoftBindings", err).and_then(|err| cx.throw(err))
+            }
+        });
+    });
+    Ok(promise)
+}
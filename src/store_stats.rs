@@ -0,0 +1,241 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::VecDeque;
+
+use c2pa::jumbf_io::load_jumbf_from_stream;
+use neon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Error, Result};
+use crate::jumbf::{jumbf_boxes, superbox, superboxes};
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_timeout};
+use crate::validation::jumbf_labels;
+
+/// Sizes and counts for a manifest store, read from its JUMBF boxes.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStats {
+    /// The size of the manifest store in bytes.
+    total_bytes: usize,
+    manifest_count: usize,
+    assertion_count: usize,
+    active_manifest: Option<String>,
+    /// The most ingredient links between the active manifest and another
+    /// manifest in the store.
+    ingredient_depth: usize,
+    /// In the order they are stored, which ends with the active manifest.
+    manifests: Vec<ManifestStats>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestStats {
+    label: String,
+    bytes: usize,
+    claim_bytes: usize,
+    signature_bytes: usize,
+    assertion_count: usize,
+    assertion_bytes: usize,
+    ingredient_count: usize,
+    /// Ingredient links from the active manifest, or None when the active
+    /// manifest doesn't lead to this one.
+    depth: Option<usize>,
+    assertions: Vec<BoxStats>,
+    /// Thumbnails and other binary data, such as icons in data boxes.
+    resources: Vec<BoxStats>,
+    /// The labels of the manifests of this manifest's ingredients.
+    #[serde(skip)]
+    ingredient_manifests: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct BoxStats {
+    label: String,
+    bytes: usize,
+}
+
+/// The links of an ingredient assertion to the ingredient's manifest. Version
+/// 3 ingredients call it `activeManifest`, earlier versions `c2pa_manifest`.
+#[derive(Deserialize)]
+struct IngredientLinks {
+    c2pa_manifest: Option<HashedUrl>,
+    #[serde(rename = "activeManifest")]
+    active_manifest: Option<HashedUrl>,
+}
+
+#[derive(Deserialize)]
+struct HashedUrl {
+    url: String,
+}
+
+/// The label of the manifest an ingredient assertion links to.
+fn ingredient_manifest(assertion: &[u8]) -> Option<String> {
+    let cbor = jumbf_boxes(assertion).find(|jumbf_box| jumbf_box.kind == b"cbor")?;
+    let links: IngredientLinks = ciborium::from_reader(cbor.payload).ok()?;
+    let url = links.active_manifest.or(links.c2pa_manifest)?.url;
+    jumbf_labels(&url).0
+}
+
+impl ManifestStats {
+    fn new(label: String, bytes: usize, children: &[u8]) -> Self {
+        let mut stats = Self {
+            label,
+            bytes,
+            claim_bytes: 0,
+            signature_bytes: 0,
+            assertion_count: 0,
+            assertion_bytes: 0,
+            ingredient_count: 0,
+            depth: None,
+            assertions: Vec::new(),
+            resources: Vec::new(),
+            ingredient_manifests: Vec::new(),
+        };
+        for jumbf_box in jumbf_boxes(children) {
+            let Some((label, children)) = superbox(&jumbf_box) else {
+                continue;
+            };
+            match label.as_str() {
+                "c2pa.assertions" => stats.assertions(children),
+                "c2pa.signature" => stats.signature_bytes = jumbf_box.len,
+                "c2pa.databoxes" => stats.resources.extend(box_stats(children)),
+                label if label.starts_with("c2pa.claim") => stats.claim_bytes = jumbf_box.len,
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    fn assertions(&mut self, assertions: &[u8]) {
+        for jumbf_box in jumbf_boxes(assertions) {
+            let Some((label, children)) = superbox(&jumbf_box) else {
+                continue;
+            };
+            if label.starts_with("c2pa.ingredient") {
+                self.ingredient_count += 1;
+                self.ingredient_manifests
+                    .extend(ingredient_manifest(children));
+            }
+            let stats = BoxStats {
+                label,
+                bytes: jumbf_box.len,
+            };
+            if stats.label.starts_with("c2pa.thumbnail") {
+                self.resources.push(stats);
+                continue;
+            }
+            self.assertion_count += 1;
+            self.assertion_bytes += stats.bytes;
+            self.assertions.push(stats);
+        }
+    }
+}
+
+/// The labels and sizes of the superboxes in `data`.
+fn box_stats(data: &[u8]) -> impl Iterator<Item = BoxStats> + '_ {
+    jumbf_boxes(data).filter_map(|jumbf_box| {
+        let (label, _) = superbox(&jumbf_box)?;
+        Some(BoxStats {
+            label,
+            bytes: jumbf_box.len,
+        })
+    })
+}
+
+impl StoreStats {
+    pub fn new(store: &[u8]) -> Result<Self> {
+        let Some((_, children)) = superboxes(store).next() else {
+            return Err(Error::Reading(
+                "The manifest store is malformed".to_string(),
+            ));
+        };
+        let mut manifests: Vec<ManifestStats> = jumbf_boxes(children)
+            .filter_map(|jumbf_box| {
+                let (label, children) = superbox(&jumbf_box)?;
+                Some(ManifestStats::new(label, jumbf_box.len, children))
+            })
+            .collect();
+
+        // Walk the ingredient links from the active manifest, which is last.
+        let mut queue: VecDeque<(usize, usize)> = manifests
+            .len()
+            .checked_sub(1)
+            .map(|last| (last, 0))
+            .into_iter()
+            .collect();
+        while let Some((index, depth)) = queue.pop_front() {
+            if manifests[index].depth.is_some() {
+                continue;
+            }
+            manifests[index].depth = Some(depth);
+            for label in manifests[index].ingredient_manifests.clone() {
+                if let Some(next) = manifests.iter().position(|m| m.label == label) {
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        Ok(Self {
+            total_bytes: store.len(),
+            manifest_count: manifests.len(),
+            assertion_count: manifests.iter().map(|m| m.assertion_count).sum(),
+            active_manifest: manifests.last().map(|m| m.label.clone()),
+            ingredient_depth: manifests.iter().filter_map(|m| m.depth).max().unwrap_or(0),
+            manifests,
+        })
+    }
+}
+
+/// Sizes and counts for the manifest store embedded in an asset, as JSON, or
+/// null when the asset has no manifest store. The store is not validated.
+pub fn manifest_store_stats(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let timeout = parse_timeout(&mut cx, 1).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Read);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let (format, mut stream) = source.into_typed_read_stream()?;
+            let format = format
+                .ok_or_else(|| Error::Reading("Source asset must have a mime type".to_string()))?;
+            let store = match load_jumbf_from_stream(&format, &mut stream) {
+                Ok(store) => store,
+                Err(c2pa::Error::JumbfNotFound) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+            let stats = StoreStats::new(&store)?;
+            Ok(Some(serde_json::to_string(&stats)?))
+        });
+        let result: Result<Option<String>> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(Some(json)) => Ok(cx.string(json).upcast::<JsValue>()),
+            Ok(None) => Ok(cx.null().upcast::<JsValue>()),
+            Err(err) => {
+                as_js_error(&mut cx, "manifestStoreStats", err).and_then(|err| cx.throw(err))
+            }
+        });
+    });
+    Ok(promise)
+}
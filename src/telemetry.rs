@@ -0,0 +1,406 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use neon::prelude::*;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::error::{as_js_error, Result};
+use crate::instance::instance_id;
+use crate::network;
+use crate::runtime::runtime;
+
+/// How long finished spans wait before being sent to the OTLP endpoint, so
+/// they are sent in batches.
+const EXPORT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, Deserialize)]
+struct OtlpConfig {
+    /// The OTLP/HTTP collector, e.g. "http://localhost:4318". Spans are
+    /// POSTed as JSON to its /v1/traces path.
+    endpoint: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    service_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+struct TelemetryConfig {
+    otlp: Option<OtlpConfig>,
+}
+
+struct SpanListener {
+    instance: u64,
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+}
+
+/// Where finished spans go, set up by `configureTelemetry`.
+#[derive(Default)]
+struct Exporter {
+    listener: Option<SpanListener>,
+    otlp: Option<OtlpConfig>,
+    /// Spans waiting to be sent to the OTLP endpoint.
+    pending: Vec<SpanRecord>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static EXPORTER: Mutex<Option<Exporter>> = Mutex::new(None);
+
+/// The W3C trace context of the JS code making a call, so native spans join
+/// its trace.
+#[derive(Clone, Copy)]
+struct TraceParent {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+}
+
+thread_local! {
+    /// Set by `setTraceParent` around a call on the JS thread, and picked up
+    /// by spans started during it.
+    static TRACE_PARENT: Cell<Option<TraceParent>> = const { Cell::new(None) };
+}
+
+impl TraceParent {
+    /// Parse a `traceparent` header, e.g.
+    /// "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".
+    fn parse(header: &str) -> Option<Self> {
+        let parts: Vec<&str> = header.trim().split('-').collect();
+        let [_version, trace_id, span_id, _flags] = parts[..] else {
+            return None;
+        };
+        let mut parent = TraceParent {
+            trace_id: [0; 16],
+            span_id: [0; 8],
+        };
+        decode_hex(trace_id, &mut parent.trace_id)?;
+        decode_hex(span_id, &mut parent.span_id)?;
+        Some(parent)
+    }
+}
+
+fn decode_hex(hex: &str, out: &mut [u8]) -> Option<()> {
+    if hex.len() != out.len() * 2 {
+        return None;
+    }
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(())
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos())
+}
+
+#[derive(Clone, Copy)]
+pub enum SpanKind {
+    Internal,
+    Client,
+}
+
+/// A finished span.
+struct SpanRecord {
+    name: String,
+    kind: SpanKind,
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start: SystemTime,
+    end: SystemTime,
+    ok: bool,
+    attributes: Vec<(&'static str, String)>,
+}
+
+/// A span being timed, which is exported when `end` is called or, as failed,
+/// when dropped first.
+pub struct Span {
+    record: Option<SpanRecord>,
+}
+
+impl Span {
+    /// Start a span, as a child of the caller's trace context when one was set
+    /// with `setTraceParent`. Does nothing unless telemetry is configured.
+    pub fn start(name: &str, kind: SpanKind) -> Self {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Self { record: None };
+        }
+        let parent = TRACE_PARENT.with(Cell::get);
+        Self {
+            record: Some(SpanRecord {
+                name: name.to_string(),
+                kind,
+                trace_id: parent.map_or_else(rand::random, |parent| parent.trace_id),
+                span_id: rand::random(),
+                parent_span_id: parent.map(|parent| parent.span_id),
+                start: SystemTime::now(),
+                end: SystemTime::now(),
+                ok: false,
+                attributes: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn set_attribute(&mut self, key: &'static str, value: impl ToString) {
+        if let Some(record) = &mut self.record {
+            record.attributes.push((key, value.to_string()));
+        }
+    }
+
+    pub fn end(&mut self, ok: bool) {
+        if let Some(mut record) = self.record.take() {
+            record.ok = ok;
+            record.end = SystemTime::now();
+            export(record);
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.end(false);
+    }
+}
+
+fn export(record: SpanRecord) {
+    let Ok(mut exporter) = EXPORTER.lock() else {
+        return;
+    };
+    let Some(exporter) = exporter.as_mut() else {
+        return;
+    };
+    if let Some(listener) = &exporter.listener {
+        let callback = Arc::clone(&listener.callback);
+        let span = record.to_json();
+        // Dropped if the listener's thread has exited.
+        let _ = listener.channel.try_send(move |mut cx| {
+            let span = neon_serde4::to_value(&mut cx, &span)
+                .or_else(|err| cx.throw_error(err.to_string()))?;
+            callback
+                .to_inner(&mut cx)
+                .call_with(&cx)
+                .arg(span)
+                .exec(&mut cx)
+        });
+    }
+    if exporter.otlp.is_some() {
+        exporter.pending.push(record);
+        if exporter.pending.len() == 1 {
+            if let Ok(rt) = runtime() {
+                rt.spawn(async {
+                    tokio::time::sleep(EXPORT_DELAY).await;
+                    if let Err(err) = flush().await {
+                        log::warn!("Failed to export spans: {err}");
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl SpanRecord {
+    /// The span as given to the `onSpan` callback.
+    fn to_json(&self) -> serde_json::Value {
+        let attributes: serde_json::Map<_, _> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), json!(value)))
+            .collect();
+        json!({
+            "name": self.name,
+            "kind": match self.kind {
+                SpanKind::Internal => "internal",
+                SpanKind::Client => "client",
+            },
+            "traceId": encode_hex(&self.trace_id),
+            "spanId": encode_hex(&self.span_id),
+            "parentSpanId": self.parent_span_id.map(|id| encode_hex(&id)),
+            "startTime": unix_nanos(self.start) as f64 / 1e6,
+            "endTime": unix_nanos(self.end) as f64 / 1e6,
+            "status": if self.ok { "ok" } else { "error" },
+            "attributes": attributes,
+        })
+    }
+
+    /// The span in the OTLP/JSON encoding.
+    fn to_otlp(&self) -> serde_json::Value {
+        let attributes: Vec<_> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+            .collect();
+        json!({
+            "traceId": encode_hex(&self.trace_id),
+            "spanId": encode_hex(&self.span_id),
+            "parentSpanId": self.parent_span_id.map(|id| encode_hex(&id)).unwrap_or_default(),
+            "name": self.name,
+            "kind": match self.kind {
+                SpanKind::Internal => 1,
+                SpanKind::Client => 3,
+            },
+            "startTimeUnixNano": unix_nanos(self.start).to_string(),
+            "endTimeUnixNano": unix_nanos(self.end).to_string(),
+            "attributes": attributes,
+            "status": { "code": if self.ok { 1 } else { 2 } },
+        })
+    }
+}
+
+/// Send the spans waiting for the OTLP endpoint.
+async fn flush() -> Result<()> {
+    let (otlp, spans) = {
+        let mut exporter = EXPORTER.lock()?;
+        match exporter.as_mut() {
+            Some(Exporter {
+                otlp: Some(otlp),
+                pending,
+                ..
+            }) => (otlp.clone(), mem::take(pending)),
+            _ => return Ok(()),
+        }
+    };
+    if spans.is_empty() {
+        return Ok(());
+    }
+    let service_name = otlp.service_name.as_deref().unwrap_or("c2pa-node");
+    let body = json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": service_name } },
+                ],
+            },
+            "scopeSpans": [{
+                "scope": {
+                    "name": "@contentauth/c2pa-node",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "spans": spans.iter().map(SpanRecord::to_otlp).collect::<Vec<_>>(),
+            }],
+        }],
+    });
+    let url = format!("{}/v1/traces", otlp.endpoint.trim_end_matches('/'));
+    network::post_json(&url, &otlp.headers, &body).await
+}
+
+/// Remove the span callback registered by an instance that is being unloaded.
+pub(crate) fn remove_instance_span_listener(instance: u64) {
+    if let Ok(mut exporter) = EXPORTER.lock() {
+        if let Some(exporter) = exporter.as_mut() {
+            if exporter
+                .listener
+                .as_ref()
+                .is_some_and(|listener| listener.instance == instance)
+            {
+                exporter.listener = None;
+            }
+        }
+    }
+}
+
+/// Export spans for native reads, signs, and network requests to a callback,
+/// an OTLP/HTTP endpoint, or both, replacing any previous configuration.
+/// Passing null for both turns telemetry off.
+pub fn configure_telemetry(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let config = match cx.argument_opt(0) {
+        Some(json) if json.is_a::<JsString, _>(&mut cx) => {
+            let json = json
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            serde_json::from_str::<TelemetryConfig>(&json)
+                .or_else(|err| cx.throw_error(format!("Invalid telemetry configuration: {err}")))?
+        }
+        _ => TelemetryConfig::default(),
+    };
+    if let Some(otlp) = &config.otlp {
+        if let Err(err) = url::Url::parse(&otlp.endpoint) {
+            return cx.throw_type_error(format!(
+                "Invalid OTLP endpoint \"{}\": {err}",
+                otlp.endpoint
+            ));
+        }
+    }
+    let listener = match cx.argument_opt(1) {
+        Some(callback) if callback.is_a::<JsFunction, _>(&mut cx) => {
+            let callback = callback.downcast_or_throw::<JsFunction, _>(&mut cx)?;
+            let mut channel = cx.channel();
+            // Telemetry should not keep the process alive.
+            channel.unref(&mut cx);
+            Some(SpanListener {
+                instance: instance_id(&mut cx),
+                channel,
+                callback: Arc::new(callback.root(&mut cx)),
+            })
+        }
+        _ => None,
+    };
+
+    let exporter = (listener.is_some() || config.otlp.is_some()).then(|| Exporter {
+        listener,
+        otlp: config.otlp,
+        pending: Vec::new(),
+    });
+    let mut current = EXPORTER
+        .lock()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    ENABLED.store(exporter.is_some(), Ordering::Relaxed);
+    *current = exporter;
+    Ok(cx.undefined())
+}
+
+/// Set the trace context that spans started by calls on this thread join,
+/// from a `traceparent` header. Passing null clears it.
+pub fn set_trace_parent(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let parent = match cx.argument_opt(0) {
+        Some(header) if header.is_a::<JsString, _>(&mut cx) => {
+            let header = header
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            match TraceParent::parse(&header) {
+                Some(parent) => Some(parent),
+                None => return cx.throw_type_error(format!("Invalid traceparent \"{header}\"")),
+            }
+        }
+        _ => None,
+    };
+    TRACE_PARENT.with(|current| current.set(parent));
+    Ok(cx.undefined())
+}
+
+/// Send spans waiting for the OTLP endpoint now, such as before the process
+/// exits.
+pub fn flush_telemetry(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    rt.spawn(async move {
+        let result = flush().await;
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(()) => Ok(cx.undefined()),
+            Err(err) => as_js_error(&mut cx, "flushTelemetry", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
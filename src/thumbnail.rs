@@ -0,0 +1,85 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, Read};
+
+use image::{ImageFormat, ImageReader};
+use neon::prelude::*;
+
+use crate::asset::parse_asset;
+use crate::error::{as_js_error, catch_panic, Result};
+use crate::metrics::{Operation, OperationTimer};
+use crate::runtime::{in_lane, runtime, with_timeout};
+use crate::utils::{parse_priority, parse_timeout};
+
+/// A decoded image as 8-bit RGBA pixels, row by row.
+struct Rgba {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+/// Decode `bytes`, taking the format from `mime_type` when the image crate
+/// knows it and from the bytes otherwise.
+fn decode(bytes: &[u8], mime_type: Option<&str>) -> Result<Rgba> {
+    let reader = match mime_type.and_then(ImageFormat::from_mime_type) {
+        Some(format) => ImageReader::with_format(Cursor::new(bytes), format),
+        None => ImageReader::new(Cursor::new(bytes)).with_guessed_format()?,
+    };
+    let image = reader.decode()?.to_rgba8();
+    Ok(Rgba {
+        width: image.width(),
+        height: image.height(),
+        data: image.into_raw(),
+    })
+}
+
+/// Decode an image, such as a thumbnail extracted with `resourceToAsset`, to
+/// RGBA pixels.
+pub fn decode_thumbnail(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime().or_else(|err| cx.throw_error(err.to_string()))?;
+    let channel = cx.channel();
+    let source = cx
+        .argument::<JsObject>(0)
+        .and_then(|obj| parse_asset(&mut cx, obj))?;
+    let timeout = parse_timeout(&mut cx, 1).or_else(|err| cx.throw_error(err.to_string()))?;
+    let priority = parse_priority(&mut cx, 2).or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let (deferred, promise) = cx.promise();
+    let timer = OperationTimer::start(Operation::Resource);
+    rt.spawn(async move {
+        let task = catch_panic(async move {
+            let mime_type = source.mime_type();
+            let mut bytes = Vec::new();
+            source.into_read_stream()?.read_to_end(&mut bytes)?;
+            decode(&bytes, mime_type.as_deref())
+        });
+        let result: Result<Rgba> = with_timeout(timeout, in_lane(priority, task)).await;
+        timer.finish(result.is_ok());
+
+        deferred.settle_with(&channel, move |mut cx| match result {
+            Ok(rgba) => {
+                let obj = cx.empty_object();
+                let width = cx.number(rgba.width);
+                obj.set(&mut cx, "width", width)?;
+                let height = cx.number(rgba.height);
+                obj.set(&mut cx, "height", height)?;
+                let data = JsBuffer::from_slice(&mut cx, &rgba.data)?;
+                obj.set(&mut cx, "data", data)?;
+                Ok(obj)
+            }
+            Err(err) => as_js_error(&mut cx, "decodeThumbnail", err).and_then(|err| cx.throw(err)),
+        });
+    });
+    Ok(promise)
+}
@@ -12,9 +12,17 @@
 // each license.
 
 use c2pa::Context;
+use neon::context::Context as _;
 use neon::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::error::{Error, Result};
+use crate::js_value::from_js;
+use crate::neon_context::NeonContext;
+use crate::network::{build_context, build_context_with, NetworkOptions};
+use crate::runtime::Priority;
+use crate::settings::{resolve_settings, VerifyOptions};
 
 #[allow(dead_code)]
 // Used in debugging
@@ -32,39 +40,203 @@ pub fn log_message<'a, C: neon::context::Context<'a>>(cx: &mut C, message: &str)
     log.call(cx, this, args).unwrap();
 }
 
-/// Parse optional settings string from JS argument and create a Context.
-/// Returns Ok(Some(Context)) if settings are provided, Ok(None) if not provided,
+/// The MIME types in a list of c2pa-rs supported formats, which also holds
+/// file extensions, sorted.
+pub fn mime_types(formats: Vec<String>) -> Vec<String> {
+    let mut mime_types: Vec<String> = formats
+        .into_iter()
+        .filter(|format| format.contains('/'))
+        .collect();
+    mime_types.sort();
+    mime_types.dedup();
+    mime_types
+}
+
+/// The MIME types in a list of c2pa-rs supported formats as a JS array.
+pub fn mime_type_array<'a>(
+    cx: &mut FunctionContext<'a>,
+    formats: Vec<String>,
+) -> JsResult<'a, JsArray> {
+    let array = cx.empty_array();
+    for (index, mime_type) in mime_types(formats).iter().enumerate() {
+        let mime_type = cx.string(mime_type);
+        array.set(cx, index as u32, mime_type)?;
+    }
+    Ok(array)
+}
+
+/// Parse optional settings string or Context handle from JS argument.
+/// Returns Ok(Some(Context)) if settings or a Context are provided, Ok(None) if not provided,
 /// or Err if settings are invalid.
 pub fn parse_settings(
     cx: &mut FunctionContext,
     arg_index: usize,
     error_prefix: &str,
-) -> Result<Option<Context>> {
+) -> Result<Option<Arc<Context>>> {
     let settings_opt = cx.argument_opt(arg_index);
 
     match settings_opt {
         Some(js_value) => {
             if js_value.is_a::<JsString, _>(cx) {
                 let settings_string = js_value
-                    .downcast::<JsString, _>(cx).map_err(|_| Error::Signing(format!("{}: Expected settings string", error_prefix)))?
+                    .downcast::<JsString, _>(cx).map_err(|_| Error::Settings(format!("{}: Expected settings string", error_prefix)))?
                     .value(cx);
 
                 // Create context with settings
-                let context = Context::new()
-                    .with_settings(settings_string.as_str())
-                    .map_err(|e| Error::Signing(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+                let settings = resolve_settings(Some(settings_string.as_str()))
+                    .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+                let context = build_context(settings)
+                    .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
 
-                Ok(Some(context))
+                Ok(Some(context.into_shared()))
+            } else if let Ok(handle) = js_value.downcast::<JsBox<NeonContext>, _>(cx) {
+                Ok(Some(handle.context()))
             } else if js_value.is_a::<JsNull, _>(cx) || js_value.is_a::<JsUndefined, _>(cx) {
-                Ok(None)
+                global_context(error_prefix)
+            } else if js_value.is_a::<JsObject, _>(cx) {
+                // Settings objects are converted natively, skipping JSON.stringify.
+                let settings = cx.try_catch(|cx| from_js(cx, js_value))
+                    .map_err(|_| Error::Settings(format!("{}: Settings can't be converted to JSON", error_prefix)))?;
+                let settings = resolve_settings(Some(settings.to_string().as_str()))
+                    .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+                let context = build_context(settings)
+                    .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+
+                Ok(Some(context.into_shared()))
             } else {
-                Err(Error::Signing(format!(
-                    "{}: Settings must be a string, null, or undefined",
+                Err(Error::Settings(format!(
+                    "{}: Settings must be a string, object, Context, null, or undefined",
                     error_prefix
                 )))
             }
         }
-        None => Ok(None),
+        None => global_context(error_prefix),
+    }
+}
+
+/// Parse optional verify options (a JSON string) from JS argument and layer them
+/// over the settings of `context`, or over the default settings when there is none.
+/// Returns `context` unchanged when no options are provided.
+pub fn parse_verify_options(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+    context: Option<Arc<Context>>,
+    error_prefix: &str,
+) -> Result<Option<Arc<Context>>> {
+    let options: VerifyOptions = match cx.argument_opt(arg_index) {
+        Some(js_value) if js_value.is_a::<JsString, _>(cx) => {
+            let options_string = js_value
+                .downcast::<JsString, _>(cx)
+                .map_err(|_| Error::Settings(format!("{}: Expected options string", error_prefix)))?
+                .value(cx);
+            serde_json::from_str(&options_string)
+                .map_err(|e| Error::Settings(format!("{}: Invalid verify options: {}", error_prefix, e)))?
+        }
+        Some(js_value)
+            if !js_value.is_a::<JsNull, _>(cx) && !js_value.is_a::<JsUndefined, _>(cx) =>
+        {
+            return Err(Error::Settings(format!(
+                "{}: Verify options must be a string, null, or undefined",
+                error_prefix
+            )));
+        }
+        _ => return Ok(context),
+    };
+
+    let mut settings = match &context {
+        Some(context) => context.settings().clone(),
+        None => resolve_settings(None)?,
+    };
+    options
+        .apply(&mut settings)
+        .and_then(|_| build_context_with(settings, options.network))
+        .map(|context| Some(context.into_shared()))
+        .map_err(|e| Error::Settings(format!("{}: Invalid verify options: {}", error_prefix, e)))
+}
+
+/// Parse an optional `timeoutMs` number from JS argument. Returns Ok(None) if no
+/// timeout is provided, or Err if it is not a positive number.
+pub fn parse_timeout(cx: &mut FunctionContext, arg_index: usize) -> Result<Option<Duration>> {
+    match cx.argument_opt(arg_index) {
+        Some(js_value) if js_value.is_a::<JsNumber, _>(cx) => {
+            let timeout_ms = js_value
+                .downcast::<JsNumber, _>(cx)
+                .map_err(|_| Error::Settings("Expected timeoutMs number".to_string()))?
+                .value(cx);
+            if !timeout_ms.is_finite() || timeout_ms <= 0.0 {
+                return Err(Error::Settings(format!(
+                    "timeoutMs must be a positive number, got {timeout_ms}"
+                )));
+            }
+            Ok(Some(Duration::from_millis(timeout_ms.ceil() as u64)))
+        }
+        Some(js_value)
+            if !js_value.is_a::<JsNull, _>(cx) && !js_value.is_a::<JsUndefined, _>(cx) =>
+        {
+            Err(Error::Settings(
+                "timeoutMs must be a number, null, or undefined".to_string(),
+            ))
+        }
+        _ => Ok(None),
     }
 }
 
+/// Parse optional network options, as a JSON string, from JS argument.
+pub fn parse_network_options(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+) -> Result<Option<NetworkOptions>> {
+    match cx.argument_opt(arg_index) {
+        Some(js_value) if js_value.is_a::<JsString, _>(cx) => {
+            let json = js_value
+                .downcast::<JsString, _>(cx)
+                .map_err(|_| Error::Settings("Expected network options string".to_string()))?
+                .value(cx);
+            serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| Error::Settings(format!("Invalid network options: {e}")))
+        }
+        Some(js_value)
+            if !js_value.is_a::<JsNull, _>(cx) && !js_value.is_a::<JsUndefined, _>(cx) =>
+        {
+            Err(Error::Settings(
+                "Network options must be a string, null, or undefined".to_string(),
+            ))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parse an optional priority ("interactive" or "batch") from JS argument.
+/// Defaults to interactive.
+pub fn parse_priority(cx: &mut FunctionContext, arg_index: usize) -> Result<Priority> {
+    match cx.argument_opt(arg_index) {
+        Some(js_value) if js_value.is_a::<JsString, _>(cx) => {
+            let priority = js_value
+                .downcast::<JsString, _>(cx)
+                .map_err(|_| Error::Settings("Expected priority string".to_string()))?
+                .value(cx);
+            Priority::parse(&priority)
+        }
+        Some(js_value)
+            if !js_value.is_a::<JsNull, _>(cx) && !js_value.is_a::<JsUndefined, _>(cx) =>
+        {
+            Err(Error::Settings(
+                "priority must be a string, null, or undefined".to_string(),
+            ))
+        }
+        _ => Ok(Priority::default()),
+    }
+}
+
+/// Build a Context from the process-wide settings alone. Always built, rather than
+/// falling back to the c2pa-rs default Context, so offline mode and other
+/// process-wide settings apply to every call.
+fn global_context(error_prefix: &str) -> Result<Option<Arc<Context>>> {
+    let settings = resolve_settings(None)
+        .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+    let context = build_context(settings)
+        .map_err(|e| Error::Settings(format!("{}: Invalid settings: {}", error_prefix, e)))?;
+    Ok(Some(context.into_shared()))
+}
+
@@ -0,0 +1,296 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::collections::BTreeMap;
+
+use c2pa::status_tracker::LogKind;
+use c2pa::validation_results::StatusCodes;
+use c2pa::validation_status::{self, *};
+use c2pa::Reader;
+use neon::prelude::*;
+use serde::Serialize;
+
+use crate::validation_policy::{self, policy_kind};
+
+/// Pairs each c2pa-rs validation code constant with its name, so the values
+/// always come from the linked c2pa-rs release.
+macro_rules! validation_codes {
+    ($($name:ident),* $(,)?) => {
+        const VALIDATION_CODES: &[(&str, &str)] = &[$((stringify!($name), $name)),*];
+    };
+}
+
+validation_codes![
+    CLAIM_SIGNATURE_VALIDATED,
+    CLAIM_SIGNATURE_INSIDE_VALIDITY,
+    SIGNING_CREDENTIAL_TRUSTED,
+    SIGNING_CREDENTIAL_NOT_REVOKED,
+    TIMESTAMP_VALIDATED,
+    TIMESTAMP_TRUSTED,
+    ASSERTION_HASHEDURI_MATCH,
+    ASSERTION_DATAHASH_MATCH,
+    ASSERTION_DATAHASH_ADDITIONAL_EXCLUSIONS,
+    ASSERTION_BMFFHASH_MATCH,
+    ASSERTION_BOXHASH_MATCH,
+    ASSERTION_COLLECTIONHASH_MATCH,
+    ASSERTION_ACCESSIBLE,
+    INGREDIENT_MANIFEST_VALIDATED,
+    INGREDIENT_PROVENANCE_UNKNOWN,
+    INGREDIENT_CLAIM_SIGNATURE_VALIDATED,
+    SIGNING_CREDENTIAL_OCSP_SKIPPED,
+    SIGNING_CREDENTIAL_OCSP_INACCESSIBLE,
+    TIMESTAMP_MISMATCH,
+    TIMESTAMP_MALFORMED,
+    TIMESTAMP_OUTSIDE_VALIDITY,
+    TIMESTAMP_UNTRUSTED,
+    MANIFEST_UNKNOWN_PROVENANCE,
+    MANIFEST_UNREFERENCED,
+    ALGORITHM_DEPRECATED,
+    TIME_OF_SIGNING_INSIDE_VALIDITY,
+    CLAIM_MALFORMED,
+    CLAIM_MISSING,
+    CLAIM_MULTIPLE,
+    HARD_BINDINGS_MISSING,
+    HARD_BINDINGS_MULTIPLE,
+    CLAIM_REQUIRED_MISSING,
+    CLAIM_CBOR_INVALID,
+    INGREDIENT_HASHEDURI_MISMATCH,
+    CLAIM_SIGNATURE_MISSING,
+    CLAIM_SIGNATURE_MISMATCH,
+    MANIFEST_INACCESSIBLE,
+    MANIFEST_MULTIPLE_PARENTS,
+    MANIFEST_UPDATE_INVALID,
+    MANIFEST_UPDATE_WRONG_PARENTS,
+    SIGNING_CREDENTIAL_UNTRUSTED,
+    SIGNING_CREDENTIAL_INVALID,
+    SIGNING_CREDENTIAL_REVOKED,
+    SIGNING_CREDENTIAL_EXPIRED,
+    ASSERTION_HASHEDURI_MISMATCH,
+    ASSERTION_MISSING,
+    ASSERTION_UNDECLARED,
+    ASSERTION_INACCESSIBLE,
+    ASSERTION_NOT_REDACTED,
+    ASSERTION_SELF_REDACTED,
+    ASSERTION_REQUIRED_MISSING,
+    ASSERTION_JSON_INVALID,
+    ASSERTION_CBOR_INVALID,
+    ACTION_ASSERTION_INGREDIENT_MISMATCH,
+    ACTION_ASSERTION_REDACTED,
+    ASSERTION_DATAHASH_MISMATCH,
+    ASSERTION_BMFFHASH_MISMATCH,
+    ASSERTION_BOXHASH_MISMATCH,
+    ASSERTION_BOXHASH_UNKNOWN_BOX,
+    ASSERTION_CLOUD_DATA_HARD_BINDING,
+    ASSERTION_CLOUD_DATA_ACTIONS,
+    ALGORITHM_UNSUPPORTED,
+    GENERAL_ERROR,
+    CLAIM_SIGNATURE_OUTSIDE_VALIDITY,
+    MANIFEST_TIMESTAMP_INVALID,
+    MANIFEST_TIMESTAMP_WRONG_PARENTS,
+    MANIFEST_COMPRESSED_INVALID,
+    SIGNING_CREDENTIAL_OCSP_UNKNOWN,
+    ASSERTION_OUTSIDE_MANIFEST,
+    ASSERTION_ACTION_MALFORMED,
+    ASSERTION_ACTION_INGREDIENT_MISMATCH,
+    ASSERTION_ACTION_REDACTION_MISMATCH,
+    ASSERTION_ACTION_REDACTED,
+    ASSERTION_DATAHASH_MALFORMED,
+    ASSERTION_DATAHASH_REDACTED,
+    ASSERTION_BMFFHASH_MALFORMED,
+    ASSERTION_BOXESHASH_MALFORMED,
+    ASSERTION_CLOUD_DATA_MALFORMED,
+    ASSERTION_COLLECTIONHASH_MISMATCH,
+    ASSERTION_COLLECTIONHASH_INCORRECT_FILE_COUNT,
+    ASSERTION_COLLECTIONHASH_INVALID_URI,
+    ASSERTION_COLLECTIONHASH_MALFORMED,
+    ASSERTION_INGREDIENT_MALFORMED,
+    ASSERTION_METADATA_DISALLOWED,
+    INGREDIENT_MANIFEST_MISSING,
+    INGREDIENT_MANIFEST_MISMATCH,
+    INGREDIENT_CLAIM_SIGNATURE_MISSING,
+    INGREDIENT_CLAIM_SIGNATURE_MISMATCH,
+    HASHED_URI_MISSING,
+    HASHED_URI_MISMATCH,
+    ASSERTION_TIMESTAMP_MALFORMED,
+];
+
+#[derive(Serialize)]
+struct ValidationCode {
+    code: &'static str,
+    kind: &'static str,
+}
+
+fn kind_name(code: &str) -> &'static str {
+    match validation_status::log_kind(code) {
+        LogKind::Success => "success",
+        LogKind::Informational => "informational",
+        LogKind::Failure => "failure",
+    }
+}
+
+/// Every validation status code known to c2pa-rs, keyed by its constant name,
+/// as JSON.
+pub fn validation_status_codes(mut cx: FunctionContext) -> JsResult<JsString> {
+    let codes: BTreeMap<&str, ValidationCode> = VALIDATION_CODES
+        .iter()
+        .map(|&(name, code)| {
+            let kind = kind_name(code);
+            (name, ValidationCode { code, kind })
+        })
+        .collect();
+    let json = serde_json::to_string(&codes).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
+
+/// Whether a validation status code reports a success, is informational, or
+/// reports a failure. Unknown codes are failures.
+pub fn validation_status_kind(mut cx: FunctionContext) -> JsResult<JsString> {
+    let code = cx.argument::<JsString>(0)?.value(&mut cx);
+    Ok(cx.string(kind_name(&code)))
+}
+
+/// The manifest label and assertion label in a JUMBF URI such as
+/// `self#jumbf=/c2pa/<manifest>/c2pa.assertions/<assertion>`.
+pub fn jumbf_labels(uri: &str) -> (Option<String>, Option<String>) {
+    let Some((_, path)) = uri.split_once("jumbf=") else {
+        return (None, None);
+    };
+    let mut parts = path.split('/').filter(|part| !part.is_empty());
+    if parts.next() != Some("c2pa") {
+        return (None, None);
+    }
+    let manifest_label = parts.next().map(str::to_string);
+    let assertion = match parts.next() {
+        Some("c2pa.assertions") => parts.next().map(str::to_string),
+        _ => None,
+    };
+    (manifest_label, assertion)
+}
+
+/// A validation finding with a stable key and parameters, so applications can
+/// show their own localized text instead of the English explanation.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationMessage {
+    pub code: String,
+    pub kind: &'static str,
+    /// `c2pa.validation.` followed by the code.
+    pub key: String,
+    pub params: BTreeMap<&'static str, String>,
+    /// The explanation given by c2pa-rs, in English.
+    pub message: Option<String>,
+}
+
+fn push_messages(
+    messages: &mut Vec<ValidationMessage>,
+    codes: &StatusCodes,
+    ingredient_uri: Option<&str>,
+) {
+    let statuses = codes
+        .success()
+        .iter()
+        .map(|status| (status, "success"))
+        .chain(
+            codes
+                .informational()
+                .iter()
+                .map(|status| (status, "informational")),
+        )
+        .chain(codes.failure().iter().map(|status| (status, "failure")));
+    for (status, reported_kind) in statuses {
+        let mut params = BTreeMap::new();
+        if let Some(url) = status.url() {
+            let (manifest_label, assertion) = jumbf_labels(url);
+            params.insert("url", url.to_string());
+            if let Some(manifest_label) = manifest_label {
+                params.insert("manifestLabel", manifest_label);
+            }
+            if let Some(assertion) = assertion {
+                params.insert("assertion", assertion);
+            }
+        }
+        if let Some(ingredient_uri) = ingredient_uri.or(status.ingredient_uri()) {
+            params.insert("ingredientUri", ingredient_uri.to_string());
+        }
+        messages.push(ValidationMessage {
+            code: status.code().to_string(),
+            kind: policy_kind(status.code()).unwrap_or_else(|| {
+                // c2pa-rs only knows the kinds of C2PA codes, so CAWG codes
+                // keep the kind they were reported with.
+                if status.code().starts_with("cawg.") {
+                    reported_kind
+                } else {
+                    kind_name(status.code())
+                }
+            }),
+            key: format!("c2pa.validation.{}", status.code()),
+            params,
+            message: status.explanation().map(str::to_string),
+        });
+    }
+}
+
+/// Which findings to return, by how severe they are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageFilter {
+    /// Only failures.
+    Errors,
+    /// Failures and informational findings.
+    Warnings,
+    All,
+}
+
+impl MessageFilter {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "errors" => Some(MessageFilter::Errors),
+            "warnings" => Some(MessageFilter::Warnings),
+            "all" => Some(MessageFilter::All),
+            _ => None,
+        }
+    }
+
+    pub fn includes(self, kind: &str) -> bool {
+        match self {
+            MessageFilter::Errors => kind == "failure",
+            MessageFilter::Warnings => kind != "success",
+            MessageFilter::All => true,
+        }
+    }
+}
+
+/// The findings in the Reader's validation results that report a failure.
+pub fn validation_failures(reader: &Reader) -> Vec<ValidationMessage> {
+    let mut messages = validation_messages(reader);
+    messages.retain(|message| MessageFilter::Errors.includes(message.kind));
+    messages
+}
+
+/// Every finding in the Reader's validation results, for the active manifest
+/// and then for each ingredient.
+pub fn validation_messages(reader: &Reader) -> Vec<ValidationMessage> {
+    let mut messages = Vec::new();
+    if let Some(results) = validation_policy::validation_results(reader) {
+        if let Some(active_manifest) = results.active_manifest() {
+            push_messages(&mut messages, active_manifest, None);
+        }
+        for delta in results.ingredient_deltas().into_iter().flatten() {
+            push_messages(
+                &mut messages,
+                delta.validation_deltas(),
+                Some(delta.ingredient_assertion_uri()),
+            );
+        }
+    }
+    messages
+}
@@ -0,0 +1,198 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use c2pa::status_tracker::LogKind;
+use c2pa::validation_results::{IngredientDeltaValidationResult, StatusCodes, ValidationResults};
+use c2pa::validation_status::ValidationStatus;
+use c2pa::{Reader, ValidationState};
+use neon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How the validation policy treats a status code, in place of the kind
+/// c2pa-rs gives it.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// A failure, which makes the manifest store invalid.
+    Error,
+    /// Informational.
+    Warning,
+    /// Left out of the validation results.
+    Ignore,
+}
+
+type Policy = BTreeMap<String, Severity>;
+
+static POLICY: RwLock<Policy> = RwLock::new(BTreeMap::new());
+
+fn severity(code: &str) -> Option<Severity> {
+    POLICY.read().ok()?.get(code).copied()
+}
+
+fn has_policy() -> bool {
+    POLICY.read().is_ok_and(|policy| !policy.is_empty())
+}
+
+/// The kind the policy gives `code`, or None when it leaves it to c2pa-rs.
+pub fn policy_kind(code: &str) -> Option<&'static str> {
+    match severity(code)? {
+        Severity::Error => Some("failure"),
+        Severity::Warning => Some("informational"),
+        Severity::Ignore => None,
+    }
+}
+
+/// Sort `codes` into success, informational, and failure by the policy.
+fn apply_to_codes(policy: &Policy, codes: &StatusCodes) -> StatusCodes {
+    let statuses = codes
+        .success()
+        .iter()
+        .map(|status| (status, LogKind::Success))
+        .chain(
+            codes
+                .informational()
+                .iter()
+                .map(|status| (status, LogKind::Informational)),
+        )
+        .chain(
+            codes
+                .failure()
+                .iter()
+                .map(|status| (status, LogKind::Failure)),
+        );
+    statuses.fold(StatusCodes::default(), |codes, (status, kind)| {
+        let kind = match policy.get(status.code()) {
+            Some(Severity::Error) => LogKind::Failure,
+            Some(Severity::Warning) => LogKind::Informational,
+            Some(Severity::Ignore) => return codes,
+            None => kind,
+        };
+        let status = status.clone().set_kind(kind.clone());
+        match kind {
+            LogKind::Success => codes.add_success_val(status),
+            LogKind::Informational => codes.add_informational_val(status),
+            LogKind::Failure => codes.add_failure_val(status),
+        }
+    })
+}
+
+/// The Reader's validation results with the validation policy applied.
+pub fn validation_results(reader: &Reader) -> Option<Cow<'_, ValidationResults>> {
+    let results = reader.validation_results()?;
+    let Ok(policy) = POLICY.read() else {
+        return Some(Cow::Borrowed(results));
+    };
+    if policy.is_empty() {
+        return Some(Cow::Borrowed(results));
+    }
+    let mut applied = ValidationResults::default();
+    if let Some(active_manifest) = results.active_manifest() {
+        applied = applied.add_active_manifest(apply_to_codes(&policy, active_manifest));
+    }
+    for delta in results.ingredient_deltas().into_iter().flatten() {
+        applied = applied.add_ingredient_delta(IngredientDeltaValidationResult::new(
+            delta.ingredient_assertion_uri(),
+            apply_to_codes(&policy, delta.validation_deltas()),
+        ));
+    }
+    Some(Cow::Owned(applied))
+}
+
+/// The failures in validation results, as `validation_status` lists them.
+fn failures(results: &ValidationResults) -> Vec<ValidationStatus> {
+    let mut failures: Vec<ValidationStatus> = results
+        .active_manifest()
+        .map(|codes| codes.failure().clone())
+        .unwrap_or_default();
+    for delta in results.ingredient_deltas().into_iter().flatten() {
+        failures.extend(delta.validation_deltas().failure().iter().cloned());
+    }
+    failures
+}
+
+/// The Reader's validation state with the validation policy applied. Any
+/// status code the policy makes an error makes the manifest store invalid,
+/// even one c2pa-rs allows in a valid store, such as
+/// `signingCredential.untrusted`.
+pub fn validation_state(reader: &Reader) -> ValidationState {
+    if !has_policy() {
+        return reader.validation_state();
+    }
+    let Some(results) = validation_results(reader) else {
+        return reader.validation_state();
+    };
+    let errors = failures(&results)
+        .iter()
+        .any(|status| severity(status.code()) == Some(Severity::Error));
+    if errors {
+        ValidationState::Invalid
+    } else {
+        results.validation_state()
+    }
+}
+
+/// The Reader's manifest store as a JSON value, with the validation policy
+/// applied to its validation results, status, and state.
+pub fn manifest_store_value(reader: &Reader) -> serde_json::Result<serde_json::Value> {
+    let mut store: serde_json::Value = serde_json::from_str(&reader.json())?;
+    if !has_policy() {
+        return Ok(store);
+    }
+    let Some(results) = validation_results(reader) else {
+        return Ok(store);
+    };
+    let failures = failures(&results);
+    if failures.is_empty() {
+        if let Some(store) = store.as_object_mut() {
+            store.remove("validation_status");
+        }
+    } else {
+        store["validation_status"] = serde_json::to_value(failures)?;
+    }
+    store["validation_results"] = serde_json::to_value(results.as_ref())?;
+    store["validation_state"] = serde_json::to_value(validation_state(reader))?;
+    Ok(store)
+}
+
+/// Replace the validation policy with one read from JSON, an object mapping
+/// status codes to "error", "warning", or "ignore". Null clears the policy.
+pub fn set_validation_policy(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let policy = match cx.argument_opt(0) {
+        Some(value) if value.is_a::<JsString, _>(&mut cx) => {
+            let json = value
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            serde_json::from_str::<Policy>(&json)
+                .or_else(|err| cx.throw_range_error(format!("Invalid validation policy: {err}")))?
+        }
+        _ => Policy::new(),
+    };
+    let mut current = POLICY
+        .write()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    *current = policy;
+    Ok(cx.undefined())
+}
+
+/// The validation policy as JSON.
+pub fn get_validation_policy(mut cx: FunctionContext) -> JsResult<JsString> {
+    let policy = POLICY
+        .read()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+    let json = serde_json::to_string(&*policy).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
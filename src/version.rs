@@ -0,0 +1,120 @@
+// Copyright 2025 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use c2pa::{Builder, Reader, SigningAlg};
+use neon::prelude::*;
+use serde::Serialize;
+
+use crate::utils::mime_types;
+
+/// The c2pa-rs features every build enables.
+const REQUIRED_FEATURES: [&str; 3] = ["default_http", "file_io", "rust_native_crypto"];
+
+/// The c2pa-rs features a platform build can leave out, and whether this one
+/// has them.
+const OPTIONAL_FEATURES: [(&str, bool); 3] = [
+    ("add_thumbnails", cfg!(feature = "add_thumbnails")),
+    (
+        "fetch_remote_manifests",
+        cfg!(feature = "fetch_remote_manifests"),
+    ),
+    ("pdf", cfg!(feature = "pdf")),
+];
+
+/// The version of the C2PA specification c2pa-rs implements.
+const SPEC_VERSION: &str = "2.2";
+
+const CLAIM_VERSIONS: [u8; 2] = [1, 2];
+
+const SIGNING_ALGORITHMS: [SigningAlg; 7] = [
+    SigningAlg::Es256,
+    SigningAlg::Es384,
+    SigningAlg::Es512,
+    SigningAlg::Ps256,
+    SigningAlg::Ps384,
+    SigningAlg::Ps512,
+    SigningAlg::Ed25519,
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionInfo {
+    c2pa_version: &'static str,
+    spec_version: &'static str,
+    claim_versions: [u8; 2],
+    /// The c2pa-rs cargo features enabled in this build, sorted.
+    features: Vec<String>,
+    signing_algorithms: Vec<String>,
+    /// MIME types, sorted.
+    readable_formats: Vec<String>,
+    signable_formats: Vec<String>,
+}
+
+/// Capabilities that depend on how the binary was built, so that an
+/// application can degrade gracefully when a platform build lacks one.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeFeatures {
+    pdf: bool,
+    fetch_remote_manifests: bool,
+    thumbnails: bool,
+    // CAWG identity assertions and TrustMark watermarks are built into every
+    // binary, but are listed so every capability is checked the same way.
+    cawg: bool,
+    trustmark: bool,
+}
+
+fn c2pa_features() -> Vec<String> {
+    let mut features: Vec<String> = REQUIRED_FEATURES
+        .into_iter()
+        .chain(
+            OPTIONAL_FEATURES
+                .into_iter()
+                .filter_map(|(feature, enabled)| enabled.then_some(feature)),
+        )
+        .map(str::to_string)
+        .collect();
+    features.sort();
+    features
+}
+
+/// The bundled c2pa-rs version and what this build supports, as JSON.
+pub fn version_info(mut cx: FunctionContext) -> JsResult<JsString> {
+    let info = VersionInfo {
+        c2pa_version: c2pa::VERSION,
+        spec_version: SPEC_VERSION,
+        claim_versions: CLAIM_VERSIONS,
+        features: c2pa_features(),
+        signing_algorithms: SIGNING_ALGORITHMS
+            .iter()
+            .map(|alg| alg.to_string())
+            .collect(),
+        readable_formats: mime_types(Reader::supported_mime_types()),
+        signable_formats: mime_types(Builder::supported_mime_types()),
+    };
+    let json = serde_json::to_string(&info).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}
+
+/// The optional capabilities of this build, as JSON.
+pub fn native_features(mut cx: FunctionContext) -> JsResult<JsString> {
+    let features = NativeFeatures {
+        pdf: cfg!(feature = "pdf"),
+        fetch_remote_manifests: cfg!(feature = "fetch_remote_manifests"),
+        thumbnails: cfg!(feature = "add_thumbnails"),
+        cawg: true,
+        trustmark: true,
+    };
+    let json = serde_json::to_string(&features).or_else(|err| cx.throw_error(err.to_string()))?;
+    Ok(cx.string(json))
+}